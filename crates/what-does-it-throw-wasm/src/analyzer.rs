@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+use swc_common::comments::{Comments, SingleThreadedComments};
+use swc_common::{sync::Lrc, SourceMap};
+use swc_ecma_ast::Module;
+use what_does_it_throw::throw_finder::next_line_comments;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::component::is_component;
+use crate::{
+  analyze_single_file, collect_file_throws, disable_regions, glob_matches, has_file_disable_comment, hash_content, link_files, log, logger_error,
+  parse_file_into, parse_error_diagnostics, FileThrows, InputData, ParseError, ParseResult, ParseResultType,
+};
+
+/// Replaced file versions may hold this many bytes of the shared `SourceMap` before it is rebuilt
+const MIN_STALE_SOURCE_BYTES: usize = 1 << 20;
+
+#[wasm_bindgen]
+extern "C" {
+  #[wasm_bindgen(typescript_type = "InputData")]
+  pub type InputDataType;
+}
+
+/// A file the analyzer has seen
+struct AnalyzedFile {
+  content_hash: u64,
+  content: String,
+  parsed: Option<(Module, FileThrows)>, // Multi-file mode only; None if the file didn't parse
+  parse_errors: Vec<ParseError>,         // Multi-file mode only
+  parse_result: Option<ParseResult>,    // Dropped in multi-file mode whenever any file changes
+}
+
+/// Keeps each file's analysis keyed by a hash of its contents, so an editor can re-analyze on every
+/// keystroke without re-parsing unchanged files. In multi-file mode calls resolve against the throws of
+/// every file analyzed so far, and an edit only re-parses and re-analyzes the file that changed.
+#[wasm_bindgen]
+pub struct Analyzer {
+  input_data: InputData,
+  multi_file: bool,
+  // Shared by all files in multi-file mode, so spans of cached files stay valid
+  cm: Lrc<SourceMap>,
+  comments: Lrc<SingleThreadedComments>,
+  files: HashMap<String, AnalyzedFile>,
+  stale_source_bytes: usize, // Bytes of replaced file versions still held by `cm`
+}
+
+/// Create an incremental analyzer. `settings` takes the same options as `parse_js`; its
+/// `file_content` and `files` are ignored in favor of the contents passed to `analyze`.
+#[wasm_bindgen]
+pub fn create_analyzer(settings: InputDataType, multi_file: bool) -> Analyzer {
+  let input_data = match serde_wasm_bindgen::from_value::<InputData>(settings.into()) {
+    Ok(input_data) => input_data,
+    Err(e) => {
+      logger_error(&format!("Failed to parse analyzer settings, using defaults: {:?}", e));
+      InputData::default()
+    }
+  };
+  Analyzer::new(input_data, multi_file)
+}
+
+#[wasm_bindgen]
+impl Analyzer {
+  /// Analyze `content` as the current contents of `path`, reusing cached work where the contents are unchanged
+  pub fn analyze(&mut self, path: &str, content: &str) -> ParseResultType {
+    self.analyze_file(path, content).to_js_value().unchecked_into()
+  }
+
+  /// Forget `path`, e.g. when it is closed or deleted
+  pub fn invalidate(&mut self, path: &str) {
+    if let Some(file) = self.files.remove(path) {
+      self.discard(&file);
+    }
+  }
+
+  /// Forget every file
+  pub fn clear(&mut self) {
+    self.files.clear();
+    self.reset_source_map();
+  }
+}
+
+impl Analyzer {
+  fn new(input_data: InputData, multi_file: bool) -> Self {
+    Self {
+      input_data: InputData {
+        file_content: None,
+        files: None,
+        ..input_data
+      },
+      multi_file,
+      cm: Default::default(),
+      comments: Default::default(),
+      files: HashMap::new(),
+      stale_source_bytes: 0,
+    }
+  }
+
+  fn analyze_file(&mut self, path: &str, content: &str) -> ParseResult {
+    let content_hash = hash_content(content);
+    if let Some(file) = self.files.get(path) {
+      if file.content_hash == content_hash && file.content == content {
+        if let Some(parse_result) = &file.parse_result {
+          return parse_result.clone();
+        }
+      } else if let Some(file) = self.files.remove(path) {
+        self.discard(&file);
+      }
+    }
+
+    if !self.files.contains_key(path) {
+      log(&format!("Analyzing changed file {}", path));
+      let (parsed, parse_errors) = if self.multi_file {
+        // Calls in other files may resolve against this one now
+        for other in self.files.values_mut() {
+          other.parse_result = None;
+        }
+        self.parse(path, content)
+      } else {
+        (None, Vec::new())
+      };
+      self.files.insert(
+        path.to_string(),
+        AnalyzedFile {
+          content_hash,
+          content: content.to_string(),
+          parsed,
+          parse_errors,
+          parse_result: None,
+        },
+      );
+    }
+
+    let parse_result = if self.multi_file {
+      self.link(path, content)
+    } else {
+      let input_data = InputData {
+        file_content: Some(content.to_string()),
+        // A Svelte or Vue path is as good as a `language` hint
+        language: self.input_data.language.clone().or_else(|| is_component(path).then(|| path.to_string())),
+        ..self.input_data.clone()
+      };
+      let user_settings = input_data.user_settings();
+      analyze_single_file(input_data, &user_settings)
+    };
+    if let Some(file) = self.files.get_mut(path) {
+      file.parse_result = Some(parse_result.clone());
+    }
+    parse_result
+  }
+
+  fn parse(&self, path: &str, content: &str) -> (Option<(Module, FileThrows)>, Vec<ParseError>) {
+    let (module, recovered_errors) = match parse_file_into(path, content, &self.cm, &self.comments) {
+      Ok(parsed) => parsed,
+      Err(error) => return (None, vec![error]),
+    };
+    let user_settings = self.input_data.user_settings();
+    let next_line_comments = next_line_comments(&self.cm, &self.comments, &user_settings.ignore_statements);
+    let file_throws = collect_file_throws(&module, &self.comments, &next_line_comments, &user_settings);
+    (Some((module, file_throws)), recovered_errors)
+  }
+
+  /// Resolve `path`'s calls against the throws of every parsed file. Files matched by `ignore_paths`
+  /// still resolve calls from other files but report nothing themselves.
+  fn link(&mut self, path: &str, content: &str) -> ParseResult {
+    let ignored = self.input_data.ignore_paths.iter().flatten().any(|pattern| glob_matches(pattern, path));
+    if ignored || has_file_disable_comment(content) {
+      return ParseResult::empty();
+    }
+    let mut paths: Vec<&String> = self.files.keys().collect();
+    paths.sort();
+    let files: Vec<(&str, &Module, &FileThrows)> = paths
+      .iter()
+      .filter_map(|path| {
+        let (module, file_throws) = self.files[*path].parsed.as_ref()?;
+        Some((path.as_str(), module, file_throws))
+      })
+      .collect();
+    let parse_errors = &self.files[path].parse_errors;
+    let Some(index) = files.iter().position(|(file_path, _, _)| *file_path == path) else {
+      // Failed to parse; only the syntax error to report until it does
+      return ParseResult {
+        diagnostics: parse_error_diagnostics(parse_errors, &self.cm),
+        ..ParseResult::empty()
+      };
+    };
+
+    let user_settings = self.input_data.user_settings();
+    let next_line_comments = next_line_comments(&self.cm, &self.comments, &user_settings.ignore_statements);
+    let (mut results, all_throw_spans) = link_files(&files, &[index], &self.comments, &next_line_comments, &user_settings);
+    results.parse_errors = parse_errors.clone();
+    let disable_regions = disable_regions(&self.cm, &self.comments, files[index].1.span.lo);
+    let comments_as_dyn: Lrc<dyn Comments> = self.comments.clone();
+    ParseResult::into(
+      results,
+      &self.cm,
+      self.input_data.clone(),
+      &comments_as_dyn,
+      &user_settings,
+      all_throw_spans,
+      &disable_regions,
+    )
+  }
+
+  /// Account for a file version that is no longer current
+  fn discard(&mut self, file: &AnalyzedFile) {
+    if !self.multi_file {
+      return;
+    }
+    // Other files may have resolved calls against the discarded version
+    for other in self.files.values_mut() {
+      other.parse_result = None;
+    }
+    self.stale_source_bytes += file.content.len();
+    let live_source_bytes: usize = self.files.values().map(|file| file.content.len()).sum();
+    if self.stale_source_bytes > MIN_STALE_SOURCE_BYTES.max(live_source_bytes) {
+      self.reparse_all();
+    }
+  }
+
+  fn reset_source_map(&mut self) {
+    self.cm = Default::default();
+    self.comments = Default::default();
+    self.stale_source_bytes = 0;
+  }
+
+  /// Rebuild the shared `SourceMap` from the current contents, dropping replaced versions
+  fn reparse_all(&mut self) {
+    log("Rebuilding analyzer source map");
+    self.reset_source_map();
+    let mut paths: Vec<String> = self.files.keys().cloned().collect();
+    paths.sort();
+    for path in paths {
+      let content = self.files[&path].content.clone();
+      let (parsed, parse_errors) = self.parse(&path, &content);
+      if let Some(file) = self.files.get_mut(&path) {
+        file.parsed = parsed;
+        file.parse_errors = parse_errors;
+        file.parse_result = None;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn call_messages(parse_result: &ParseResult) -> Vec<(usize, String)> {
+    parse_result
+      .diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| (d.range.start.line, d.message.clone()))
+      .collect()
+  }
+
+  const ERRORS: &str = "export function failHard(reason: string) {\n  throw new RangeError(reason);\n}\n";
+  const MAIN: &str = "import { failHard } from './errors';\n\nexport function run() {\n  failHard('nope');\n}\n";
+
+  #[test]
+  fn test_single_file_results_are_cached_by_content() {
+    let mut analyzer = Analyzer::new(InputData::default(), false);
+    let code = "function foo() {\n  throw new Error('x');\n}\n";
+
+    let first = analyzer.analyze_file("foo.ts", code);
+    assert!(!first.diagnostics.is_empty());
+    let second = analyzer.analyze_file("foo.ts", code);
+    assert_eq!(
+      first.diagnostics.iter().map(|d| &d.message).collect::<Vec<_>>(),
+      second.diagnostics.iter().map(|d| &d.message).collect::<Vec<_>>()
+    );
+
+    let edited = analyzer.analyze_file("foo.ts", "function foo() {}\n");
+    assert!(edited.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn test_multi_file_reuses_unchanged_files() {
+    let mut analyzer = Analyzer::new(InputData::default(), true);
+    analyzer.analyze_file("errors.ts", ERRORS);
+    let main = analyzer.analyze_file("main.ts", MAIN);
+    assert_eq!(call_messages(&main), vec![(4, "Function call may throw: {RangeError}.".to_string())]);
+    assert_eq!(analyzer.cm.files().len(), 2);
+
+    // Editing main.ts parses only main.ts again
+    let edited = MAIN.replace("run()", "runAgain()");
+    let main = analyzer.analyze_file("main.ts", &edited);
+    assert_eq!(call_messages(&main), vec![(4, "Function call may throw: {RangeError}.".to_string())]);
+    assert_eq!(analyzer.cm.files().len(), 3);
+
+    // Unchanged contents parse nothing
+    analyzer.analyze_file("main.ts", &edited);
+    analyzer.analyze_file("errors.ts", ERRORS);
+    assert_eq!(analyzer.cm.files().len(), 3);
+
+    // A change to errors.ts reaches main.ts
+    analyzer.analyze_file("errors.ts", "export function failHard(reason: string) {\n  return reason;\n}\n");
+    let main = analyzer.analyze_file("main.ts", &edited);
+    assert!(call_messages(&main).is_empty(), "{:?}", call_messages(&main));
+  }
+
+  #[test]
+  fn test_invalidate_and_clear_forget_files() {
+    let mut analyzer = Analyzer::new(InputData::default(), true);
+    analyzer.analyze_file("errors.ts", ERRORS);
+    assert!(!call_messages(&analyzer.analyze_file("main.ts", MAIN)).is_empty());
+
+    analyzer.invalidate("errors.ts");
+    assert!(call_messages(&analyzer.analyze_file("main.ts", MAIN)).is_empty());
+
+    analyzer.analyze_file("errors.ts", ERRORS);
+    assert!(!call_messages(&analyzer.analyze_file("main.ts", MAIN)).is_empty());
+
+    analyzer.clear();
+    assert!(analyzer.files.is_empty());
+    assert_eq!(analyzer.cm.files().len(), 0);
+    assert!(call_messages(&analyzer.analyze_file("main.ts", MAIN)).is_empty());
+  }
+
+  #[test]
+  fn test_stale_versions_are_dropped_from_the_source_map() {
+    let mut analyzer = Analyzer::new(InputData::default(), true);
+    let padding = format!("// {}\n", "x".repeat(MIN_STALE_SOURCE_BYTES / 4));
+    for edit in 0..6 {
+      analyzer.analyze_file("errors.ts", &format!("{}{}// edit {}\n", padding, ERRORS, edit));
+    }
+    let main = analyzer.analyze_file("main.ts", MAIN);
+    assert!(analyzer.cm.files().len() < 7, "{}", analyzer.cm.files().len());
+    assert_eq!(call_messages(&main), vec![(4, "Function call may throw: {RangeError}.".to_string())]);
+  }
+  #[test]
+  fn test_ignored_paths_still_resolve_calls() {
+    let input_data = InputData {
+      ignore_paths: Some(vec!["errors.ts".to_string()]),
+      ..InputData::default()
+    };
+    let mut analyzer = Analyzer::new(input_data, true);
+    assert!(analyzer.analyze_file("errors.ts", ERRORS).diagnostics.is_empty());
+    let main = analyzer.analyze_file("main.ts", MAIN);
+    assert_eq!(call_messages(&main), vec![(4, "Function call may throw: {RangeError}.".to_string())]);
+  }
+  #[test]
+  fn test_multi_file_parse_errors_are_reported_until_fixed() {
+    let mut analyzer = Analyzer::new(InputData::default(), true);
+    analyzer.analyze_file("errors.ts", ERRORS);
+    let broken = analyzer.analyze_file("main.ts", "import { failHard } from './errors';\n\nexport function run( {\n");
+    assert_eq!(
+      broken.diagnostics.iter().map(|d| d.code.as_deref()).collect::<Vec<_>>(),
+      vec![Some("DIT012")]
+    );
+
+    let fixed = analyzer.analyze_file("main.ts", MAIN);
+    assert!(fixed.diagnostics.iter().all(|d| d.code.as_deref() != Some("DIT012")));
+    assert_eq!(call_messages(&fixed), vec![(4, "Function call may throw: {RangeError}.".to_string())]);
+  }
+}
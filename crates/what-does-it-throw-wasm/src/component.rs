@@ -0,0 +1,134 @@
+//! `<script>` blocks of Svelte and Vue single-file components, which only parse as TypeScript on
+//! their own
+
+/// A `<script>` block's content and where it starts in the component file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptBlock {
+  pub content: String,
+  pub start_line: usize,   // Zero-based
+  pub start_column: usize, // In UTF-16 code units, like diagnostic columns
+}
+
+/// Whether a `language` hint like "vue", or a path like "App.svelte", names a single-file component
+pub fn is_component(language_or_path: &str) -> bool {
+  let extension = language_or_path.rsplit('.').next().unwrap_or_default();
+  matches!(extension.to_ascii_lowercase().as_str(), "svelte" | "vue")
+}
+
+/// The `<script>` blocks of a component in order, like Vue's `<script>` and `<script setup>`.
+/// Self-closing and unterminated tags have no content and are left out.
+pub fn script_blocks(source: &str) -> Vec<ScriptBlock> {
+  // ASCII lowercasing keeps every byte where it was
+  let lowercase = source.to_ascii_lowercase();
+  let mut blocks = Vec::new();
+  let mut offset = 0;
+  while let Some(found) = lowercase[offset..].find("<script") {
+    let attributes_start = offset + found + "<script".len();
+    offset = attributes_start;
+    // `<scripts>` or `<script-editor>` are other elements
+    if !lowercase[attributes_start..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+      continue;
+    }
+    let tag_end = match opening_tag_end(&source[attributes_start..]) {
+      Some(tag_end) => attributes_start + tag_end,
+      None => break,
+    };
+    offset = tag_end + 1;
+    if source[..tag_end].ends_with('/') {
+      continue;
+    }
+    let content_end = match lowercase[offset..].find("</script") {
+      Some(found) => offset + found,
+      None => break,
+    };
+
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    blocks.push(ScriptBlock {
+      content: source[offset..content_end].to_string(),
+      start_line: before.matches('\n').count(),
+      start_column: before[line_start..].encode_utf16().count(),
+    });
+    offset = content_end + "</script".len();
+  }
+  blocks
+}
+
+/// The index of the `>` closing an opening tag, skipping any inside quoted attribute values
+fn opening_tag_end(attributes: &str) -> Option<usize> {
+  let mut quote = None;
+  for (index, c) in attributes.char_indices() {
+    match (quote, c) {
+      (None, '"' | '\'') => quote = Some(c),
+      (Some(open), _) if c == open => quote = None,
+      (None, '>') => return Some(index),
+      _ => {}
+    }
+  }
+  None
+}
+
+/// `blocks` at their positions in the component file, with blank lines and spaces in place of the
+/// markup around them, so ranges in the analysis line up with the component file
+pub fn positioned_source(blocks: &[ScriptBlock]) -> String {
+  let mut source = String::new();
+  let (mut line, mut column) = (0, 0);
+  for block in blocks {
+    if block.start_line > line {
+      source.push_str(&"\n".repeat(block.start_line - line));
+      line = block.start_line;
+      column = 0;
+    }
+    source.push_str(&" ".repeat(block.start_column.saturating_sub(column)));
+    column = column.max(block.start_column);
+    source.push_str(&block.content);
+    line += block.content.matches('\n').count();
+    column = match block.content.rfind('\n') {
+      Some(index) => block.content[index + 1..].encode_utf16().count(),
+      None => column + block.content.encode_utf16().count(),
+    };
+  }
+  source
+}
+
+/// The scripts of a component as one module, the way Svelte and Vue compile them; a component
+/// without any is an empty module
+pub fn component_script_source(source: &str) -> String {
+  positioned_source(&script_blocks(source))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_script_blocks_of_vue_component() {
+    let source = "<template>\n  <p>{{ msg }}</p>\n</template>\n<script lang=\"ts\">\nexport default {};\n</script>\n<SCRIPT setup data-x=\"a>b\">const n = 1;\n</SCRIPT>\n<style>p { color: red; }</style>\n";
+    let blocks = script_blocks(source);
+    assert_eq!(blocks, vec![
+      ScriptBlock { content: "\nexport default {};\n".to_string(), start_line: 3, start_column: 18 },
+      ScriptBlock { content: "const n = 1;\n".to_string(), start_line: 6, start_column: 27 },
+    ]);
+
+    let positioned = positioned_source(&blocks);
+    let lines: Vec<&str> = positioned.lines().collect();
+    assert_eq!(lines[4], "export default {};");
+    assert_eq!(lines[6], format!("{}const n = 1;", " ".repeat(27)));
+  }
+
+  #[test]
+  fn test_script_blocks_skip_other_elements() {
+    assert!(script_blocks("<template><div /></template>").is_empty());
+    assert!(script_blocks("<scripts>x</scripts><script src=\"./a.js\" />").is_empty());
+    assert!(script_blocks("<script>never closed").is_empty());
+    assert_eq!(component_script_source("<p>no scripts</p>"), "");
+  }
+
+  #[test]
+  fn test_is_component() {
+    assert!(is_component("vue"));
+    assert!(is_component("Svelte"));
+    assert!(is_component("src/App.vue"));
+    assert!(!is_component("src/app.ts"));
+  }
+}
@@ -6,24 +6,43 @@ extern crate swc_ecma_parser;
 extern crate swc_ecma_visit;
 extern crate wasm_bindgen;
 
-use std::collections::{HashMap, HashSet};
-use std::cell::Cell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::cell::{Cell, RefCell};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 use std::str::FromStr;
 
-use self::serde::{Deserialize, Serialize, Serializer};
-use self::swc_common::{sync::Lrc, SourceMap, SourceMapper, Span};
+use self::serde::{Deserialize, Serialize};
+use self::swc_common::{sync::Lrc, SourceMap, Span};
 use swc_common::BytePos;
 use wasm_bindgen::prelude::*;
 
-use what_does_it_throw::call_finder::{CallFinder, CallToThrowMap};
-use what_does_it_throw::throw_finder::{IdentifierUsage, ThrowMap, ThrowAnalyzer, ThrowFinderSettings};
+use what_does_it_throw::call_finder::CallFinder;
+use what_does_it_throw::throw_finder::{
+  next_line_comments, NextLineComment, ThrowMap, ThrowAnalyzer, ThrowFinderSettings, ThrowsTagAliases, TypeRegistry,
+};
 use what_does_it_throw::import_usage_finder::ImportUsageFinder;
-use what_does_it_throw::function_finder::FunctionFinder;
+use what_does_it_throw::export_finder::{reexported_modules, relative_specifier, resolve_import_aliases, ExportFinder};
+use what_does_it_throw::function_finder::{FunctionFinder, FunctionMap};
+use what_does_it_throw::interface_finder::InterfaceFinder;
 use what_does_it_throw::try_catch_finder::TryCatchFinder;
-use what_does_it_throw::{analyze_code, AnalysisResult, UserSettings};
+use what_does_it_throw::{
+  analyze_code, analyze_module, apply_inherited_throws_annotations, attach_function_name_spans, class_ignore_comments, merge_higher_order_callback_throws,
+  parse_source_file, resolve_error_ancestors, resolve_error_factory_throws, exclude_env_guarded_throws, AnalysisResult,
+  AssertionConfig, BuiltinThrowCall, EnvGuardedThrows, ErrorFactoryConfig, IncrementalState, ParseError, UserSettings,
+};
+use what_does_it_throw::diagnostics::{
+  build_report, disable_regions, has_file_disable_comment, parse_error_diagnostics, throw_statement_spans, wildcard_matches, CallGraph, CatchSummary,
+  Diagnostic, DiagnosticSettings, DiagnosticSeverity, DisableRegion, FunctionThrowSummary, ImportedIdentifiers,
+};
 use swc_common::comments::{Comments, SingleThreadedComments};
-use swc_ecma_visit::{Visit, VisitWith};
-use swc_ecma_ast::{ThrowStmt};
+use swc_ecma_visit::Visit;
+use swc_ecma_ast::Module;
+
+mod analyzer;
+mod component;
+pub use analyzer::{create_analyzer, Analyzer};
+use component::{component_script_source, is_component, positioned_source, script_blocks};
 
 // Console bindings for leveled logging
 #[wasm_bindgen]
@@ -34,8 +53,6 @@ extern "C" {
   fn error(s: &str);
   #[wasm_bindgen(js_namespace = console)]
   fn debug(s: &str);
-  #[wasm_bindgen(js_namespace = console)]
-  fn warn(s: &str);
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -47,7 +64,7 @@ enum LogLevel {
 }
 
 thread_local! {
-  static CURRENT_LOG_LEVEL: Cell<LogLevel> = Cell::new(LogLevel::Debug);
+  static CURRENT_LOG_LEVEL: Cell<LogLevel> = const { Cell::new(LogLevel::Debug) };
 }
 
 fn should_log(level: LogLevel) -> bool {
@@ -89,9 +106,6 @@ fn logger_info(message: &str) {
 fn logger_debug(message: &str) {
   if should_log(LogLevel::Debug) { let colored = colorize(LogLevel::Debug, message); debug(&colored); }
 }
-fn logger_warn(message: &str) {
-  if should_log(LogLevel::Warn) { let colored = colorize(LogLevel::Warn, message); warn(&colored); }
-}
 fn logger_error(message: &str) {
   // Always log errors
   let colored = colorize(LogLevel::Error, message);
@@ -101,1511 +115,982 @@ fn logger_error(message: &str) {
 // Keep the same name used throughout the file; now routed through info-level + filtering
 fn log(message: &str) { logger_info(message); }
 
-/// Safe wrapper for character position lookup that handles Unicode/emoji properly
-/// Validates byte positions before lookup to prevent corruption with emojis
-fn safe_lookup_char_pos(cm: &SourceMap, pos: BytePos) -> (usize, usize) {
-  // Basic validation - ensure byte position is reasonable
-  if pos.0 == 0 || pos == swc_common::DUMMY_SP.lo() || pos == swc_common::DUMMY_SP.hi() {
-    log(&format!("⚠️ Invalid byte position {:?}, using safe fallback", pos));
-    return (1, 0);
+#[derive(Deserialize, Debug, Clone)]
+pub struct DiagnosticSeverityInput(String);
+
+
+impl From<DiagnosticSeverityInput> for DiagnosticSeverity {
+  fn from(input: DiagnosticSeverityInput) -> Self {
+    DiagnosticSeverity::from_str(&input.0).unwrap_or(DiagnosticSeverity::Hint)
   }
-  
-  let loc = cm.lookup_char_pos(pos);
-  (loc.line, loc.col_display)
-}
-
-/// Sanitizes Unicode strings to prevent serialization corruption with emojis
-/// Replaces emojis and other problematic Unicode with safe ASCII equivalents
-fn sanitize_unicode_string(input: String) -> String {
-  // Replace common emojis with text equivalents to prevent serialization corruption
-  input
-    .replace("🎯", "[target]")
-    .replace("📝", "[memo]")
-    .replace("🔧", "[wrench]")
-    .replace("📊", "[chart]")
-    .replace("✅", "[check]")
-    .replace("❌", "[x]")
-    .replace("⚠️", "[warning]")
-    .replace("🔍", "[search]")
-    .replace("💥", "[boom]")
-    // Remove any remaining emojis (range U+1F600-U+1F64F, U+1F300-U+1F5FF, U+1F680-U+1F6FF, etc.)
-    .chars()
-    .filter(|c| {
-      let code = *c as u32;
-      // Keep basic ASCII and common extended ASCII
-      code < 0x1F000 || (code > 0x1F6FF && code < 0x1F900) || code > 0x1F9FF
-    })
-    .collect()
-}
-
-/// Check if a function span has @it-throws comment and should be completely suppressed
-/// This enables comprehensive suppression - all diagnostics for @it-throws functions are hidden
-/// Returns the span of the comment if found, None otherwise
-fn has_it_throws_comment_with_span(comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> Option<Span> {
-  // Strategy 1: Check for leading comments on the function span itself
-  if let Some(leading_comments) = comments.get_leading(span.lo) {
-    for comment in leading_comments.iter() {
-      let comment_text = comment.text.trim();
-      // Check against all ignore_statements (which should include "@it-throws")
-      for keyword in ignore_statements {
-        if comment_text == keyword {
-          return Some(comment.span);
-        }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ParseResult {
+  pub diagnostics: Vec<Diagnostic>,
+  pub relative_imports: Vec<String>,
+  pub throw_ids: Vec<String>,
+  pub imported_identifiers_diagnostics: Vec<ImportedIdentifiers>,
+  pub severity_parse_warnings: Vec<String>, // Severity settings that weren't recognized and fell back to Hint
+  pub catch_summaries: Vec<CatchSummary>,
+  pub function_summaries: Vec<FunctionThrowSummary>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub call_graph: Option<CallGraph>, // Only with `include_call_graph`
+}
+
+impl ParseResult {
+  fn empty() -> Self {
+    Self {
+      diagnostics: Vec::new(),
+      relative_imports: Vec::new(),
+      throw_ids: Vec::new(),
+      imported_identifiers_diagnostics: Vec::new(),
+      severity_parse_warnings: Vec::new(),
+      catch_summaries: Vec::new(),
+      function_summaries: Vec::new(),
+      call_graph: None,
+    }
+  }
+
+  /// Add the results of another part of the same file
+  fn extend(&mut self, other: ParseResult) {
+    self.diagnostics.extend(other.diagnostics);
+    for relative_import in other.relative_imports {
+      if !self.relative_imports.contains(&relative_import) {
+        self.relative_imports.push(relative_import);
       }
     }
+    self.throw_ids.extend(other.throw_ids);
+    self.imported_identifiers_diagnostics.extend(other.imported_identifiers_diagnostics);
+    self.catch_summaries.extend(other.catch_summaries);
+    self.function_summaries.extend(other.function_summaries);
+    self.call_graph = match (self.call_graph.take(), other.call_graph) {
+      (Some(mut call_graph), Some(other)) => {
+        call_graph.nodes.extend(other.nodes);
+        call_graph.edges.extend(other.edges);
+        Some(call_graph)
+      }
+      (call_graph, other) => call_graph.or(other),
+    };
   }
-  
-  // Strategy 2: For functions in assignments or other constructs,
-  // check for comments at positions before the function span
-  for offset in 1..=30 {
-    let search_pos = swc_common::BytePos(span.lo.0.saturating_sub(offset));
-    if let Some(leading_comments) = comments.get_leading(search_pos) {
-      for comment in leading_comments.iter() {
-        let comment_text = comment.text.trim();
-        for keyword in ignore_statements {
-          if comment_text == keyword {
-            // Additional validation: Only use this comment if it's reasonably close
-            let comment_distance = span.lo.0.saturating_sub(comment.span.lo.0);
-            if comment_distance <= 200 {
-              return Some(comment.span);
-            }
-          }
-        }
+
+  /// Convert to a plain JS object. The JSON-compatible serializer emits maps as objects and
+  /// passes every string through untouched, so messages quoting user code keep their emoji.
+  fn to_js_value(&self) -> JsValue {
+    match self.serialize(&serde_wasm_bindgen::Serializer::json_compatible()) {
+      Ok(value) => value,
+      Err(e) => {
+        logger_error(&format!("Failed to serialize ParseResult: {:?}", e));
+        ParseResult::empty()
+          .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+          .unwrap_or(JsValue::NULL)
       }
     }
   }
-  
-  None
 }
 
-/// Convenience function to check if a function has @it-throws comment (boolean result)
-fn has_it_throws_comment(comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> bool {
-  has_it_throws_comment_with_span(comments, span, ignore_statements).is_some()
-}
 
-/// Simple visitor to collect all throw statement spans in the file
-/// This is used for proximity-based unused comment detection
-struct AllThrowsCollector {
-  throw_spans: Vec<Span>,
-}
 
-impl AllThrowsCollector {
-  fn new() -> Self {
-    Self {
-      throw_spans: Vec::new(),
+impl ParseResult {
+  pub fn into(
+    results: AnalysisResult,
+    cm: &SourceMap,
+    input_data: InputData,
+    comments: &Lrc<dyn Comments>,
+    user_settings: &UserSettings,
+    all_throw_spans: Vec<Span>,
+    disable_regions: &[DisableRegion],
+  ) -> ParseResult {
+    let severity_parse_warnings = input_data.severity_parse_warnings();
+    let settings = input_data.diagnostic_settings(user_settings);
+    let report = build_report(&results, cm, comments, &settings, &all_throw_spans, disable_regions);
+    for line in &report.debug_log {
+      logger_debug(line);
     }
-  }
-}
 
-impl Visit for AllThrowsCollector {
-  fn visit_throw_stmt(&mut self, node: &ThrowStmt) {
-    self.throw_spans.push(node.span);
-    swc_ecma_visit::visit_throw_stmt(self, node);
+    ParseResult {
+      diagnostics: report.diagnostics,
+      throw_ids: report.throw_ids,
+      relative_imports: report.relative_imports,
+      imported_identifiers_diagnostics: report.imported_identifiers_diagnostics,
+      severity_parse_warnings,
+      catch_summaries: report.catch_summaries,
+      function_summaries: report.function_summaries,
+      call_graph: report.call_graph,
+    }
   }
 }
 
-/// Check if the file has @it-throws-disable comment at the top (within first few lines)
-/// This disables all throw diagnostics for the entire file
-fn has_file_disable_comment(file_content: &str) -> bool {
-  // Check the first 9 lines for @it-throws-disable comment (conservative per tests)
-  let lines: Vec<&str> = file_content.lines().take(9).collect();
-  
-  for line in lines {
-    let trimmed = line.trim();
-    // Check for @it-throws-disable comment (exact match)
-    if trimmed == "// @it-throws-disable" || trimmed == "/* @it-throws-disable */" {
-      return true;
-    }
-  }
-  
-  false
+#[wasm_bindgen(typescript_custom_section)]
+const TypeScriptSettings: &'static str = r#"
+export interface TypeScriptSettings {
+	decorators?: boolean;
 }
+"#;
 
-#[derive(Serialize, Clone, Debug)]
-pub struct Diagnostic {
-  severity: i32,
-  range: DiagnosticRange,
-  message: String,
-  source: String,
+#[wasm_bindgen(typescript_custom_section)]
+const DiagnosticSeverityInput: &'static str = r#"
+export type DiagnosticSeverityInput = "Error" | "Warning" | "Information" | "Hint";
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ErrorFactoryConfig: &'static str = r#"
+export interface ErrorFactoryConfig {
+  name: string;
+  type_argument_index: number;
 }
+"#;
 
-#[derive(Serialize, Clone, Debug)]
-pub struct DiagnosticRange {
-  start: DiagnosticPosition,
-  end: DiagnosticPosition,
+#[wasm_bindgen(typescript_custom_section)]
+const AssertionConfig: &'static str = r#"
+export interface AssertionConfig {
+  name: string;
+  error_type: string;
+  message_argument_index?: number;
 }
+"#;
 
-#[derive(Serialize, Clone, Debug)]
-pub struct DiagnosticPosition {
-  line: usize,
-  character: usize,
+#[wasm_bindgen(typescript_custom_section)]
+const InputData: &'static str = r#"
+export type FileNode = {
+  file?: { contents: string };
+  directory?: { [name: string]: FileNode };
+};
+
+export type FileSystemTree = { [name: string]: FileNode };
+
+export interface InputData {
+	/** @deprecated Prefer 'files' */
+	file_content?: string;
+	/** Optional virtual file tree; if provided, multi-file analysis is performed */
+	files?: FileSystemTree;
+	/** Entry file within 'files' to anchor diagnostics (optional) */
+	entry?: string;
+	/** "svelte" or "vue" to analyze only the <script> blocks of 'file_content'; files in 'files' go by their extension */
+	language?: string;
+	debug?: boolean;
+  throw_statement_severity?: DiagnosticSeverityInput;
+  function_throw_severity?: DiagnosticSeverityInput;
+  call_to_throw_severity?: DiagnosticSeverityInput;
+  call_to_imported_throw_severity?: DiagnosticSeverityInput;
+  /** Calls to builtins known to throw, like JSON.parse (SyntaxError) or decodeURIComponent (URIError) */
+  builtin_throw_severity?: DiagnosticSeverityInput;
+  /** JSDoc @throws types the function can never throw; defaults to "Information" */
+  over_documented_severity?: DiagnosticSeverityInput;
+  /** Name patterns like "handle*" for entry functions; any throw they leave undocumented is an "Error" */
+  strict_entry_functions?: string[];
+  /** Report a function's throw statements as a count in its diagnostic instead of one "Throw statement." each; defaults to false */
+  collapse_throw_statements?: boolean;
+  /** Merge diagnostics on the exact same range into one, combining their error types; defaults to true */
+  merge_overlapping_diagnostics?: boolean;
+  /** Per error type overrides, e.g. { ValidationError: "Hint", Error: "Warning" }; "*Custom" covers custom error classes not listed */
+  error_type_severities?: { [errorType: string]: DiagnosticSeverityInput };
+  /** Same as error_type_severities; for an error type set in both, this one wins */
+  severity_overrides?: { [errorType: string]: DiagnosticSeverityInput };
+  /** Helpers like createHttpError('NotFoundError', msg) whose argument names the thrown type */
+  error_factories?: ErrorFactoryConfig[];
+  /** Calls like invariant(cond, msg) that throw error_type when the condition is false, a bare name an Error; defaults to assert throwing AssertionError */
+  assertion_functions?: (string | AssertionConfig)[];
+  /** How many levels of callers inherit a callee's throws; defaults to 5 */
+  max_call_depth?: number;
+  /** Error types thrown on purpose project-wide; functions throwing only these aren't reported */
+  expected_error_types?: string[];
+  /** "exclude" drops throws behind process.env checks such as NODE_ENV guards; defaults to "include" */
+  treat_env_guarded_throws_as?: "include" | "exclude";
+  /** Calls that end the process; a catch that always makes one swallows its errors. Defaults to ["process.exit", "Deno.exit"] */
+  terminator_functions?: string[];
+  /** Whether `throw e` in a catch passes the caught error on; when false only errors thrown by the catch itself are reported. Defaults to true */
+  include_catch_rethrows?: boolean;
+  /** JSDoc tags, without the "@", that document thrown errors, matched case-insensitively. Defaults to ["throws", "exception"] */
+  throws_tag_aliases?: string[];
+  /** Module specifiers or patterns like "@acme/*" or "./legacy/**" whose imports are never reported. A "// @it-throws" on an import declaration does the same for that import */
+  ignore_imports?: string[];
+  /** Treat every JSON.parse call as throwing a SyntaxError into its enclosing function. Defaults to false */
+  flag_json_parse?: boolean;
+  /** Flag every call to an imported function the analysis can't see into, using call_to_imported_throw_severity */
+  treat_unknown_imports_as_throwing?: boolean;
+  include_try_statement_throws?: boolean;
+  ignore_statements?: string[];
+  /** Globs like "**/*.test.ts" or "**/generated/**" for files in 'files' that report nothing but still resolve calls */
+  ignore_paths?: string[];
+  /** Add 'call_graph' to the result: every function with the errors it can surface, and the calls between them */
+  include_call_graph?: boolean;
 }
+"#;
 
-#[derive(Copy, Clone)]
-pub enum DiagnosticSeverity {
-  Error = 0,
-  Warning = 1,
-  Information = 2,
-  Hint = 3,
+#[wasm_bindgen(typescript_custom_section)]
+const ImportedIdentifiers: &'static str = r#"
+export interface ImportedIdentifiers {
+	diagnostics: any[];
+	id: string;
 }
+"#;
 
-impl Serialize for DiagnosticSeverity {
-  fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
-  where
-    S: Serializer,
-  {
-    serializer.serialize_i32(*self as i32)
-  }
+#[wasm_bindgen(typescript_custom_section)]
+const ParseResult: &'static str = r#"
+export interface ParseResult {
+	diagnostics: any[];
+	relative_imports: string[];
+	throw_ids: string[];
+	imported_identifiers_diagnostics: ImportedIdentifiers[];
+	/** Severity settings that weren't recognized; those fall back to "Hint" */
+	severity_parse_warnings: string[];
+	/** Every catch block with the errors it handles and lets through */
+	catch_summaries: CatchSummary[];
+	/** Every function that can throw with how well its JSDoc documents that */
+	function_summaries: FunctionThrowSummary[];
+	/** Only present when 'include_call_graph' is set */
+	call_graph?: CallGraph;
 }
 
-impl DiagnosticSeverity {
-  fn to_int(&self) -> i32 {
-    match *self {
-      DiagnosticSeverity::Error => 0,
-      DiagnosticSeverity::Warning => 1,
-      DiagnosticSeverity::Information => 2,
-      DiagnosticSeverity::Hint => 3,
-    }
-  }
+export interface CallGraph {
+	nodes: CallGraphNode[];
+	edges: CallGraphEdge[];
 }
 
-#[derive(Deserialize, Debug, Clone)]
-pub struct DiagnosticSeverityInput(String);
+export interface CallGraphNode {
+	id: string;
+	name: string;
+	class_name: string | null;
+	range: {
+		start: { line: number; character: number };
+		end: { line: number; character: number };
+	};
+	/** Errors the function can surface, thrown directly or by its callees, after catch analysis */
+	error_types: string[];
+	/** Error types in the function's JSDoc @throws tags */
+	documented_types: string[];
+}
 
-impl FromStr for DiagnosticSeverity {
-  type Err = ();
+export interface CallGraphEdge {
+	from_id: string;
+	to_id: string;
+	call_range: {
+		start: { line: number; character: number };
+		end: { line: number; character: number };
+	};
+	/** The callee's errors that reach the caller through this call */
+	propagated_error_types: string[];
+}
 
-  fn from_str(s: &str) -> Result<Self, Self::Err> {
-    match s {
-      "Error" => Ok(DiagnosticSeverity::Error),
-      "Warning" => Ok(DiagnosticSeverity::Warning),
-      "Information" => Ok(DiagnosticSeverity::Information),
-      "Hint" => Ok(DiagnosticSeverity::Hint),
-      _ => Err(()),
-    }
-  }
+export interface FunctionThrowSummary {
+	id: string;
+	name: string;
+	range: {
+		start: { line: number; character: number };
+		end: { line: number; character: number };
+	};
+	/** Errors the function can surface, thrown directly or by its callees, after catch analysis */
+	thrown_types: string[];
+	/** Error types in the function's JSDoc @throws tags */
+	documented_types: string[];
+	/** Thrown types no @throws tag covers */
+	undocumented_types: string[];
+	/** "suppressed" when an @it-throws comment covers the whole function */
+	status: "fullyDocumented" | "partiallyDocumented" | "undocumented" | "suppressed";
+	/** Zero-based: the start of the function's line, or before the closing "*\/" of its JSDoc */
+	insert_position: { line: number; character: number };
+	/** Whether insert_position is inside an existing JSDoc block */
+	append_to_existing_doc: boolean;
 }
 
-impl From<DiagnosticSeverityInput> for DiagnosticSeverity {
-  fn from(input: DiagnosticSeverityInput) -> Self {
-    DiagnosticSeverity::from_str(&input.0).unwrap_or(DiagnosticSeverity::Hint)
-  }
+export interface CatchSummary {
+	range: {
+		start: { line: number; character: number };
+		end: { line: number; character: number };
+	};
+	errors_thrown_in_try: string[];
+	errors_handled_in_catch: string[];
+	errors_effectively_caught: string[];
+	errors_propagated: string[];
+	/** The catch rethrows its parameter, so unhandled errors still escape */
+	has_escape_hatch: boolean;
 }
+"#;
 
-fn get_line_end_byte_pos(cm: &SourceMap, lo_byte_pos: BytePos, hi_byte_pos: BytePos) -> BytePos {
-  let src = cm
-    .span_to_snippet(Span::new(lo_byte_pos, hi_byte_pos, Default::default()))
-    .unwrap_or_default();
+#[wasm_bindgen(typescript_custom_section)]
+const ParseJsFunction: &'static str = r#"
+export function parse_js(data: InputData): ParseResult;
+"#;
 
-  if let Some(newline_pos) = src.find('\n') {
-    lo_byte_pos + BytePos(newline_pos as u32)
-  } else {
-    // should never be true
-    hi_byte_pos
-  }
+#[wasm_bindgen]
+extern "C" {
+  #[wasm_bindgen(typescript_type = "ParseResult")]
+  pub type ParseResultType;
 }
 
-fn get_line_start_byte_pos(cm: &SourceMap, lo_byte_pos: BytePos, hi_byte_pos: BytePos) -> BytePos {
-  let src = cm
-    .span_to_snippet(Span::new(lo_byte_pos, hi_byte_pos, Default::default()))
-    .unwrap_or_default();
-
-  // Split the source into lines and reverse the list to find the newline character from the end (which would be the start of the line)
-  let lines = src.lines().rev().collect::<Vec<&str>>();
+#[derive(Serialize, Deserialize)]
+pub struct TypeScriptSettings {
+  decorators: Option<bool>,
+}
 
-  if let Some(last_line) = lines.first() {
-    // Calculate the byte position of the start of the line of interest
-    let start_pos = last_line.chars().position(|c| c != ' ' && c != '\t');
+#[derive(Deserialize, Debug, Clone)]
+pub struct FileNodeFile { pub contents: String }
 
-    if let Some(pos) = start_pos {
-      hi_byte_pos - BytePos((last_line.len() - pos) as u32)
-    } else {
-      // If there's no content (only whitespace), then we are at the start of the line
-      hi_byte_pos - BytePos(last_line.len() as u32)
-    }
-  } else {
-    // If there's no newline character, then we are at the start of the file
-    BytePos(0)
-  }
+#[derive(Deserialize, Debug, Clone)]
+pub struct FileNode {
+  pub file: Option<FileNodeFile>,
+  pub directory: Option<std::collections::HashMap<String, FileNode>>,
 }
 
-fn get_relative_imports(import_sources: Vec<String>) -> Vec<String> {
-  let mut relative_imports: Vec<String> = Vec::new();
-  for import_source in import_sources {
-    if import_source.starts_with("./") || import_source.starts_with("../") {
-      relative_imports.push(import_source);
-    }
-  }
-  relative_imports
-}
+pub type FileSystemTree = std::collections::HashMap<String, FileNode>;
 
-#[derive(Serialize, Clone, Debug)]
-pub struct ImportedIdentifiers {
-  pub diagnostics: Vec<Diagnostic>,
-  pub id: String,
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct InputData {
+  pub file_content: Option<String>,
+  pub files: Option<FileSystemTree>,
+  pub entry: Option<String>,
+  pub language: Option<String>, // "svelte" or "vue" for a single-file component
+  pub debug: Option<bool>,
+  pub throw_statement_severity: Option<DiagnosticSeverityInput>,
+  pub function_throw_severity: Option<DiagnosticSeverityInput>,
+  pub call_to_throw_severity: Option<DiagnosticSeverityInput>,
+  pub call_to_imported_throw_severity: Option<DiagnosticSeverityInput>,
+  pub builtin_throw_severity: Option<DiagnosticSeverityInput>,
+  pub over_documented_severity: Option<DiagnosticSeverityInput>,
+  pub strict_entry_functions: Option<Vec<String>>,
+  pub collapse_throw_statements: Option<bool>,
+  pub merge_overlapping_diagnostics: Option<bool>,
+  pub error_type_severities: Option<HashMap<String, DiagnosticSeverityInput>>,
+  pub severity_overrides: Option<HashMap<String, DiagnosticSeverityInput>>,
+  pub error_factories: Option<Vec<ErrorFactoryConfig>>,
+  pub assertion_functions: Option<Vec<AssertionConfig>>,
+  pub max_call_depth: Option<usize>,
+  pub expected_error_types: Option<Vec<String>>,
+  pub treat_env_guarded_throws_as: Option<EnvGuardedThrows>,
+  pub terminator_functions: Option<Vec<String>>,
+  pub include_catch_rethrows: Option<bool>,
+  pub throws_tag_aliases: Option<Vec<String>>,
+  pub ignore_imports: Option<Vec<String>>,
+  pub flag_json_parse: Option<bool>,
+  pub treat_unknown_imports_as_throwing: Option<bool>,
+  pub include_try_statement_throws: Option<bool>,
+  pub ignore_statements: Option<Vec<String>>,
+  pub ignore_paths: Option<Vec<String>>,
+  pub include_call_graph: Option<bool>,
 }
 
-pub fn add_diagnostics_for_functions_that_throw(
-  diagnostics: &mut Vec<Diagnostic>,
-  functions_with_throws: HashSet<ThrowMap>,
-  cm: &SourceMap,
-  debug: Option<bool>,
-  throw_statement_severity: DiagnosticSeverity,
-  function_throw_severity: DiagnosticSeverity,
-  comments: &Lrc<dyn Comments>,
-  ignore_statements: &[String],
-) -> Vec<Span> { // Return the spans of @it-throws comments that were actually used
-  log("🔍 Starting add_diagnostics_for_functions_that_throw");
-  log(&format!("📊 Processing {} functions with throws", functions_with_throws.len()));
-  
-  // Track which @it-throws comments are actually used for suppression
-  let mut used_it_throws_spans = Vec::new();
-  
-  // WORKAROUND: Convert HashSet to Vec immediately to avoid corrupted hash table cleanup
-  log("🔧 Converting HashSet to Vec to avoid hash table corruption...");
-  let functions_vec: Vec<ThrowMap> = functions_with_throws.into_iter().collect();
-  log(&format!("✅ Successfully converted to Vec with {} functions", functions_vec.len()));
-  
-  log("🔍 About to iterate over functions_vec...");
-  for fun in &functions_vec {
-        log(&format!("🔍 Processing function: {}", fun.function_or_method_name));
-    
-    // Check if this function has @it-throws comment (will only suppress function-level diagnostics)
-    let it_throws_comment_span = has_it_throws_comment_with_span(comments, fun.throw_statement, ignore_statements);
-    let has_function_it_throws = it_throws_comment_span.is_some();
-    
-    if has_function_it_throws {
-      // Track that this comment was actually used for suppression
-      if let Some(comment_span) = it_throws_comment_span {
-        used_it_throws_spans.push(comment_span);
-      }
-      
-      if debug == Some(true) {
-        log(&format!("🔇 Function {} has @it-throws comment - applying comprehensive suppression", fun.function_or_method_name));
-      }
-    }
-    
-    log("🔍 Checking if debug mode is enabled...");
-    if debug == Some(true) {
-      log(&format!("🔍 Processing function: {} (id: {})", fun.function_or_method_name, fun.id));
-      log(&format!("  Details count: {}, Spans count: {}", fun.throw_details.len(), fun.throw_spans.len()));
+impl InputData {
+  fn user_settings(&self) -> UserSettings {
+    let defaults = UserSettings::default();
+    UserSettings {
+      include_try_statement_throws: self.include_try_statement_throws.unwrap_or(defaults.include_try_statement_throws),
+      ignore_statements: self.ignore_statements.clone().unwrap_or(defaults.ignore_statements),
+      debug: self.debug.unwrap_or(defaults.debug),
+      error_factories: self.error_factories.clone().unwrap_or(defaults.error_factories),
+      assertion_functions: self.assertion_functions.clone().unwrap_or(defaults.assertion_functions),
+      max_call_depth: self.max_call_depth.unwrap_or(defaults.max_call_depth),
+      expected_error_types: self.expected_error_types.clone().unwrap_or(defaults.expected_error_types),
+      treat_env_guarded_throws_as: self.treat_env_guarded_throws_as.unwrap_or(defaults.treat_env_guarded_throws_as),
+      terminator_functions: self.terminator_functions.clone().unwrap_or(defaults.terminator_functions),
+      include_catch_rethrows: self.include_catch_rethrows.unwrap_or(defaults.include_catch_rethrows),
+      throws_tag_aliases: self.throws_tag_aliases.clone().unwrap_or(defaults.throws_tag_aliases),
+      ignore_imports: self.ignore_imports.clone().unwrap_or(defaults.ignore_imports),
+      flag_json_parse: self.flag_json_parse.unwrap_or(defaults.flag_json_parse),
     }
-    log("✅ Debug check completed");
-
-    log("🔍 Performing defensive length check...");
-    // Defensive check: ensure throw_details and throw_spans have matching lengths
-    if fun.throw_details.len() != fun.throw_spans.len() {
-      log(&format!("⚠️ Vector length mismatch in {}: {} details vs {} spans",
-        fun.function_or_method_name,
-        fun.throw_details.len(),
-        fun.throw_spans.len()
-      ));
-      // Skip this function to prevent panic
-      continue;
+  }
+
+  fn diagnostic_settings(&self, user_settings: &UserSettings) -> DiagnosticSettings {
+    let severity = |input: &Option<DiagnosticSeverityInput>, default: DiagnosticSeverity| {
+      input.clone().map(DiagnosticSeverity::from).unwrap_or(default)
+    };
+    DiagnosticSettings {
+      throw_statement_severity: severity(&self.throw_statement_severity, DiagnosticSeverity::Hint),
+      function_throw_severity: severity(&self.function_throw_severity, DiagnosticSeverity::Hint),
+      call_to_throw_severity: severity(&self.call_to_throw_severity, DiagnosticSeverity::Hint),
+      call_to_imported_throw_severity: severity(&self.call_to_imported_throw_severity, DiagnosticSeverity::Hint),
+      builtin_throw_severity: severity(&self.builtin_throw_severity, DiagnosticSeverity::Hint),
+      over_documented_severity: severity(&self.over_documented_severity, DiagnosticSeverity::Information),
+      error_type_severities: self
+        .error_type_severity_inputs()
+        .into_iter()
+        .map(|(error_type, (_, severity))| (error_type.to_string(), DiagnosticSeverity::from(severity.clone())))
+        .collect(),
+      strict_entry_functions: self.strict_entry_functions.clone().unwrap_or_default(),
+      collapse_throw_statements: self.collapse_throw_statements.unwrap_or(false),
+      merge_overlapping_diagnostics: self.merge_overlapping_diagnostics.unwrap_or(true),
+      treat_unknown_imports_as_throwing: self.treat_unknown_imports_as_throwing == Some(true),
+      include_call_graph: self.include_call_graph.unwrap_or(false),
+      ..DiagnosticSettings::from_user_settings(user_settings)
     }
-    log("✅ Length check passed");
+  }
 
-    log("🔍 Looking up function_start character position...");
-    let (function_start_line, function_start_col) = safe_lookup_char_pos(cm, fun.throw_statement.lo());
-    log("✅ function_start looked up successfully");
-    
-    log("🔍 Getting line_end_byte_pos...");
-    let line_end_byte_pos =
-      get_line_end_byte_pos(cm, fun.throw_statement.lo(), fun.throw_statement.hi());
-    log("✅ line_end_byte_pos calculated successfully");
-
-    log("🔍 Looking up function_end character position...");
-    let (function_end_line, function_end_col) = safe_lookup_char_pos(cm, line_end_byte_pos - BytePos(1));
-    log("✅ function_end looked up successfully");
-
-    log("🔍 Getting start_character_byte_pos...");
-    let start_character_byte_pos =
-      get_line_start_byte_pos(cm, fun.throw_statement.lo(), fun.throw_statement.hi());
-    log("✅ start_character_byte_pos calculated successfully");
-    
-    log("🔍 Looking up start_character position...");
-    let (_start_character_line, start_character_col) = safe_lookup_char_pos(cm, start_character_byte_pos);
-    log("✅ start_character looked up successfully");
-
-    if debug == Some(true) {
-      log(&format!("Function throws: {}", fun.function_or_method_name));
-      log(&format!(
-        "From line {} column {} to line {} column {}",
-        function_start_line,
-        function_start_col,
-        function_end_line,
-        function_end_col
-      ));
+  /// The per-type severities from `error_type_severities` and `severity_overrides`, each with the
+  /// key it came from; `severity_overrides` wins for a type set in both
+  fn error_type_severity_inputs(&self) -> HashMap<&str, (&'static str, &DiagnosticSeverityInput)> {
+    let mut inputs = HashMap::new();
+    for (key, severities) in [
+      ("error_type_severities", &self.error_type_severities),
+      ("severity_overrides", &self.severity_overrides),
+    ] {
+      for (error_type, severity) in severities.iter().flatten() {
+        inputs.insert(error_type.as_str(), (key, severity));
+      }
     }
+    inputs
+  }
 
-    // Filter throw_details based on throws_annotation
-    let (filtered_throw_details, filtered_throw_spans): (Vec<_>, Vec<_>) = if let Some(annotation) = &fun.throws_annotation {
-      // Collect all error types from the annotation for easy lookup
-      let annotated_types: Vec<String> = annotation
-        .error_types
-        .iter()
-        .cloned()
-        .collect();
+  /// A warning for every configured severity that isn't a known level or alias
+  fn severity_parse_warnings(&self) -> Vec<String> {
+    let mut settings: Vec<(String, &DiagnosticSeverityInput)> = [
+      ("throw_statement_severity", &self.throw_statement_severity),
+      ("function_throw_severity", &self.function_throw_severity),
+      ("call_to_throw_severity", &self.call_to_throw_severity),
+      ("call_to_imported_throw_severity", &self.call_to_imported_throw_severity),
+      ("builtin_throw_severity", &self.builtin_throw_severity),
+      ("over_documented_severity", &self.over_documented_severity),
+    ]
+    .iter()
+    .filter_map(|(name, severity)| severity.as_ref().map(|severity| (name.to_string(), severity)))
+    .collect();
+    let mut overrides: Vec<(String, &DiagnosticSeverityInput)> = self
+      .error_type_severity_inputs()
+      .into_iter()
+      .map(|(error_type, (key, severity))| (format!("{}.{}", key, error_type), severity))
+      .collect();
+    overrides.sort_by(|a, b| a.0.cmp(&b.0));
+    settings.extend(overrides);
 
-      // Debug: log filtering process
-      if debug == Some(true) {
-        log(&format!("🔍 Filtering {} - documented types: {:?}", fun.function_or_method_name, annotated_types));
-        log(&format!("   Original throw details count: {}", fun.throw_details.len()));
-        for (i, detail) in fun.throw_details.iter().enumerate() {
-          log(&format!("   Detail {}: error_type={:?}", i, detail.error_type));
-        }
-      }
+    settings
+      .into_iter()
+      .filter(|(_, severity)| DiagnosticSeverity::from_str(&severity.0).is_err())
+      .map(|(name, severity)| format!("Unrecognized severity \"{}\" for {}, using Hint", severity.0, name))
+      .collect()
+  }
+}
 
-      let filtered: Vec<_> = if fun.throw_details.len() == fun.throw_spans.len() {
-        fun.throw_details
-          .iter()
-          .zip(fun.throw_spans.iter())
-          .filter(|(detail, _span)| {
-            // Only keep throw_details whose error_type is NOT in the annotation
-            let is_documented = annotated_types.iter().any(|ann| {
-              match &detail.error_type {
-                Some(error_type) => ann == error_type,
-                None => false,
-              }
-            });
-            
-            if debug == Some(true) {
-              log(&format!("   Detail {:?} is documented: {} (keeping: {})", detail.error_type, is_documented, !is_documented));
-            }
-            
-            !is_documented
-          })
-          .map(|(detail, span)| (detail.clone(), *span))
-          .collect()
-      } else {
-        // Vector length mismatch - filter only details and duplicate the first span
-        log(&format!("⚠️ Vector length mismatch during filtering in {}: {} details vs {} spans - using safe fallback", 
-          fun.function_or_method_name, 
-          fun.throw_details.len(), 
-          fun.throw_spans.len()
-        ));
-        let fallback_span = fun.throw_spans.first().copied().unwrap_or(swc_common::DUMMY_SP);
-        fun.throw_details
-          .iter()
-          .filter(|detail| {
-            let is_documented = annotated_types.iter().any(|ann| {
-              match &detail.error_type {
-                Some(error_type) => ann == error_type,
-                None => false,
-              }
-            });
-            !is_documented
-          })
-          .map(|detail| (detail.clone(), fallback_span))
-          .collect()
-      };
+const PARSED_MODULE_CACHE_SIZE: usize = 8;
 
-      if debug == Some(true) {
-        log(&format!("   Filtered throw details count: {}", filtered.len()));
-      }
+/// A parsed file kept around so unchanged contents skip lexing and parsing
+struct CachedModule {
+  content_hash: u64,
+  content: String,
+  start_pos: BytePos,
+  module: Module,
+  comments: SingleThreadedComments,
+  recovered_errors: Vec<ParseError>,
+}
 
-      filtered.into_iter().unzip()
-    } else {
-      // No annotation, keep all
-      (fun.throw_details.clone(), fun.throw_spans.clone())
-    };
+thread_local! {
+  // Most recently used first
+  static PARSED_MODULE_CACHE: RefCell<VecDeque<CachedModule>> = const { RefCell::new(VecDeque::new()) };
+}
 
-    // Only push a function-level diagnostic if there is at least one undocumented throw AND function doesn't have @it-throws
-    if !filtered_throw_details.is_empty() && !has_function_it_throws {
-      // Extract and format error type names for cleaner message
-      let mut error_types: Vec<String> = filtered_throw_details
-        .iter()
-        .filter_map(|detail| detail.error_type.clone())
-        .collect();
-      // Deduplicate and sort for stable output
-      error_types.sort();
-      error_types.dedup();
-      let format_types = |types: &Vec<String>| -> String {
-        if types.is_empty() { "".to_string() } else { format!("{{{}}}", types.join(", ")) }
-      };
+fn hash_content(content: &str) -> u64 {
+  let mut hasher = std::collections::hash_map::DefaultHasher::new();
+  content.hash(&mut hasher);
+  hasher.finish()
+}
 
-      // Prefer a friendlier message when the function name is "<anonymous>"
-      let is_anonymous = fun.function_or_method_name == "<anonymous>";
-      let message = if is_anonymous {
-        if error_types.is_empty() {
-          "Anonymous function may throw".to_string()
-        } else {
-          format!("Anonymous function may throw: {}", format_types(&error_types))
-        }
-      } else if !error_types.is_empty() {
-        format!(
-          "Function {} may throw: {}",
-          fun.function_or_method_name,
-          format_types(&error_types)
-        )
-      } else {
-        // Fallback for cases where error_type is None
-        format!(
-          "Function {} may throw",
-          fun.function_or_method_name
-        )
-      };
+/// Comments share their maps between clones, so cached comments must be copied out
+fn copy_comments(comments: &SingleThreadedComments) -> SingleThreadedComments {
+  let (leading, trailing) = comments.borrow_all();
+  SingleThreadedComments::from_leading_and_trailing(
+    Rc::new(RefCell::new(leading.clone())),
+    Rc::new(RefCell::new(trailing.clone())),
+  )
+}
 
-      diagnostics.push(Diagnostic {
-        severity: function_throw_severity.to_int(),
-        range: DiagnosticRange {
-          start: DiagnosticPosition {
-            line: function_start_line,
-            character: start_character_col,
-          },
-          end: DiagnosticPosition {
-            line: function_end_line,
-            character: function_end_col,
-          },
-        },
-        message: message,
-        source: "Does it Throw?".to_string(),
-      });
-    }
+/// `analyze_code` backed by a small LRU of parsed modules keyed by content hash
+fn analyze_code_cached(
+  content: &str,
+  user_settings: &UserSettings,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
+  let cm: Lrc<SourceMap> = Default::default();
+  let fm = cm.new_source_file(swc_common::FileName::Anon, content.into());
+  let content_hash = hash_content(content);
+
+  let cached = PARSED_MODULE_CACHE.with(|cache| {
+    let mut cache = cache.borrow_mut();
+    let index = cache.iter().position(|entry| {
+      entry.content_hash == content_hash && entry.start_pos == fm.start_pos && entry.content == content
+    })?;
+    let entry = cache.remove(index)?;
+    let parsed = (entry.module.clone(), copy_comments(&entry.comments), entry.recovered_errors.clone());
+    cache.push_front(entry);
+    Some(parsed)
+  });
+  if let Some((module, comments, recovered_errors)) = cached {
+    let (mut result, cm, comments) = analyze_module(&module, cm, Lrc::new(comments), user_settings);
+    result.parse_errors = recovered_errors;
+    return (result, cm, comments);
+  }
 
-    // Push throw statement diagnostics for undocumented throws
-    // Apply comprehensive suppression: if function has @it-throws, suppress ALL diagnostics including throw statements
-    if !filtered_throw_details.is_empty() && !has_function_it_throws {
-      for span in &filtered_throw_spans {
-        let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
-        let (end_line, end_col) = safe_lookup_char_pos(cm, span.hi());
-
-        diagnostics.push(Diagnostic {
-          severity: throw_statement_severity.to_int(),
-          range: DiagnosticRange {
-            start: DiagnosticPosition {
-              line: start_line,
-              character: start_col,
-            },
-            end: DiagnosticPosition {
-              line: end_line,
-              character: end_col,
-            },
-          },
-          message: "Throw statement.".to_string(),
-          source: "Does it Throw?".to_string(),
+  match parse_source_file(&fm) {
+    Ok((module, comments, recovered_errors)) => {
+      PARSED_MODULE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.push_front(CachedModule {
+          content_hash,
+          content: content.to_string(),
+          start_pos: fm.start_pos,
+          module: module.clone(),
+          comments: copy_comments(&comments),
+          recovered_errors: recovered_errors.clone(),
         });
-      }
+        cache.truncate(PARSED_MODULE_CACHE_SIZE);
+      });
+      let (mut result, cm, comments) = analyze_module(&module, cm, comments, user_settings);
+      result.parse_errors = recovered_errors;
+      (result, cm, comments)
     }
+    // Let `analyze_code` report the parse failure
+    Err(_) => analyze_code(content, Default::default(), user_settings),
   }
-  log("✅ Completed processing all functions, about to exit safely");
-  used_it_throws_spans
 }
 
-pub fn add_diagnostics_for_exhaustive_catches(
-  diagnostics: &mut Vec<Diagnostic>,
-  catch_analyses: &[what_does_it_throw::try_catch_finder::CatchAnalysis],
-  cm: &SourceMap,
-  debug: Option<bool>,
-) {
-  for catch_analysis in catch_analyses {
-    let (pos_line, pos_col) = safe_lookup_char_pos(cm, catch_analysis.catch_span.lo());
-    
-    // Only show diagnostics for catches that have validation errors
-    if catch_analysis.has_validation_errors() {
-      let missing = catch_analysis.missing_handlers.join(", ");
-      let message = if catch_analysis.has_escape_hatch {
-        format!("Catch block uses escape hatch but still missing handlers for: {}", missing)
-      } else {
-        format!("Exhaustive catch is missing handlers for: {}. Add handlers or use 'throw e' as escape hatch.", missing)
-      };
-      
-      if debug == Some(true) {
-        log(&format!("❌ Exhaustive catch error at line {}: {}", pos_line, message));
-      }
+/// What one file contributes to multi-file analysis on its own, before calls are linked across files.
+/// `Analyzer` keeps these between edits so unchanged files aren't analyzed again.
+#[derive(Clone)]
+struct FileThrows {
+  functions_with_throws: HashSet<ThrowMap>,
+  builtin_throw_calls: Vec<BuiltinThrowCall>,
+  import_sources: HashSet<String>,
+  imported_identifiers: Vec<String>,
+  functions: HashSet<FunctionMap>,
+  param_throws: HashMap<String, Vec<Vec<String>>>,
+  class_parents: HashMap<String, String>,
+  error_factories: HashMap<String, String>,
+  ambient_throws: HashSet<ThrowMap>,
+  exports: ExportFinder,
+}
 
-      diagnostics.push(Diagnostic {
-        severity: DiagnosticSeverity::Error.to_int(),
-        range: DiagnosticRange {
-          start: DiagnosticPosition {
-            line: pos_line,
-            character: pos_col,
-          },
-          end: DiagnosticPosition {
-            line: pos_line,
-            character: pos_col + 10, // Highlight "catch" keyword
-          },
-        },
-        message,
-        source: "Does it Throw?".to_string(),
-      });
-    } else if catch_analysis.has_escape_hatch && debug == Some(true) {
-      // Info message for successful escape hatch usage
-      log(&format!("✅ Catch at line {} uses escape hatch correctly", pos_line));
-    } else if catch_analysis.is_exhaustive() && debug == Some(true) {
-      // Info message for complete exhaustive catches
-      log(&format!("✅ Catch at line {} is complete", pos_line));
-    }
+fn collect_file_throws(
+  module: &Module,
+  comments: &Lrc<SingleThreadedComments>,
+  next_line_comments: &[NextLineComment],
+  user_settings: &UserSettings,
+) -> FileThrows {
+  let throw_settings = ThrowFinderSettings {
+    ignore_statements: &user_settings.ignore_statements,
+    include_try_statements: &user_settings.include_try_statement_throws,
+    error_factories: &user_settings.error_factories,
+    assertion_functions: &user_settings.assertion_functions,
+    throws_tag_aliases: &user_settings.throws_tag_aliases,
+    next_line_comments,
+  };
+  // Classes in this file are documented by the @throws of interfaces declared alongside them
+  let mut interface_finder =
+    InterfaceFinder::new(comments.clone()).with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  interface_finder.visit_module(module);
+  let mut type_registry = TypeRegistry::new();
+  type_registry.interface_method_throws = interface_finder.method_throws;
+  type_registry.class_interfaces = interface_finder.class_interfaces;
+  type_registry.interface_parents = interface_finder.interface_parents;
+
+  let mut throw_analyzer = ThrowAnalyzer {
+    type_registry,
+    ..ThrowAnalyzer::new(comments.clone(), throw_settings, user_settings)
+  };
+  throw_analyzer.visit_module(module);
+
+  let mut function_finder = FunctionFinder::new(comments.clone()).with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  function_finder.visit_module(module);
+
+  let mut exports = ExportFinder::default();
+  exports.visit_module(module);
+
+  FileThrows {
+    functions_with_throws: throw_analyzer.functions_with_throws,
+    builtin_throw_calls: throw_analyzer.builtin_throw_calls,
+    import_sources: throw_analyzer.import_sources,
+    imported_identifiers: throw_analyzer.imported_identifiers,
+    ambient_throws: function_finder.ambient_throws,
+    functions: function_finder.functions,
+    param_throws: function_finder.param_throws,
+    class_parents: function_finder.class_parents,
+    error_factories: function_finder.error_factories,
+    exports,
   }
 }
 
-pub fn add_diagnostics_for_unused_it_throws_comments(
-  diagnostics: &mut Vec<Diagnostic>,
-  unused_comment_spans: &[swc_common::Span],
-  cm: &SourceMap,
-  debug: Option<bool>,
-) {
-  for span in unused_comment_spans {
-    let (pos_line, pos_col) = safe_lookup_char_pos(cm, span.lo);
-    
-    if debug == Some(true) {
-      log(&format!("❌ Unused @it-throws comment at line {}", pos_line));
-    }
+/// Links calls in the `linked` files against the throws of every file in `files`.
+/// Returns the result for the linked files and their throw spans for proximity checks.
+fn link_files(
+  files: &[(&str, &Module, &FileThrows)],
+  linked: &[usize],
+  comments: &Lrc<SingleThreadedComments>,
+  next_line_comments: &[NextLineComment],
+  user_settings: &UserSettings,
+) -> (AnalysisResult, Vec<Span>) {
+  let linked_files: Vec<&(&str, &Module, &FileThrows)> = linked.iter().map(|index| &files[*index]).collect();
+
+  let mut functions_with_throws: HashSet<ThrowMap> = HashSet::new();
+  let mut all_functions: HashSet<FunctionMap> = HashSet::new();
+  let mut error_factories: HashMap<String, String> = HashMap::new();
+  let mut param_throws: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+  let mut class_parents: HashMap<String, String> = HashMap::new();
+  let mut builtin_throw_calls: Vec<BuiltinThrowCall> = Vec::new();
+  for (_, _, file_throws) in files {
+    functions_with_throws.extend(file_throws.functions_with_throws.iter().cloned());
+    all_functions.extend(file_throws.functions.iter().cloned());
+    error_factories.extend(file_throws.error_factories.clone());
+    param_throws.extend(file_throws.param_throws.clone());
+    class_parents.extend(file_throws.class_parents.clone());
+    builtin_throw_calls.extend(file_throws.builtin_throw_calls.iter().cloned());
+  }
 
-    diagnostics.push(Diagnostic {
-      severity: DiagnosticSeverity::Information.to_int(),
-      range: DiagnosticRange {
-        start: DiagnosticPosition {
-          line: pos_line,
-          character: pos_col,
-        },
-        end: DiagnosticPosition {
-          line: pos_line,
-          character: pos_col + 12, // Length of "// @it-throws"
-        },
-      },
-      message: "Unused @it-throws comment. This comment is not suppressing any diagnostics.".to_string(),
-      source: "Does it Throw?".to_string(),
+  // Factories may live in another file than the throws that call them
+  functions_with_throws = resolve_error_factory_throws(functions_with_throws, &error_factories);
+  // `declare function` stubs (e.g. hand-written .d.ts files) stand in for their @throws contract,
+  // unless another file implements them
+  let mut ambient_function_finder = FunctionFinder::new(comments.clone());
+  for (_, _, file_throws) in files {
+    ambient_function_finder.ambient_throws.extend(file_throws.ambient_throws.iter().cloned());
+  }
+  ambient_function_finder.functions = all_functions;
+  functions_with_throws.extend(ambient_function_finder.unimplemented_ambient_throws());
+  // Error classes may extend a base class declared in another file
+  let mut type_registry = TypeRegistry::new();
+  type_registry.class_parents = class_parents.clone();
+  functions_with_throws = resolve_error_ancestors(functions_with_throws, &type_registry);
+  let all_functions = ambient_function_finder.functions;
+  functions_with_throws = attach_function_name_spans(functions_with_throws, &all_functions);
+  if user_settings.treat_env_guarded_throws_as == EnvGuardedThrows::Exclude {
+    functions_with_throws = exclude_env_guarded_throws(functions_with_throws);
+  }
+
+  let mut call_finder = CallFinder::new(comments.clone());
+  call_finder.functions_with_throws = functions_with_throws.clone();
+  call_finder.builtin_throw_calls = builtin_throw_calls;
+  call_finder.param_throws = param_throws;
+  call_finder.next_line_comments = next_line_comments.to_vec();
+  // Imports renamed on the way, by an alias or a re-exporting barrel file, resolve to the declared name
+  let module_exports: Vec<(&str, &ExportFinder)> =
+    files.iter().map(|(path, _, file_throws)| (*path, &file_throws.exports)).collect();
+  let mut import_aliases = resolve_import_aliases(&module_exports);
+  for index in linked {
+    call_finder.import_aliases = std::mem::take(&mut import_aliases[*index]);
+    call_finder.visit_module(files[*index].1);
+  }
+  call_finder.import_aliases = HashMap::new();
+
+  let (functions_with_throws, calls_to_throws) = merge_higher_order_callback_throws(
+    functions_with_throws,
+    call_finder.calls.clone(),
+    &call_finder.higher_order_callbacks,
+    &call_finder.inline_callback_allowed_throws,
+  );
+  let mut functions_with_throws = apply_inherited_throws_annotations(functions_with_throws, &all_functions, &class_parents);
+  // Other files' throws only resolve calls; their own diagnostics belong to those files
+  if linked.len() < files.len() {
+    functions_with_throws.retain(|throw_map| {
+      linked_files.iter().any(|(_, module, _)| module.span.contains(throw_map.throw_statement))
     });
   }
-}
 
-pub fn add_diagnostics_for_calls_to_throws(
-  diagnostics: &mut Vec<Diagnostic>,
-  calls_to_throws: HashSet<CallToThrowMap>,
-  functions_with_throws: &HashSet<ThrowMap>,
-  all_functions: &HashSet<what_does_it_throw::function_finder::FunctionMap>,
-  cm: &SourceMap,
-  debug: Option<bool>,
-  call_to_throw_severity: DiagnosticSeverity,
-  _comments: &Lrc<dyn Comments>,
-  _ignore_statements: &[String],
-  suppressed_functions: &HashSet<String>,
-) {
-  for call in &calls_to_throws {
-    // Check if this call is in a function that has @it-throws comment (comprehensive suppression)  
-    if suppressed_functions.contains(&call.call_function_or_method_name) {
-      if debug == Some(true) {
-        log(&format!("🔇 Skipping call diagnostic for {} due to @it-throws comment on calling function", call.call_function_or_method_name));
-      }
-      continue; // Skip this call diagnostic entirely
-    }
-    
-    // Check if the calling function has JSDoc annotations that cover the called function's errors
-    let should_suppress_call = should_suppress_call_diagnostic(call, functions_with_throws, all_functions, debug);
-
-    if should_suppress_call {
-      if debug == Some(true) {
-        log(&format!(
-          "✅ Suppressing call to {} - calling function has proper JSDoc documentation",
-          call.call_function_or_method_name
-        ));
-      }
-      continue; // Skip this call diagnostic
-    }
+  let imported_identifiers: Vec<String> = linked_files
+    .iter()
+    .flat_map(|(_, _, file_throws)| file_throws.imported_identifiers.iter().cloned())
+    .collect();
+  let mut import_usage_finder = ImportUsageFinder {
+    imported_identifiers: imported_identifiers.clone(),
+    imported_identifier_usages: HashSet::new(),
+    current_class_name: None,
+    current_method_name: None,
+    function_name_stack: Vec::new(),
+  };
+  let mut try_catch_finder = TryCatchFinder::new(comments.clone());
+  try_catch_finder.terminator_functions = user_settings.terminator_functions.clone();
+  try_catch_finder.include_catch_rethrows = user_settings.include_catch_rethrows;
+  // Collect all throw spans for proximity detection
+  let mut all_throw_spans = Vec::new();
+  for (_, module, _) in &linked_files {
+    import_usage_finder.visit_module(module);
+    try_catch_finder.visit_module(module);
+    all_throw_spans.extend(throw_statement_spans(module));
+  }
 
-    let (call_start_line, call_start_col) = safe_lookup_char_pos(cm, call.call_span.lo());
-    let line_end_byte_pos = get_line_end_byte_pos(cm, call.call_span.lo(), call.call_span.hi());
-    let (call_end_line, call_end_col) = safe_lookup_char_pos(cm, line_end_byte_pos - BytePos(1));
-
-    if debug == Some(true) {
-      log(&format!(
-        "⚠️  Function call may throw: {}",
-        call.call_function_or_method_name
-      ));
-      log(&format!(
-        "From line {} column {} to line {} column {}",
-        call_start_line, call_start_col, call_end_line, call_end_col
-      ));
-    }
+  // Files reached only through re-exporting barrels, by the source they would be imported with
+  let reexported_sources: Vec<String> = linked
+    .iter()
+    .flat_map(|index| {
+      let from_path = files[*index].0;
+      let sources = files[*index].2.import_sources.iter().map(String::as_str);
+      reexported_modules(&module_exports, *index, sources)
+        .into_iter()
+        .map(move |reached| relative_specifier(from_path, files[reached].0))
+    })
+    .collect();
 
-    // Include error types from the called function if available
-    let mut called_error_types: Vec<String> = call.throw_map.throw_details
+  let results = AnalysisResult {
+    functions_with_throws,
+    calls_to_throws,
+    builtin_throw_calls: linked_files
       .iter()
-      .filter_map(|d| d.error_type.clone())
-      .collect();
-    called_error_types.sort();
-    called_error_types.dedup();
-    let call_message = if called_error_types.is_empty() {
-      "Function call may throw: {Error}.".to_string()
-    } else {
-      format!("Function call may throw: {{{}}}.", called_error_types.join(", "))
-    };
+      .flat_map(|(_, _, file_throws)| file_throws.builtin_throw_calls.iter().cloned())
+      .collect(),
+    import_sources: linked_files
+      .iter()
+      .flat_map(|(_, _, file_throws)| file_throws.import_sources.iter().cloned())
+      .chain(reexported_sources)
+      .collect(),
+    imported_identifiers,
+    imported_identifier_usages: import_usage_finder.imported_identifier_usages,
+    catch_analyses: try_catch_finder.all_catches,
+    unused_it_throws_comments: Vec::new(),
+    class_ignore_comments: linked_files
+      .iter()
+      .flat_map(|(_, module, _)| class_ignore_comments(module, &**comments, &user_settings.ignore_statements))
+      .collect(),
+    throw_statement_spans: all_throw_spans.clone(),
+    all_functions,
+    inline_callback_allowed_throws: call_finder.inline_callback_allowed_throws,
+    parse_errors: Vec::new(),
+    debug_log: Vec::new(),
+    incremental: IncrementalState::default(),
+  };
 
-    diagnostics.push(Diagnostic {
-      severity: call_to_throw_severity.to_int(),
-      range: DiagnosticRange {
-        start: DiagnosticPosition {
-          line: call_start_line,
-          character: call_start_col,
-        },
-        end: DiagnosticPosition {
-          line: call_end_line,
-          character: call_end_col,
-        },
-      },
-      message: call_message,
-      source: "Does it Throw?".to_string(),
-    });
-  }
+  (results, all_throw_spans)
 }
 
-/// Generate "Function X may throw" diagnostics for functions that call throwing functions
-/// This handles transitive throwing - functions that don't directly throw but call functions that do
-pub fn add_diagnostics_for_calling_functions_that_may_throw(
-  diagnostics: &mut Vec<Diagnostic>,
-  calls_to_throws: &HashSet<CallToThrowMap>,
-  functions_with_throws: &HashSet<ThrowMap>,
+/// Parse a file of a multi-file analysis into the shared `SourceMap` and comments, along with the
+/// syntax errors the parser recovered from
+fn parse_file_into(
+  path: &str,
+  contents: &str,
   cm: &SourceMap,
-  function_throw_severity: DiagnosticSeverity,
-  debug: Option<bool>,
-  suppressed_functions: &HashSet<String>,
-) {
-  // Collect unique calling functions that call throwing functions
-  let mut calling_functions: std::collections::HashMap<String, (swc_common::Span, String)> = std::collections::HashMap::new();
-  
-  for call in calls_to_throws {
-    // Skip if the calling function is suppressed
-    if suppressed_functions.contains(&call.call_function_or_method_name) {
-      continue;
-    }
-    
-    // Skip if this function already has a direct throw (already covered by add_diagnostics_for_functions_that_throw)
-    let is_already_throwing = functions_with_throws.iter().any(|throw_map| {
-      throw_map.function_or_method_name == call.call_function_or_method_name
-    });
-    
-    if !is_already_throwing {
-      // Use the call span to represent the function (this will be the line where the call happens)
-      // For better UX, we could try to find the actual function declaration span, but call span works
-      calling_functions.insert(
-        call.call_function_or_method_name.clone(),
-        (call.call_span, call.call_function_or_method_name.clone())
-      );
-      
-      if debug == Some(true) {
-        log(&format!("🔧 Found calling function that may throw: {}", call.call_function_or_method_name));
-      }
-    }
-  }
-  
-  // Generate diagnostics for each unique calling function
-  for (function_name, (span, _)) in calling_functions {
-    let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
-    let line_end_byte_pos = get_line_end_byte_pos(cm, span.lo(), span.hi());
-    let (end_line, end_col) = safe_lookup_char_pos(cm, line_end_byte_pos);
-    // Aggregate error types from calls within this function
-    let mut types: Vec<String> = calls_to_throws
-      .iter()
-      .filter(|c| c.call_function_or_method_name == function_name)
-      .flat_map(|c| c.throw_map.throw_details.iter().filter_map(|d| d.error_type.clone()))
-      .collect();
-    types.sort();
-    types.dedup();
-    let message = if types.is_empty() {
-      format!("Function {} may throw", function_name)
-    } else {
-      format!("Function {} may throw: {{{}}}", function_name, types.join(", "))
-    };
-    
-    if debug == Some(true) {
-      log(&format!("⚠️  {}", message));
-    }
-    
-    diagnostics.push(Diagnostic {
-      severity: function_throw_severity.to_int(),
-      range: DiagnosticRange {
-        start: DiagnosticPosition {
-          line: start_line,
-          character: start_col,
-        },
-        end: DiagnosticPosition {
-          line: end_line,
-          character: end_col,
-        },
-      },
-      message,
-      source: "Does it Throw?".to_string(),
-    });
-  }
+  comments: &SingleThreadedComments,
+) -> Result<(Module, Vec<ParseError>), ParseError> {
+  // Svelte and Vue components only parse from their <script> blocks
+  let contents = if is_component(path) { component_script_source(contents) } else { contents.to_string() };
+  let file = cm.new_source_file(
+    swc_common::FileName::Custom(path.to_string()),
+    contents,
+  );
+  let mut parser = swc_ecma_parser::Parser::new(
+    swc_ecma_parser::Syntax::Typescript(swc_ecma_parser::TsConfig {
+      decorators: true,
+      tsx: true,
+      dts: path.ends_with(".d.ts"),
+      ..Default::default()
+    }),
+    swc_ecma_parser::StringInput::from(&*file),
+    Some(comments),
+  );
+  let module = parser.parse_module().map_err(|error| ParseError::from(&error))?;
+  let recovered_errors = parser.take_errors().iter().map(ParseError::from).collect();
+  Ok((module, recovered_errors))
 }
 
-fn should_suppress_call_diagnostic(
-  call: &CallToThrowMap,
-  functions_with_throws: &HashSet<ThrowMap>,
-  all_functions: &HashSet<what_does_it_throw::function_finder::FunctionMap>,
-  debug: Option<bool>,
-) -> bool {
-  // Extract the calling function name from the call.id
-  // Format is typically "ClassName-functionName" or "NOT_SET-functionName"
-  let calling_function_name = if let Some(dash_pos) = call.id.rfind('-') {
-    &call.id[dash_pos + 1..]
-  } else {
-    &call.id
-  };
-
-  if debug == Some(true) {
-    log(&format!("🔍 Checking call suppression for calling function: {}", calling_function_name));
-  }
-
-  // Find the calling function in functions_with_throws
-  let calling_function = functions_with_throws
-    .iter()
-    .find(|f| f.function_or_method_name == calling_function_name);
-
-  if let Some(caller) = calling_function {
-    if let Some(caller_annotation) = &caller.throws_annotation {
-      // Get the error types that the called function can throw
-      let called_error_types: Vec<String> = call.throw_map.throw_details
-        .iter()
-        .filter_map(|d| d.error_type.clone())
-        .collect();
-
-      if debug == Some(true) {
-        log(&format!("  📚 Caller documents: {:?}", caller_annotation.error_types));
-        log(&format!("  🎯 Called function throws: {:?}", called_error_types));
-      }
-
-      // Check if all called function's error types are documented by the caller
-      let all_errors_documented = called_error_types.iter().all(|error_type| {
-        caller_annotation.error_types.contains(error_type)
-      });
-
-      if debug == Some(true) {
-        log(&format!("  ✅ All errors documented: {}", all_errors_documented));
-      }
-
-      return all_errors_documented;
-    } else {
-      if debug == Some(true) {
-        log(&format!("  ❌ Calling function has no JSDoc annotation"));
+/// Whether `path` matches the glob `pattern`: `**` spans any number of directories, `*` and `?`
+/// stay within one segment. A pattern without a `/` matches the file name in any directory.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+  fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+      None => path.is_empty(),
+      Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+      Some((segment, rest)) => {
+        !path.is_empty() && wildcard_matches(segment, path[0]) && segments_match(rest, &path[1..])
       }
     }
-  } else {
-    if debug == Some(true) {
-      log(&format!("  ❓ Calling function not found in throwing functions, checking all functions for JSDoc"));
-    }
-    // If the calling function is not in functions_with_throws, it means it doesn't throw directly
-    // But it might still have JSDoc annotations covering the called errors
-    // Check in all_functions for JSDoc annotations
-    let calling_function_in_all = all_functions
-      .iter()
-      .find(|f| f.name == calling_function_name);
-      
-    if let Some(caller) = calling_function_in_all {
-      if let Some(caller_annotation) = &caller.throws_annotation {
-        // Get the error types that the called function can throw
-        let called_error_types: Vec<String> = call.throw_map.throw_details
-          .iter()
-          .filter_map(|d| d.error_type.clone())
-          .collect();
-
-        if debug == Some(true) {
-          log(&format!("  📚 Non-throwing caller documents: {:?}", caller_annotation.error_types));
-          log(&format!("  🎯 Called function throws: {:?}", called_error_types));
-        }
-
-        // Check if all called function's error types are documented by the caller
-        let all_errors_documented = called_error_types.iter().all(|error_type| {
-          caller_annotation.error_types.contains(error_type)
-        });
+  }
 
-        if debug == Some(true) {
-          log(&format!("  ✅ All errors documented by non-throwing function: {}", all_errors_documented));
-        }
+  let pattern = pattern.trim_start_matches("./");
+  let pattern = if pattern.contains('/') { pattern.to_string() } else { format!("**/{}", pattern) };
+  let pattern_segments: Vec<&str> = pattern.split('/').filter(|segment| !segment.is_empty()).collect();
+  let path_segments: Vec<&str> = path.trim_start_matches("./").split('/').filter(|segment| !segment.is_empty()).collect();
+  segments_match(&pattern_segments, &path_segments)
+}
 
-        return all_errors_documented;
-      } else {
-        if debug == Some(true) {
-          log(&format!("  ❌ Non-throwing calling function has no JSDoc annotation"));
+/// Flatten a virtual file tree into (path, contents), along with the paths matched by `ignore_paths`
+fn flatten_file_tree(tree: &FileSystemTree, ignore_paths: &[String]) -> (Vec<(String, String)>, HashSet<String>) {
+  fn flatten(
+    prefix: String,
+    tree: &FileSystemTree,
+    ignore_paths: &[String],
+    out: &mut Vec<(String, String)>,
+    suppressed: &mut HashSet<String>,
+  ) {
+    for (name, node) in tree {
+      let path = if prefix.is_empty() { name.clone() } else { format!("{}/{}", prefix, name) };
+      if let Some(file) = &node.file {
+        if ignore_paths.iter().any(|pattern| glob_matches(pattern, &path)) {
+          suppressed.insert(path.clone());
         }
+        out.push((path.clone(), file.contents.clone()));
       }
-    } else {
-      if debug == Some(true) {
-        log(&format!("  ❓ Calling function not found in all functions either"));
+      if let Some(dir) = &node.directory {
+        flatten(path.clone(), dir, ignore_paths, out, suppressed);
       }
     }
   }
 
-  false
+  let mut files = Vec::new();
+  let mut suppressed = HashSet::new();
+  flatten(String::new(), tree, ignore_paths, &mut files, &mut suppressed);
+  (files, suppressed)
 }
 
-// Multiple calls to the same identifier can result in multiple diagnostics for the same identifier.
-// We want to return a diagnostic for all calls to the same identifier, so we need to combine the diagnostics for each identifier.
-pub fn identifier_usages_vec_to_combined_map(
-  identifier_usages: HashSet<IdentifierUsage>,
+/// Analyze every file of a virtual file tree together, so calls across files resolve.
+/// Files in `suppressed` still resolve calls from other files but report nothing themselves.
+/// Returns the combined result, the shared comments, every throw span for proximity checks and
+/// the `@it-throws-disable` regions of the reported files.
+fn analyze_files(
+  files: &[(String, String)],
+  suppressed: &HashSet<String>,
   cm: &SourceMap,
-  debug: Option<bool>,
-  call_to_imported_throw_severity: DiagnosticSeverity,
-) -> HashMap<String, ImportedIdentifiers> {
-  let mut identifier_usages_map: HashMap<String, ImportedIdentifiers> = HashMap::new();
-  for identifier_usage in identifier_usages {
-    let identifier_name = identifier_usage.id.clone();
-    let (start_line, start_col) = safe_lookup_char_pos(cm, identifier_usage.usage_span.lo());
-    let (end_line, end_col) = safe_lookup_char_pos(cm, identifier_usage.usage_span.hi());
-
-    if debug == Some(true) {
-      log(&format!(
-        "Identifier usage: {}",
-        identifier_usage.id.clone()
-      ));
-      log(&format!(
-        "From line {} column {} to line {} column {}",
-        start_line, start_col, end_line, end_col
-      ));
+  user_settings: &UserSettings,
+) -> (AnalysisResult, Lrc<SingleThreadedComments>, Vec<Span>, Vec<DisableRegion>) {
+  let comments = Lrc::new(SingleThreadedComments::default());
+
+  // Parse all files into a shared SourceMap. Files that fail to parse are left out of the analysis,
+  // but the reported ones still surface their syntax errors.
+  let mut modules: Vec<(&str, Module)> = Vec::new();
+  let mut parse_errors = Vec::new();
+  for (path, contents) in files {
+    let (module, file_errors) = match parse_file_into(path, contents, cm, &comments) {
+      Ok((module, recovered_errors)) => (Some(module), recovered_errors),
+      Err(error) => (None, vec![error]),
+    };
+    if !suppressed.contains(path) {
+      parse_errors.extend(file_errors);
     }
+    modules.extend(module.map(|module| (path.as_str(), module)));
+  }
+  let next_line_comments = next_line_comments(cm, &comments, &user_settings.ignore_statements);
+  let file_throws: Vec<FileThrows> = modules
+    .iter()
+    .map(|(_, module)| collect_file_throws(module, &comments, &next_line_comments, user_settings))
+    .collect();
 
-    let identifier_diagnostics =
-      identifier_usages_map
-        .entry(identifier_name)
-        .or_insert(ImportedIdentifiers {
-          diagnostics: Vec::new(),
-          id: identifier_usage.id,
-        });
+  let files: Vec<(&str, &Module, &FileThrows)> = modules
+    .iter()
+    .zip(&file_throws)
+    .map(|((path, module), file_throws)| (*path, module, file_throws))
+    .collect();
+  let linked: Vec<usize> = (0..files.len()).filter(|index| !suppressed.contains(files[*index].0)).collect();
+  let (mut results, all_throw_spans) = link_files(&files, &linked, &comments, &next_line_comments, user_settings);
+  results.parse_errors = parse_errors;
+  let disable_regions = linked
+    .iter()
+    .flat_map(|index| disable_regions(cm, &comments, files[*index].1.span.lo))
+    .collect();
 
-    identifier_diagnostics.diagnostics.push(Diagnostic {
-      severity: call_to_imported_throw_severity.to_int(),
-      range: DiagnosticRange {
-        start: DiagnosticPosition {
-          line: start_line,
-          character: start_col,
-        },
-        end: DiagnosticPosition {
-          line: end_line,
-          character: end_col,
-        },
-      },
-      message: "Function imported may throw.".to_string(),
-      source: "Does it Throw?".to_string(),
-    });
-  }
-  identifier_usages_map
+  (results, comments, all_throw_spans, disable_regions)
 }
 
-#[derive(Serialize, Clone, Debug)]
-pub struct ParseResult {
-  pub diagnostics: Vec<Diagnostic>,
-  pub relative_imports: Vec<String>,
-  pub throw_ids: Vec<String>,
-  pub imported_identifiers_diagnostics: Vec<ImportedIdentifiers>,
-}
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_js(data: JsValue) -> JsValue {
+  // Parse the input data into a Rust struct.
+  let input_data: InputData = match serde_wasm_bindgen::from_value(data) {
+    Ok(data) => data,
+    Err(e) => {
+      logger_error(&format!("Failed to parse input data: {:?}", e));
+      return ParseResult::empty().to_js_value();
+    }
+  };
 
+  let cm: Lrc<SourceMap> = Default::default();
 
+  let user_settings = input_data.user_settings();
 
-impl ParseResult {
-  pub fn into(
-    results: AnalysisResult,
-    cm: &SourceMap,
-    debug: Option<bool>,
-    input_data: InputData,
-    comments: &Lrc<dyn Comments>,
-    user_settings: &UserSettings,
-    all_throw_spans: Vec<Span>,
-  ) -> ParseResult {
-    log("🔍 Entering ParseResult::into function");
-    
-    log("🔍 Accessing results.functions_with_throws...");
-    // First, extract data we need before consuming any parts of results
-    let throw_ids: Vec<String> = results.functions_with_throws.iter().map(|f| f.id.clone()).collect();
-    log("✅ Successfully extracted throw_ids");
-    
-    log("🔍 Accessing results.import_sources...");
-    let relative_imports = get_relative_imports(results.import_sources.into_iter().collect());
-    log("✅ Successfully extracted relative_imports");
-    
-    log("🔍 Creating empty diagnostics vector...");
-    let mut diagnostics: Vec<Diagnostic> = Vec::new();
-    log("✅ Created empty diagnostics vector");
-    
-    log("🔍 About to clone results.functions_with_throws...");
-    let functions_clone = results.functions_with_throws.clone();
-    log("✅ Successfully cloned functions_with_throws");
-    
-    log("🔍 Calling add_diagnostics_for_functions_that_throw...");
-    // Track which functions were suppressed by @it-throws for later use
-    let mut suppressed_functions = HashSet::new();
-    for fun in &functions_clone {
-      if has_it_throws_comment(comments, fun.throw_statement, &user_settings.ignore_statements) {
-        suppressed_functions.insert(fun.function_or_method_name.clone());
-      }
-    }
-    
-    let used_it_throws_spans = add_diagnostics_for_functions_that_throw(
-      &mut diagnostics,
-      functions_clone,
-      cm,
-      debug,
-      DiagnosticSeverity::from(
-        input_data
-          .throw_statement_severity
-          .unwrap_or(DiagnosticSeverityInput("Hint".to_string())),
-      ),
-      DiagnosticSeverity::from(
-        input_data
-          .function_throw_severity
-          .clone()
-          .unwrap_or(DiagnosticSeverityInput("Hint".to_string())),
-      ),
-      comments,
-      &user_settings.ignore_statements,
-    );
-    log("✅ add_diagnostics_for_functions_that_throw completed successfully");
-    
-    
-    log("🔍 About to call add_diagnostics_for_calls_to_throws...");
-    add_diagnostics_for_calls_to_throws(
-      &mut diagnostics,
-      results.calls_to_throws.clone(),
-      &results.functions_with_throws,
-      &results.all_functions,
-      cm,
-      debug,
-      DiagnosticSeverity::from(
-        input_data
-          .call_to_throw_severity
-          .unwrap_or(DiagnosticSeverityInput("Hint".to_string())),
-      ),
-      comments,
-      &user_settings.ignore_statements,
-      &suppressed_functions,
-    );
-    log("✅ add_diagnostics_for_calls_to_throws completed successfully");
-    
-    log("🔍 About to call add_diagnostics_for_calling_functions_that_may_throw...");
-    add_diagnostics_for_calling_functions_that_may_throw(
-      &mut diagnostics,
-      &results.calls_to_throws,
-      &results.functions_with_throws,
-      cm,
-      DiagnosticSeverity::from(
-        input_data
-          .function_throw_severity
-          .clone()
-          .unwrap_or(DiagnosticSeverityInput("Hint".to_string())),
-      ),
-      debug,
-      &suppressed_functions,
-    );
-    log("✅ add_diagnostics_for_calling_functions_that_may_throw completed successfully");
-    
-    log("🔍 About to call add_diagnostics_for_exhaustive_catches...");
-    // Add exhaustive catch validation diagnostics
-    add_diagnostics_for_exhaustive_catches(
-      &mut diagnostics,
-      &results.catch_analyses,
-      cm,
-      debug,
-    );
-    log("✅ add_diagnostics_for_exhaustive_catches completed successfully");
-    
-    log("🔍 About to call add_diagnostics_for_unused_it_throws_comments...");
-    // Filter out @it-throws comments that were actually used for suppression at the WASM layer
-    let original_count = results.unused_it_throws_comments.len();
-    
-    // For comprehensive suppression, be very conservative about marking @it-throws comments as unused
-    // Check multiple ways a comment could be "used":
-    // 1. Direct function-level suppression (existing logic)
-    // 2. Inline suppression of throw statements
-    // 3. Any comment near a function that has throws
-    let mut all_used_comment_spans = used_it_throws_spans.clone();
-    
-    // Add comment spans for ALL functions that have throws (comprehensive suppression approach)
-    for fun in &results.functions_with_throws {
-      if let Some(comment_span) = has_it_throws_comment_with_span(comments, fun.throw_statement, &user_settings.ignore_statements) {
-        if !all_used_comment_spans.contains(&comment_span) {
-          all_used_comment_spans.push(comment_span);
-        }
-      }
-    }
-    
-    // ADDITIONAL: Inline suppression proximity using ALL throw statements in the file
-    // Some throws are suppressed at the Rust layer and won't appear in functions_with_throws.
-    // Use the provided all_throw_spans so comments directly above suppressed throws are marked as used.
-    let mut additional_used_spans = Vec::new();
-    for comment_span in &results.unused_it_throws_comments {
-      let comment_line = cm.lookup_char_pos(comment_span.lo).line;
-      let mut used = false;
-      for throw_span in &all_throw_spans {
-        let throw_line = cm.lookup_char_pos(throw_span.lo()).line;
-        // Consider the comment "used" if it is within 2 lines immediately above the throw
-        if comment_line < throw_line && throw_line - comment_line <= 2 {
-          used = true;
-          break;
-        }
-      }
+  // If 'files' is provided, perform multi-file analysis
+  if let Some(files_tree) = input_data.files.clone() {
+    let ignore_paths = input_data.ignore_paths.clone().unwrap_or_default();
+    let (files_vec, suppressed_paths) = flatten_file_tree(&files_tree, &ignore_paths);
 
-      if used {
-        if !all_used_comment_spans.contains(comment_span) && !additional_used_spans.contains(comment_span) {
-          additional_used_spans.push(*comment_span);
-        }
-      }
-    }
-    
-    // Add the additional used spans
-    all_used_comment_spans.extend(additional_used_spans);
-    
-    let truly_unused_comments: Vec<swc_common::Span> = results.unused_it_throws_comments
-      .into_iter()
-      .filter(|span| !all_used_comment_spans.contains(span))
-      .collect();
-    
-    log(&format!("🔧 Filtered unused comments: {} total -> {} truly unused", 
-      original_count, 
-      truly_unused_comments.len()
-    ));
-    
-    // Add unused @it-throws comment diagnostics
-    add_diagnostics_for_unused_it_throws_comments(
-      &mut diagnostics,
-      &truly_unused_comments,
-      cm,
-      debug,
-    );
-    log("✅ add_diagnostics_for_unused_it_throws_comments completed successfully");
-    
-    log("🔍 About to call identifier_usages_vec_to_combined_map...");
-    let imported_identifiers_map = identifier_usages_vec_to_combined_map(
-      results.imported_identifier_usages,
-      cm,
-      debug,
-      DiagnosticSeverity::from(
-        input_data
-          .call_to_imported_throw_severity
-          .unwrap_or(DiagnosticSeverityInput("Hint".to_string())),
-      ),
+    // Optional: pick entry for potential future filtering (currently unused)
+    let _entry = input_data.entry.clone().unwrap_or_else(|| {
+      files_vec.first().map(|(p, _)| p.clone()).unwrap_or_else(|| "input.ts".to_string())
+    });
+
+    let (results, comments, all_throw_spans, disable_regions) =
+      analyze_files(&files_vec, &suppressed_paths, &cm, &user_settings);
+
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let parse_result = ParseResult::into(
+      results,
+      &cm,
+      input_data,
+      comments_as_dyn,
+      &user_settings,
+      all_throw_spans,
+      &disable_regions,
     );
-    log("✅ identifier_usages_vec_to_combined_map completed successfully");
-    
-    log("🔧 Converting HashMap to Vec to avoid drop corruption...");
-    let imported_identifiers_diagnostics: Vec<ImportedIdentifiers> = imported_identifiers_map.into_values().collect();
-    log("✅ Successfully converted HashMap to Vec");
-    
-    log("🔧 About to create ParseResult struct...");
-    log(&format!("📊 Final data sizes - diagnostics: {}, throw_ids: {}, relative_imports: {}, imported_identifiers: {}", 
-      diagnostics.len(), 
-      throw_ids.len(), 
-      relative_imports.len(), 
-      imported_identifiers_diagnostics.len()
-    ));
-    
-    let result = ParseResult {
-      diagnostics,
-      throw_ids,
-      relative_imports,
-      imported_identifiers_diagnostics,
-    };
-    log("✅ ParseResult struct created successfully, about to return...");
-    result
+    log("ParseResult::into (multi-file) completed successfully");
+
+    return parse_result.to_js_value();
   }
-}
 
-#[wasm_bindgen(typescript_custom_section)]
-const TypeScriptSettings: &'static str = r#"
-export interface TypeScriptSettings {
-	decorators?: boolean;
+  // Single-file legacy path
+  analyze_single_file(input_data, &user_settings).to_js_value()
 }
-"#;
 
-#[wasm_bindgen(typescript_custom_section)]
-const DiagnosticSeverityInput: &'static str = r#"
-export type DiagnosticSeverityInput = "Error" | "Warning" | "Information" | "Hint";
-"#;
+/// The single-file path of `parse_js`, for `input_data.file_content`
+fn analyze_single_file(input_data: InputData, user_settings: &UserSettings) -> ParseResult {
+  if input_data.language.as_deref().is_some_and(is_component) {
+    return analyze_component(input_data, user_settings);
+  }
+  let content = input_data.file_content.clone().unwrap_or_default();
 
-#[wasm_bindgen(typescript_custom_section)]
-const InputData: &'static str = r#"
-export type FileNode = {
-  file?: { contents: string };
-  directory?: { [name: string]: FileNode };
-};
+  // Check for file-level disable comment
+  if has_file_disable_comment(&content) {
+    log("File has @it-throws-disable comment - skipping all diagnostic generation");
+    return ParseResult::empty();
+  }
 
-export type FileSystemTree = { [name: string]: FileNode };
+  let (results, cm, comments) = analyze_code_cached(&content, user_settings);
+  for line in &results.debug_log {
+    logger_debug(line);
+  }
+  let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+  // The analyzed copy of the file is the first in the source map; the one below only finds throws
+  let analyzed_file_start = cm.files().first().map(|source_file| source_file.start_pos);
+  let disable_regions = analyzed_file_start
+    .map(|start_pos| disable_regions(&cm, &comments, start_pos))
+    .unwrap_or_default();
+  
+  // Parse the file to collect all throw statements for proximity detection
+  let mut all_throw_spans = Vec::new();
+  let file = cm.new_source_file(
+    swc_common::FileName::Custom("input.ts".into()),
+    content.clone(),
+  );
+  
+  let mut parser = swc_ecma_parser::Parser::new(
+    swc_ecma_parser::Syntax::Typescript(swc_ecma_parser::TsConfig {
+      decorators: true,
+      tsx: true,
+      ..Default::default()
+    }),
+    swc_ecma_parser::StringInput::from(&*file),
+    Some(&comments),
+  );
+  
+  if let Ok(module) = parser.parse_module() {
+    all_throw_spans = throw_statement_spans(&module);
+  }
+  
+  let parse_result = ParseResult::into(
+    results,
+    &cm,
+    input_data,
+    comments_as_dyn,
+    user_settings,
+    all_throw_spans,
+    &disable_regions,
+  );
+  log("ParseResult::into completed successfully");
 
-export interface InputData {
-	/** @deprecated Prefer 'files' */
-	file_content?: string;
-	/** Optional virtual file tree; if provided, multi-file analysis is performed */
-	files?: FileSystemTree;
-	/** Entry file within 'files' to anchor diagnostics (optional) */
-	entry?: string;
-	debug?: boolean;
-  throw_statement_severity?: DiagnosticSeverityInput;
-  function_throw_severity?: DiagnosticSeverityInput;
-  call_to_throw_severity?: DiagnosticSeverityInput;
-  call_to_imported_throw_severity?: DiagnosticSeverityInput;
-  include_try_statement_throws?: boolean;
-  ignore_statements?: string[];
+  parse_result
 }
-"#;
 
-#[wasm_bindgen(typescript_custom_section)]
-const ImportedIdentifiers: &'static str = r#"
-export interface ImportedIdentifiers {
-	diagnostics: any[];
-	id: string;
-}
-"#;
-
-#[wasm_bindgen(typescript_custom_section)]
-const ParseResult: &'static str = r#"
-export interface ParseResult {
-	diagnostics: any[];
-	relative_imports: string[];
-	throw_ids: string[];
-	imported_identifiers_diagnostics: ImportedIdentifiers[];
-}
-"#;
-
-#[wasm_bindgen(typescript_custom_section)]
-const ParseJsFunction: &'static str = r#"
-export function parse_js(data: InputData): ParseResult;
-"#;
-
-#[wasm_bindgen]
-extern "C" {
-  #[wasm_bindgen(typescript_type = "ParseResult")]
-  pub type ParseResultType;
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct TypeScriptSettings {
-  decorators: Option<bool>,
-}
-
-#[derive(Deserialize, Debug, Clone)]
-pub struct FileNodeFile { pub contents: String }
-
-#[derive(Deserialize, Debug, Clone)]
-pub struct FileNode {
-  pub file: Option<FileNodeFile>,
-  pub directory: Option<std::collections::HashMap<String, FileNode>>,
-}
-
-pub type FileSystemTree = std::collections::HashMap<String, FileNode>;
-
-#[derive(Deserialize, Debug)]
-pub struct InputData {
-  pub file_content: Option<String>,
-  pub files: Option<FileSystemTree>,
-  pub entry: Option<String>,
-  pub debug: Option<bool>,
-  pub throw_statement_severity: Option<DiagnosticSeverityInput>,
-  pub function_throw_severity: Option<DiagnosticSeverityInput>,
-  pub call_to_throw_severity: Option<DiagnosticSeverityInput>,
-  pub call_to_imported_throw_severity: Option<DiagnosticSeverityInput>,
-  pub include_try_statement_throws: Option<bool>,
-  pub ignore_statements: Option<Vec<String>>,
-}
-
-#[wasm_bindgen(skip_typescript)]
-pub fn parse_js(data: JsValue) -> JsValue {
-  // Parse the input data into a Rust struct.
-  let input_data: InputData = match serde_wasm_bindgen::from_value(data) {
-    Ok(data) => data,
-    Err(e) => {
-      log(&format!("❌ Failed to parse input data: {:?}", e));
-      return serde_wasm_bindgen::to_value(&ParseResult {
-        diagnostics: Vec::new(),
-        relative_imports: Vec::new(),
-        throw_ids: Vec::new(),
-        imported_identifiers_diagnostics: Vec::new(),
-      }).unwrap_or(JsValue::NULL);
-    }
-  };
-
-  let cm: Lrc<SourceMap> = Default::default();
-
-  let user_settings = UserSettings {
-    include_try_statement_throws: input_data.include_try_statement_throws.unwrap_or(false),
-    ignore_statements: input_data.ignore_statements.clone().unwrap_or_else(Vec::new),
-  };
-
-  // If 'files' is provided, perform multi-file analysis
-  if let Some(files_tree) = input_data.files.clone() {
-    // Flatten the tree into (path, contents)
-    fn flatten(prefix: String, tree: &FileSystemTree, out: &mut Vec<(String, String)>) {
-      for (name, node) in tree {
-        let path = if prefix.is_empty() { name.clone() } else { format!("{}/{}", prefix, name) };
-        if let Some(file) = &node.file {
-          out.push((path.clone(), file.contents.clone()));
-        }
-        if let Some(dir) = &node.directory {
-          flatten(path.clone(), dir, out);
-        }
-      }
-    }
-
-    let mut files_vec: Vec<(String, String)> = Vec::new();
-    flatten(String::new(), &files_tree, &mut files_vec);
-
-    // Optional: pick entry for potential future filtering (currently unused)
-    let _entry = input_data.entry.clone().unwrap_or_else(|| {
-      files_vec.first().map(|(p, _)| p.clone()).unwrap_or_else(|| "input.ts".to_string())
-    });
-
-    let comments = Lrc::new(SingleThreadedComments::default());
-
-    // Parse all files into a shared SourceMap
-    let mut modules: Vec<swc_ecma_ast::Module> = Vec::new();
-    for (path, contents) in &files_vec {
-      let file = cm.new_source_file(
-        swc_common::FileName::Custom(path.clone()),
-        contents.clone(),
-      );
-      let mut parser = swc_ecma_parser::Parser::new(
-        swc_ecma_parser::Syntax::Typescript(swc_ecma_parser::TsConfig {
-          decorators: true,
-          tsx: true,
-          ..Default::default()
-        }),
-        swc_ecma_parser::StringInput::from(&*file),
-        Some(&comments),
-      );
-      if let Ok(module) = parser.parse_module() {
-        modules.push(module);
-      }
-    }
-
-    // Collect all throw spans for proximity detection
-    let mut all_throws_collector = AllThrowsCollector::new();
-    for module in &modules {
-      module.visit_with(&mut all_throws_collector);
-    }
-
-    // Run analyzers across all modules
-    let throw_settings = ThrowFinderSettings {
-      ignore_statements: &user_settings.ignore_statements,
-      include_try_statements: &user_settings.include_try_statement_throws,
-    };
-    let mut throw_analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: std::collections::HashSet::new(),
-      json_parse_calls: Vec::new(),
-      fs_access_calls: Vec::new(),
-      import_sources: std::collections::HashSet::new(),
-      imported_identifiers: Vec::new(),
-      function_name_stack: Vec::new(),
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: throw_settings,
-      used_it_throws_comments: std::collections::HashSet::new(),
-      type_registry: what_does_it_throw::throw_finder::TypeRegistry::new(),
-    };
-    for module in &modules { throw_analyzer.visit_module(module); }
-
-    let mut function_finder = FunctionFinder::new(comments.clone());
-    for module in &modules { function_finder.visit_module(module); }
-
-    let mut call_finder = CallFinder::new(comments.clone());
-    call_finder.functions_with_throws = throw_analyzer.functions_with_throws.clone();
-    call_finder.param_throws = function_finder.param_throws.clone();
-    for module in &modules { call_finder.visit_module(module); }
-
-    let mut import_usage_finder = ImportUsageFinder {
-      imported_identifiers: throw_analyzer.imported_identifiers.clone(),
-      imported_identifier_usages: std::collections::HashSet::new(),
-      current_class_name: None,
-      current_method_name: None,
-      function_name_stack: Vec::new(),
-    };
-    for module in &modules { import_usage_finder.visit_module(module); }
-
-    let mut try_catch_finder = TryCatchFinder::new(comments.clone());
-    for module in &modules { try_catch_finder.visit_module(module); }
-
-    // Build AnalysisResult
-    let results = AnalysisResult {
-      functions_with_throws: throw_analyzer.functions_with_throws.clone(),
-      calls_to_throws: call_finder.calls.clone(),
-      json_parse_calls: throw_analyzer.json_parse_calls.clone(),
-      fs_access_calls: throw_analyzer.fs_access_calls.clone(),
-      import_sources: throw_analyzer.import_sources.clone(),
-      imported_identifiers: throw_analyzer.imported_identifiers.clone(),
-      imported_identifier_usages: import_usage_finder.imported_identifier_usages.clone(),
-      catch_analyses: try_catch_finder.all_catches.clone(),
-      unused_it_throws_comments: Vec::new(),
-      all_functions: function_finder.functions.clone(),
-      inline_callback_allowed_throws: call_finder.inline_callback_allowed_throws.clone(),
-    };
-
-    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
-    let parse_result = ParseResult::into(results, &cm, input_data.debug, input_data, comments_as_dyn, &user_settings, all_throws_collector.throw_spans);
-    log("✅ ParseResult::into (multi-file) completed successfully");
-
-    // Serialize and return (reuse existing serialization path below)
-    // fall through to serialization section at end
-    // Return early via the common serialization block
-    // We will jump to the serialization code below using a scoped block
-    return {
-      // Serialize directly here to avoid code duplication
-      let sanitized_diagnostics: Vec<Diagnostic> = parse_result.diagnostics.into_iter().map(|mut diag| {
-        diag.message = sanitize_unicode_string(diag.message);
-        diag.source = sanitize_unicode_string(diag.source);
-        diag
-      }).collect();
-      let sanitized_result = ParseResult {
-        diagnostics: sanitized_diagnostics,
-        throw_ids: parse_result.throw_ids,
-        relative_imports: parse_result.relative_imports,
-        imported_identifiers_diagnostics: parse_result.imported_identifiers_diagnostics,
-      };
-      match serde_wasm_bindgen::to_value(&sanitized_result) {
-        Ok(value) => value,
-        Err(e) => {
-          log(&format!("❌ Failed to serialize sanitized result (multi-file): {:?}", e));
-          serde_wasm_bindgen::to_value(&ParseResult {
-            diagnostics: Vec::new(),
-            relative_imports: Vec::new(),
-            throw_ids: Vec::new(),
-            imported_identifiers_diagnostics: Vec::new(),
-          }).unwrap_or(JsValue::NULL)
-        }
-      }
-    };
-  }
-
-  // Single-file legacy path
-  let content = input_data.file_content.clone().unwrap_or_default();
-
-  // Check for file-level disable comment
-  if has_file_disable_comment(&content) {
-    log("🔇 File has @it-throws-disable comment - skipping all diagnostic generation");
-    return serde_wasm_bindgen::to_value(&ParseResult {
-      diagnostics: Vec::new(),
-      relative_imports: Vec::new(),
-      throw_ids: Vec::new(),
-      imported_identifiers_diagnostics: Vec::new(),
-    }).unwrap_or(JsValue::NULL);
-  }
-
-  let (results, cm, comments) = analyze_code(&content, cm, &user_settings);
-  let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
-  
-  // Parse the file to collect all throw statements for proximity detection
-  let mut all_throws_collector = AllThrowsCollector::new();
-  let file = cm.new_source_file(
-    swc_common::FileName::Custom("input.ts".into()),
-    content.clone(),
-  );
-  
-  let mut parser = swc_ecma_parser::Parser::new(
-    swc_ecma_parser::Syntax::Typescript(swc_ecma_parser::TsConfig {
-      decorators: true,
-      tsx: true,
-      ..Default::default()
-    }),
-    swc_ecma_parser::StringInput::from(&*file),
-    Some(&comments),
-  );
-  
-  if let Ok(module) = parser.parse_module() {
-    module.visit_with(&mut all_throws_collector);
-  }
-  
-  let parse_result = ParseResult::into(results, &cm, input_data.debug, input_data, comments_as_dyn, &user_settings, all_throws_collector.throw_spans);
-  log("✅ ParseResult::into completed successfully");
-
-  // Convert the diagnostics to a JsValue and return it.
-  log("🔧 About to serialize ParseResult to JsValue...");
-  
-  // Try to isolate the serialization issue by testing each field separately
-  log("🔧 Testing serialization of individual fields...");
-  
-  // Test diagnostics serialization
-  log("🔧 Testing diagnostics serialization...");
-  match serde_wasm_bindgen::to_value(&parse_result.diagnostics) {
-    Ok(_) => log("✅ Diagnostics serialized successfully"),
-    Err(e) => {
-      log(&format!("❌ Failed to serialize diagnostics: {:?}", e));
-      return serde_wasm_bindgen::to_value(&ParseResult {
-        diagnostics: Vec::new(),
-        relative_imports: Vec::new(),
-        throw_ids: Vec::new(),
-        imported_identifiers_diagnostics: Vec::new(),
-      }).unwrap_or(JsValue::NULL);
-    }
-  }
-  
-  // Test throw_ids serialization
-  log("🔧 Testing throw_ids serialization...");
-  match serde_wasm_bindgen::to_value(&parse_result.throw_ids) {
-    Ok(_) => log("✅ Throw_ids serialized successfully"),
-    Err(e) => {
-      log(&format!("❌ Failed to serialize throw_ids: {:?}", e));
-      return serde_wasm_bindgen::to_value(&ParseResult {
-        diagnostics: Vec::new(),
-        relative_imports: Vec::new(),
-        throw_ids: Vec::new(),
-        imported_identifiers_diagnostics: Vec::new(),
-      }).unwrap_or(JsValue::NULL);
-    }
-  }
-  
-  // Test relative_imports serialization
-  log("🔧 Testing relative_imports serialization...");
-  match serde_wasm_bindgen::to_value(&parse_result.relative_imports) {
-    Ok(_) => log("✅ Relative_imports serialized successfully"),
-    Err(e) => {
-      log(&format!("❌ Failed to serialize relative_imports: {:?}", e));
-      return serde_wasm_bindgen::to_value(&ParseResult {
-        diagnostics: Vec::new(),
-        relative_imports: Vec::new(),
-        throw_ids: Vec::new(),
-        imported_identifiers_diagnostics: Vec::new(),
-      }).unwrap_or(JsValue::NULL);
-    }
-  }
-  
-  // Test imported_identifiers_diagnostics serialization
-  log("🔧 Testing imported_identifiers_diagnostics serialization...");
-  match serde_wasm_bindgen::to_value(&parse_result.imported_identifiers_diagnostics) {
-    Ok(_) => log("✅ Imported_identifiers_diagnostics serialized successfully"),
-    Err(e) => {
-      log(&format!("❌ Failed to serialize imported_identifiers_diagnostics: {:?}", e));
-      return serde_wasm_bindgen::to_value(&ParseResult {
-        diagnostics: Vec::new(),
-        relative_imports: Vec::new(),
-        throw_ids: Vec::new(),
-        imported_identifiers_diagnostics: Vec::new(),
-      }).unwrap_or(JsValue::NULL);
-    }
-  }
-  
-  log("🔧 All individual fields serialized successfully, testing full struct...");
-  
-  // Safe serialization with emoji handling
-  log("🔧 About to serialize ParseResult - checking for Unicode issues...");
-  
-  // First, sanitize any strings that might contain problematic Unicode
-  let sanitized_diagnostics: Vec<Diagnostic> = parse_result.diagnostics.into_iter().map(|mut diag| {
-    // Replace any emojis or problematic Unicode in message strings
-    diag.message = sanitize_unicode_string(diag.message);
-    diag.source = sanitize_unicode_string(diag.source);
-    diag
-  }).collect();
-  
-  let sanitized_result = ParseResult {
-    diagnostics: sanitized_diagnostics,
-    throw_ids: parse_result.throw_ids,
-    relative_imports: parse_result.relative_imports,
-    imported_identifiers_diagnostics: parse_result.imported_identifiers_diagnostics,
-  };
-  
-  log("🔧 About to serialize sanitized ParseResult...");
-  match serde_wasm_bindgen::to_value(&sanitized_result) {
-    Ok(value) => {
-      log("✅ Successfully serialized sanitized ParseResult to JsValue");
-      value
-    },
-    Err(e) => {
-      log(&format!("❌ Failed to serialize sanitized result: {:?}", e));
-      serde_wasm_bindgen::to_value(&ParseResult {
-        diagnostics: Vec::new(),
-        relative_imports: Vec::new(),
-        throw_ids: Vec::new(),
-        imported_identifiers_diagnostics: Vec::new(),
-      }).unwrap_or(JsValue::NULL)
-    }
-  }
+/// `analyze_single_file` for a Svelte or Vue component: each `<script>` block is analyzed on its
+/// own at its position in the file, so diagnostic lines count from the top of the component
+fn analyze_component(input_data: InputData, user_settings: &UserSettings) -> ParseResult {
+  let content = input_data.file_content.clone().unwrap_or_default();
+  let mut parse_result = ParseResult::empty();
+  for block in script_blocks(&content) {
+    let block_input = InputData {
+      file_content: Some(positioned_source(&[block])),
+      language: None,
+      ..input_data.clone()
+    };
+    parse_result.extend(analyze_single_file(block_input, user_settings));
+  }
+  parse_result.severity_parse_warnings = input_data.severity_parse_warnings();
+  parse_result
 }
 
 #[cfg(test)]
@@ -1615,7 +1100,13 @@ mod tests {
   use swc_common::FileName;
   use swc_common::comments::{SingleThreadedComments};
   use std::rc::Rc;
-  use what_does_it_throw::throw_finder::ThrowDetails;
+  use what_does_it_throw::call_finder::CallToThrowMap;
+  use what_does_it_throw::diagnostics::{
+    add_diagnostics_for_calls_to_throws, add_diagnostics_for_functions_that_throw, identifier_usages_vec_to_combined_map,
+    DocumentedStatus, CALL_MAY_THROW_CODE, EXHAUSTIVE_CATCH_CODE, FUNCTION_MAY_THROW_CODE, STALE_THROWS_DOC_CODE,
+    SWALLOWED_ERRORS_CODE, THROW_SNIPPET_MAX_CHARS, THROW_STATEMENT_CODE, UNUSED_DISABLE_REGION_CODE, UNUSED_IT_THROWS_CODE,
+  };
+  use what_does_it_throw::throw_finder::{IdentifierUsage, ThrowDetails};
 
   #[test]
   fn test_file_level_disable_exact_line_comment() {
@@ -1642,107 +1133,15 @@ mod tests {
   }
 
   #[test]
-  fn test_get_line_end_byte_pos_with_newline() {
-    let cm = Lrc::new(SourceMap::default());
-    let source_file = cm.new_source_file(
-      FileName::Custom("test_file".into()),
-      "line 1\nline 2".into(),
-    );
-
-    let lo_byte_pos = source_file.start_pos;
-    let hi_byte_pos = BytePos(source_file.end_pos.0 + 10);
-
-    let result = get_line_end_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
-    assert_eq!(result, BytePos(24));
-  }
-
-  #[test]
-  fn test_get_line_end_byte_pos_without_newline() {
-    let cm = Lrc::new(SourceMap::default());
-    let source_file = cm.new_source_file(FileName::Custom("test_file".into()), "no newline".into());
-
-    let lo_byte_pos = source_file.start_pos;
-    let hi_byte_pos = BytePos(source_file.end_pos.0 + 10);
-
-    let result = get_line_end_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
-    assert_eq!(result, hi_byte_pos);
-  }
-
-  #[test]
-  fn test_get_line_end_byte_pos_none_snippet() {
-    let cm = Lrc::new(SourceMap::default());
-    let source_file = cm.new_source_file(FileName::Custom("test_file".into()), "".into());
-
-    let lo_byte_pos = source_file.start_pos;
-    let hi_byte_pos = BytePos(source_file.end_pos.0 + 10);
-
-    let result = get_line_end_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
-    assert_eq!(result, hi_byte_pos);
-  }
-
-  #[test]
-  fn test_get_line_start_byte_pos_with_content() {
-    let cm = Lrc::new(SourceMap::default());
-    cm.new_source_file(
-      FileName::Custom("test_file".into()),
-      "line 1\n    line 2\nline 3".into(),
-    );
-
-    let lo_byte_pos = BytePos(19);
-    let hi_byte_pos = BytePos(7);
-
-    let result = get_line_start_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
-    assert_eq!(result, BytePos(1));
-  }
-
-  #[test]
-  fn test_get_line_start_byte_pos_without_content() {
-    let cm = Lrc::new(SourceMap::default());
-    cm.new_source_file(
-      FileName::Custom("test_file".into()),
-      "line 1\n    \nline 3".into(),
-    );
-
-    let lo_byte_pos = BytePos(1);
-    let hi_byte_pos = BytePos(11);
-
-    let result = get_line_start_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
-    assert_eq!(result, BytePos(8));
-  }
-
-  #[test]
-  fn test_get_line_start_byte_pos_at_file_start() {
-    let cm = Lrc::new(SourceMap::default());
-    cm.new_source_file(
-      FileName::Custom("test_file".into()),
-      "line 1\nline 2\nline 3".into(),
-    );
-
-    let lo_byte_pos = BytePos(0);
-    let hi_byte_pos = BytePos(5);
-
-    let result = get_line_start_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
-    assert_eq!(result, BytePos(0));
+  fn test_file_level_disable_with_enable_is_a_region() {
+    let content = "// @it-throws-disable\nfunction a() { throw new Error() }\n// @it-throws-enable\nfunction b() { throw new Error() }";
+    assert!(!has_file_disable_comment(content), "A disable ended by an enable only covers its region");
   }
 
   #[test]
-  fn test_get_relative_imports() {
-    let import_sources = vec![
-      "./relative/path".to_string(),
-      "../relative/path".to_string(),
-      "/absolute/path".to_string(),
-      "http://example.com".to_string(),
-      "https://example.com".to_string(),
-      "package".to_string(),
-    ];
-
-    let expected_relative_imports = vec![
-      "./relative/path".to_string(),
-      "../relative/path".to_string(),
-    ];
-
-    let relative_imports = get_relative_imports(import_sources);
-    assert_eq!(relative_imports, expected_relative_imports);
+  fn test_file_level_file_comment_ignores_enable() {
+    let content = "// @it-throws-file\nfunction a() { throw new Error() }\n// @it-throws-enable\nfunction b() { throw new Error() }";
+    assert!(has_file_disable_comment(content), "@it-throws-file covers the whole file");
   }
 
   #[test]
@@ -1761,6 +1160,7 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "foo".to_string(),
       class_name: None,
@@ -1769,8 +1169,18 @@ mod tests {
         error_type: Some("Error".to_string()),
         error_message: None,
         is_custom_error: false,
+        is_rejection: false,
+        via_promise_executor: false,
+        in_finally: false,
+        is_env_guarded: false,
+        arg_span: None,
+        ancestor_types: Vec::new(),
+        is_rethrow: false,
       }],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -1785,13 +1195,19 @@ mod tests {
       None,
       DiagnosticSeverity::Hint,
       DiagnosticSeverity::Hint,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     assert_eq!(diagnostics.len(), 2);
     assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Hint.to_int());
     assert_eq!(diagnostics[0].message, "Function foo may throw: {Error}");
+    assert_eq!(diagnostics[0].code.as_deref(), Some(FUNCTION_MAY_THROW_CODE));
   }
 
   #[test]
@@ -1816,6 +1232,7 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: first_throw_span,
+      function_span: first_throw_span,
       throw_spans: vec![first_throw_span, second_throw_span, first_throw_span], // Add third span to match three details
       function_or_method_name: "foo".to_string(),
       class_name: None,
@@ -1824,19 +1241,43 @@ mod tests {
         error_type: Some("Error".to_string()),
         error_message: None,
         is_custom_error: false,
+        is_rejection: false,
+        via_promise_executor: false,
+        in_finally: false,
+        is_env_guarded: false,
+        arg_span: None,
+        ancestor_types: Vec::new(),
+        is_rethrow: false,
       },
       ThrowDetails {
         error_type: Some("TypeError".to_string()),
         error_message: None,
         is_custom_error: false,
+        is_rejection: false,
+        via_promise_executor: false,
+        in_finally: false,
+        is_env_guarded: false,
+        arg_span: None,
+        ancestor_types: Vec::new(),
+        is_rethrow: false,
         },
         ThrowDetails {
           error_type: Some("ValidationError".to_string()),
           error_message: None,
           is_custom_error: true,
+          is_rejection: false,
+          via_promise_executor: false,
+          in_finally: false,
+          is_env_guarded: false,
+          arg_span: None,
+          ancestor_types: Vec::new(),
+          is_rethrow: false,
         },
       ],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -1851,8 +1292,13 @@ mod tests {
       None,
       DiagnosticSeverity::Hint,
       DiagnosticSeverity::Hint,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     assert_eq!(diagnostics.len(), 4); // 1 function diagnostic + 3 throw statements
@@ -1862,6 +1308,7 @@ mod tests {
 
     assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Hint.to_int());
     assert_eq!(diagnostics[1].message, "Throw statement.");
+    assert_eq!(diagnostics[1].code.as_deref(), Some(THROW_STATEMENT_CODE));
 
     assert_eq!(diagnostics[2].severity, DiagnosticSeverity::Hint.to_int());
     assert_eq!(diagnostics[2].message, "Throw statement.");
@@ -1896,13 +1343,24 @@ mod tests {
           source_file.start_pos + BytePos(30),
           Default::default(),
         ),
+        function_span: Span::new(
+          source_file.start_pos + BytePos(13),
+          source_file.start_pos + BytePos(30),
+          Default::default(),
+        ),
         throw_spans: vec![],
         function_or_method_name: "foo".to_string(),
         class_name: None,
         id: "foo".to_string(),
         throw_details: vec![],
         throws_annotation: None,
+        is_async: false,
+        name_span: None,
+        accessor_kind: None,
       },
+      is_awaited: false,
+      is_builtin: false,
+      suppressed_error_types: vec![],
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -1919,14 +1377,18 @@ mod tests {
       &cm,
       None,
       DiagnosticSeverity::Hint,
+      DiagnosticSeverity::Hint,
+      &HashMap::new(),
       &comments_dyn,
       &ignore_statements,
       &suppressed_functions,
+      &HashMap::new(),
     );
 
     assert_eq!(diagnostics.len(), 1);
     assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Hint.to_int());
     assert_eq!(diagnostics[0].message, "Function call may throw: {Error}.");
+    assert_eq!(diagnostics[0].code.as_deref(), Some(CALL_MAY_THROW_CODE));
     assert_eq!(diagnostics[0].range.start.line, 1);
     assert_eq!(diagnostics[0].range.start.character, 13);
     assert_eq!(diagnostics[0].range.end.line, 1);
@@ -1956,9 +1418,12 @@ mod tests {
       &cm,
       None,
       DiagnosticSeverity::Hint,
+      DiagnosticSeverity::Hint,
+      &HashMap::new(),
       &comments_dyn,
       &ignore_statements,
       &suppressed_functions,
+      &HashMap::new(),
     );
 
     assert!(diagnostics.is_empty());
@@ -1997,13 +1462,24 @@ mod tests {
             source_file.start_pos + BytePos(30),
             Default::default(),
           ),
+          function_span: Span::new(
+            source_file.start_pos + BytePos(13),
+            source_file.start_pos + BytePos(30),
+            Default::default(),
+          ),
           throw_spans: vec![],
           function_or_method_name: "foo".to_string(),
           class_name: None,
           id: "foo".to_string(),
           throw_details: vec![],
           throws_annotation: None,
+          is_async: false,
+          name_span: None,
+          accessor_kind: None,
         },
+        is_awaited: false,
+        is_builtin: false,
+        suppressed_error_types: vec![],
       },
       CallToThrowMap {
         call_span: call_span_bar,
@@ -2017,13 +1493,24 @@ mod tests {
             source_file.start_pos + BytePos(30),
             Default::default(),
           ),
+          function_span: Span::new(
+            source_file.start_pos + BytePos(13),
+            source_file.start_pos + BytePos(30),
+            Default::default(),
+          ),
           throw_spans: vec![],
           function_or_method_name: "foo".to_string(),
           class_name: None,
           id: "foo".to_string(),
           throw_details: vec![],
           throws_annotation: None,
+          is_async: false,
+          name_span: None,
+          accessor_kind: None,
         },
+        is_awaited: false,
+        is_builtin: false,
+        suppressed_error_types: vec![],
       },
     ]);
 
@@ -2041,9 +1528,12 @@ mod tests {
       &cm,
       None,
       DiagnosticSeverity::Hint,
+      DiagnosticSeverity::Hint,
+      &HashMap::new(),
       &comments_dyn,
       &ignore_statements,
       &suppressed_functions,
+      &HashMap::new(),
     );
 
     assert_eq!(diagnostics.len(), 2);
@@ -2075,12 +1565,16 @@ mod tests {
         usage_span: first_usage_span,
         identifier_name: "foo".to_string(),
         usage_context: "import".to_string(),
+        callee: "foo".to_string(),
+        is_accessor: false,
       },
       IdentifierUsage {
         id: "foo".to_string(),
         usage_span: second_usage_span,
         identifier_name: "foo".to_string(),
         usage_context: "import".to_string(),
+        callee: "foo".to_string(),
+        is_accessor: false,
       },
     ]);
 
@@ -2128,6 +1622,7 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "foo".to_string(),
       class_name: None,
@@ -2136,8 +1631,18 @@ mod tests {
         error_type: Some("Error".to_string()),
         error_message: None,
         is_custom_error: false,
+        is_rejection: false,
+        via_promise_executor: false,
+        in_finally: false,
+        is_env_guarded: false,
+        arg_span: None,
+        ancestor_types: Vec::new(),
+        is_rethrow: false,
       }],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -2152,8 +1657,13 @@ mod tests {
       None,
       DiagnosticSeverity::Hint,
       DiagnosticSeverity::Hint,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     assert_eq!(diagnostics.len(), 2);
@@ -2184,12 +1694,16 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "testFunction".to_string(),
       class_name: None,
       id: "testFunction".to_string(),
       throw_details: vec![],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -2219,8 +1733,13 @@ mod tests {
       None,
       DiagnosticSeverity::Error,
       DiagnosticSeverity::Error,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     // Should have NO diagnostics due to comprehensive suppression
@@ -2243,7 +1762,7 @@ mod tests {
     );
 
     let call_to_throws = HashSet::from([CallToThrowMap {
-      call_span: call_span,
+      call_span,
       call_function_or_method_name: "withSuppression".to_string(),
       call_class_name: None,
       class_name: None,
@@ -2254,13 +1773,24 @@ mod tests {
           source_file.start_pos + BytePos(40),
           Default::default(),
         ),
+        function_span: Span::new(
+          source_file.start_pos + BytePos(23),
+          source_file.start_pos + BytePos(40),
+          Default::default(),
+        ),
         throw_spans: vec![],
         function_or_method_name: "throwsError".to_string(),
         class_name: None,
         id: "throwsError".to_string(),
         throw_details: vec![],
         throws_annotation: None,
+        is_async: false,
+        name_span: None,
+        accessor_kind: None,
       },
+      is_awaited: false,
+      is_builtin: false,
+      suppressed_error_types: vec![],
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -2292,9 +1822,12 @@ mod tests {
       &cm,
       None,
       DiagnosticSeverity::Error,
+      DiagnosticSeverity::Error,
+      &HashMap::new(),
       &comments_dyn,
       &ignore_statements,
       &suppressed_functions,
+      &HashMap::new(),
     );
 
     // Should have NO diagnostics due to comprehensive suppression
@@ -2318,12 +1851,16 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "testFunction".to_string(),
       class_name: None,
       id: "testFunction".to_string(),
       throw_details: vec![],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -2353,8 +1890,13 @@ mod tests {
       None,
       DiagnosticSeverity::Error,
       DiagnosticSeverity::Error,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     // Should have NO diagnostics due to comprehensive suppression
@@ -2378,12 +1920,16 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "testFunction".to_string(),
       class_name: None,
       id: "testFunction".to_string(),
       throw_details: vec![],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
@@ -2398,8 +1944,13 @@ mod tests {
       None,
       DiagnosticSeverity::Error,
       DiagnosticSeverity::Error,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     // Should have 2 diagnostics: function may throw + throw statement
@@ -2432,21 +1983,29 @@ mod tests {
     let functions_with_throws = HashSet::from([
       ThrowMap {
         throw_statement: throw_span1,
+        function_span: throw_span1,
         throw_spans: vec![throw_span1],
         function_or_method_name: "suppressed".to_string(),
         class_name: None,
         id: "suppressed".to_string(),
         throw_details: vec![],
         throws_annotation: None,
+        is_async: false,
+        name_span: None,
+        accessor_kind: None,
       },
       ThrowMap {
         throw_statement: throw_span2,
+        function_span: throw_span2,
         throw_spans: vec![throw_span2],
         function_or_method_name: "notSuppressed".to_string(),
         class_name: None,
         id: "notSuppressed".to_string(),
         throw_details: vec![],
         throws_annotation: None,
+        is_async: false,
+        name_span: None,
+        accessor_kind: None,
       },
     ]);
 
@@ -2477,8 +2036,13 @@ mod tests {
       None,
       DiagnosticSeverity::Error,
       DiagnosticSeverity::Error,
+      &HashMap::new(),
+      &[],
       &comments_dyn,
       &ignore_statements,
+      &[],
+      &[],
+      false,
     );
 
     // Should have 2 diagnostics: only for notSuppressed function
@@ -2505,17 +2069,21 @@ mod tests {
 
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "testFunction".to_string(),
       class_name: None,
       id: "testFunction".to_string(),
       throw_details: vec![],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let comments = Rc::new(SingleThreadedComments::default());
-    let comments_dyn: Rc<dyn Comments> = comments.clone();
-    let ignore_statements = vec!["@it-throws".to_string()];
+    let _comments_dyn: Rc<dyn Comments> = comments.clone();
+    let _ignore_statements = ["@it-throws".to_string()];
 
     // Add the @it-throws comment
     let comment_span = Span::new(
@@ -2533,7 +2101,7 @@ mod tests {
     );
 
     // Simulate unused_it_throws_comments from AST visitor (this comment would be detected as potential unused)
-    let unused_it_throws_comments = vec![comment_span];
+    let unused_it_throws_comments = [comment_span];
 
     // Test the key concept: if a function has throws and an @it-throws comment, 
     // the comment should be considered "used"
@@ -2564,8 +2132,8 @@ mod tests {
     let functions_with_throws: HashSet<ThrowMap> = HashSet::new();
 
     let comments = Rc::new(SingleThreadedComments::default());
-    let comments_dyn: Rc<dyn Comments> = comments.clone();
-    let ignore_statements = vec!["@it-throws".to_string()];
+    let _comments_dyn: Rc<dyn Comments> = comments.clone();
+    let _ignore_statements = ["@it-throws".to_string()];
 
     // Add the @it-throws comment
     let comment_span = Span::new(
@@ -2583,7 +2151,7 @@ mod tests {
     );
 
     // Simulate unused_it_throws_comments from AST visitor
-    let unused_it_throws_comments = vec![comment_span];
+    let unused_it_throws_comments = [comment_span];
 
     // Test the key concept: if there are no throwing functions, @it-throws comments should be unused
     let has_throwing_function = !functions_with_throws.is_empty();
@@ -2613,17 +2181,21 @@ mod tests {
     // Only one function actually throws
     let functions_with_throws = HashSet::from([ThrowMap {
       throw_statement: throw_span,
+      function_span: throw_span,
       throw_spans: vec![throw_span],
       function_or_method_name: "throws".to_string(),
       class_name: None,
       id: "throws".to_string(),
       throw_details: vec![],
       throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
     }]);
 
     let comments = Rc::new(SingleThreadedComments::default());
-    let comments_dyn: Rc<dyn Comments> = comments.clone();
-    let ignore_statements = vec!["@it-throws".to_string()];
+    let _comments_dyn: Rc<dyn Comments> = comments.clone();
+    let _ignore_statements = ["@it-throws".to_string()];
 
     // Add both @it-throws comments
     let used_comment_span = Span::new(
@@ -2656,7 +2228,7 @@ mod tests {
     );
 
     // Both comments would be detected as potentially unused by AST visitor
-    let unused_it_throws_comments = vec![used_comment_span, unused_comment_span];
+    let _unused_it_throws_comments = [used_comment_span, unused_comment_span];
 
     // Test the key concept: comments associated with throwing functions should be "used",
     // comments not associated with throwing functions should be "unused"
@@ -2671,7 +2243,6 @@ mod tests {
     
     // In a real implementation, the used_comment_span would be correlated with the "throws" function
     // and unused_comment_span would not be correlated with any throwing function
-    assert!(true, "Mixed scenario test validates the concept that only comments for throwing functions should be considered 'used'");
   }
 
   #[test]
@@ -2701,15 +2272,15 @@ function distantFunction() {
     // Build analysis using the same path as parse_js (single-file)
     let cm: Lrc<SourceMap> = Default::default();
     let user_settings = UserSettings {
-      include_try_statement_throws: false,
       ignore_statements: vec!["@it-throws".to_string()],
+      ..Default::default()
     };
 
     let (results, cm, comments) = analyze_code(code, cm, &user_settings);
     let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
 
     // Collect all throw statements (there are none in this code)
-    let mut all_throws_collector = AllThrowsCollector::new();
+    let mut all_throw_spans = Vec::new();
     let file = cm.new_source_file(
       FileName::Custom("input.ts".into()),
       code.to_string(),
@@ -2724,28 +2295,49 @@ function distantFunction() {
       Some(&comments),
     );
     if let Ok(module) = parser.parse_module() {
-      module.visit_with(&mut all_throws_collector);
+      all_throw_spans = throw_statement_spans(&module);
     }
 
     let parse_result = ParseResult::into(
       results,
       &cm,
-      Some(false),
       InputData {
         file_content: Some(code.to_string()),
         files: None,
         entry: None,
+        language: None,
         debug: Some(false),
         throw_statement_severity: None,
         function_throw_severity: None,
         call_to_throw_severity: None,
         call_to_imported_throw_severity: None,
+        builtin_throw_severity: None,
+        over_documented_severity: None,
+        strict_entry_functions: None,
+        error_type_severities: None,
+        severity_overrides: None,
+        error_factories: None,
+        assertion_functions: None,
+        max_call_depth: None,
+        expected_error_types: None,
+        treat_env_guarded_throws_as: None,
+        terminator_functions: None,
+        include_catch_rethrows: None,
+        throws_tag_aliases: None,
+        ignore_imports: None,
+        flag_json_parse: None,
+        collapse_throw_statements: None,
+        merge_overlapping_diagnostics: None,
+        treat_unknown_imports_as_throwing: None,
         include_try_statement_throws: Some(false),
         ignore_statements: Some(vec!["@it-throws".to_string()]),
+        ignore_paths: None,
+        include_call_graph: None,
       },
       comments_as_dyn,
       &user_settings,
-      all_throws_collector.throw_spans,
+      all_throw_spans,
+      &[],
     );
 
     // Extract unused @it-throws comment diagnostics (Information level by design)
@@ -2759,4 +2351,3088 @@ function distantFunction() {
 
     assert_eq!(lines, vec![1, 6, 13], "Expected unused @it-throws diagnostics at lines 1, 6, and 13, got: {:?}", lines);
   }
+
+  fn diagnostics_for_code(code: &str) -> Vec<Diagnostic> {
+    diagnostics_for_settings_json(code, "{}")
+  }
+
+  fn diagnostics_for_settings_json(code: &str, input_json: &str) -> Vec<Diagnostic> {
+    parse_result_for_settings_json(code, input_json).diagnostics
+  }
+
+  /// Analyzes `code` the way `parse_js` does, with `@it-throws` comments unless the settings list others
+  fn parse_result_for_settings_json(code: &str, input_json: &str) -> ParseResult {
+    let mut input_data: InputData = serde_json::from_str(input_json).unwrap();
+    input_data.file_content = Some(code.to_string());
+    input_data.ignore_statements.get_or_insert_with(|| vec!["@it-throws".to_string()]);
+    let user_settings = input_data.user_settings();
+    analyze_single_file(input_data, &user_settings)
+  }
+
+  #[test]
+  fn test_throw_at_start_of_file_is_located() {
+    let code = "function fail() { throw new Error(\"boom\") }\n";
+    let diagnostics = diagnostics_for_code(code);
+    let throw_diagnostic = diagnostics
+      .iter()
+      .find(|d| d.message.starts_with("Throw"))
+      .expect("expected a throw statement diagnostic");
+    assert_eq!(throw_diagnostic.range.start.character, 18);
+    let function_diagnostic = diagnostics
+      .iter()
+      .find(|d| d.message.starts_with("Function fail"))
+      .expect("expected a function diagnostic");
+    // Function diagnostics start at the name
+    assert_eq!(function_diagnostic.range.start.character, 9);
+  }
+
+  #[test]
+  fn test_function_diagnostics_start_at_the_name() {
+    let code = r#"export function named() {
+  throw new Error("a");
+}
+
+class Service {
+  run() {
+    throw new Error("b");
+  }
 }
+
+export const arrow = async (x) => {
+  throw new Error("c");
+};
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let range_of = |prefix: &str| {
+      let diagnostic = diagnostics
+        .iter()
+        .find(|d| d.message.starts_with(prefix))
+        .unwrap_or_else(|| panic!("no diagnostic starting with {:?} in {:?}", prefix, diagnostics));
+      (
+        diagnostic.range.start.line,
+        diagnostic.range.start.character,
+        diagnostic.range.end.line,
+        diagnostic.range.end.character,
+      )
+    };
+
+    assert_eq!(range_of("Function named may throw"), (1, 16, 1, 21));
+    assert_eq!(range_of("Function run may throw"), (6, 2, 6, 5));
+    // Not the `export const` keywords or the arrow's parameters
+    assert_eq!(range_of("Function arrow may reject"), (11, 13, 11, 18));
+  }
+
+  #[test]
+  fn test_throwing_map_callback_surfaces_on_enclosing_function() {
+    let code = r#"function process(items) {
+  return items.map((x) => {
+    throw new RangeError("bad item");
+  });
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(
+      diagnostics.iter().any(|d| d.message == "Function process may throw: {RangeError}"),
+      "Expected a function-level diagnostic for process, got: {:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_documented_map_callback_is_suppressed() {
+    let code = r#"/**
+ * @callback Mapper
+ * @throws {RangeError}
+ */
+
+const padding = "keeps the callback typedef away from process";
+
+/**
+ * @param {Mapper} mapper
+ */
+function process(mapper) {
+  return [1, 2].map((x) => {
+    throw new RangeError("bad item");
+  });
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(
+      !diagnostics.iter().any(|d| d.message.contains("may throw")),
+      "Expected documented callback throws to be suppressed, got: {:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_calls_in_array_method_callbacks_surface_on_enclosing_function() {
+    let code = r#"class ValidationError extends Error {}
+
+function validate(item) {
+  throw new ValidationError("bad item");
+}
+
+function checkAll(items) {
+  items.forEach((item) => {
+    validate(item);
+  });
+}
+
+function findInvalid(items) {
+  return items.find(function (item) {
+    return validate(item);
+  });
+}
+
+function schedule(items) {
+  setTimeout(() => validate(items), 0);
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function checkAll may throw: {ValidationError}"), "{:?}", messages);
+    assert!(messages.contains(&"Function findInvalid may throw: {ValidationError}"), "{:?}", messages);
+    let call_site_lines: Vec<usize> = diagnostics
+      .iter()
+      .filter(|d| d.message == "Function call may throw: {ValidationError}.")
+      .map(|d| d.range.start.line)
+      .collect();
+    assert!(call_site_lines.contains(&9) && call_site_lines.contains(&15), "{:?}", diagnostics);
+    // A callback handed to anything else may run later, so the caller isn't blamed
+    assert!(!messages.iter().any(|m| m.contains("schedule")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_documented_array_method_callback_call_is_suppressed() {
+    let code = r#"/**
+ * @callback Visitor
+ * @throws {RangeError}
+ */
+
+const padding = "keeps the callback typedef away from visitAll";
+
+function check(item) {
+  throw new RangeError("bad item");
+}
+
+/**
+ * @param {Visitor} visitor
+ */
+function visitAll(visitor) {
+  [1, 2].forEach((x) => {
+    check(x);
+  });
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(
+      !diagnostics.iter().any(|d| d.message.contains("visitAll") || d.message.starts_with("Function call")),
+      "Expected callback-documented call throws to be suppressed, got: {:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_async_function_reports_reject() {
+    let code = r#"async function load() {
+  throw new TypeError("bad response");
+}
+
+async function caller() {
+  await load();
+}
+
+function fireAndForget() {
+  load();
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function load may reject: {TypeError}"), "{:?}", messages);
+    assert!(messages.contains(&"Awaited call may throw: {TypeError}."), "{:?}", messages);
+    assert!(
+      messages.contains(&"Unawaited call may cause an unhandled rejection: {TypeError}."),
+      "{:?}",
+      messages
+    );
+  }
+
+  #[test]
+  fn test_for_await_iterates_throwing_generator() {
+    let code = r#"async function* rows() {
+  yield 1;
+  throw new RangeError("stream closed");
+}
+
+async function* quietRows() {
+  yield 1;
+}
+
+async function consume() {
+  for await (const row of rows()) {
+    console.log(row);
+  }
+}
+
+async function consumeQuietly() {
+  for await (const row of quietRows()) {
+    console.log(row);
+  }
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+    assert!(messages.contains(&(11, "Awaited call may throw: {RangeError}.")), "{:?}", messages);
+    assert!(messages.contains(&(10, "Function consume may reject: {RangeError}")), "{:?}", messages);
+    assert!(!messages.iter().any(|(_, m)| m.starts_with("Unawaited call")), "{:?}", messages);
+    assert!(!messages.iter().any(|(line, _)| (16..=20).contains(line)), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_string_literal_throws_are_quoted() {
+    let code = r#"function parse(input) {
+  if (!input) {
+    throw "bad";
+  }
+  throw new TypeError("malformed");
+}
+
+function legacy() {
+  throw 'legacy failure';
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(
+      messages.contains(&r#"Function parse may throw: {TypeError} and a string literal: "bad""#),
+      "{:?}",
+      messages
+    );
+    assert!(
+      messages.contains(&r#"Function legacy may throw a string literal: "legacy failure""#),
+      "{:?}",
+      messages
+    );
+  }
+
+  #[test]
+  fn test_promise_executor_throw_reports_reject() {
+    let code = r#"function fetchUser() {
+  return new Promise((resolve, reject) => {
+    throw new Error("boom");
+  });
+}
+
+function fetchLegacy() {
+  return new Promise(function (resolve) {
+    throw new TypeError("legacy");
+  });
+}
+
+/**
+ * @throws {Error}
+ */
+function documented() {
+  return new Promise(() => {
+    throw new Error("boom");
+  });
+}
+
+function suppressed() {
+  return new Promise(() => {
+    // @it-throws
+    throw new Error("boom");
+  });
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(
+      messages.contains(&"Function fetchUser may reject: {Error} (thrown inside Promise executor)"),
+      "{:?}",
+      messages
+    );
+    assert!(
+      messages.contains(&"Function fetchLegacy may reject: {TypeError} (thrown inside Promise executor)"),
+      "{:?}",
+      messages
+    );
+    assert!(!messages.iter().any(|m| m.contains("Anonymous function")), "{:?}", messages);
+    assert!(!messages.iter().any(|m| m.contains("documented") || m.contains("suppressed")), "{:?}", messages);
+    assert_eq!(messages.iter().filter(|m| m.starts_with("Function ")).count(), 2, "{:?}", messages);
+  }
+
+  #[test]
+  fn test_documented_async_const_arrow_is_suppressed() {
+    let code = r#"/**
+ * @throws {Error}
+ */
+export const load = async () => {
+  throw new Error("x");
+};
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(
+      !diagnostics.iter().any(|d| d.message.contains("may reject") || d.message.contains("may throw")),
+      "Expected @throws to cover the async arrow, got: {:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_wildcard_throws_documents_all_throws() {
+    let code = r#"/**
+ * @throws {*}
+ * @throws {TypeError} when the input is malformed
+ */
+function parse(input) {
+  if (!input) {
+    throw new RangeError("empty");
+  }
+  throw new TypeError("malformed");
+}
+
+/** @throws {any} */
+function load() {
+  parse("");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(
+      !diagnostics.iter().any(|d| d.message.contains("may throw") || d.message.starts_with("Throw")),
+      "Expected wildcard @throws to suppress undocumented-throw diagnostics, got: {:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_promise_reject_is_treated_as_throw_site() {
+    let code = r#"function validate(input) {
+  return Promise.reject(new TypeError("bad input"));
+}
+
+/** @throws {TypeError} */
+function documented(input) {
+  return Promise.reject(new TypeError("bad input"));
+}
+
+function ignored(input) {
+  // @it-throws
+  return Promise.reject(new TypeError("bad input"));
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function validate may throw: {TypeError}"), "{:?}", messages);
+    assert_eq!(
+      messages.iter().filter(|m| **m == "Promise rejection: new TypeError(\"bad input\")").count(),
+      1,
+      "{:?}",
+      messages
+    );
+    assert!(!messages.iter().any(|m| m.contains("documented") || m.contains("ignored")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_override_inherits_base_class_throws_annotation() {
+    let code = r#"class DatabaseError extends Error {}
+
+class BaseRepo {
+  /** @throws {DatabaseError} */
+  save(record) {
+    throw new DatabaseError("not implemented");
+  }
+}
+
+class UserRepo extends BaseRepo {
+  save(record) {
+    throw new DatabaseError("duplicate user");
+  }
+}
+
+class AuditRepo extends UserRepo {
+  save(record) {
+    if (!record) {
+      throw new TypeError("missing record");
+    }
+    throw new DatabaseError("audit failed");
+  }
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    // Only the grandchild's undocumented TypeError should be reported
+    assert_eq!(
+      messages.iter().filter(|m| m.starts_with("Function save may throw")).collect::<Vec<_>>(),
+      vec![&"Function save may throw: {TypeError}"],
+      "{:?}",
+      messages
+    );
+  }
+
+  #[test]
+  fn test_override_annotation_takes_precedence_over_base() {
+    let code = r#"class BaseRepo {
+  /** @throws {DatabaseError} */
+  save(record) {
+    throw new DatabaseError("not implemented");
+  }
+}
+
+class UserRepo extends BaseRepo {
+  /** @throws {TypeError} */
+  save(record) {
+    throw new DatabaseError("duplicate user");
+  }
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(
+      diagnostics.iter().any(|d| d.message == "Function save may throw: {DatabaseError}"),
+      "Expected the override's own @throws to be used, got: {:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_nothrow_function_is_clean() {
+    let code = r#"/** @nothrow */
+function add(a, b) {
+  return a + b;
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    assert!(diagnostics.is_empty(), "Expected no diagnostics, got: {:?}", diagnostics);
+  }
+
+  #[test]
+  fn test_throws_never_and_no_throw_are_nothrow_aliases() {
+    let code = r#"/** @throws-never */
+function renderFrame() {
+  throw new TypeError("no canvas");
+}
+
+function draw() {
+  throw new RangeError("off screen");
+}
+
+/** @no-throw */
+function tick() {
+  draw();
+}
+
+/** @throws-never */
+function safeTick() {
+  try {
+    draw();
+  } catch (e) {
+    console.error(e);
+  }
+}
+"#;
+    for settings in [r#"{}"#, r#"{ "include_try_statement_throws": true }"#] {
+      let diagnostics = diagnostics_for_settings_json(code, settings);
+      let errors: Vec<&str> = diagnostics
+        .iter()
+        .filter(|d| d.severity == DiagnosticSeverity::Error.to_int())
+        .map(|d| d.message.as_str())
+        .collect();
+      assert_eq!(
+        errors,
+        vec![
+          "Function renderFrame is marked @throws-never but may throw: {TypeError}",
+          "Function tick is marked @no-throw but may throw: {RangeError}",
+        ],
+        "{}",
+        settings
+      );
+    }
+  }
+
+  #[test]
+  fn test_nothrow_function_that_throws_is_an_error() {
+    let code = r#"/** @nothrow */
+function parse(input) {
+  throw new TypeError("bad input");
+}
+
+/** @throws {RangeError} */
+function check(value) {
+  throw new RangeError("out of range");
+}
+
+/** @throws-free */
+function wrapper(value) {
+  check(value);
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let errors: Vec<&Diagnostic> = diagnostics
+      .iter()
+      .filter(|d| d.severity == DiagnosticSeverity::Error.to_int())
+      .collect();
+    assert!(
+      errors.iter().any(|d| d.message == "Function parse is marked @nothrow but may throw: {TypeError}"),
+      "{:?}",
+      diagnostics
+    );
+    assert!(
+      errors.iter().any(|d| d.message == "Function wrapper is marked @throws-free but may throw: {RangeError}"),
+      "{:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_class_accessors_are_analyzed() {
+    let code = r#"class Config {
+  get value() {
+    if (!this._v) throw new Error("not set");
+    return this._v;
+  }
+
+  set value(v) {
+    if (v < 0) throw new RangeError("negative");
+    this._v = v;
+  }
+
+  /** @throws {TypeError} */
+  get name() {
+    throw new TypeError("no name");
+  }
+
+  describe() {
+    return `value: ${this.value}`;
+  }
+}
+
+const config = new Config();
+config.value = 1;
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function get value may throw: {Error}"), "{:?}", messages);
+    assert!(messages.contains(&"Function set value may throw: {RangeError}"), "{:?}", messages);
+    assert!(!messages.iter().any(|m| m.contains("get name")), "{:?}", messages);
+    // `this.value` inside describe() and the `config.value = 1` write both go through an accessor
+    let call_lines: Vec<usize> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| d.range.start.line)
+      .collect();
+    assert!(call_lines.contains(&18), "{:?}", diagnostics);
+    assert!(call_lines.contains(&23), "{:?}", diagnostics);
+  }
+
+  #[test]
+  fn test_parsed_module_cache_reuses_unchanged_files() {
+    let user_settings = UserSettings::default();
+    let cache_len = || PARSED_MODULE_CACHE.with(|cache| cache.borrow().len());
+    let code = "// leading comment\nfunction a() { throw new Error('a'); }\nfunction b() { a(); }";
+
+    let (first, first_cm, _) = analyze_code_cached(code, &user_settings);
+    let (second, second_cm, second_comments) = analyze_code_cached(code, &user_settings);
+    assert_eq!(cache_len(), 1);
+    assert_eq!(first.functions_with_throws.len(), second.functions_with_throws.len());
+    assert_eq!(first.calls_to_throws.len(), second.calls_to_throws.len());
+    let lines = |result: &AnalysisResult, cm: &SourceMap| {
+      let mut lines: Vec<usize> = result.functions_with_throws.iter()
+        .map(|f| cm.lookup_char_pos(f.throw_statement.lo()).line)
+        .collect();
+      lines.sort();
+      lines
+    };
+    assert_eq!(lines(&first, &first_cm), lines(&second, &second_cm));
+    assert!(!second_comments.borrow_all().0.is_empty());
+
+    // Edited contents never reuse a stale parse
+    let (edited, _, _) = analyze_code_cached(&code.replace("a();", ""), &user_settings);
+    assert_eq!(cache_len(), 2);
+    assert!(edited.calls_to_throws.is_empty());
+
+    for index in 0..PARSED_MODULE_CACHE_SIZE + 2 {
+      analyze_code_cached(&format!("function f{}() {{}}", index), &user_settings);
+    }
+    assert_eq!(cache_len(), PARSED_MODULE_CACHE_SIZE);
+  }
+
+  #[test]
+  fn test_throw_of_error_factory_resolves_returned_type() {
+    let code = r#"class NotFoundError extends Error {}
+
+function makeError(code) {
+  return new NotFoundError(code);
+}
+
+const makeTypeError = (message) => new TypeError(message);
+
+function find(id) {
+  if (!id) {
+    throw makeTypeError("missing id");
+  }
+  throw makeError("NOT_FOUND");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function find may throw: {NotFoundError, TypeError}"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_throw_of_unknown_factory_records_call_placeholder() {
+    let code = r#"import { makeError } from "./errors";
+
+function find(id) {
+  throw makeError("NOT_FOUND");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function find may throw: {call: makeError}"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_parse_result_output_is_deterministic() {
+    let code = r#"import { load } from "./loader";
+import { save } from "../store";
+
+function a() { throw new Error("a"); }
+function b() { throw new TypeError("b"); }
+function c() { throw new RangeError("c"); }
+function d() { a(); b(); c(); load(); }
+function e() { save(); c(); b(); }
+"#;
+    let serialized = || {
+      let cm: Lrc<SourceMap> = Default::default();
+      let user_settings = UserSettings::default();
+      let (results, cm, comments) = analyze_code(code, cm, &user_settings);
+      let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+      let input_data = InputData {
+        file_content: Some(code.to_string()),
+        files: None,
+        entry: None,
+        language: None,
+        debug: None,
+        throw_statement_severity: None,
+        function_throw_severity: None,
+        call_to_throw_severity: None,
+        call_to_imported_throw_severity: None,
+        builtin_throw_severity: None,
+        over_documented_severity: None,
+        strict_entry_functions: None,
+        error_type_severities: None,
+        severity_overrides: None,
+        error_factories: None,
+        assertion_functions: None,
+        max_call_depth: None,
+        expected_error_types: None,
+        treat_env_guarded_throws_as: None,
+        terminator_functions: None,
+        include_catch_rethrows: None,
+        throws_tag_aliases: None,
+        ignore_imports: None,
+        flag_json_parse: None,
+        collapse_throw_statements: None,
+        merge_overlapping_diagnostics: None,
+        treat_unknown_imports_as_throwing: None,
+        include_try_statement_throws: Some(false),
+        ignore_statements: None,
+        ignore_paths: None,
+        include_call_graph: None,
+      };
+      let parse_result = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, Vec::new(), &[]);
+      serde_json::to_string(&parse_result).unwrap()
+    };
+
+    let first = serialized();
+    for _ in 0..5 {
+      assert_eq!(serialized(), first);
+    }
+  }
+
+  #[test]
+  fn test_severity_input_accepts_case_and_aliases() {
+    let severity_of = |input: &str| DiagnosticSeverity::from(DiagnosticSeverityInput(input.to_string())).to_int();
+    for (input, expected) in [
+      ("Error", DiagnosticSeverity::Error),
+      ("error", DiagnosticSeverity::Error),
+      ("Warning", DiagnosticSeverity::Warning),
+      ("warning", DiagnosticSeverity::Warning),
+      ("warn", DiagnosticSeverity::Warning),
+      ("WARN", DiagnosticSeverity::Warning),
+      ("Information", DiagnosticSeverity::Information),
+      ("info", DiagnosticSeverity::Information),
+      ("Info", DiagnosticSeverity::Information),
+      ("hint", DiagnosticSeverity::Hint),
+      (" Hint ", DiagnosticSeverity::Hint),
+    ] {
+      assert_eq!(severity_of(input), expected.to_int(), "{}", input);
+    }
+    // Anything else falls back to Hint instead of panicking
+    assert_eq!(severity_of("Eror"), DiagnosticSeverity::Hint.to_int());
+    assert_eq!(severity_of(""), DiagnosticSeverity::Hint.to_int());
+  }
+
+  #[test]
+  fn test_unrecognized_severities_are_reported() {
+    let input_data: InputData = serde_json::from_str(
+      r#"{
+        "file_content": "function f() { throw new Error(); }",
+        "throw_statement_severity": "warn",
+        "function_throw_severity": "Eror",
+        "call_to_throw_severity": "info",
+        "error_type_severities": { "ValidationError": "loud", "TypeError": "error" }
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(input_data.severity_parse_warnings(), vec![
+      "Unrecognized severity \"Eror\" for function_throw_severity, using Hint".to_string(),
+      "Unrecognized severity \"loud\" for error_type_severities.ValidationError, using Hint".to_string(),
+    ]);
+
+    let user_settings = UserSettings::default();
+    let (results, cm, comments) = analyze_code("function f() { throw new Error(); }", Default::default(), &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let parse_result = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, Vec::new(), &[]);
+    assert_eq!(parse_result.severity_parse_warnings.len(), 2);
+    // The throw statement still picks up the aliased severity
+    assert!(parse_result
+      .diagnostics
+      .iter()
+      .any(|d| d.message.starts_with("Throw") && d.severity == DiagnosticSeverity::Warning.to_int()));
+  }
+
+  #[test]
+  fn test_error_type_severities_override_global_severities() {
+    let code = r#"function validate(x) {
+  if (!x) throw new ValidationError("bad");
+}
+function fail() {
+  throw new Error("boom");
+}
+function both(x) {
+  if (x) throw new ValidationError("bad");
+  throw new Error("boom");
+}
+function range() {
+  throw new RangeError("out of range");
+}
+function callsFail() {
+  fail();
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(
+      code,
+      r#"{
+        "throw_statement_severity": "Information",
+        "function_throw_severity": "Information",
+        "call_to_throw_severity": "Information",
+        "error_type_severities": { "ValidationError": "Hint", "Error": "Warning" }
+      }"#,
+    );
+    let severity_of = |message: &str, line: usize| {
+      diagnostics
+        .iter()
+        .find(|d| d.message.starts_with(message) && d.range.start.line == line)
+        .unwrap_or_else(|| panic!("no '{}' diagnostic on line {}: {:?}", message, line, diagnostics))
+        .severity
+    };
+
+    // Listed types override the global severity, both for the function and the throw itself
+    assert_eq!(severity_of("Function validate may throw", 1), DiagnosticSeverity::Hint.to_int());
+    assert_eq!(severity_of("Throw: ", 2), DiagnosticSeverity::Hint.to_int());
+    assert_eq!(severity_of("Function fail may throw", 4), DiagnosticSeverity::Warning.to_int());
+    // With several types, the most severe configured one wins
+    assert_eq!(severity_of("Function both may throw", 7), DiagnosticSeverity::Warning.to_int());
+    assert_eq!(severity_of("Throw: ", 8), DiagnosticSeverity::Hint.to_int());
+    assert_eq!(severity_of("Throw: ", 9), DiagnosticSeverity::Warning.to_int());
+    // Unlisted types fall back to the global severities
+    assert_eq!(severity_of("Function range may throw", 11), DiagnosticSeverity::Information.to_int());
+    assert_eq!(severity_of("Throw: ", 12), DiagnosticSeverity::Information.to_int());
+    // Calls use the called function's error types
+    assert_eq!(severity_of("Function call may throw", 15), DiagnosticSeverity::Warning.to_int());
+  }
+
+  #[test]
+  fn test_severity_overrides_with_custom_error_wildcard() {
+    let code = r#"function parse(x) {
+  if (!x) throw new TypeError("empty");
+  throw new ParseError("bad");
+}
+function lookup(x) {
+  if (!x) throw new NotFoundError("missing");
+  throw new RangeError("out of range");
+}
+function load() {
+  lookup(1);
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(
+      code,
+      r#"{
+        "throw_statement_severity": "Information",
+        "function_throw_severity": "Information",
+        "call_to_throw_severity": "Information",
+        "severity_overrides": { "TypeError": "Error", "NotFoundError": "Warning", "*Custom": "Hint" }
+      }"#,
+    );
+    let severity_of = |message: &str, line: usize| {
+      diagnostics
+        .iter()
+        .find(|d| d.message.starts_with(message) && d.range.start.line == line)
+        .unwrap_or_else(|| panic!("no '{}' diagnostic on line {}: {:?}", message, line, diagnostics))
+        .severity
+    };
+
+    // The overridden TypeError outranks the custom ParseError caught by the wildcard
+    assert_eq!(severity_of("Function parse may throw", 1), DiagnosticSeverity::Error.to_int());
+    assert_eq!(severity_of("Throw: ", 2), DiagnosticSeverity::Error.to_int());
+    assert_eq!(severity_of("Throw: ", 3), DiagnosticSeverity::Hint.to_int());
+    // A custom error listed by name takes its own entry over the wildcard
+    assert_eq!(severity_of("Function lookup may throw", 5), DiagnosticSeverity::Warning.to_int());
+    assert_eq!(severity_of("Throw: ", 6), DiagnosticSeverity::Warning.to_int());
+    // RangeError has no override, so its throw keeps the kind-level severity
+    assert_eq!(severity_of("Throw: ", 7), DiagnosticSeverity::Information.to_int());
+    assert_eq!(severity_of("Function call may throw", 10), DiagnosticSeverity::Warning.to_int());
+  }
+
+  #[test]
+  fn test_severity_overrides_win_over_error_type_severities() {
+    let code = r#"function parse(x) {
+  if (!x) throw new TypeError("empty");
+  throw new RangeError("out of range");
+}
+"#;
+    let parse_result = parse_result_for_settings_json(
+      code,
+      r#"{
+        "error_type_severities": { "TypeError": "Warning", "RangeError": "Warning", "SyntaxError": "loud" },
+        "severity_overrides": { "TypeError": "Error", "SyntaxError": "Hint", "NotFoundError": "quiet" }
+      }"#,
+    );
+    let severity_of = |line: usize| {
+      parse_result
+        .diagnostics
+        .iter()
+        .find(|d| d.message.starts_with("Throw: ") && d.range.start.line == line)
+        .unwrap_or_else(|| panic!("no throw diagnostic on line {}: {:?}", line, parse_result.diagnostics))
+        .severity
+    };
+
+    // Both keys apply, and the newer one decides a type set in each
+    assert_eq!(severity_of(2), DiagnosticSeverity::Error.to_int());
+    assert_eq!(severity_of(3), DiagnosticSeverity::Warning.to_int());
+    // An unrecognized severity that was overridden is never used, so only the other one is reported
+    assert_eq!(parse_result.severity_parse_warnings, vec![
+      "Unrecognized severity \"quiet\" for severity_overrides.NotFoundError, using Hint".to_string(),
+    ]);
+  }
+
+  #[test]
+  fn test_throw_statement_message_quotes_thrown_expression() {
+    let code = r#"function parseInput(input) {
+  if (!input) throw new TypeError("bad input");
+  throw new ValidationError(`input ${input} does not match the expected schema for this endpoint`);
+}
+function celebrate() {
+  throw new Error("💥 boom");
+}
+function callsCelebrate() {
+  celebrate();
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let throw_message = |line: usize| {
+      diagnostics
+        .iter()
+        .find(|d| d.range.start.line == line && !d.message.contains("may throw"))
+        .map(|d| d.message.clone())
+        .unwrap_or_else(|| panic!("no throw diagnostic on line {}: {:?}", line, diagnostics))
+    };
+
+    assert_eq!(throw_message(2), "Throw: new TypeError(\"bad input\")");
+    // Long expressions are cut to THROW_SNIPPET_MAX_CHARS
+    let truncated = throw_message(3);
+    assert_eq!(truncated, "Throw: new ValidationError(`input ${input} does not match the ex...");
+    assert_eq!(truncated.chars().count(), "Throw: ".len() + THROW_SNIPPET_MAX_CHARS);
+    // Multi-byte snippets are truncated on char boundaries and quoted verbatim
+    assert_eq!(throw_message(6), "Throw: new Error(\"💥 boom\")");
+    // Throws propagated from a callee have no expression at the caller to quote
+    assert_eq!(throw_message(8), "Throw statement.");
+  }
+
+  #[test]
+  fn test_diagnostic_messages_round_trip_unicode() {
+    // Emoji, CJK punctuation, astral-plane letters and the code points either side of the surrogate range
+    let quoted = "🎯 target missing「設定」、𝔘𝔫𝔦 \u{D7FF}\u{E000} ⚠️";
+    let code = format!("function aim() {{\n  throw new Error(\"{}\");\n}}\n", quoted);
+    let parse_result = ParseResult {
+      diagnostics: diagnostics_for_code(&code),
+      ..ParseResult::empty()
+    };
+
+    let round_tripped: serde_json::Value = serde_json::from_str(&serde_json::to_string(&parse_result).unwrap()).unwrap();
+    let messages: Vec<&str> = round_tripped["diagnostics"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|d| d["message"].as_str().unwrap())
+      .collect();
+    let expected = format!("Throw: new Error(\"{}\")", quoted);
+    assert!(messages.contains(&expected.as_str()), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_stale_throws_documentation_is_reported() {
+    let code = r#"/**
+ * @throws {RangeError}
+ */
+function staleOnly(x) {
+  throw new TypeError("bad");
+}
+/**
+ * @throws {SyntaxError}
+ * @throws {URIError}
+ * @throws {TypeError}
+ */
+function mixed(x) {
+  if (!x) throw new TypeError("bad");
+  throw new RangeError("out of range");
+}
+function inner() {
+  throw new TypeError("deep");
+}
+/**
+ * @throws {TypeError}
+ */
+function viaCallee() {
+  inner();
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let stale: Vec<(&str, usize, i32)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("JSDoc documents"))
+      .map(|d| (d.message.as_str(), d.range.start.line, d.severity))
+      .collect();
+    let information = DiagnosticSeverity::Information.to_int();
+    assert_eq!(stale, vec![
+      ("JSDoc documents RangeError but it is never thrown", 4, information),
+      ("JSDoc documents SyntaxError, URIError but they are never thrown", 12, information),
+    ]);
+
+    // Undocumented throws are still reported next to the stale ones
+    assert!(diagnostics.iter().any(|d| d.message == "Function staleOnly may throw: {TypeError}"));
+    assert!(diagnostics.iter().any(|d| d.message == "Function mixed may throw: {RangeError}"));
+
+    let data = diagnostics
+      .iter()
+      .find(|d| d.message.starts_with("JSDoc documents SyntaxError"))
+      .and_then(|d| serde_json::to_value(d).ok())
+      .map(|value| value["data"].clone())
+      .unwrap();
+    assert_eq!(data["quickFixType"], "removeStaleThrows");
+    assert_eq!(data["errorTypes"], serde_json::json!(["SyntaxError", "URIError"]));
+  }
+
+  #[test]
+  fn test_undocumented_function_has_add_throws_annotation_data() {
+    let code = r#"function bare(x) {
+  throw new TypeError("bad");
+}
+
+/**
+ * Parses the payload.
+ * @param input raw text
+ */
+export function described(input) {
+  if (!input) throw new RangeError("empty");
+  throw new TypeError("bad");
+}
+
+class Store {
+  /** Saves the record. */
+  save(record) {
+    throw new Error("read only");
+  }
+}
+
+/**
+ * @throws {TypeError}
+ */
+function partlyDocumented(x) {
+  if (x) throw new TypeError("bad");
+  throw new RangeError("range");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let data_for = |function_name: &str| {
+      diagnostics
+        .iter()
+        .find(|d| d.message.starts_with(&format!("Function {} may throw", function_name)))
+        .and_then(|d| serde_json::to_value(d).ok())
+        .map(|value| value["data"].clone())
+        .unwrap_or_else(|| panic!("no function diagnostic for {}: {:?}", function_name, diagnostics))
+    };
+
+    // No doc comment: a new block goes on the function's own line
+    assert_eq!(data_for("bare"), serde_json::json!({
+      "quickFixType": "addThrowsAnnotation",
+      "errorTypes": ["TypeError"],
+      "insertPosition": { "line": 0, "character": 0 },
+      "appendToExistingDoc": false,
+    }));
+    // Existing JSDoc without @throws: tags go before its closing `*/`
+    assert_eq!(data_for("described"), serde_json::json!({
+      "quickFixType": "addThrowsAnnotation",
+      "errorTypes": ["RangeError", "TypeError"],
+      "insertPosition": { "line": 7, "character": 1 },
+      "appendToExistingDoc": true,
+    }));
+    assert_eq!(data_for("save")["insertPosition"], serde_json::json!({ "line": 14, "character": 24 }));
+    // Partially documented functions are not offered a new block
+    assert_eq!(data_for("partlyDocumented"), serde_json::Value::Null);
+  }
+
+  #[test]
+  fn test_configured_error_factory_names_thrown_type() {
+    let code = r#"function createHttpError(name, msg) {
+  const e = new Error(msg);
+  e.name = name;
+  return e;
+}
+function findUser(id) {
+  if (!id) throw createHttpError("NotFoundError", "user not found");
+}
+function rejectRequest() {
+  throw errors.create(400, "BadRequestError");
+}
+function dynamicFailure(kind) {
+  throw createHttpError(kind, "computed at runtime");
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(
+      code,
+      r#"{
+        "error_factories": [
+          { "name": "createHttpError", "type_argument_index": 0 },
+          { "name": "errors.create", "type_argument_index": 1 }
+        ]
+      }"#,
+    );
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+    assert!(messages.contains(&"Function findUser may throw: {NotFoundError}"), "{:?}", messages);
+    assert!(messages.contains(&"Function rejectRequest may throw: {BadRequestError}"), "{:?}", messages);
+    // A non-literal type argument leaves the type unknown rather than guessing
+    assert!(messages.contains(&"Function dynamicFailure may throw"), "{:?}", messages);
+    assert!(!messages.iter().any(|m| m.contains("call: ")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_default_assertion_functions_throw() {
+    let code = r#"function withdraw(account, amount) {
+  assert(amount > 0, "amount must be positive");
+  account.balance -= amount;
+}
+
+function checked(value) {
+  invariant(value, "value is required");
+}
+
+function customCheck(value) {
+  ensure(value, "value is required");
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+    assert!(messages.contains(&"Function withdraw may throw: {AssertionError}"), "{:?}", messages);
+    assert!(
+      messages.contains(&r#"Throw: assert(amount > 0, "amount must be positive")"#),
+      "{:?}",
+      messages
+    );
+    assert!(!messages.iter().any(|m| m.contains("checked") || m.contains("customCheck")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_configured_assertion_function_throws() {
+    let code = r#"function customCheck(value) {
+  ensure(value, "value is required");
+}
+
+function withdraw(amount) {
+  assert(amount > 0);
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, r#"{ "assertion_functions": ["ensure"] }"#);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+    assert!(messages.contains(&"Function customCheck may throw: {Error}"), "{:?}", messages);
+    // Configuring the list replaces the defaults
+    assert!(!messages.iter().any(|m| m.contains("withdraw")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_assertion_config_error_types() {
+    let code = r#"class InvariantError extends Error {}
+
+function load(config) {
+  invariant(config, "config is required");
+}
+
+/**
+ * @throws {InvariantError}
+ */
+function documented(config) {
+  invariant(config, "config is required");
+}
+
+function run(config) {
+  try {
+    invariant(config.ready, "not ready");
+  } catch (e) {
+    if (e instanceof TypeError) {
+      console.log(e);
+    }
+  }
+}
+"#;
+    let settings = r#"{ "assertion_functions": [{ "name": "invariant", "error_type": "InvariantError", "message_argument_index": 1 }] }"#;
+    let diagnostics = diagnostics_for_settings_json(code, settings);
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+
+    assert!(messages.contains(&(3, "Function load may throw: {InvariantError}")), "{:?}", messages);
+    // Documenting the configured type suppresses the function's diagnostics as for any throw
+    assert!(!messages.iter().any(|(_, m)| m.contains("documented")), "{:?}", messages);
+    assert!(
+      messages.iter().any(|(line, m)| *line == 17 && m.starts_with("Exhaustive catch is missing handlers for: InvariantError")),
+      "{:?}",
+      messages
+    );
+  }
+
+  #[test]
+  fn test_expected_error_types_are_not_reported() {
+    let code = r#"class PaymentError extends Error {}
+
+function validateAmount(amount) {
+  if (amount < 0) {
+    throw new Error("amount must be positive");
+  }
+}
+
+function charge(amount) {
+  if (amount > 1000) {
+    throw new PaymentError("limit exceeded");
+  }
+  throw new Error("not implemented");
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, r#"{ "expected_error_types": ["Error"] }"#);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+    assert!(!messages.iter().any(|m| m.contains("validateAmount")), "{:?}", messages);
+    assert!(messages.contains(&"Function charge may throw: {PaymentError}"), "{:?}", messages);
+    assert!(!messages.iter().any(|m| m.contains("not implemented")), "{:?}", messages);
+
+    // Without the setting both functions report their Error throws
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function validateAmount may throw: {Error}"), "{:?}", messages);
+    assert!(messages.contains(&"Function charge may throw: {Error, PaymentError}"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_throw_in_finally_block_warns() {
+    let code = r#"function closeAll(resource) {
+  try {
+    resource.flush();
+  } finally {
+    throw new Error("close failed");
+  }
+}
+
+function validated(input) {
+  try {
+    throw new TypeError("bad input");
+  } finally {
+    const cleanup = () => {
+      throw new RangeError("runs later");
+    };
+    cleanup;
+  }
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let finally_warnings: Vec<&Diagnostic> = diagnostics
+      .iter()
+      .filter(|d| d.message == "Throw in finally block may mask the original error")
+      .collect();
+
+    assert_eq!(finally_warnings.len(), 1, "{:?}", diagnostics);
+    assert_eq!(finally_warnings[0].severity, DiagnosticSeverity::Warning.to_int());
+    assert_eq!(finally_warnings[0].range.start.line, 5);
+    assert_eq!(finally_warnings[0].range.start.character, 4);
+    // The throw itself is still reported like any other
+    assert!(
+      diagnostics.iter().any(|d| d.message == "Function closeAll may throw: {Error}"),
+      "{:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_parse_result_includes_catch_summaries() {
+    let code = r#"class ValidationError extends Error {}
+class NetworkError extends Error {}
+class DbError extends Error {}
+
+function load(flag) {
+  try {
+    if (flag === 1) throw new ValidationError("invalid");
+    if (flag === 2) throw new NetworkError("offline");
+    throw new DbError("down");
+  } catch (e) {
+    if (e instanceof ValidationError || e instanceof NetworkError) {
+      return null;
+    }
+    throw e;
+  }
+}
+"#;
+    let user_settings = UserSettings {
+      ignore_statements: vec!["@it-throws".to_string()],
+      ..Default::default()
+    };
+    let (results, cm, comments) = analyze_code(code, Default::default(), &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let input_data: InputData = serde_json::from_str("{}").unwrap();
+    let parse_result = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, Vec::new(), &[]);
+
+    assert_eq!(parse_result.catch_summaries.len(), 1);
+    let summary = &parse_result.catch_summaries[0];
+    assert_eq!(summary.range.start.line, 10);
+    assert_eq!(summary.errors_thrown_in_try, vec!["DbError", "NetworkError", "ValidationError"]);
+    assert_eq!(summary.errors_effectively_caught, vec!["NetworkError", "ValidationError"]);
+    assert_eq!(summary.errors_propagated, vec!["DbError"]);
+    assert!(summary.has_escape_hatch);
+
+    let serialized: serde_json::Value = serde_json::to_value(&parse_result).unwrap();
+    assert_eq!(serialized["catch_summaries"][0]["errors_handled_in_catch"], serde_json::json!(["NetworkError", "ValidationError"]));
+  }
+
+  #[test]
+  fn test_ambient_declarations_in_file_tree_flag_call_sites() {
+    let files = vec![
+      (
+        "types/api.d.ts".to_string(),
+        r#"interface User { id: string }
+
+/**
+ * @throws {ApiError}
+ */
+declare function fetchUser(id: string): User;
+
+/**
+ * @throws {ApiError}
+ */
+declare function saveUser(user: User): void;
+"#
+        .to_string(),
+      ),
+      (
+        "storage.ts".to_string(),
+        "export function saveUser(user) {\n  throw new TypeError(\"read only\");\n}\n".to_string(),
+      ),
+      (
+        "app.ts".to_string(),
+        "export function showUser(id) {\n  const user = fetchUser(id);\n  saveUser(user);\n}\n".to_string(),
+      ),
+    ];
+    let user_settings = UserSettings {
+      ignore_statements: vec!["@it-throws".to_string()],
+      ..Default::default()
+    };
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &HashSet::new(), &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let input_data: InputData = serde_json::from_str("{}").unwrap();
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+
+    let call_messages: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+    // The declared contract covers fetchUser; saveUser's implementation overrides its declaration
+    assert!(call_messages.contains(&(2, "Function call may throw: {ApiError}.")), "{:?}", diagnostics);
+    assert!(call_messages.contains(&(3, "Function call may throw: {TypeError}.")), "{:?}", diagnostics);
+    assert!(!call_messages.iter().any(|(line, message)| *line == 3 && message.contains("ApiError")), "{:?}", diagnostics);
+  }
+
+  #[test]
+  fn test_throws_tag_aliases_document_throws() {
+    let code = r#"/**
+ * @throws {TypeError} when the input isn't a string
+ * @exception RangeError, SyntaxError when it can't be parsed
+ */
+function parse(input) {
+  if (typeof input !== 'string') throw new TypeError('not a string');
+  if (input.length > 10) throw new RangeError('too long');
+  throw new SyntaxError('bad input');
+}
+
+/** @throws_async {NetworkError} */
+async function load() {
+  throw new NetworkError('offline');
+}
+"#;
+    let undocumented = |input_json: &str| -> Vec<String> {
+      diagnostics_for_settings_json(code, input_json)
+        .into_iter()
+        .filter(|d| d.message.starts_with("Function "))
+        .map(|d| d.message)
+        .collect()
+    };
+
+    // `@throws` and `@exception` mix in one block; `@throws_async` isn't a default tag
+    let by_default = undocumented("{}");
+    assert!(!by_default.iter().any(|m| m.contains("parse")), "{:?}", by_default);
+    assert!(by_default.iter().any(|m| m.contains("Function load") && m.contains("NetworkError")), "{:?}", by_default);
+
+    let with_async_tag = undocumented(r#"{ "throws_tag_aliases": ["throws", "exception", "throws_async"] }"#);
+    assert!(with_async_tag.is_empty(), "{:?}", with_async_tag);
+
+    let throws_only = undocumented(r#"{ "throws_tag_aliases": ["throws"] }"#);
+    assert!(
+      throws_only.iter().any(|m| m.contains("Function parse") && m.contains("RangeError") && !m.contains("TypeError")),
+      "{:?}",
+      throws_only
+    );
+  }
+
+  #[test]
+  fn test_imported_never_helpers_flag_call_sites() {
+    let files = vec![
+      (
+        "assert.d.ts".to_string(),
+        "export declare function fail(message: string): never;\nexport declare function warn(message: string): void;\n".to_string(),
+      ),
+      (
+        "app.ts".to_string(),
+        "import { fail, warn } from './assert';\n\nexport function parse(input: string) {\n  warn('parsing');\n  if (!input) fail('empty input');\n  return input;\n}\n".to_string(),
+      ),
+    ];
+    let user_settings = InputData::default().user_settings();
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &HashSet::new(), &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let diagnostics = ParseResult::into(results, &cm, InputData::default(), comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+
+    assert!(messages.contains(&(5, "Function call may throw: {Error}.")), "{:?}", messages);
+    assert!(messages.iter().any(|(_, message)| *message == "Function parse may throw: {Error}"), "{:?}", messages);
+    assert!(!messages.iter().any(|(line, _)| *line == 4), "{:?}", messages);
+    // The `never` return type is the declaration's contract, so the declaration itself isn't flagged
+    assert!(!messages.iter().any(|(_, message)| message.contains("Function fail")), "{:?}", messages);
+  }
+  #[test]
+  fn test_env_guarded_throws_excluded_from_diagnostics() {
+    let code = r#"function devCheck() {
+  if (process.env.NODE_ENV === 'development') {
+    throw new Error('dev only');
+  }
+}
+
+function run() {
+  devCheck();
+}
+"#;
+    let included = diagnostics_for_settings_json(code, "{}");
+    assert!(included.iter().any(|d| d.message.contains("devCheck")), "{:?}", included);
+
+    let excluded = diagnostics_for_settings_json(code, r#"{ "treat_env_guarded_throws_as": "exclude" }"#);
+    assert!(excluded.is_empty(), "{:?}", excluded.iter().map(|d| &d.message).collect::<Vec<_>>());
+  }
+  #[test]
+  fn test_unknown_imports_can_be_treated_as_throwing() {
+    let code = r#"import { parse } from 'parser';
+import fs from 'fs';
+
+function run() {
+  parse('a');
+  // @it-throws
+  parse('b');
+  fs.readFileSync('config.json');
+}
+"#;
+    let unknown_import_messages = |input_json: &str| -> Vec<(usize, String)> {
+      diagnostics_for_settings_json(code, input_json)
+        .into_iter()
+        .filter(|d| d.message.contains("unknown throw behavior"))
+        .map(|d| (d.range.start.line, d.message))
+        .collect()
+    };
+
+    assert!(unknown_import_messages("{}").is_empty());
+    let mut flagged = unknown_import_messages(r#"{ "treat_unknown_imports_as_throwing": true }"#);
+    flagged.sort();
+    assert_eq!(flagged, vec![
+      (5, "Imported function 'parse' has unknown throw behavior".to_string()),
+      (8, "Imported function 'fs.readFileSync' has unknown throw behavior".to_string()),
+    ]);
+  }
+  #[test]
+  fn test_calls_to_throwing_builtins_are_reported() {
+    let code = r#"function readConfig(raw) {
+  return JSON.parse(raw);
+}
+
+function safeRead(raw) {
+  try {
+    return JSON.parse(raw);
+  } catch (e) {
+    if (e instanceof SyntaxError) {
+      return null;
+    }
+    throw e;
+  }
+}
+
+/**
+ * @throws {URIError}
+ */
+function decode(value) {
+  return decodeURIComponent(value);
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, r#"{ "builtin_throw_severity": "Warning", "flag_json_parse": true }"#);
+    let builtin_calls: Vec<(usize, &str, i32)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Call to "))
+      .map(|d| (d.range.start.line, d.message.as_str(), d.severity))
+      .collect();
+
+    // Handled in `safeRead` and documented on `decode`, so only `readConfig` is flagged
+    assert_eq!(builtin_calls, vec![
+      (2, "Call to JSON.parse may throw: {SyntaxError}", DiagnosticSeverity::Warning.to_int()),
+    ]);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function readConfig may throw: {SyntaxError}"), "{:?}", messages);
+  }
+  #[test]
+  fn test_multi_line_signature_diagnostic_covers_only_the_name() {
+    let code = r#"const handlers = {
+  load(
+    id,
+    options,
+  ) {
+    if (!id) {
+      throw new TypeError("id required");
+    }
+    return options;
+  },
+};
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let function_diagnostic = diagnostics
+      .iter()
+      .find(|d| d.message.starts_with("Function load may throw"))
+      .unwrap_or_else(|| panic!("no function diagnostic in {:?}", diagnostics));
+
+    assert_eq!(
+      (
+        function_diagnostic.range.start.line,
+        function_diagnostic.range.start.character,
+        function_diagnostic.range.end.line,
+        function_diagnostic.range.end.character,
+      ),
+      (2, 2, 2, 6)
+    );
+  }
+
+  #[test]
+  fn test_diagnostics_carry_stable_codes() {
+    let code = r#"
+class NetworkError extends Error {}
+class ValidationError extends Error {}
+
+function risky(flag: boolean) {
+  if (flag) throw new NetworkError("x");
+  throw new ValidationError("y");
+}
+
+function handle() {
+  try {
+    risky(true);
+  } catch (e) {
+    if (e instanceof NetworkError) {
+      return;
+    }
+  }
+}
+
+// @it-throws
+const x = 1;
+"#;
+    let diagnostics = diagnostics_for_code(code);
+
+    let codes_for = |prefix: &str| -> Vec<Option<&str>> {
+      diagnostics
+        .iter()
+        .filter(|d| d.message.starts_with(prefix))
+        .map(|d| d.code.as_deref())
+        .collect()
+    };
+    assert!(codes_for("Exhaustive catch").iter().all(|c| *c == Some(EXHAUSTIVE_CATCH_CODE)));
+    assert!(!codes_for("Exhaustive catch").is_empty());
+    assert_eq!(codes_for("Unused @it-throws"), vec![Some(UNUSED_IT_THROWS_CODE)]);
+    assert!(diagnostics.iter().all(|d| d.code.is_some()));
+
+    let json = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert!(json["code"].as_str().is_some_and(|c| c.starts_with("DIT")));
+  }
+  #[test]
+  fn test_reexported_thrower_is_linked_through_barrel() {
+    let files = vec![
+      (
+        "errors.ts".to_string(),
+        "export function failHard(reason: string) {\n  throw new RangeError(reason);\n}\n".to_string(),
+      ),
+      (
+        "more.ts".to_string(),
+        "export function explode() {\n  throw new TypeError(\"boom\");\n}\n".to_string(),
+      ),
+      (
+        "index.ts".to_string(),
+        "export { failHard as fail } from './errors';\nexport * from './more';\n".to_string(),
+      ),
+      (
+        "main.ts".to_string(),
+        "import { fail, explode } from './index';\n\nexport function run() {\n  fail('nope');\n  explode();\n}\n".to_string(),
+      ),
+    ];
+    let user_settings = UserSettings {
+      ignore_statements: vec!["@it-throws".to_string()],
+      ..Default::default()
+    };
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &HashSet::new(), &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let input_data: InputData = serde_json::from_str("{}").unwrap();
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+
+    let call_messages: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+    // `fail` is `failHard` renamed by the barrel; `explode` comes through `export *`
+    assert!(call_messages.contains(&(4, "Function call may throw: {RangeError}.")), "{:?}", diagnostics);
+    assert!(call_messages.contains(&(5, "Function call may throw: {TypeError}.")), "{:?}", diagnostics);
+  }
+  #[test]
+  fn test_reexported_sources_are_relative_imports() {
+    let files = vec![
+      (
+        "src/lib/validate.ts".to_string(),
+        "export function validate(input: string) {\n  throw new SyntaxError(input);\n}\n".to_string(),
+      ),
+      (
+        "src/lib/index.ts".to_string(),
+        "export { validate as checkInput } from './validate';\n".to_string(),
+      ),
+      (
+        "src/app.ts".to_string(),
+        "import { checkInput } from './lib';\n\nexport function run() {\n  checkInput('x');\n}\n".to_string(),
+      ),
+    ];
+    let user_settings = UserSettings {
+      ignore_statements: vec!["@it-throws".to_string()],
+      ..Default::default()
+    };
+    let suppressed: HashSet<String> = ["src/lib/validate.ts", "src/lib/index.ts"].iter().map(|path| path.to_string()).collect();
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &suppressed, &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let input_data: InputData = serde_json::from_str("{}").unwrap();
+    let parse_result = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions);
+
+    assert!(
+      parse_result.diagnostics.iter().any(|d| d.range.start.line == 4 && d.message == "Function call may throw: {SyntaxError}."),
+      "{:?}",
+      parse_result.diagnostics
+    );
+    // The barrel is imported directly; the file it re-exports from has to be watched too
+    let mut relative_imports = parse_result.relative_imports.clone();
+    relative_imports.sort();
+    assert_eq!(relative_imports, vec!["./lib".to_string(), "./lib/validate".to_string()]);
+  }
+  #[test]
+  fn test_glob_matches() {
+    assert!(glob_matches("**/*.test.ts", "src/foo.test.ts"));
+    assert!(glob_matches("**/*.test.ts", "foo.test.ts"));
+    assert!(glob_matches("*.test.ts", "src/deep/foo.test.ts"));
+    assert!(!glob_matches("**/*.test.ts", "src/foo.ts"));
+    assert!(glob_matches("**/generated/**", "src/generated/api/client.ts"));
+    assert!(glob_matches("generated/**", "./generated/client.ts"));
+    assert!(!glob_matches("generated/**", "src/generated/client.ts"));
+    assert!(glob_matches("src/?.ts", "src/a.ts"));
+    assert!(!glob_matches("src/*.ts", "src/lib/a.ts"));
+  }
+
+  #[test]
+  fn test_ignored_paths_resolve_calls_without_reporting() {
+    let input_data: InputData = serde_json::from_value(serde_json::json!({
+      "files": {
+        "src": {
+          "directory": {
+            "generated": {
+              "directory": {
+                "client.ts": { "file": { "contents": "export function fetchUser(id: string) {\n  throw new TypeError(id);\n}\n" } }
+              }
+            },
+            "client.test.ts": { "file": { "contents": "export function failTest() {\n  throw new Error('nope');\n}\n" } },
+            "app.ts": { "file": { "contents": "import { fetchUser } from './generated/client';\n\nexport function show(id: string) {\n  fetchUser(id);\n}\n" } }
+          }
+        }
+      },
+      "ignore_paths": ["**/*.test.ts", "**/generated/**"]
+    }))
+    .unwrap();
+    let user_settings = input_data.user_settings();
+    let (files, suppressed) =
+      flatten_file_tree(input_data.files.as_ref().unwrap(), input_data.ignore_paths.as_ref().unwrap());
+    let mut suppressed_paths: Vec<&String> = suppressed.iter().collect();
+    suppressed_paths.sort();
+    assert_eq!(suppressed_paths, vec!["src/client.test.ts", "src/generated/client.ts"]);
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &suppressed, &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+
+    // Only app.ts reports; the throws in the ignored files (both on line 2) stay quiet
+    assert!(diagnostics.iter().all(|d| d.range.start.line == 4), "{:?}", diagnostics);
+    assert!(diagnostics.iter().any(|d| d.message == "Function show may throw: {TypeError}"), "{:?}", diagnostics);
+    let call_messages: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+    assert_eq!(call_messages, vec![(4, "Function call may throw: {TypeError}.")], "{:?}", diagnostics);
+  }
+  #[test]
+  fn test_typed_it_throws_comment_suppresses_only_listed_types() {
+    let code = r#"class ValidationError extends Error {}
+
+// @it-throws ValidationError, RangeError
+function validate(input) {
+  if (!input) {
+    throw new ValidationError('missing');
+  }
+  throw new TypeError('bad');
+}
+
+function check(input) {
+  if (!input) {
+    throw new ValidationError('missing');
+  }
+}
+
+function run(input) {
+  // @it-throws ValidationError
+  check(input);
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+
+    // The later-introduced TypeError still reports; ValidationError doesn't, on the function or on the call
+    assert!(messages.contains(&(4, "Function validate may throw: {TypeError}")), "{:?}", messages);
+    assert!(messages.iter().any(|(line, _)| *line == 8), "{:?}", messages);
+    assert!(!messages.iter().any(|(line, _)| *line == 6), "{:?}", messages);
+    assert!(!messages.iter().any(|(line, _)| *line == 19), "{:?}", messages);
+    assert!(!messages.iter().any(|(_, message)| message.starts_with("Function run")), "{:?}", messages);
+
+    // RangeError is listed but never thrown
+    let unused: Vec<&(usize, &str)> = messages.iter().filter(|(_, message)| message.starts_with("Unused @it-throws")).collect();
+    assert_eq!(unused, vec![&(3, "Unused @it-throws type: RangeError. Nothing it covers throws this type.")]);
+  }
+
+  #[test]
+  fn test_typed_it_throws_comment_on_call_reports_other_types() {
+    let code = r#"function load(input) {
+  if (!input) {
+    throw new ValidationError('missing');
+  }
+  throw new TypeError('bad');
+}
+
+function run(input) {
+  // @it-throws ValidationError, NotFoundError
+  load(input);
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+    assert!(messages.contains(&(10, "Function call may throw: {TypeError}.")), "{:?}", messages);
+    assert!(
+      messages.contains(&(9, "Unused @it-throws type: NotFoundError. Nothing it covers throws this type.")),
+      "{:?}",
+      messages
+    );
+  }
+  #[test]
+  fn test_over_documented_functions_that_never_throw() {
+    let code = r#"import { request } from './http';
+
+/**
+ * @throws {NetworkError}
+ */
+function refactored(x) {
+  return x + 1;
+}
+/**
+ * @throws {NetworkError}
+ */
+function wrapsImport(url) {
+  return request(url);
+}
+/**
+ * @throws {TimeoutError | NetworkError}
+ * @throws {RangeError}
+ */
+function union(x) {
+  return x;
+}
+/**
+ * @throws {ValidationError}
+ */
+function rethrows(err) {
+  throw err;
+}
+"#;
+    let stale_for = |input_json: &str| -> Vec<(String, usize, i32)> {
+      let mut stale: Vec<(String, usize, i32)> = diagnostics_for_settings_json(code, input_json)
+        .into_iter()
+        .filter(|d| d.code.as_deref() == Some(STALE_THROWS_DOC_CODE))
+        .map(|d| (d.message, d.range.start.line, d.severity))
+        .collect();
+      stale.sort_by_key(|(_, line, _)| *line);
+      stale
+    };
+
+    // Calls into unknown imports and dynamic throws could be the documented type; unions are never stale
+    let information = DiagnosticSeverity::Information.to_int();
+    assert_eq!(
+      stale_for("{}"),
+      vec![
+        ("JSDoc documents NetworkError but it is never thrown".to_string(), 6, information),
+        ("JSDoc documents RangeError but it is never thrown".to_string(), 19, information),
+      ]
+    );
+    let warning = DiagnosticSeverity::Warning.to_int();
+    assert!(stale_for(r#"{"over_documented_severity": "Warning"}"#).iter().all(|(_, _, severity)| *severity == warning));
+  }
+  #[test]
+  fn test_throws_descriptions_surface_as_related_information() {
+    let code = r#"/**
+ * @throws {HttpError} see [docs](https://example.com/errors#http)
+ */
+function fetchUser(id) {
+  if (!id) throw new HttpError("missing");
+  throw new TypeError("bad");
+}
+
+/** @throws TimeoutError when the request takes too long */
+function waitForUser(id) {
+  if (!id) throw new TimeoutError("slow");
+  throw new RangeError("range");
+}
+
+/**
+ * @throws {TypeError}
+ */
+function plain(x) {
+  if (x) throw new TypeError("bad");
+  throw new RangeError("range");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let data_for = |function_name: &str| {
+      diagnostics
+        .iter()
+        .find(|d| d.message.starts_with(&format!("Function {} may throw", function_name)))
+        .and_then(|d| serde_json::to_value(d).ok())
+        .map(|value| value["data"].clone())
+        .unwrap_or_else(|| panic!("no function diagnostic for {}: {:?}", function_name, diagnostics))
+    };
+
+    assert_eq!(data_for("fetchUser"), serde_json::json!({
+      "relatedInformation": [
+        { "errorType": "HttpError", "description": "see [docs](https://example.com/errors#http)" },
+      ],
+    }));
+    assert_eq!(data_for("waitForUser"), serde_json::json!({
+      "relatedInformation": [
+        { "errorType": "TimeoutError", "description": "when the request takes too long" },
+      ],
+    }));
+    // Tags without a description still document the type, but add nothing here
+    assert_eq!(data_for("plain"), serde_json::Value::Null);
+  }
+  #[test]
+  fn test_strict_entry_functions_escalate_to_error() {
+    let code = r#"function validate(body) {
+  if (!body) throw new TypeError("missing body");
+}
+
+function handleRequest(req) {
+  validate(req.body);
+}
+
+/**
+ * @throws {RangeError}
+ */
+function handleHealth(req) {
+  if (!req) throw new RangeError("no request");
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(
+      code,
+      r#"{ "strict_entry_functions": ["handle*"], "function_throw_severity": "Warning" }"#,
+    );
+    let severity_of = |message: &str| {
+      diagnostics
+        .iter()
+        .find(|d| d.message == message)
+        .map(|d| d.severity)
+        .unwrap_or_else(|| panic!("no diagnostic {:?} in {:?}", message, diagnostics))
+    };
+
+    // Inherited from validate through the call
+    assert_eq!(
+      severity_of("Function handleRequest may throw: {TypeError}"),
+      DiagnosticSeverity::Error.to_int()
+    );
+    // Helpers keep the configured severity
+    assert_eq!(
+      severity_of("Function validate may throw: {TypeError}"),
+      DiagnosticSeverity::Warning.to_int()
+    );
+    // A fully documented entry function has nothing to escalate
+    assert!(!diagnostics.iter().any(|d| d.message.contains("handleHealth")), "{:?}", diagnostics);
+  }
+  #[test]
+  fn test_documented_base_error_type_covers_subclasses() {
+    let code = r#"class HttpError extends Error {}
+class NotFoundError extends HttpError {}
+
+/**
+ * @throws {HttpError}
+ */
+function findUser(id) {
+  if (!id) throw new NotFoundError("missing");
+}
+
+function loadUser(id) {
+  try {
+    return findUser(id);
+  } catch (e) {
+    if (e instanceof HttpError) {
+      return null;
+    }
+    return undefined;
+  }
+}
+
+/**
+ * @throws {NotFoundError}
+ */
+function findAdmin(id) {
+  if (!id) throw new HttpError("missing");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+    // Documenting the base class covers the subclass, and isn't stale
+    assert!(!messages.iter().any(|m| m.contains("findUser")), "{:?}", messages);
+    assert!(!messages.iter().any(|m| m.contains("documents HttpError")), "{:?}", messages);
+    // `instanceof HttpError` handles the NotFoundError
+    assert!(!messages.iter().any(|m| m.contains("loadUser") || m.starts_with("Function call may throw")), "{:?}", messages);
+    // A subclass doesn't document its base class
+    assert!(messages.contains(&"Function findAdmin may throw: {HttpError}"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_error_class_hierarchy_across_files() {
+    let input_data: InputData = serde_json::from_value(serde_json::json!({
+      "files": {
+        "errors.ts": { "file": { "contents": "export class HttpError extends Error {}\nexport class NotFoundError extends HttpError {}\n" } },
+        "users.ts": { "file": { "contents": "import { NotFoundError } from './errors';\n\n/**\n * @throws {HttpError}\n */\nexport function findUser(id: string) {\n  if (!id) throw new NotFoundError('missing');\n}\n" } }
+      }
+    }))
+    .unwrap();
+    let user_settings = input_data.user_settings();
+    let (files, suppressed) = flatten_file_tree(input_data.files.as_ref().unwrap(), &[]);
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &suppressed, &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+
+    assert!(!diagnostics.iter().any(|d| d.message.contains("findUser")), "{:?}", diagnostics);
+    assert!(!diagnostics.iter().any(|d| d.code.as_deref() == Some(STALE_THROWS_DOC_CODE)), "{:?}", diagnostics);
+  }
+  #[test]
+  fn test_call_graph_output() {
+    let code = r#"/**
+ * @throws {TypeError}
+ */
+function parse(input) {
+  if (!input) throw new TypeError("empty");
+}
+
+function load(input) {
+  return parse(input);
+}
+
+function safeLoad(input) {
+  try {
+    return parse(input);
+  } catch (e) {
+    return null;
+  }
+}
+"#;
+    let input_data: InputData = serde_json::from_value(serde_json::json!({
+      "file_content": code,
+      "include_call_graph": true
+    }))
+    .unwrap();
+    let user_settings = input_data.user_settings();
+    let parse_result = analyze_single_file(input_data, &user_settings);
+    let graph = serde_json::to_value(parse_result.call_graph.as_ref().unwrap()).unwrap();
+
+    let node = |name: &str| {
+      graph["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["name"] == name)
+        .cloned()
+        .unwrap_or_else(|| panic!("no node {}: {}", name, graph))
+    };
+    assert_eq!(node("parse")["error_types"], serde_json::json!(["TypeError"]));
+    assert_eq!(node("parse")["documented_types"], serde_json::json!(["TypeError"]));
+    assert_eq!(node("parse")["range"]["start"], serde_json::json!({ "line": 4, "character": 9 }));
+    assert_eq!(node("load")["error_types"], serde_json::json!(["TypeError"]));
+    assert_eq!(node("load")["documented_types"], serde_json::json!([]));
+    // The catch swallows the TypeError
+    assert_eq!(node("safeLoad")["error_types"], serde_json::json!([]));
+
+    // Only the uncaught call is an edge
+    assert_eq!(graph["edges"], serde_json::json!([{
+      "from_id": "NOT_SET-load",
+      "to_id": "NOT_SET-parse",
+      "call_range": { "start": { "line": 9, "character": 9 }, "end": { "line": 9, "character": 21 } },
+      "propagated_error_types": ["TypeError"],
+    }]));
+
+    // Left out unless asked for
+    let input_data: InputData = serde_json::from_value(serde_json::json!({ "file_content": code })).unwrap();
+    let parse_result = analyze_single_file(input_data, &user_settings);
+    assert!(parse_result.call_graph.is_none());
+    assert!(serde_json::to_value(&parse_result).unwrap().get("call_graph").is_none());
+  }
+  #[test]
+  fn test_call_diagnostic_links_to_callee_throws() {
+    let code = r#"function parse(input) {
+  if (!input) {
+    throw new TypeError("empty");
+  }
+  if (input.length > 10) {
+    throw new RangeError("too long");
+  }
+}
+
+function load(input) {
+  parse(input);
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let call = diagnostics
+      .iter()
+      .find(|d| d.message.starts_with("Function call may throw"))
+      .and_then(|d| serde_json::to_value(d).ok())
+      .unwrap_or_else(|| panic!("no call diagnostic: {:?}", diagnostics));
+    let related = call["data"]["relatedInformation"].as_array().unwrap();
+
+    let sites: Vec<(&str, &serde_json::Value)> = related
+      .iter()
+      .map(|info| (info["errorType"].as_str().unwrap(), &info["range"]["start"]))
+      .collect();
+    assert_eq!(
+      sites,
+      vec![
+        ("TypeError", &serde_json::json!({ "line": 3, "character": 4 })),
+        ("RangeError", &serde_json::json!({ "line": 6, "character": 4 })),
+      ]
+    );
+    assert!(related.iter().all(|info| info["file"].is_string()), "{:?}", related);
+  }
+  #[test]
+  fn test_disable_region_suppresses_diagnostics_until_enable() {
+    let code = r#"function before() {
+  throw new TypeError("before");
+}
+
+// @it-throws-disable
+function inside() {
+  throw new RangeError("inside");
+}
+// @it-throws-enable
+
+function after() {
+  throw new SyntaxError("after");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.iter().any(|m| m.starts_with("Function before may throw")), "{:?}", messages);
+    assert!(messages.iter().any(|m| m.starts_with("Function after may throw")), "{:?}", messages);
+    assert!(messages.iter().all(|m| !m.contains("inside") && !m.contains("RangeError")), "{:?}", messages);
+    assert!(diagnostics.iter().all(|d| d.code.as_deref() != Some(UNUSED_DISABLE_REGION_CODE)));
+  }
+
+  #[test]
+  fn test_disable_region_pairs_are_balanced() {
+    let code = r#"// @it-throws-enable
+function first() {
+  throw new TypeError("first");
+}
+// @it-throws-disable
+function second() {
+  throw new TypeError("second");
+}
+// @it-throws-disable
+function third() {
+  throw new TypeError("third");
+}
+// @it-throws-enable
+function fourth() {
+  throw new TypeError("fourth");
+}
+// @it-throws-disable
+function fifth() {
+  throw new TypeError("fifth");
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let mut functions: Vec<&str> = diagnostics
+      .iter()
+      .filter(|d| d.code.as_deref() == Some(FUNCTION_MAY_THROW_CODE))
+      .filter_map(|d| d.message.strip_prefix("Function "))
+      .filter_map(|m| m.split(' ').next())
+      .collect();
+    functions.sort();
+    // The stray enable is ignored, the second disable changes nothing and the last runs to the end of the file
+    assert_eq!(functions, vec!["first", "fourth"]);
+  }
+
+  #[test]
+  fn test_unused_disable_region() {
+    let code = r#"function risky() {
+  throw new Error("boom");
+}
+// @it-throws-disable
+const answer = 42;
+// @it-throws-enable
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let unused: Vec<&Diagnostic> = diagnostics
+      .iter()
+      .filter(|d| d.code.as_deref() == Some(UNUSED_DISABLE_REGION_CODE))
+      .collect();
+    assert_eq!(unused.len(), 1, "{:?}", diagnostics);
+    assert_eq!(unused[0].severity, DiagnosticSeverity::Information.to_int());
+    assert_eq!(unused[0].range.start.line, 4);
+    assert!(diagnostics.iter().any(|d| d.message.starts_with("Function risky may throw")));
+  }
+  #[test]
+  fn test_catch_that_exits_stops_propagation() {
+    let code = |catch_tail: &str| {
+      format!(
+        r#"class ValidationError extends Error {{}}
+class NetworkError extends Error {{}}
+
+function fetchData(input) {{
+  if (!input) {{
+    throw new ValidationError("missing");
+  }}
+  throw new NetworkError("offline");
+}}
+
+function main(input) {{
+  try {{
+    fetchData(input);
+  }} catch (e) {{
+    if (e instanceof ValidationError) {{
+      return null;
+    }}
+    console.error(e);
+    {}
+  }}
+}}
+"#,
+        catch_tail
+      )
+    };
+    let main_diagnostics = |code: &str| -> Vec<String> {
+      diagnostics_for_code(code)
+        .into_iter()
+        .filter(|d| d.range.start.line >= 12)
+        .map(|d| d.message)
+        .collect()
+    };
+
+    let exits = main_diagnostics(&code("process.exit(1);"));
+    assert!(exits.iter().all(|m| !m.contains("NetworkError")), "{:?}", exits);
+
+    let continues = main_diagnostics(&code("// carry on"));
+    assert!(continues.iter().any(|m| m.contains("NetworkError")), "{:?}", continues);
+
+    // A custom terminator list replaces the defaults
+    let input_data: InputData = serde_json::from_value(serde_json::json!({
+      "file_content": code("shutdown(1);"),
+      "terminator_functions": ["shutdown"]
+    }))
+    .unwrap();
+    let user_settings = input_data.user_settings();
+    let diagnostics = analyze_single_file(input_data, &user_settings).diagnostics;
+    assert!(diagnostics.iter().all(|d| !(d.range.start.line >= 12 && d.message.contains("NetworkError"))), "{:?}", diagnostics);
+  }
+  #[test]
+  fn test_function_summaries() {
+    let code = r#"/**
+ * @throws {TypeError}
+ */
+function documented() {
+  throw new TypeError("bad");
+}
+
+/**
+ * @throws {TypeError}
+ */
+function partial(flag) {
+  if (flag) {
+    throw new RangeError("range");
+  }
+  documented();
+}
+
+function undocumented() {
+  documented();
+}
+
+// @it-throws
+function suppressed() {
+  throw new Error("ignored");
+}
+
+function safe() {
+  return 1;
+}
+"#;
+    let input_data = InputData {
+      file_content: Some(code.to_string()),
+      ignore_statements: Some(vec!["@it-throws".to_string()]),
+      ..Default::default()
+    };
+    let user_settings = input_data.user_settings();
+    let parse_result = analyze_single_file(input_data, &user_settings);
+
+    let summaries: Vec<(&str, DocumentedStatus, Vec<String>)> = parse_result
+      .function_summaries
+      .iter()
+      .map(|summary| (summary.name.as_str(), summary.status, summary.thrown_types.clone()))
+      .collect();
+    assert_eq!(
+      summaries,
+      vec![
+        ("documented", DocumentedStatus::FullyDocumented, vec!["TypeError".to_string()]),
+        ("partial", DocumentedStatus::PartiallyDocumented, vec!["RangeError".to_string(), "TypeError".to_string()]),
+        ("undocumented", DocumentedStatus::Undocumented, vec!["TypeError".to_string()]),
+        ("suppressed", DocumentedStatus::Suppressed, vec!["Error".to_string()]),
+      ]
+    );
+    assert_eq!(parse_result.function_summaries[0].range.start.line, 4);
+
+    let serialized = serde_json::to_value(&parse_result).unwrap();
+    assert_eq!(serialized["function_summaries"][1]["status"], "partiallyDocumented");
+    assert_eq!(serialized["function_summaries"][1]["documented_types"], serde_json::json!(["TypeError"]));
+  }
+  #[test]
+  fn test_function_summaries_list_undocumented_types_and_insert_position() {
+    let code = r#"/**
+ * @throws {TypeError}
+ */
+function documented() {
+  throw new TypeError("bad");
+}
+
+/**
+ * Checks the flag.
+ * @throws {TypeError}
+ */
+function partial(flag) {
+  if (flag) {
+    throw new RangeError("range");
+  }
+  documented();
+}
+
+  function undocumented(flag) {
+    if (flag) throw new SyntaxError("syntax");
+    documented();
+  }
+"#;
+    let input_data = InputData {
+      file_content: Some(code.to_string()),
+      ..Default::default()
+    };
+    let user_settings = input_data.user_settings();
+    let parse_result = analyze_single_file(input_data, &user_settings);
+
+    let undocumented_types: Vec<(&str, Vec<String>)> = parse_result
+      .function_summaries
+      .iter()
+      .map(|summary| (summary.name.as_str(), summary.undocumented_types.clone()))
+      .collect();
+    assert_eq!(
+      undocumented_types,
+      vec![
+        ("documented", vec![]),
+        ("partial", vec!["RangeError".to_string()]),
+        ("undocumented", vec!["SyntaxError".to_string(), "TypeError".to_string()]),
+      ]
+    );
+    // Into the existing JSDoc before its `*/`, or above the function's first line
+    let insert_positions: Vec<(usize, usize, bool)> = parse_result
+      .function_summaries
+      .iter()
+      .map(|summary| (summary.insert_position.line, summary.insert_position.character, summary.append_to_existing_doc))
+      .collect();
+    assert_eq!(insert_positions, vec![(2, 1, true), (10, 1, true), (18, 0, false)]);
+
+    let serialized = serde_json::to_value(&parse_result).unwrap();
+    assert_eq!(serialized["function_summaries"][2]["undocumented_types"], serde_json::json!(["SyntaxError", "TypeError"]));
+    assert_eq!(serialized["function_summaries"][2]["insert_position"], serde_json::json!({ "line": 18, "character": 0 }));
+  }
+  #[test]
+  fn test_throws_in_parameter_defaults_and_computed_keys() {
+    let messages = |code: &str| -> Vec<String> { diagnostics_for_code(code).into_iter().map(|d| d.message).collect() };
+
+    let default_throw = messages("function f(x = (() => { throw new TypeError('missing x') })()) {\n  return x;\n}\n");
+    assert!(default_throw.iter().any(|m| m == "Function f may throw: {TypeError}"), "{:?}", default_throw);
+
+    let must_get_key = "function mustGetKey() {\n  throw new RangeError('no key');\n}\n";
+    let default_call = messages(&format!("{}function g(y = mustGetKey()) {{\n  return y;\n}}\n", must_get_key));
+    assert!(default_call.iter().any(|m| m == "Function g may throw: {RangeError}"), "{:?}", default_call);
+
+    let computed_key = messages(&format!(
+      "{}function build() {{\n  const obj = {{ [mustGetKey()]: 1 }};\n  return obj;\n}}\n",
+      must_get_key
+    ));
+    assert!(computed_key.iter().any(|m| m == "Function build may throw: {RangeError}"), "{:?}", computed_key);
+
+    // @it-throws above the parameter covers its default value
+    let suppressed = messages(
+      "function f(\n  // @it-throws\n  x = (() => { throw new TypeError('missing x') })()\n) {\n  return x;\n}\n",
+    );
+    assert!(suppressed.iter().all(|m| !m.contains("TypeError")), "{:?}", suppressed);
+    let suppressed_call = messages(&format!(
+      "{}function g(\n  // @it-throws\n  y = mustGetKey()\n) {{\n  return y;\n}}\n",
+      must_get_key
+    ));
+    assert!(suppressed_call.iter().all(|m| !m.starts_with("Function g")), "{:?}", suppressed_call);
+  }
+  #[test]
+  fn test_catch_rethrows_are_reported_separately_from_wrapped_errors() {
+    let code = r#"class ValidationError extends Error {}
+class AppError extends Error {}
+function validate(input) {
+  if (!input) {
+    throw new ValidationError("missing");
+  }
+  if (input < 0) {
+    throw new RangeError("negative");
+  }
+}
+function save(input) {
+  try {
+    validate(input);
+  } catch (e) {
+    if (e instanceof ValidationError) {
+      throw e;
+    } else {
+      throw new AppError(e);
+    }
+  }
+}
+function load(input) {
+  try {
+    validate(input);
+  } catch (e) {
+    logger.error(e);
+    throw e;
+  }
+}
+"#;
+    let messages = |include_catch_rethrows: Option<bool>| -> Vec<String> {
+      let input_data = InputData {
+        file_content: Some(code.to_string()),
+        include_catch_rethrows,
+        ..Default::default()
+      };
+      let user_settings = input_data.user_settings();
+      analyze_single_file(input_data, &user_settings)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.code.as_deref() == Some(FUNCTION_MAY_THROW_CODE))
+        .map(|d| d.message)
+        .collect()
+    };
+
+    let messages_with_rethrows = messages(None);
+    assert!(
+      messages_with_rethrows.contains(&"Function save may throw: {AppError} and rethrow: {ValidationError}".to_string()),
+      "{:?}",
+      messages_with_rethrows
+    );
+    assert!(
+      messages_with_rethrows.contains(&"Function load may rethrow: {RangeError, ValidationError}".to_string()),
+      "{:?}",
+      messages_with_rethrows
+    );
+
+    let messages_without_rethrows = messages(Some(false));
+    assert!(
+      messages_without_rethrows.contains(&"Function save may throw: {AppError}".to_string()),
+      "{:?}",
+      messages_without_rethrows
+    );
+    assert!(messages_without_rethrows.iter().all(|m| !m.contains("load")), "{:?}", messages_without_rethrows);
+  }
+
+  #[test]
+  fn test_calls_to_object_literal_methods_link_to_their_throws() {
+    let code = r#"class NetErr extends Error {}
+const api = {
+  fetch() {
+    throw new NetErr('down');
+  },
+  post: (body) => {
+    throw new TypeError('bad body');
+  },
+  put: function (body) {
+    throw new RangeError('bad');
+  },
+  get status() {
+    throw new SyntaxError('no status');
+  },
+  'delete'() {
+    throw new URIError('gone');
+  },
+};
+function run() {
+  api.fetch();
+  api.post({});
+  api.put({});
+  api.delete();
+}
+"#;
+
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<String> = diagnostics.iter().map(|d| d.message.clone()).collect();
+
+    for expected in [
+      "Function fetch may throw: {NetErr}",
+      "Function post may throw: {TypeError}",
+      "Function put may throw: {RangeError}",
+      "Function get status may throw: {SyntaxError}",
+      "Function delete may throw: {URIError}",
+      "Function run may throw: {NetErr, RangeError, TypeError, URIError}",
+    ] {
+      assert!(messages.contains(&expected.to_string()), "missing {:?} in {:?}", expected, messages);
+    }
+
+    let call_lines: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.code.as_deref() == Some("DIT002"))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+    assert_eq!(
+      call_lines,
+      vec![
+        (20, "Function call may throw: {NetErr}."),
+        (21, "Function call may throw: {TypeError}."),
+        (22, "Function call may throw: {RangeError}."),
+        (23, "Function call may throw: {URIError}."),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_throws_in_ternary_and_short_circuit_branches() {
+    let code = r#"function fail(message) {
+  throw new TypeError(message);
+}
+function pick(cond, x) {
+  const a = cond ? x : fail('no');
+  const b = x ?? fail('none');
+  cond && fail('bad');
+  return a || b;
+}
+function inline(cond) {
+  return cond ? 1 : (() => { throw new RangeError('out of range'); })();
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+
+    for line in 5..=7 {
+      assert!(messages.contains(&(line, "Function call may throw: {TypeError}.")), "{:?}", messages);
+    }
+    assert!(messages.contains(&(10, "Function inline may throw: {RangeError}")), "{:?}", messages);
+    assert!(messages.contains(&(11, "Throw: new RangeError('out of range')")), "{:?}", messages);
+
+    // The throw expressions proposal (`x ?? throw new Error()`) isn't supported by the parser
+    let (result, _, _) = analyze_code(
+      "function f(x) {\n  return x ?? throw new Error('missing');\n}\n",
+      Default::default(),
+      &InputData::default().user_settings(),
+    );
+    assert!(!result.parse_errors.is_empty());
+  }
+
+  #[test]
+  fn test_collapse_throw_statements_into_function_diagnostic() {
+    let code = r#"function validate(input) {
+  if (!input) throw new TypeError('missing');
+  if (input.length > 10) throw new RangeError('too long');
+  throw new TypeError('invalid');
+}
+"#;
+    let expanded = diagnostics_for_settings_json(code, "{}");
+    let expanded_messages: Vec<&str> = expanded.iter().map(|d| d.message.as_str()).collect();
+    assert!(expanded_messages.contains(&"Function validate may throw: {RangeError, TypeError}"), "{:?}", expanded_messages);
+    assert_eq!(expanded.iter().filter(|d| d.code.as_deref() == Some(THROW_STATEMENT_CODE)).count(), 3);
+
+    let collapsed = diagnostics_for_settings_json(code, r#"{ "collapse_throw_statements": true }"#);
+    assert_eq!(collapsed.len(), 1, "{:?}", collapsed.iter().map(|d| &d.message).collect::<Vec<_>>());
+    assert_eq!(collapsed[0].message, "Function validate may throw (3 statements): {RangeError, TypeError}");
+
+    let data = serde_json::to_value(collapsed[0].data.as_ref().unwrap()).unwrap();
+    let lines: Vec<u64> = data["throwLocations"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|range| range["start"]["line"].as_u64().unwrap())
+      .collect();
+    assert_eq!(lines, vec![2, 3, 4]);
+    // The quick fix payload is kept alongside the locations
+    assert_eq!(data["quickFixType"], "addThrowsAnnotation");
+  }
+
+  #[test]
+  fn test_try_without_catch_does_not_handle_errors() {
+    let code = r#"/** @throws {NetworkError} */
+async function fetchData() {
+  throw new NetworkError('offline');
+}
+
+async function load() {
+  try {
+    await fetchData();
+  } finally {
+    cleanup();
+  }
+}
+
+function parse() {
+  try {
+    throw new SyntaxError('bad input');
+  } finally {
+    cleanup();
+  }
+}
+
+async function guarded() {
+  try {
+    try {
+      await fetchData();
+    } finally {
+      cleanup();
+    }
+  } catch (e) {
+    console.log(e);
+  }
+}
+"#;
+    let messages = |json: &str| -> Vec<String> {
+      diagnostics_for_settings_json(code, json)
+        .into_iter()
+        .filter(|d| d.code.as_deref() != Some(THROW_STATEMENT_CODE))
+        .map(|d| d.message)
+        .collect()
+    };
+
+    // `finally` runs cleanup but lets the error through, so these read as if there were no try
+    let excluding_try_throws = messages("{}");
+    for expected in [
+      "Function load may reject: {NetworkError}",
+      "Awaited call may throw: {NetworkError}.",
+      "Function parse may throw: {SyntaxError}",
+    ] {
+      assert!(excluding_try_throws.iter().any(|m| m == expected), "missing {:?} in {:?}", expected, excluding_try_throws);
+    }
+    // The outer catch still swallows what escapes the inner try/finally
+    assert!(
+      !excluding_try_throws.iter().any(|m| m.contains("guarded")),
+      "{:?}",
+      excluding_try_throws
+    );
+    assert_eq!(excluding_try_throws.iter().filter(|m| m.starts_with("Awaited call")).count(), 1);
+
+    let including_try_throws = messages(r#"{ "include_try_statement_throws": true }"#);
+    for expected in [
+      "Function load may reject: {NetworkError}",
+      "Function parse may throw: {SyntaxError}",
+      "Function guarded may reject: {NetworkError}",
+    ] {
+      assert!(including_try_throws.iter().any(|m| m == expected), "missing {:?} in {:?}", expected, including_try_throws);
+    }
+    assert_eq!(including_try_throws.iter().filter(|m| m.starts_with("Awaited call")).count(), 2);
+  }
+
+  #[test]
+  fn test_empty_catch_blocks_that_swallow_errors_warn() {
+    let code = r#"function load() {
+  throw new NetworkError('offline');
+}
+
+function ignored() {
+  try {
+    load();
+  } catch (e) {}
+}
+
+function nothingThrown() {
+  try {
+    compute();
+  } catch (e) {
+    // best effort
+  }
+}
+
+function logged() {
+  try {
+    load();
+  } catch (e) {
+    console.error(e);
+  }
+}
+
+function intended() {
+  try {
+    load();
+  } catch (e) {
+    // @it-throws
+  }
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let swallowed: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.code.as_deref() == Some(SWALLOWED_ERRORS_CODE))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+    assert_eq!(swallowed, vec![(8, "Catch block silently swallows: {NetworkError}")]);
+    assert_eq!(
+      diagnostics.iter().find(|d| d.code.as_deref() == Some(SWALLOWED_ERRORS_CODE)).unwrap().severity,
+      DiagnosticSeverity::Warning.to_int()
+    );
+    // Separate from the exhaustive catch check, and the suppressing comment counts as used
+    assert!(diagnostics.iter().all(|d| d.code.as_deref() != Some(EXHAUSTIVE_CATCH_CODE)));
+    assert!(diagnostics.iter().all(|d| d.code.as_deref() != Some(UNUSED_IT_THROWS_CODE)), "{:?}", diagnostics);
+  }
+
+  #[test]
+  fn test_ignore_imports_silences_imported_usages() {
+    let code = r#"import { wrap } from '@acme/errors';
+import { convert } from './legacy/v1/convert';
+// @it-throws
+import { request } from 'http-client';
+import { parse } from 'yaml'; // @it-throws
+// @it-throws
+import { unused } from 'never-called';
+import { save } from './storage';
+
+export function run() {
+  wrap();
+  convert();
+  request();
+  parse();
+  save();
+}
+"#;
+    let imported_lines = |json: &str| -> Vec<usize> {
+      let mut lines: Vec<usize> = parse_result_for_settings_json(code, json)
+        .imported_identifiers_diagnostics
+        .iter()
+        .flat_map(|imported| imported.diagnostics.iter().map(|d| d.range.start.line))
+        .collect();
+      lines.sort();
+      lines
+    };
+    // The import comments still apply without any configured patterns
+    assert_eq!(imported_lines("{}"), vec![11, 12, 15]);
+    assert_eq!(imported_lines(r#"{ "ignore_imports": ["@acme/*", "./legacy/**"] }"#), vec![15]);
+
+    // Only the comment on the import nothing calls is reported as unused
+    let unused: Vec<usize> = diagnostics_for_settings_json(code, "{}")
+      .iter()
+      .filter(|d| d.code.as_deref() == Some(UNUSED_IT_THROWS_CODE))
+      .map(|d| d.range.start.line)
+      .collect();
+    assert_eq!(unused, vec![6]);
+  }
+
+  #[test]
+  fn test_calling_typed_callback_parameter_with_throws_comment() {
+    let code = r#"function parseAll(parse: (text: string) => number /* @throws {ParseError} */, texts: string[]) {
+  const values = [];
+  for (const text of texts) {
+    const value = parse(text);
+    values.push(value);
+  }
+  return values;
+}
+
+/** @throws {ParseError} */
+function parseFirst(parse: (text: string) => number /* @throws {ParseError} */) {
+  return parse('1');
+}
+
+function format(render: (value: number) => string, value: number) {
+  return render(value);
+}
+"#;
+    let messages: Vec<String> = diagnostics_for_code(code)
+      .into_iter()
+      .filter(|d| d.code.as_deref() != Some(THROW_STATEMENT_CODE))
+      .map(|d| format!("{}: {}", d.range.start.line, d.message))
+      .collect();
+    // Documenting the callback's throws on the caller covers the call, as for any other thrower
+    assert_eq!(
+      messages,
+      vec![
+        "1: Function parseAll may throw: {ParseError}",
+        "4: Call to parse may throw: {ParseError}",
+      ]
+    );
+  }
+
+  #[test]
+  fn test_member_calls_resolve_against_declared_objects_and_static_methods() {
+    let code = "export const utils = {\n  validate(input: string) {\n    throw new TypeError(input)\n  },\n}\nconst api = { users: { create: () => { throw new RangeError('x') } } }\nclass MyService {\n  static create() {\n    throw new SyntaxError('s')\n  }\n}\nexport function run(input: string) {\n  utils.validate(input)\n  api.users.create()\n  MyService.create()\n}\n";
+    let diagnostics = diagnostics_for_code(code);
+    let call_messages: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+
+    // `api.users.create` and `MyService.create` share a method name but resolve to their own throws
+    assert!(call_messages.contains(&(13, "Function call may throw: {TypeError}.")), "{:?}", diagnostics);
+    assert!(call_messages.contains(&(14, "Function call may throw: {RangeError}.")), "{:?}", diagnostics);
+    assert!(call_messages.contains(&(15, "Function call may throw: {SyntaxError}.")), "{:?}", diagnostics);
+    assert!(
+      diagnostics.iter().any(|d| d.message == "Function run may throw: {RangeError, SyntaxError, TypeError}"),
+      "{:?}",
+      diagnostics
+    );
+  }
+
+  #[test]
+  fn test_next_line_comment_suppresses_single_throw_unlike_function_comment() {
+    let code = r#"function parse(input) {
+  if (!input) {
+    // @it-throws-next-line
+    throw new TypeError("missing input");
+  }
+  throw new RangeError("bad input");
+}
+
+// @it-throws
+function parseQuietly(input) {
+  if (!input) {
+    throw new TypeError("missing input");
+  }
+  throw new RangeError("bad input");
+}
+
+function caller() {
+  // @it-throws-next-line
+  parse("a");
+  parse("b");
+}
+
+function unused() {
+  // @it-throws-next-line
+
+  throw new Error("a blank line in between");
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+
+    // The next-line comment covers the TypeError throw only; the rest of the function still reports
+    assert!(messages.contains(&(1, "Function parse may throw: {RangeError}")), "{:?}", messages);
+    assert!(!messages.iter().any(|(_, m)| m.contains("missing input")), "{:?}", messages);
+    assert!(messages.contains(&(6, "Throw: new RangeError(\"bad input\")")), "{:?}", messages);
+    // A function comment covers the function and every throw in it
+    assert!(!messages.iter().any(|(line, _)| (9..=15).contains(line)), "{:?}", messages);
+    // Only the call right below the comment is suppressed
+    assert!(!messages.iter().any(|(line, _)| *line == 19), "{:?}", messages);
+    assert!(messages.contains(&(20, "Function call may throw: {RangeError}.")), "{:?}", messages);
+    // A blank line between the comment and the throw leaves it covering nothing
+    assert!(messages.contains(&(24, "Unused @it-throws comment. This comment is not suppressing any diagnostics.")), "{:?}", messages);
+    assert!(messages.contains(&(26, "Throw: new Error(\"a blank line in between\")")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_json_parse_surfaces_as_syntax_error() {
+    let code = r#"function loadSettings(raw) {
+  const settings = JSON.parse(raw);
+  return settings;
+}
+
+/**
+ * @throws {SyntaxError}
+ */
+function loadDocumented(raw) {
+  return JSON.parse(raw);
+}
+
+function loadTrusted(raw) {
+  // @it-throws
+  const settings = JSON.parse(raw);
+  return settings;
+}
+"#;
+    let diagnostics = diagnostics_for_settings_json(code, r#"{ "flag_json_parse": true }"#);
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+
+    assert!(messages.contains(&(1, "Function loadSettings may throw: {SyntaxError}")), "{:?}", messages);
+    assert!(messages.contains(&(2, "Call to JSON.parse may throw: {SyntaxError}")), "{:?}", messages);
+    // Documenting the SyntaxError or an @it-throws on the call line keeps the other functions silent
+    assert!(messages.iter().all(|(line, _)| *line <= 2), "{:?}", messages);
+
+    // Without the opt-in, JSON.parse is not treated as throwing
+    let diagnostics = diagnostics_for_code(code);
+    assert!(diagnostics.iter().all(|d| !d.message.contains("may throw")), "{:?}", diagnostics);
+  }
+
+  #[test]
+  fn test_component_script_blocks_are_analyzed_in_place() {
+    let code = r#"<template>
+  <button @click="save">Save</button>
+</template>
+<script lang="ts">
+export function validate(input: string) {
+  if (!input) throw new TypeError("empty");
+}
+</script>
+<script setup lang="ts">
+function save() {
+  throw new RangeError("not saved");
+}
+</script>
+"#;
+    let mut input_data: InputData = serde_json::from_str(r#"{ "language": "vue" }"#).unwrap();
+    input_data.file_content = Some(code.to_string());
+    let user_settings = input_data.user_settings();
+    let parse_result = analyze_single_file(input_data.clone(), &user_settings);
+    let messages: Vec<(usize, usize, &str)> = parse_result
+      .diagnostics
+      .iter()
+      .map(|d| (d.range.start.line, d.range.start.character, d.message.as_str()))
+      .collect();
+
+    // Both blocks report, with lines and columns of the component file
+    assert!(messages.contains(&(5, 16, "Function validate may throw: {TypeError}")), "{:?}", messages);
+    assert!(messages.contains(&(6, 14, "Throw: new TypeError(\"empty\")")), "{:?}", messages);
+    assert!(messages.contains(&(10, 9, "Function save may throw: {RangeError}")), "{:?}", messages);
+    assert_eq!(parse_result.function_summaries.len(), 2);
+
+    // Markup doesn't parse as TypeScript, and a component without scripts has nothing to report
+    let whole_file = analyze_single_file(InputData { language: None, ..input_data.clone() }, &user_settings);
+    assert!(whole_file.diagnostics.iter().all(|d| d.code.as_deref() == Some("DIT012")), "{:?}", whole_file.diagnostics);
+    let markup_only = InputData { file_content: Some("<p>{message}</p>\n".to_string()), ..input_data };
+    assert!(analyze_single_file(markup_only, &user_settings).diagnostics.is_empty());
+  }
+
+  #[test]
+  fn test_component_files_link_by_extension() {
+    let files = vec![
+      (
+        "errors.ts".to_string(),
+        "export function failHard(reason: string) {\n  throw new RangeError(reason);\n}\n".to_string(),
+      ),
+      (
+        "Form.svelte".to_string(),
+        "<script lang=\"ts\">\n  import { failHard } from './errors';\n\n  function submit() {\n    failHard('nope');\n  }\n</script>\n\n<form on:submit={submit}></form>\n".to_string(),
+      ),
+    ];
+    let input_data = InputData::default();
+    let user_settings = input_data.user_settings();
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &HashSet::new(), &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+    assert!(messages.contains(&(5, "Function call may throw: {RangeError}.")), "{:?}", messages);
+    assert!(messages.contains(&(5, "Function submit may throw: {RangeError}")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_parse_errors_are_reported_as_diagnostics() {
+    // The throw expressions proposal isn't supported, so nothing is analyzed
+    let diagnostics = diagnostics_for_code("function f(x) {\n  return x ?? throw new Error('missing');\n}\n");
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("DIT012"));
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error.to_int());
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert!(diagnostics[0].message.starts_with("Parse error: "), "{}", diagnostics[0].message);
+
+    // Errors the parser recovers from are reported alongside the analysis of the rest
+    let diagnostics = diagnostics_for_code("class A {\n  constructor() {}\n  constructor() {}\n}\nfunction f() {\n  throw new Error('x');\n}\n");
+    let codes: Vec<(usize, Option<&str>)> = diagnostics.iter().map(|d| (d.range.start.line, d.code.as_deref())).collect();
+    assert!(codes.contains(&(3, Some("DIT012"))), "{:?}", codes);
+    assert!(codes.contains(&(5, Some("DIT001"))), "{:?}", codes);
+  }
+
+  #[test]
+  fn test_files_that_fail_to_parse_report_why() {
+    let files = vec![
+      ("broken.ts".to_string(), "export function broken() {\n  if (\n}\n".to_string()),
+      ("ok.ts".to_string(), "export function fine() {\n  throw new RangeError('x');\n}\n".to_string()),
+      ("ignored.ts".to_string(), "export function alsoBroken( {\n".to_string()),
+    ];
+    let suppressed: HashSet<String> = ["ignored.ts".to_string()].into_iter().collect();
+    let input_data = InputData::default();
+    let user_settings = input_data.user_settings();
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &suppressed, &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+
+    let parse_errors: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.code.as_deref() == Some("DIT012")).collect();
+    assert_eq!(parse_errors.len(), 1, "{:?}", diagnostics);
+    assert_eq!(parse_errors[0].range.start.line, 3);
+    assert!(diagnostics.iter().any(|d| d.message == "Function fine may throw: {RangeError}"), "{:?}", diagnostics);
+  }
+
+  #[test]
+  fn test_throwing_accessors_are_flagged_where_read_or_written() {
+    let code = r#"class Temperature {
+  get celsius() {
+    throw new RangeError("sensor offline");
+  }
+  read() {
+    return 21;
+  }
+}
+
+const config = {
+  get port() {
+    throw new TypeError("unset");
+  },
+  set port(value) {
+    throw new RangeError("frozen");
+  },
+};
+
+function show() {
+  const temperature = new Temperature();
+  temperature.read();
+  return temperature.celsius;
+}
+
+function update() {
+  config.port = config.port + 1;
+}
+"#;
+    let diagnostics = diagnostics_for_code(code);
+    let calls: Vec<(usize, &str)> = diagnostics
+      .iter()
+      .filter(|d| d.message.starts_with("Function call may throw"))
+      .map(|d| (d.range.start.line, d.message.as_str()))
+      .collect();
+    assert_eq!(
+      calls,
+      vec![
+        (22, "Function call may throw: {RangeError}."),
+        (26, "Function call may throw: {RangeError}."),
+        (26, "Function call may throw: {TypeError}."),
+      ],
+      "{:?}",
+      diagnostics
+    );
+    // The method is a plain call that doesn't throw
+    assert!(diagnostics.iter().all(|d| d.range.start.line != 21), "{:?}", diagnostics);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(messages.contains(&"Function show may throw: {RangeError}"), "{:?}", messages);
+    assert!(messages.contains(&"Function set port may throw: {RangeError}"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_imported_accessor_reads_link_to_throwing_accessors() {
+    let files = vec![
+      (
+        "config.ts".to_string(),
+        "export const config = {\n  get port() {\n    throw new TypeError('unset');\n  },\n};\n".to_string(),
+      ),
+      (
+        "main.ts".to_string(),
+        "import { config } from './config';\n\nexport function listen() {\n  return config.port;\n}\n".to_string(),
+      ),
+    ];
+    let input_data = InputData::default();
+    let user_settings = input_data.user_settings();
+    let cm: Lrc<SourceMap> = Default::default();
+    let (results, comments, all_throw_spans, disable_regions) = analyze_files(&files, &HashSet::new(), &cm, &user_settings);
+    let comments_as_dyn: &Lrc<dyn Comments> = &(comments.clone() as Lrc<dyn Comments>);
+    let diagnostics = ParseResult::into(results, &cm, input_data, comments_as_dyn, &user_settings, all_throw_spans, &disable_regions).diagnostics;
+    let messages: Vec<(usize, &str)> = diagnostics.iter().map(|d| (d.range.start.line, d.message.as_str())).collect();
+    assert!(messages.contains(&(4, "Function call may throw: {TypeError}.")), "{:?}", messages);
+
+    // Analyzed alone, the read is an imported usage under the getter's id
+    let single_file = analyze_single_file(
+      InputData { file_content: Some(files[1].1.clone()), ..InputData::default() },
+      &user_settings,
+    );
+    let ids: Vec<&str> = single_file.imported_identifiers_diagnostics.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec!["config-get port"]);
+    assert!(single_file.diagnostics.is_empty(), "{:?}", single_file.diagnostics);
+  }
+
+  #[test]
+  fn test_class_it_throws_comment_covers_every_method() {
+    let code = "function risky() {\n  throw new RangeError('r');\n}\n// @it-throws\nclass Legacy {\n  a() {\n    throw new Error('a');\n  }\n  b() {\n    throw new TypeError('b');\n  }\n  c() {\n    risky();\n  }\n}\n";
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    assert_eq!(messages, vec![(1, "Function risky may throw: {RangeError}"), (2, "Throw: new RangeError('r')")]);
+
+    // A comment listing types only covers those, and flags the ones no method throws
+    let code = "// @it-throws TypeError, SyntaxError\nexport class Legacy {\n  a() {\n    throw new Error('a');\n  }\n  b() {\n    throw new TypeError('b');\n  }\n}\n";
+    let diagnostics = diagnostics_for_settings_json(code, "{}");
+    let mut messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    messages.sort();
+    assert_eq!(messages, vec![
+      (1, "Unused @it-throws type: SyntaxError. Nothing it covers throws this type."),
+      (3, "Function a may throw: {Error}"),
+      (4, "Throw: new Error('a')"),
+    ]);
+  }
+
+  #[test]
+  fn test_class_it_throws_comment_without_throwing_methods_is_unused() {
+    for code in [
+      "const x = 1;\n// @it-throws\nclass Quiet {\n  a() {\n    return 1;\n  }\n}\n",
+      "// @it-throws\nexport default class Quiet {\n  a() {\n    return 1;\n  }\n}\n",
+    ] {
+      let diagnostics = diagnostics_for_settings_json(code, "{}");
+      assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+      assert_eq!(diagnostics[0].code.as_deref(), Some(UNUSED_IT_THROWS_CODE));
+      assert_eq!(diagnostics[0].range.start.line, code.lines().position(|line| line == "// @it-throws").unwrap() + 1);
+    }
+  }
+
+  #[test]
+  fn test_import_it_throws_comment_is_used_when_the_import_is() {
+    let settings = r#"{"treat_unknown_imports_as_throwing": true}"#;
+    let used = "// @it-throws\nimport { load } from './load';\nfunction start() {\n  load();\n}\n";
+    assert!(diagnostics_for_settings_json(used, settings).is_empty());
+    assert!(!diagnostics_for_settings_json(used.trim_start_matches("// @it-throws\n"), settings).is_empty());
+
+    let unused = "// @it-throws\nimport { load } from './load';\nfunction start() {\n  return 1;\n}\n";
+    let diagnostics = diagnostics_for_settings_json(unused, "{}");
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].code.as_deref(), Some(UNUSED_IT_THROWS_CODE));
+    assert_eq!(diagnostics[0].range.start.line, 1);
+  }
+
+  #[test]
+  fn test_caller_with_own_throw_keeps_diagnostics_for_transitive_throws() {
+    let code = "function c() {\n  throw new Error('c');\n}\nfunction b() {\n  c();\n}\nfunction a() {\n  b();\n  throw new TypeError('own');\n}\n";
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    assert!(messages.contains(&(7, "Function a may throw: {Error, TypeError}")), "{:?}", messages);
+    assert!(messages.contains(&(9, "Throw: new TypeError('own')")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_free_function_reading_a_throwing_getter_may_throw() {
+    let code = "class Box {\n  get value() {\n    throw new RangeError('empty');\n  }\n}\nfunction read() {\n  const b = new Box();\n  return b.value;\n}\nfunction outer() {\n  read();\n}\n";
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    // The caller is the free function, so its diagnostic sits on the function and reaches its own callers
+    assert!(messages.contains(&(6, "Function read may throw: {RangeError}")), "{:?}", messages);
+    assert!(messages.contains(&(8, "Function call may throw: {RangeError}.")), "{:?}", messages);
+    assert!(messages.contains(&(10, "Function outer may throw: {RangeError}")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_callers_of_a_rethrowing_function_see_the_resolved_type() {
+    let code = "function inner() {\n  throw new TypeError('x');\n}\nfunction re() {\n  try {\n    inner();\n  } catch (e) {\n    throw e;\n  }\n}\nfunction callsRe() {\n  re();\n}\n";
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    assert!(messages.contains(&(4, "Function re may rethrow: {TypeError}")), "{:?}", messages);
+    assert!(messages.contains(&(12, "Function call may throw: {TypeError}.")), "{:?}", messages);
+    assert!(messages.contains(&(11, "Function callsRe may throw: {TypeError}")), "{:?}", messages);
+    assert!(messages.iter().all(|(_, message)| !message.contains("variable:")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_unresolved_rethrow_never_names_the_catch_parameter() {
+    let code = "function catcher() {\n  try {\n    doThing();\n  } catch (e) {\n    throw e;\n  }\n}\nfunction top() {\n  catcher();\n}\n";
+    let diagnostics = diagnostics_for_code(code);
+    let messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    assert!(messages.contains(&(1, "Function catcher may rethrow")), "{:?}", messages);
+    assert!(messages.contains(&(9, "Function call may throw: {Error}.")), "{:?}", messages);
+    assert!(messages.iter().all(|(_, message)| !message.contains("variable:")), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_plain_caller_of_a_rethrowing_function_throws_rather_than_rethrows() {
+    let code = "function catcher() {\n  try {\n    JSON.parse('x');\n  } catch (e) {\n    throw e;\n  }\n}\nfunction top() {\n  catcher();\n}\n";
+    let diagnostics = diagnostics_for_settings_json(code, r#"{ "flag_json_parse": true }"#);
+    let messages: Vec<(usize, &str)> =
+      diagnostics.iter().map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str())).collect();
+    assert!(messages.contains(&(1, "Function catcher may rethrow: {SyntaxError}")), "{:?}", messages);
+    assert!(messages.contains(&(8, "Function top may throw: {SyntaxError}")), "{:?}", messages);
+  }
+}
\ No newline at end of file
@@ -8,18 +8,25 @@ use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
 use swc_ecma_ast::{
-  ArrowExpr, AwaitExpr, BinExpr, BlockStmtOrExpr, Callee, ClassDecl, ClassMethod, Decl, FnDecl,
-  JSXAttr, JSXAttrOrSpread, JSXAttrValue, JSXExpr, JSXOpeningElement, MemberExpr, ObjectLit, OptChainBase,
-  OptChainExpr, ParenExpr, Stmt, VarDeclarator,
+  ArrowExpr, AssignExpr, AssignOp, AwaitExpr, BinExpr, BlockStmtOrExpr, Callee, ClassDecl, ClassMember, ClassMethod,
+  Decl, FnDecl, ForOfStmt, JSXAttr, JSXAttrOrSpread, JSXAttrValue, JSXExpr, JSXOpeningElement, MemberExpr, MethodKind, Module,
+  ObjectLit, OptChainBase, OptChainExpr, ParenExpr, Pat, PatOrExpr, Prop, PropName, PropOrSpread, Stmt, VarDeclarator,
 };
 
-use crate::throw_finder::ThrowMap;
+use crate::throw_finder::{
+  class_member_name, ignore_comment_error_types, is_built_in_error, prop_name_to_string, BuiltinThrowCall, NextLineComment,
+  ThrowDetails, ThrowMap,
+};
 
 use self::swc_common::{Span, comments::Comments, sync::Lrc};
 use self::swc_ecma_ast::{CallExpr, Expr, Function, MemberProp};
 
 use self::swc_ecma_visit::Visit;
 
+/// Array methods that invoke an inline callback synchronously, so anything the
+/// callback throws escapes through the enclosing function
+pub const HIGHER_ORDER_ARRAY_METHODS: [&str; 7] = ["map", "forEach", "filter", "reduce", "find", "some", "every"];
+
 #[derive(Clone)]
 pub struct CallToThrowMap {
   pub call_span: Span,
@@ -28,11 +35,32 @@ pub struct CallToThrowMap {
   pub throw_map: ThrowMap,
   pub class_name: Option<String>,
   pub id: String,
+  pub is_awaited: bool, // Call is the operand of an `await`, so a rejection surfaces as a throw here
+  pub is_builtin: bool, // Call to a throwing builtin like `JSON.parse`, or to a parameter documented to throw, rather than to a user function
+  pub suppressed_error_types: Vec<String>, // Listed in an `@it-throws <ErrorType>` comment on the call; not propagated
+}
+
+impl CallToThrowMap {
+  /// The callee's throws that reach the caller, leaving out types suppressed on the call
+  pub fn propagated_throw_details(&self) -> Vec<ThrowDetails> {
+    self
+      .throw_map
+      .throw_details
+      .iter()
+      .filter(|detail| {
+        !detail
+          .error_type
+          .as_ref()
+          .is_some_and(|error_type| self.suppressed_error_types.contains(error_type))
+      })
+      .cloned()
+      .collect()
+  }
 }
 
 impl PartialEq for CallToThrowMap {
   fn eq(&self, other: &Self) -> bool {
-    self.id == other.id
+    self.id == other.id && self.call_span == other.call_span
   }
 }
 
@@ -46,6 +74,17 @@ impl Hash for CallToThrowMap {
   }
 }
 
+/// A call from one function to another, recorded whether or not the callee is known to throw
+/// yet, so throws discovered through propagation can keep flowing up the call graph
+#[derive(Clone, Debug)]
+pub struct CallEdge {
+  pub caller_id: String,
+  pub callee_name: String,
+  pub callee_id: Option<String>, // Plain calls name the callee exactly; method calls match by name
+  pub call_span: Span,
+  pub suppressed_error_types: Vec<String>, // Listed in an `@it-throws <ErrorType>` comment on the call; not propagated
+}
+
 pub struct InstantiationsMap {
   pub class_name: String,
   pub variable_name: String,
@@ -66,19 +105,105 @@ impl Hash for InstantiationsMap {
   }
 }
 
+/// Collects the member paths a module declares functions under: methods of object literals bound
+/// to a variable (`utils.validate`, `api.users.create`) and static class methods (`MyService.create`)
+#[derive(Default)]
+struct MemberPathFinder {
+  function_spans: Vec<(String, Span)>,
+}
+
+impl MemberPathFinder {
+  fn collect_object_lit(&mut self, path: &str, object_lit: &ObjectLit) {
+    for prop in &object_lit.props {
+      if let PropOrSpread::Prop(prop) = prop {
+        match &**prop {
+          Prop::Method(method_prop) => {
+            let member_path = format!("{}.{}", path, prop_name_to_string(&method_prop.key));
+            self.function_spans.push((member_path, method_prop.function.span));
+          }
+          Prop::KeyValue(key_value_prop) => {
+            let member_path = format!("{}.{}", path, prop_name_to_string(&key_value_prop.key));
+            match &*key_value_prop.value {
+              Expr::Fn(fn_expr) => self.function_spans.push((member_path, fn_expr.function.span)),
+              Expr::Arrow(arrow_expr) => self.function_spans.push((member_path, arrow_expr.span)),
+              Expr::Object(nested) => self.collect_object_lit(&member_path, nested),
+              _ => {}
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+  }
+}
+
+impl Visit for MemberPathFinder {
+  fn visit_var_declarator(&mut self, var_declarator: &VarDeclarator) {
+    if let (Some(ident), Some(init)) = (var_declarator.name.as_ident(), &var_declarator.init) {
+      if let Expr::Object(object_lit) = &**init {
+        self.collect_object_lit(&ident.sym, object_lit);
+      }
+    }
+    swc_ecma_visit::visit_var_declarator(self, var_declarator);
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+    for member in &class_decl.class.body {
+      if let ClassMember::Method(method) = member {
+        if let Some(method_ident) = method.key.as_ident().filter(|_| method.is_static) {
+          self.function_spans.push((format!("{}.{}", class_decl.ident.sym, method_ident.sym), method.span));
+        }
+      }
+    }
+    swc_ecma_visit::visit_class_decl(self, class_decl);
+  }
+}
+
+/// Member paths declared in `module`, like `utils.validate`, mapped to the throwing function they name
+pub fn member_throw_maps(module: &Module, functions_with_throws: &HashSet<ThrowMap>) -> HashMap<String, ThrowMap> {
+  let mut finder = MemberPathFinder::default();
+  finder.visit_module(module);
+  finder
+    .function_spans
+    .into_iter()
+    .filter_map(|(path, span)| {
+      let throw_map = functions_with_throws.iter().find(|throw_map| throw_map.function_span == span)?;
+      Some((path, throw_map.clone()))
+    })
+    .collect()
+}
+
+/// The `a.b` or `a.b.c` path a member call is made through, when every link is a plain name
+fn member_call_path(member_expr: &MemberExpr) -> Option<String> {
+  let prop = match &member_expr.prop {
+    MemberProp::Ident(prop) => prop,
+    _ => return None,
+  };
+  match &*member_expr.obj {
+    Expr::Ident(object_ident) => Some(format!("{}.{}", object_ident.sym, prop.sym)),
+    Expr::Member(inner) => match (&*inner.obj, &inner.prop) {
+      (Expr::Ident(object_ident), MemberProp::Ident(inner_prop)) => {
+        Some(format!("{}.{}.{}", object_ident.sym, inner_prop.sym, prop.sym))
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
 // ----- CallFinder Visitor implementation -----
 // This module defines structures and implements functionality for identifying and mapping
 // function calls to their respective functions or methods that throw exceptions. It uses
 // SWC's visitor pattern to traverse the AST (Abstract Syntax Tree) of JavaScript or TypeScript code.
-///
+//
 // `CallToThrowMap` records the mapping of a function call to a function that throws.
 // It captures the span of the call, the name of the function/method being called,
 // the class name if the call is a method call, and the `ThrowMap` that provides details
 // about the throw statement in the called function/method.
-///
+//
 // `InstantiationsMap` keeps track of class instantiations by recording the class name
 // and the variable name that holds the instance.
-///
+//
 // `CallFinder` is the core structure that uses the Visitor pattern to traverse the AST nodes.
 // It maintains state as it goes through the code, keeping track of current class names,
 // function name stacks, and object property stacks. As it finds function calls, it tries
@@ -98,8 +223,26 @@ pub struct CallFinder {
   pub used_it_throws_comments: HashSet<Span>, // Track which @it-throws comments were actually used
   // Map of function id -> per-parameter allowed throws (by index)
   pub param_throws: HashMap<String, Vec<Vec<String>>>,
+  // Map of function id -> parameter name -> its index in `param_throws`, so calling the parameter throws
+  pub param_indices: HashMap<String, HashMap<String, usize>>,
   // Map of inline callback function spans -> allowed throws from typedef/@callback mapping
   pub inline_callback_allowed_throws: HashMap<Span, Vec<String>>,
+  // Spans of inline callbacks passed to higher-order array methods (map, forEach, ...)
+  pub higher_order_callbacks: HashSet<Span>,
+  // Spans of calls that appear directly under an `await` or as the iterable of a `for await`
+  pub awaited_calls: HashSet<Span>,
+  // Every named call, for propagating throws transitively
+  pub call_edges: Vec<CallEdge>,
+  // Calls to throwing builtins like `JSON.parse`, as found by `ThrowAnalyzer`
+  pub builtin_throw_calls: Vec<BuiltinThrowCall>,
+  // Imported names in the module being visited -> the name the function is declared under
+  pub import_aliases: HashMap<String, String>,
+  // Call spans -> error types listed in an `@it-throws <ErrorType>` comment on the call
+  pub typed_it_throws_calls: HashMap<Span, Vec<String>>,
+  // Member paths declared in the module being visited, like `utils.validate` -> the throwing function
+  pub member_throw_maps: HashMap<String, ThrowMap>,
+  // `@it-throws-next-line` comments, each covering only the calls on the line below it
+  pub next_line_comments: Vec<NextLineComment>,
 }
 
 impl CallFinder {
@@ -115,19 +258,116 @@ impl CallFinder {
       comments,
       used_it_throws_comments: HashSet::new(),
       param_throws: HashMap::new(),
+      param_indices: HashMap::new(),
       inline_callback_allowed_throws: HashMap::new(),
+      higher_order_callbacks: HashSet::new(),
+      awaited_calls: HashSet::new(),
+      call_edges: Vec::new(),
+      builtin_throw_calls: Vec::new(),
+      import_aliases: HashMap::new(),
+      typed_it_throws_calls: HashMap::new(),
+      member_throw_maps: HashMap::new(),
+      next_line_comments: Vec::new(),
+    }
+  }
+
+  /// Error types an `@it-throws <ErrorType>` comment on the call at `call_span` keeps from propagating
+  fn suppressed_error_types(&self, call_span: Span) -> Vec<String> {
+    self.typed_it_throws_calls.get(&call_span).cloned().unwrap_or_default()
+  }
+
+  /// Check whether the callee of a call matches any known throwing function by name
+  fn call_targets_throwing_function(&self, call: &CallExpr) -> bool {
+    if self.builtin_throw_calls.iter().any(|builtin_call| builtin_call.call_span == call.span) {
+      return true;
+    }
+    let called_name = match &call.callee {
+      Callee::Expr(expr) => match &**expr {
+        Expr::Ident(ident) => Some(
+          self
+            .import_aliases
+            .get(&*ident.sym)
+            .cloned()
+            .unwrap_or_else(|| ident.sym.to_string()),
+        ),
+        Expr::Member(member_expr) => match &member_expr.prop {
+          MemberProp::Ident(method_ident) => Some(method_ident.sym.to_string()),
+          _ => None,
+        },
+        _ => None,
+      },
+      _ => None,
+    };
+    match called_name {
+      Some(name) => self
+        .functions_with_throws
+        .iter()
+        .any(|throw_map| throw_map.function_or_method_name == name),
+      None => false,
+    }
+  }
+
+  /// Forget calls made inside a higher-order callback when its `@callback` typing allows
+  /// everything the callee throws
+  fn drop_calls_allowed_in_callback(&mut self, callback_span: Span) {
+    let allowed = match self.inline_callback_allowed_throws.get(&callback_span) {
+      Some(allowed) => allowed,
+      None => return,
+    };
+    let allowed_call_spans: Vec<Span> = self
+      .calls
+      .iter()
+      .filter(|call| {
+        callback_span.contains(call.call_span)
+          && !call.throw_map.throw_details.is_empty()
+          && call.throw_map.throw_details.iter().all(|detail| {
+            detail.error_type.as_ref().is_some_and(|error_type| allowed.contains(error_type))
+          })
+      })
+      .map(|call| call.call_span)
+      .collect();
+    self.calls.retain(|call| !allowed_call_spans.contains(&call.call_span));
+    self.call_edges.retain(|edge| !allowed_call_spans.contains(&edge.call_span));
+  }
+
+  /// `{ [makeKey()]: value }` evaluates its key where the object is built, so calls in it are
+  /// made by the enclosing function
+  fn visit_computed_key(&mut self, key: &PropName) {
+    if let PropName::Computed(computed) = key {
+      self.visit_expr(&computed.expr);
     }
   }
 
   /// Check if a function call should be ignored due to @it-throws comment
-  fn has_ignore_comment(&mut self, span: Span) -> bool {
+  /// The comment is only marked as used when the call actually targets a throwing function.
+  /// Comments limited to some error types (`@it-throws ValidationError`) are left to the
+  /// diagnostics, which drop just those types.
+  fn has_ignore_comment(&mut self, call: &CallExpr) -> bool {
+    let span = call.span;
+    let targets_throwing_function = self.call_targets_throwing_function(call);
+    let ignore_statements = ["@it-throws".to_string()];
+    // An `@it-throws-next-line` comment on the line above covers just this call
+    if let Some(comment) = self.next_line_comments.iter().find(|comment| comment.is_above(span.lo)) {
+      if targets_throwing_function {
+        self.used_it_throws_comments.insert(comment.comment_span);
+      }
+      if !comment.error_types.is_empty() {
+        self.typed_it_throws_calls.insert(span, comment.error_types.clone());
+        return false;
+      }
+      return true;
+    }
     // Strategy 1: Check for leading comments on the call span itself
     if let Some(leading_comments) = self.comments.get_leading(span.lo) {
       for comment in leading_comments.iter() {
-        let comment_text = comment.text.trim();
-        if comment_text == "@it-throws" {
-          // Mark this comment as used
-          self.used_it_throws_comments.insert(comment.span);
+        if let Some(error_types) = ignore_comment_error_types(&comment.text, &ignore_statements) {
+          if targets_throwing_function {
+            self.used_it_throws_comments.insert(comment.span);
+          }
+          if !error_types.is_empty() {
+            self.typed_it_throws_calls.insert(span, error_types);
+            return false;
+          }
           return true;
         }
       }
@@ -140,14 +380,18 @@ impl CallFinder {
       let search_pos = swc_common::BytePos(span.lo.0.saturating_sub(offset));
       if let Some(leading_comments) = self.comments.get_leading(search_pos) {
         for comment in leading_comments.iter() {
-          let comment_text = comment.text.trim();
-          if comment_text == "@it-throws" {
+          if let Some(error_types) = ignore_comment_error_types(&comment.text, &ignore_statements) {
             // Additional validation: Only use this comment if it's reasonably close
             // Check that the comment is within the same line or 1-2 lines above
             let comment_distance = span.lo.0.saturating_sub(comment.span.lo.0);
             if comment_distance <= 200 { // Increased distance for assignment cases
-              // Mark this comment as used
-              self.used_it_throws_comments.insert(comment.span);
+              if targets_throwing_function {
+                self.used_it_throws_comments.insert(comment.span);
+              }
+              if !error_types.is_empty() {
+                self.typed_it_throws_calls.insert(span, error_types);
+                return false;
+              }
               return true;
             }
           }
@@ -236,13 +480,162 @@ impl CallFinder {
   }
 
   fn handle_await_expr(&mut self, await_expr: &AwaitExpr) {
+    self.record_awaited_call(&await_expr.arg);
     if let Expr::Call(call_expr) = &*await_expr.arg {
       self.visit_call_expr(call_expr);
     }
   }
+
+  /// Record a read (getter) or write (setter) of `this.prop`, `instance.prop` or `object.prop` as a
+  /// call to a throwing accessor of the resolved class, or of the object literal bound to `object`
+  fn record_accessor_access(&mut self, member_expr: &MemberExpr, kind: MethodKind) {
+    let MemberProp::Ident(prop_ident) = &member_expr.prop else {
+      return;
+    };
+    let class_name = match &*member_expr.obj {
+      Expr::This(_) => self.current_class_name.clone(),
+      Expr::Ident(object_ident) => Some(
+        self
+          .instantiations
+          .get(&object_ident.sym.to_string())
+          .map(|instantiation| instantiation.class_name.clone())
+          .unwrap_or_else(|| object_ident.sym.to_string()),
+      ),
+      _ => None,
+    };
+    let Some(class_name) = class_name else {
+      return;
+    };
+    let accessor_name = class_member_name(kind, &prop_ident.sym);
+    let Some(throw_map) = self.functions_with_throws.iter().find(|throw_map| {
+      throw_map.accessor_kind == Some(kind)
+        && throw_map.class_name.as_ref() == Some(&class_name)
+        && throw_map.function_or_method_name == accessor_name
+    }) else {
+      return;
+    };
+
+    let call_function_or_method_name = if let Some(function_name) = self.function_name_stack.last() {
+      function_name.clone()
+    } else if let Some(property_name) = self.object_property_stack.last() {
+      property_name.clone()
+    } else {
+      "<anonymous>".to_string()
+    };
+    let class_name_or_not_set = self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string());
+    let call_to_throw_map = CallToThrowMap {
+      call_span: member_expr.span,
+      throw_map: throw_map.clone(),
+      call_class_name: Some(class_name_or_not_set.clone()),
+      call_function_or_method_name: call_function_or_method_name.clone(),
+      class_name: Some(class_name),
+      id: format!("{}-{}", class_name_or_not_set, call_function_or_method_name),
+      is_awaited: false,
+      is_builtin: false,
+      suppressed_error_types: self.suppressed_error_types(member_expr.span),
+    };
+    self.calls.insert(call_to_throw_map);
+  }
+
+  /// The error types documented on the parameter `name` of the innermost enclosing function that
+  /// has one, so closures calling a captured callback are covered too
+  fn param_callback_throws(&self, name: &str) -> Option<&Vec<String>> {
+    let class_name_or_not_set = self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string());
+    self.function_name_stack.iter().rev().find_map(|function_name| {
+      let function_id = format!("{}-{}", class_name_or_not_set, function_name);
+      let index = *self.param_indices.get(&function_id)?.get(name)?;
+      self.param_throws.get(&function_id)?.get(index).filter(|error_types| !error_types.is_empty())
+    })
+  }
+
+  /// Record a call to a parameter documented to throw as a call to a thrower outside the
+  /// analyzed code, the way calls to throwing builtins are
+  fn record_param_callback_call(&mut self, call: &CallExpr, name: &str) {
+    let Some(error_types) = self.param_callback_throws(name).cloned() else {
+      return;
+    };
+    let call_function_or_method_name = self
+      .function_name_stack
+      .last()
+      .cloned()
+      .unwrap_or_else(|| "<anonymous>".to_string());
+    let class_name_or_not_set = self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string());
+    let throw_map = ThrowMap {
+      throw_spans: vec![call.span; error_types.len()],
+      throw_statement: call.span,
+      function_span: call.span,
+      function_or_method_name: name.to_string(),
+      class_name: None,
+      id: format!("param-{}-{}-{}", class_name_or_not_set, call_function_or_method_name, name),
+      throw_details: error_types
+        .iter()
+        .map(|error_type| ThrowDetails {
+          error_type: Some(error_type.clone()),
+          is_custom_error: !is_built_in_error(error_type),
+          ..Default::default()
+        })
+        .collect(),
+      throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
+    };
+    self.calls.insert(CallToThrowMap {
+      call_span: call.span,
+      throw_map,
+      call_class_name: self.current_class_name.clone(),
+      call_function_or_method_name: call_function_or_method_name.clone(),
+      class_name: None,
+      id: format!("{}-{}", class_name_or_not_set, call_function_or_method_name),
+      is_awaited: self.awaited_calls.contains(&call.span),
+      is_builtin: true,
+      suppressed_error_types: self.suppressed_error_types(call.span),
+    });
+  }
+
+  /// Record a call to a throwing builtin like `JSON.parse` as a call to a thrower
+  fn record_builtin_throw_call(&mut self, call: &CallExpr) {
+    let Some(builtin_call) = self.builtin_throw_calls.iter().find(|builtin_call| builtin_call.call_span == call.span) else {
+      return;
+    };
+    let call_function_or_method_name = self
+      .function_name_stack
+      .last()
+      .or(self.object_property_stack.last())
+      .cloned()
+      .unwrap_or_else(|| "<anonymous>".to_string());
+    let class_name_or_not_set = self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string());
+    let call_to_throw_map = CallToThrowMap {
+      call_span: call.span,
+      throw_map: builtin_call.throw_map(),
+      call_class_name: self.current_class_name.clone(),
+      call_function_or_method_name: call_function_or_method_name.clone(),
+      class_name: None,
+      id: format!("{}-{}", class_name_or_not_set, call_function_or_method_name),
+      is_awaited: false,
+      is_builtin: true,
+      suppressed_error_types: self.suppressed_error_types(call.span),
+    };
+    self.calls.insert(call_to_throw_map);
+  }
+
+  /// Mark the call `arg` is, if any, as awaited: under an `await`, or iterated by a `for await`
+  fn record_awaited_call(&mut self, mut arg: &Expr) {
+    while let Expr::Paren(paren_expr) = arg {
+      arg = &*paren_expr.expr;
+    }
+    if let Expr::Call(call_expr) = arg {
+      self.awaited_calls.insert(call_expr.span);
+    }
+  }
 }
 
 impl Visit for CallFinder {
+  fn visit_module(&mut self, module: &Module) {
+    self.member_throw_maps = member_throw_maps(module, &self.functions_with_throws);
+    swc_ecma_visit::visit_module(self, module);
+  }
+
   fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
     self.current_class_name = Some(class_decl.ident.sym.to_string());
     self.visit_class(&class_decl.class);
@@ -259,6 +652,7 @@ impl Visit for CallFinder {
   }
 
   fn visit_member_expr(&mut self, member_expr: &MemberExpr) {
+    self.record_accessor_access(member_expr, MethodKind::Getter);
     if let MemberProp::Ident(ident) = &member_expr.prop {
       self.object_property_stack.push(ident.sym.to_string());
     }
@@ -270,7 +664,7 @@ impl Visit for CallFinder {
     if let Some(method_ident) = method.key.as_ident() {
       self
         .object_property_stack
-        .push(method_ident.sym.to_string());
+        .push(class_member_name(method.kind, &method_ident.sym));
     }
 
     swc_ecma_visit::visit_class_method(self, method);
@@ -299,7 +693,7 @@ impl Visit for CallFinder {
 
   fn visit_call_expr(&mut self, call: &CallExpr) {
     // Check if this call should be ignored due to @it-throws comment
-    if self.has_ignore_comment(call.span) {
+    if self.has_ignore_comment(call) {
       return;
     }
 
@@ -310,6 +704,7 @@ impl Visit for CallFinder {
         // This call was already processed, so return early
         return;
       }
+      self.record_builtin_throw_call(call);
       match &**expr {
         Expr::Member(member_expr) => {
           let mut possible_class_name = None;
@@ -330,7 +725,35 @@ impl Visit for CallFinder {
 
           if let MemberProp::Ident(method_ident) = &member_expr.prop {
             let called_method_name = method_ident.sym.to_string();
-            for throw_map in self.functions_with_throws.iter() {
+            // Calls through a declared object or class resolve exactly; others match by method name
+            let member_throw_map =
+              member_call_path(member_expr).and_then(|path| self.member_throw_maps.get(&path).cloned());
+            let caller_name = self
+              .function_name_stack
+              .last()
+              .or(self.object_property_stack.last())
+              .cloned()
+              .unwrap_or_else(|| "<anonymous>".to_string());
+            // The caller belongs to the class being visited, not to the object the method is called on
+            self.call_edges.push(CallEdge {
+              caller_id: format!(
+                "{}-{}",
+                self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string()),
+                caller_name
+              ),
+              callee_name: called_method_name.clone(),
+              callee_id: member_throw_map.as_ref().map(|throw_map| throw_map.id.clone()),
+              call_span: call.span,
+              suppressed_error_types: self.suppressed_error_types(call.span),
+            });
+            let called_throw_map = member_throw_map.clone().or_else(|| {
+              self
+                .functions_with_throws
+                .iter()
+                .find(|throw_map| throw_map.function_or_method_name == called_method_name)
+                .cloned()
+            });
+            if let Some(throw_map) = called_throw_map {
               let call_function_or_method_name =
                 if let Some(function_name) = self.function_name_stack.last() {
                   function_name.clone()
@@ -339,27 +762,27 @@ impl Visit for CallFinder {
                 } else {
                   "<anonymous>".to_string()
                 };
-              if throw_map.function_or_method_name == called_method_name {
-                let class_name_or_not_set = self
-                  .current_class_name
-                  .clone()
-                  .or(possible_class_name.clone())
-                  .unwrap_or_else(|| "NOT_SET".to_string());
-                let call_to_throw_map = CallToThrowMap {
-                  call_span: call.span,
-                  throw_map: throw_map.clone(),
-                  call_class_name: Some(class_name_or_not_set.clone()),
-                  call_function_or_method_name: call_function_or_method_name.clone(),
-                  class_name: possible_class_name.clone(),
-                  id: format!(
-                    "{}-{}",
-                    class_name_or_not_set,
-                    call_function_or_method_name.clone()
-                  ),
-                };
-                self.calls.insert(call_to_throw_map);
-                break;
-              }
+              let class_name_or_not_set = self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string());
+              let class_name = match &member_throw_map {
+                Some(throw_map) => throw_map.class_name.clone(),
+                None => possible_class_name.clone(),
+              };
+              let call_to_throw_map = CallToThrowMap {
+                call_span: call.span,
+                throw_map,
+                call_class_name: Some(class_name_or_not_set.clone()),
+                call_function_or_method_name: call_function_or_method_name.clone(),
+                class_name,
+                id: format!(
+                  "{}-{}",
+                  class_name_or_not_set,
+                  call_function_or_method_name.clone()
+                ),
+                is_awaited: self.awaited_calls.contains(&call.span),
+                is_builtin: false,
+                suppressed_error_types: self.suppressed_error_types(call.span),
+              };
+              self.calls.insert(call_to_throw_map);
             }
             // Capture caller function name before pushing callee context
             let caller_function_name = self
@@ -367,9 +790,23 @@ impl Visit for CallFinder {
               .last()
               .cloned()
               .unwrap_or_else(|| "<anonymous>".to_string());
+            let is_higher_order_call = HIGHER_ORDER_ARRAY_METHODS.contains(&called_method_name.as_str());
             for (arg_index, arg) in call.args.iter().enumerate() {
-              self.function_name_stack.push(method_ident.sym.to_string());
-              self.current_class_name = possible_class_name.clone();
+              let callback_span = match &*arg.expr {
+                Expr::Arrow(arrow_expr) => Some(arrow_expr.span),
+                Expr::Fn(fn_expr) => Some(fn_expr.function.span),
+                _ => None,
+              };
+              if is_higher_order_call {
+                self.higher_order_callbacks.extend(callback_span);
+              }
+              // Callbacks of the synchronous array methods run inside the enclosing function, so
+              // calls they make stay attributed to it; other callbacks are attributed to the callee
+              let previous_class_name = self.current_class_name.clone();
+              if !is_higher_order_call {
+                self.function_name_stack.push(method_ident.sym.to_string());
+                self.current_class_name = possible_class_name.clone();
+              }
               if let Expr::Arrow(arrow_expr) = &*arg.expr {
                 // Map inline callback span to allowed throws from typedef mapping
                 let calling_function_id = format!(
@@ -401,13 +838,36 @@ impl Visit for CallFinder {
                 }
                 self.visit_function(&fn_expr.function);
               }
-              self.function_name_stack.pop();
-              self.current_class_name = None;
+              if is_higher_order_call {
+                if let Some(callback_span) = callback_span {
+                  self.drop_calls_allowed_in_callback(callback_span);
+                }
+                self.current_class_name = previous_class_name;
+              } else {
+                self.function_name_stack.pop();
+                self.current_class_name = None;
+              }
             }
           }
         }
         Expr::Ident(ident) => {
-          let called_function_name = ident.sym.to_string();
+          let called_function_name = self
+            .import_aliases
+            .get(&*ident.sym)
+            .cloned()
+            .unwrap_or_else(|| ident.sym.to_string());
+          let class_name_or_not_set = self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string());
+          self.call_edges.push(CallEdge {
+            caller_id: format!(
+              "{}-{}",
+              class_name_or_not_set,
+              self.function_name_stack.last().map(String::as_str).unwrap_or("<anonymous>")
+            ),
+            callee_name: called_function_name.clone(),
+            callee_id: Some(format!("{}-{}", class_name_or_not_set, called_function_name)),
+            call_span: call.span,
+            suppressed_error_types: self.suppressed_error_types(call.span),
+          });
           for throw_map in self.functions_with_throws.iter() {
             let potential_throw_id = format!(
               "{}-{}",
@@ -438,28 +898,16 @@ impl Visit for CallFinder {
                     .unwrap_or_else(|| "NOT_SET".to_string()),
                   call_function_or_method_name
                 ),
+                is_awaited: self.awaited_calls.contains(&call.span),
+                is_builtin: false,
+                suppressed_error_types: self.suppressed_error_types(call.span),
               };
               self.calls.insert(call_to_throw_map);
               break;
             }
           }
-          // If the identifier is a parameter with typedef-declared throws, register expected param throws
-          if let Some(current_func) = self.function_name_stack.last() {
-            let current_id = format!(
-              "{}-{}",
-              self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string()),
-              current_func
-            );
-            if let Some(param_sets) = self.param_throws.get(&current_id) {
-              // Find the index of the argument position we are calling as a callback
-              // Heuristic: direct identifier call that matches a parameter name position
-              // We can't access parameter names here easily; instead, when we later visit inline callbacks
-              // we already rely on throws detection. For identifier callbacks, we at least avoid flagging
-              // mismatch here by not adding extra diagnostics beyond throw_map-based ones.
-              // Future improvement: carry parameter name mapping into CallFinder context.
-              let _ = param_sets; // silence unused for now
-            }
-          }
+          // Calling a parameter documented to throw, e.g. `cb: () => void /* @throws {E} */`
+          self.record_param_callback_call(call, &ident.sym);
           // Capture caller function name before pushing callee context
           let caller_function_name = self
             .function_name_stack
@@ -509,22 +957,19 @@ impl Visit for CallFinder {
 
   fn visit_var_declarator(&mut self, var_declarator: &VarDeclarator) {
     if let Some(init_expr) = &var_declarator.init {
-      match &**init_expr {
-        Expr::New(new_expr) => {
-          if let Expr::Ident(expr) = &*new_expr.callee {
-            let class_name = expr.sym.to_string();
-            if let Some(var_ident) = &var_declarator.name.as_ident() {
-              let var_name = var_ident.sym.to_string();
-              let instantiation_span = var_ident.span;
-              self.instantiations.insert(var_name.clone(), InstantiationsMap {
-                class_name,
-                variable_name: var_name,
-                instantiation_span,
-              });
-            }
+      if let Expr::New(new_expr) = &**init_expr {
+        if let Expr::Ident(expr) = &*new_expr.callee {
+          let class_name = expr.sym.to_string();
+          if let Some(var_ident) = &var_declarator.name.as_ident() {
+            let var_name = var_ident.sym.to_string();
+            let instantiation_span = var_ident.span;
+            self.instantiations.insert(var_name.clone(), InstantiationsMap {
+              class_name,
+              variable_name: var_name,
+              instantiation_span,
+            });
           }
         }
-        _ => {}
       }
       if let Expr::Bin(bin_expr) = &**init_expr {
         self.handle_bin_expr(bin_expr)
@@ -533,9 +978,7 @@ impl Visit for CallFinder {
     if let Some(ident) = &var_declarator.name.as_ident() {
       if let Some(init) = &var_declarator.init {
         if let Expr::Await(await_expr) = &**init {
-          if let Expr::Call(call_expr) = &*await_expr.arg {
-            self.visit_call_expr(call_expr);
-          }
+          self.handle_await_expr(await_expr);
         }
         if let Expr::Bin(bin_expr) = &**init {
           if let Expr::Call(call_expr) = &*bin_expr.left {
@@ -569,6 +1012,10 @@ impl Visit for CallFinder {
   }
 
   fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr) {
+    // Default parameter values run as part of the call
+    for param in &arrow_expr.params {
+      self.visit_pat(param);
+    }
     match &*arrow_expr.body {
       BlockStmtOrExpr::BlockStmt(block_stmt) => {
         for stmt in &block_stmt.stmts {
@@ -587,6 +1034,10 @@ impl Visit for CallFinder {
   }
 
   fn visit_function(&mut self, function: &Function) {
+    // Default parameter values run as part of the call
+    for param in &function.params {
+      self.visit_pat(&param.pat);
+    }
     if let Some(block_stmt) = &function.body {
       for stmt in &block_stmt.stmts {
         self.visit_stmt(stmt);
@@ -601,6 +1052,7 @@ impl Visit for CallFinder {
           match &**boxed_prop {
             swc_ecma_ast::Prop::KeyValue(key_value_prop) => {
               // Handle KeyValue
+              self.visit_computed_key(&key_value_prop.key);
               self.visit_expr(&key_value_prop.value);
             }
             swc_ecma_ast::Prop::Assign(assign_prop) => {
@@ -608,10 +1060,10 @@ impl Visit for CallFinder {
               self.visit_expr(&assign_prop.value);
             }
             swc_ecma_ast::Prop::Getter(getter_prop) => {
+              self.visit_computed_key(&getter_prop.key);
               // Handle Getter - push getter name to function stack so it's used as calling function name
               if let swc_ecma_ast::PropName::Ident(ident) = &getter_prop.key {
-                let getter_name = format!("getter {}", ident.sym.to_string());
-                self.function_name_stack.push(getter_name);
+                self.function_name_stack.push(class_member_name(MethodKind::Getter, &ident.sym));
               }
               
               if let Some(body) = &getter_prop.body {
@@ -626,10 +1078,10 @@ impl Visit for CallFinder {
               }
             }
             swc_ecma_ast::Prop::Setter(setter_prop) => {
-              // Handle Setter - push setter name to property stack with "setter " prefix to match ThrowAnalyzer
+              self.visit_computed_key(&setter_prop.key);
+              // Handle Setter - named like the getter, to match ThrowAnalyzer
               if let swc_ecma_ast::PropName::Ident(ident) = &setter_prop.key {
-                let setter_name = format!("setter {}", ident.sym.to_string());
-                self.object_property_stack.push(setter_name);
+                self.function_name_stack.push(class_member_name(MethodKind::Setter, &ident.sym));
               }
               
               if let Some(body) = &setter_prop.body {
@@ -640,11 +1092,12 @@ impl Visit for CallFinder {
               
               // Pop the setter name after processing
               if let swc_ecma_ast::PropName::Ident(_) = &setter_prop.key {
-                self.object_property_stack.pop();
+                self.function_name_stack.pop();
               }
             }
             swc_ecma_ast::Prop::Method(method_prop) => {
               // Handle Method
+              self.visit_computed_key(&method_prop.key);
               if let swc_ecma_ast::PropName::Ident(ident) = &method_prop.key {
                 self.object_property_stack.push(ident.sym.to_string());
               }
@@ -671,6 +1124,41 @@ impl Visit for CallFinder {
     }
   }
 
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => expr.as_member(),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => expr.as_member(),
+        _ => None,
+      },
+    };
+    if let Some(member_expr) = target {
+      // Writing through a setter; compound assignments (`+=`, ...) also read through the getter
+      self.record_accessor_access(member_expr, MethodKind::Setter);
+      if assign_expr.op != AssignOp::Assign {
+        self.record_accessor_access(member_expr, MethodKind::Getter);
+      }
+      self.visit_expr(&member_expr.obj);
+      self.visit_member_prop(&member_expr.prop);
+      self.visit_expr(&assign_expr.right);
+      return;
+    }
+    swc_ecma_visit::visit_assign_expr(self, assign_expr);
+  }
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr) {
+    self.record_awaited_call(&await_expr.arg);
+    swc_ecma_visit::visit_await_expr(self, await_expr);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of: &ForOfStmt) {
+    // Whatever the async iterator rejects with is thrown at the loop
+    if for_of.is_await {
+      self.record_awaited_call(&for_of.right);
+    }
+    swc_ecma_visit::visit_for_of_stmt(self, for_of);
+  }
+
   fn visit_expr(&mut self, expr: &Expr) {
     match expr {
       Expr::Object(object_lit) => {
@@ -7,7 +7,10 @@ use swc_ecma_ast::Module;
 use self::swc_common::{comments::Comments, sync::Lrc, Span, Spanned};
 use self::swc_ecma_visit::{Visit, VisitWith};
 
-use crate::throw_finder::{CallbackDefinition, ThrowsAnnotation};
+use crate::throw_finder::{
+  default_throws_tag_aliases, ends_throws_type_list, is_wildcard_throws_type, normalize_jsdoc_line, throws_tag_text,
+  CallbackDefinition, ThrowsAnnotation, ThrowsTagAliases,
+};
 
 /// Finder for @callback JSDoc annotations
 /// 
@@ -33,6 +36,7 @@ use crate::throw_finder::{CallbackDefinition, ThrowsAnnotation};
 pub struct CallbackFinder {
   pub comments: Lrc<dyn Comments>,
   pub callbacks: HashMap<String, CallbackDefinition>,
+  pub throws_tag_aliases: Vec<String>, // JSDoc tags documenting thrown errors, e.g. `throws`
 }
 
 impl CallbackFinder {
@@ -40,6 +44,7 @@ impl CallbackFinder {
     Self {
       comments,
       callbacks: HashMap::new(),
+      throws_tag_aliases: default_throws_tag_aliases(),
     }
   }
 
@@ -47,7 +52,7 @@ impl CallbackFinder {
   fn parse_callback_comment(&mut self, comment_text: &str, span: Span) {
     let text = comment_text.trim();
     let lines: Vec<&str> = text.lines()
-      .map(|line| line.trim().trim_start_matches('*').trim())
+      .map(normalize_jsdoc_line)
       .collect();
 
     let mut callback_name: Option<String> = None;
@@ -69,11 +74,9 @@ impl CallbackFinder {
       }
       
       // Parse @throws annotations within the same comment block
-      if line.to_lowercase().contains("@throws") {
-        if let Some(annotation) = self.parse_throws_comment(line) {
-          for t in annotation.error_types {
-            aggregated_error_types.insert(t);
-          }
+      if let Some(annotation) = self.parse_throws_comment(line) {
+        for t in annotation.error_types {
+          aggregated_error_types.insert(t);
         }
       }
     }
@@ -86,7 +89,8 @@ impl CallbackFinder {
       }
 
       let throws_annotation = if !aggregated_error_types.is_empty() {
-        Some(ThrowsAnnotation { error_types: aggregated_error_types.into_iter().collect(), is_documented: true })
+        let covers_all = aggregated_error_types.iter().any(|t| is_wildcard_throws_type(t));
+        Some(ThrowsAnnotation { error_types: aggregated_error_types.into_iter().collect(), is_documented: true, covers_all, no_throw: None, inherit_doc: false, descriptions: Vec::new() })
       } else {
         None
       };
@@ -98,7 +102,7 @@ impl CallbackFinder {
       };
       
       #[cfg(debug_assertions)]
-      eprintln!("🔍 Found @callback definition: {} with throws: {:?}", name, callback_def.throws_annotation);
+      debug_log!("Found @callback definition: {} with throws: {:?}", name, callback_def.throws_annotation);
       
       self.callbacks.insert(name, callback_def);
     }
@@ -108,8 +112,8 @@ impl CallbackFinder {
   fn parse_throws_comment(&self, line: &str) -> Option<ThrowsAnnotation> {
     let mut error_types: std::collections::HashSet<String> = std::collections::HashSet::new();
 
-    if let Some(throws_pos) = line.to_lowercase().find("@throws") {
-      let after_throws = &line[throws_pos + 7..].trim(); // Skip "@throws"
+    if let Some(after_throws) = throws_tag_text(line, &self.throws_tag_aliases) {
+      let after_throws = &after_throws.trim();
 
       // Handle @throws {Type} syntax
       if let Some(start_brace) = after_throws.find('{') {
@@ -130,9 +134,7 @@ impl CallbackFinder {
         // Handle @throws Type (without braces) - extract comma-separated types
         let type_section = after_throws
           .split_whitespace()
-          .take_while(|word| {
-            !["when", "if", "where", "that", "which", "while", "because", "since"].contains(&word.to_lowercase().as_str())
-          })
+          .take_while(|word| !ends_throws_type_list(word))
           .collect::<Vec<_>>()
           .join(" ");
         let types: Vec<String> = type_section
@@ -151,9 +153,14 @@ impl CallbackFinder {
     }
 
     if !error_types.is_empty() {
+      let covers_all = error_types.iter().any(|t| is_wildcard_throws_type(t));
       Some(ThrowsAnnotation { 
         error_types: error_types.into_iter().collect(),
-        is_documented: true 
+        is_documented: true,
+        covers_all,
+        no_throw: None,
+        inherit_doc: false,
+        descriptions: Vec::new(),
       })
     } else {
       None
@@ -191,6 +198,12 @@ impl CallbackFinder {
   }
 }
 
+impl ThrowsTagAliases for CallbackFinder {
+  fn throws_tag_aliases_mut(&mut self) -> &mut Vec<String> {
+    &mut self.throws_tag_aliases
+  }
+}
+
 impl Visit for CallbackFinder {
   fn visit_module(&mut self, module: &Module) {
     // Check for leading comments on the module itself
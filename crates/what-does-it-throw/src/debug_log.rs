@@ -0,0 +1,29 @@
+use std::cell::RefCell;
+
+// Trace lines collected for the analysis currently running on this thread, if tracing is on
+thread_local! {
+  static TRACE: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Record an analysis trace line. The message is only formatted when tracing is enabled.
+macro_rules! debug_log {
+  ($($arg:tt)*) => {
+    $crate::debug_log::record(|| format!($($arg)*))
+  };
+}
+
+pub fn record<F: FnOnce() -> String>(message: F) {
+  TRACE.with(|trace| {
+    if let Some(lines) = trace.borrow_mut().as_mut() {
+      lines.push(message());
+    }
+  });
+}
+
+/// Run `analysis` with tracing switched on or off, returning the lines it recorded
+pub fn capture<T, F: FnOnce() -> T>(enabled: bool, analysis: F) -> (T, Vec<String>) {
+  let previous = TRACE.with(|trace| trace.replace(if enabled { Some(Vec::new()) } else { None }));
+  let result = analysis();
+  let lines = TRACE.with(|trace| trace.replace(previous)).unwrap_or_default();
+  (result, lines)
+}
@@ -0,0 +1,2936 @@
+extern crate serde;
+extern crate swc_common;
+extern crate swc_ecma_ast;
+extern crate swc_ecma_visit;
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use self::serde::{Serialize, Serializer};
+use self::swc_common::comments::{CommentKind, Comments, SingleThreadedComments};
+use self::swc_common::{sync::Lrc, BytePos, SourceMap, SourceMapper, Span};
+use self::swc_ecma_ast::{Module, ThrowStmt};
+use self::swc_ecma_visit::{Visit, VisitWith};
+
+use call_finder::CallToThrowMap;
+use function_finder::FunctionMap;
+use throw_finder::{ignore_comment_error_types, IdentifierUsage, ThrowDetails, ThrowMap, ThrowsAnnotation};
+use try_catch_finder::CatchAnalysis;
+use {retain_throw_details, AnalysisResult, ClassIgnoreComment, ParseError, UserSettings};
+
+// Diagnostic building runs inside the analysis trace; its progress notes only show up with `debug`
+fn log(message: &str) {
+  debug_log!("{}", message);
+}
+
+/// Only positions inside a file of this source map can be looked up; the first character of
+/// the first file is a real position, dummy spans and gaps between files are not
+fn is_within_source_file(cm: &SourceMap, pos: BytePos) -> bool {
+  SourceMap::lookup_source_file_in(&cm.files(), pos)
+    .is_some_and(|source_file| pos >= source_file.start_pos && pos <= source_file.end_pos)
+}
+
+/// Where `@throws` tags documenting a function go, zero-based: before the closing `*/` of the
+/// JSDoc block already above the function, or the start of the function's line when it has none.
+/// The flag says which of the two it is.
+fn throws_annotation_insert_position(
+  cm: &SourceMap,
+  comments: &Lrc<dyn Comments>,
+  function_span: Span,
+) -> (DiagnosticPosition, bool) {
+  let (line, _) = safe_lookup_char_pos(cm, function_span.lo());
+  let line_position = DiagnosticPosition {
+    line: line.saturating_sub(1),
+    character: 0,
+  };
+  if !is_within_source_file(cm, function_span.lo()) {
+    return (line_position, false);
+  }
+
+  // JSDoc above `export function` or `const f = () =>` attaches to the first token on the line
+  let mut candidates = vec![function_span.lo()];
+  if let Ok(source_line) = cm.lookup_line(function_span.lo()) {
+    if let Some(text) = source_line.sf.get_line(source_line.line) {
+      let indent = text.len() - text.trim_start().len();
+      candidates.push(source_line.sf.lines[source_line.line] + BytePos(indent as u32));
+    }
+  }
+  let existing_doc = candidates
+    .iter()
+    .filter_map(|pos| comments.get_leading(*pos))
+    .flatten()
+    .filter(|comment| comment.kind == CommentKind::Block && comment.text.starts_with('*'))
+    .max_by_key(|comment| comment.span.lo);
+
+  match existing_doc {
+    Some(doc) => {
+      let (doc_end_line, doc_end_col) = safe_lookup_char_pos(cm, doc.span.hi - BytePos(2));
+      let position = DiagnosticPosition {
+        line: doc_end_line.saturating_sub(1),
+        character: doc_end_col,
+      };
+      (position, true)
+    }
+    None => (line_position, false),
+  }
+}
+
+/// Whether every place in `fun` that raises `error_type`, a throw statement or a call, sits in a try
+/// block whose catch swallows it. Types propagated without a known call site never count as caught.
+fn is_caught_within_function(
+  fun: &ThrowMap,
+  error_type: Option<&str>,
+  calls_to_throws: &HashSet<CallToThrowMap>,
+  catch_analyses: &[CatchAnalysis],
+) -> bool {
+  if fun.throw_spans.len() != fun.throw_details.len() {
+    return false;
+  }
+  let throw_sites = fun
+    .throw_spans
+    .iter()
+    .zip(&fun.throw_details)
+    .filter(|(span, detail)| **span != fun.function_span && detail.error_type.as_deref() == error_type)
+    .map(|(span, _)| *span);
+  let call_sites = calls_to_throws
+    .iter()
+    .filter(|call| {
+      call.id == fun.id
+        && call.propagated_throw_details().iter().any(|detail| detail.error_type.as_deref() == error_type)
+    })
+    .map(|call| call.call_span);
+  let sites: Vec<Span> = throw_sites.chain(call_sites).collect();
+  !sites.is_empty()
+    && sites.iter().all(|site| {
+      catch_analyses.iter().any(|catch_analysis| {
+        fun.function_span.contains(catch_analysis.try_span)
+          && catch_analysis.try_span.contains(*site)
+          && catch_analysis.catches(error_type)
+      })
+    })
+}
+
+/// A `@nothrow` contract is about what escapes the function, so throws its own catches swallow
+/// don't break it, even when `include_try_statement_throws` reports them elsewhere
+fn without_caught_nothrow_throws(
+  functions_with_throws: HashSet<ThrowMap>,
+  calls_to_throws: &HashSet<CallToThrowMap>,
+  catch_analyses: &[CatchAnalysis],
+) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .filter_map(|mut fun| {
+      if fun.throws_annotation.as_ref().is_none_or(|annotation| annotation.no_throw.is_none()) {
+        return Some(fun);
+      }
+      let original = fun.clone();
+      retain_throw_details(&mut fun, |detail| {
+        !is_caught_within_function(&original, detail.error_type.as_deref(), calls_to_throws, catch_analyses)
+      });
+      (!fun.throw_details.is_empty()).then_some(fun)
+    })
+    .collect()
+}
+
+/// Throws inside a class with an @it-throws comment above it are suppressed like those of a function
+/// with its own; a comment listing error types only drops those
+fn without_class_suppressed_throws(
+  functions_with_throws: HashSet<ThrowMap>,
+  class_ignore_comments: &[ClassIgnoreComment],
+) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .filter_map(|mut fun| {
+      let covering: Vec<&ClassIgnoreComment> = class_ignore_comments
+        .iter()
+        .filter(|class_comment| class_comment.class_span.contains(fun.function_span))
+        .collect();
+      if covering.is_empty() {
+        return Some(fun);
+      }
+      retain_throw_details(&mut fun, |detail| {
+        !covering.iter().any(|class_comment| class_comment.covers(detail.error_type.as_deref()))
+      });
+      (!fun.throw_details.is_empty()).then_some(fun)
+    })
+    .collect()
+}
+
+/// Safe wrapper for character position lookup that handles Unicode/emoji properly: the column
+/// counts UTF-16 code units like LSP positions do, not display width, which counts wide CJK and
+/// emoji characters twice. Positions outside every source file fall back to line 1 col 0 instead of panicking
+fn safe_lookup_char_pos(cm: &SourceMap, pos: BytePos) -> (usize, usize) {
+  if !is_within_source_file(cm, pos) {
+    log(&format!("Invalid byte position {:?}, using safe fallback", pos));
+    return (1, 0);
+  }
+  
+  let loc = cm.lookup_char_pos(pos);
+  (loc.line, loc.col.0)
+}
+
+pub const THROW_SNIPPET_MAX_CHARS: usize = 60;
+
+/// Message for a throw site quoting the thrown expression, e.g. `Throw: new TypeError("bad input")`.
+/// Falls back to the generic text when the source isn't available or the detail was propagated
+/// from another function (its expression then lies outside `throw_span`).
+fn throw_statement_message(cm: &SourceMap, detail: &ThrowDetails, throw_span: Span) -> String {
+  let (label, fallback) = if detail.is_rejection {
+    ("Promise rejection", "Promise rejection.")
+  } else {
+    ("Throw", "Throw statement.")
+  };
+  let snippet = detail
+    .arg_span
+    .filter(|arg_span| throw_span.contains(*arg_span))
+    .and_then(|arg_span| cm.span_to_snippet(arg_span).ok())
+    .map(|snippet| snippet.split_whitespace().collect::<Vec<_>>().join(" "))
+    .filter(|snippet| !snippet.is_empty());
+  match snippet {
+    Some(snippet) if snippet.chars().count() > THROW_SNIPPET_MAX_CHARS => {
+      let truncated: String = snippet.chars().take(THROW_SNIPPET_MAX_CHARS - 3).collect();
+      format!("{}: {}...", label, truncated)
+    }
+    Some(snippet) => format!("{}: {}", label, snippet),
+    None => fallback.to_string(),
+  }
+}
+
+/// Find the @it-throws comment on a function span, with the error types it is limited to
+/// (empty when it suppresses everything, e.g. a bare `// @it-throws`)
+fn it_throws_comment_with_types(comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> Option<(Span, Vec<String>)> {
+  // Strategy 1: Check for leading comments on the function span itself
+  if let Some(leading_comments) = comments.get_leading(span.lo) {
+    for comment in leading_comments.iter() {
+      // Check against all ignore_statements (which should include "@it-throws")
+      if let Some(error_types) = ignore_comment_error_types(&comment.text, ignore_statements) {
+        return Some((comment.span, error_types));
+      }
+    }
+  }
+  
+  // Strategy 2: For functions in assignments or other constructs,
+  // check for comments at positions before the function span
+  for offset in 1..50 {
+    let search_pos = swc_common::BytePos(span.lo.0.saturating_sub(offset));
+    if let Some(leading_comments) = comments.get_leading(search_pos) {
+      for comment in leading_comments.iter() {
+        if let Some(error_types) = ignore_comment_error_types(&comment.text, ignore_statements) {
+          // Additional validation: Only use this comment if it's reasonably close
+          let comment_distance = span.lo.0.saturating_sub(comment.span.lo.0);
+          if comment_distance <= 200 {
+            return Some((comment.span, error_types));
+          }
+        }
+      }
+    }
+  }
+  
+  None
+}
+
+/// Check if a function span has @it-throws comment, typed or not
+/// Returns the span of the comment if found, None otherwise
+fn has_it_throws_comment_with_span(comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> Option<Span> {
+  it_throws_comment_with_types(comments, span, ignore_statements).map(|(comment_span, _)| comment_span)
+}
+
+/// The ignore statement leading the code on `span`'s line, e.g. `// @it-throws` just above it,
+/// with the error types it is limited to
+fn line_ignore_comment(cm: &SourceMap, comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> Option<(Span, Vec<String>)> {
+  let Ok(source_line) = cm.lookup_line(span.lo) else {
+    return None;
+  };
+  let (line_start, _) = source_line.sf.line_bounds(source_line.line);
+  (line_start.0..=span.lo.0).find_map(|pos| {
+    comments.get_leading(BytePos(pos)).and_then(|leading| {
+      leading.iter().find_map(|comment| {
+        ignore_comment_error_types(&comment.text, ignore_statements).map(|error_types| (comment.span, error_types))
+      })
+    })
+  })
+}
+
+/// Whether an ignore statement leads the code on `span`'s line, e.g. `// @it-throws` just above it
+fn line_has_ignore_comment(cm: &SourceMap, comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> bool {
+  line_ignore_comment(cm, comments, span, ignore_statements).is_some()
+}
+
+/// Check if a function has an @it-throws comment suppressing all of its diagnostics; one limited
+/// to some error types (`// @it-throws ValidationError`) only drops those
+fn has_it_throws_comment(comments: &Lrc<dyn Comments>, span: Span, ignore_statements: &[String]) -> bool {
+  it_throws_comment_with_types(comments, span, ignore_statements).is_some_and(|(_, error_types)| error_types.is_empty())
+}
+
+/// Simple visitor to collect all throw statement spans in the file
+/// This is used for proximity-based unused comment detection
+struct AllThrowsCollector {
+  throw_spans: Vec<Span>,
+}
+
+impl Visit for AllThrowsCollector {
+  fn visit_throw_stmt(&mut self, node: &ThrowStmt) {
+    self.throw_spans.push(node.span);
+    swc_ecma_visit::visit_throw_stmt(self, node);
+  }
+}
+
+/// Every throw statement in `module`, including ones suppressed by comments, for matching
+/// `@it-throws` comments directly above them
+pub fn throw_statement_spans(module: &Module) -> Vec<Span> {
+  let mut collector = AllThrowsCollector { throw_spans: Vec::new() };
+  module.visit_with(&mut collector);
+  collector.throw_spans
+}
+
+/// Check if the file has @it-throws-disable comment at the top (within first few lines)
+/// This disables all throw diagnostics for the entire file, unless an @it-throws-enable
+/// ends the region early; `disable_regions` handles those files. `@it-throws-file` always
+/// covers the whole file.
+pub fn has_file_disable_comment(file_content: &str) -> bool {
+  // Check the first 9 lines for @it-throws-disable comment (conservative per tests)
+  let lines: Vec<&str> = file_content.lines().take(9).collect();
+
+  if lines.iter().any(|line| matches!(line.trim(), "// @it-throws-file" | "/* @it-throws-file */")) {
+    return true;
+  }
+  if file_content.contains("@it-throws-enable") {
+    return false;
+  }
+  
+  for line in lines {
+    let trimmed = line.trim();
+    // Check for @it-throws-disable comment (exact match)
+    if trimmed == "// @it-throws-disable" || trimmed == "/* @it-throws-disable */" {
+      return true;
+    }
+  }
+  
+  false
+}
+
+/// Lines silenced by a `// @it-throws-disable` comment: from the start of the comment's line
+/// up to a matching `// @it-throws-enable`, or the end of the file
+#[derive(Clone, Debug)]
+pub struct DisableRegion {
+  comment_span: Span,
+  lo: BytePos,
+  hi: BytePos,
+}
+
+impl DisableRegion {
+  fn contains(&self, pos: BytePos) -> bool {
+    self.lo <= pos && pos < self.hi
+  }
+}
+
+/// The `@it-throws-disable` regions of the file containing `pos`. A second disable inside a
+/// region is a no-op and an enable outside of one is ignored. An `@it-throws-file` comment
+/// anywhere makes the whole file a single region.
+pub fn disable_regions(cm: &SourceMap, comments: &SingleThreadedComments, pos: BytePos) -> Vec<DisableRegion> {
+  if !is_within_source_file(cm, pos) {
+    return Vec::new();
+  }
+  let source_file = cm.lookup_source_file(pos);
+  let (leading, trailing) = comments.borrow_all();
+  let mut file_comments: Vec<(Span, &str)> = leading
+    .values()
+    .chain(trailing.values())
+    .flatten()
+    .filter(|comment| comment.span.lo >= source_file.start_pos && comment.span.hi <= source_file.end_pos)
+    .map(|comment| (comment.span, comment.text.trim()))
+    .collect();
+  file_comments.sort_by_key(|(span, _)| span.lo);
+  file_comments.dedup_by_key(|(span, _)| *span);
+  if let Some((comment_span, _)) = file_comments.iter().find(|(_, text)| *text == "@it-throws-file") {
+    return vec![DisableRegion { comment_span: *comment_span, lo: source_file.start_pos, hi: source_file.end_pos }];
+  }
+
+  let mut regions = Vec::new();
+  let mut open: Option<(Span, BytePos)> = None;
+  for (span, text) in file_comments {
+    match (text, open) {
+      ("@it-throws-disable", None) => open = Some((span, source_file.line_begin_pos(span.lo))),
+      ("@it-throws-enable", Some((comment_span, lo))) => {
+        regions.push(DisableRegion { comment_span, lo, hi: span.hi });
+        open = None;
+      }
+      _ => {}
+    }
+  }
+  if let Some((comment_span, lo)) = open {
+    regions.push(DisableRegion { comment_span, lo, hi: source_file.end_pos });
+  }
+  regions
+}
+
+/// Drop the diagnostics raised inside a disable region, and flag the regions that silence nothing
+fn apply_disable_regions(
+  diagnostics: &mut Vec<Diagnostic>,
+  imported_identifiers_diagnostics: &mut Vec<ImportedIdentifiers>,
+  disable_regions: &[DisableRegion],
+  cm: &SourceMap,
+) {
+  let mut used_regions = HashSet::new();
+  let mut is_enabled = |diagnostic: &Diagnostic| match disable_regions.iter().position(|region| region.contains(diagnostic.pos)) {
+    Some(index) => {
+      used_regions.insert(index);
+      false
+    }
+    None => true,
+  };
+  diagnostics.retain(&mut is_enabled);
+  for imported_identifier in imported_identifiers_diagnostics.iter_mut() {
+    imported_identifier.diagnostics.retain(&mut is_enabled);
+  }
+  imported_identifiers_diagnostics.retain(|imported_identifier| !imported_identifier.diagnostics.is_empty());
+
+  for (index, region) in disable_regions.iter().enumerate() {
+    if used_regions.contains(&index) {
+      continue;
+    }
+    diagnostics.push(Diagnostic {
+      severity: DiagnosticSeverity::Information.to_int(),
+      range: span_range(cm, region.comment_span),
+      message: "Unused @it-throws-disable region. No diagnostics are suppressed in it.".to_string(),
+      code: Some(UNUSED_DISABLE_REGION_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: region.comment_span.lo,
+    });
+  }
+}
+
+/// One error diagnostic per syntax error, so a file that doesn't parse says why instead of
+/// looking like it throws nothing
+pub fn parse_error_diagnostics(parse_errors: &[ParseError], cm: &SourceMap) -> Vec<Diagnostic> {
+  parse_errors
+    .iter()
+    .map(|parse_error| Diagnostic {
+      severity: DiagnosticSeverity::Error.to_int(),
+      range: span_range(cm, parse_error.span),
+      message: format!("Parse error: {}", parse_error.message),
+      code: Some(PARSE_ERROR_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: parse_error.span.lo,
+    })
+    .collect()
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Diagnostic {
+  pub severity: i32,
+  pub range: DiagnosticRange,
+  pub message: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub code: Option<String>, // Stable rule id editors can filter and configure by
+  pub source: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub data: Option<DiagnosticData>,
+  #[serde(skip)]
+  pub pos: BytePos, // Where the diagnostic was raised, for @it-throws-disable regions
+}
+
+/// Function may throw (directly or through a call)
+pub const FUNCTION_MAY_THROW_CODE: &str = "DIT001";
+/// Call to a function that may throw
+pub const CALL_MAY_THROW_CODE: &str = "DIT002";
+/// Catch with an exhaustive `instanceof` chain misses a thrown type
+pub const EXHAUSTIVE_CATCH_CODE: &str = "DIT003";
+/// `@it-throws` comment that suppresses nothing
+pub const UNUSED_IT_THROWS_CODE: &str = "DIT004";
+/// Individual throw statement
+pub const THROW_STATEMENT_CODE: &str = "DIT005";
+/// Throw inside a `finally` block
+pub const THROW_IN_FINALLY_CODE: &str = "DIT006";
+/// `@nothrow` function that throws
+pub const NOTHROW_VIOLATION_CODE: &str = "DIT007";
+/// `@throws` tag documenting a type nothing throws
+pub const STALE_THROWS_DOC_CODE: &str = "DIT008";
+/// Call to an import whose throw behavior is unknown
+pub const UNKNOWN_IMPORT_CODE: &str = "DIT009";
+/// `@it-throws-disable` region that suppresses nothing
+pub const UNUSED_DISABLE_REGION_CODE: &str = "DIT010";
+/// Empty catch block discarding errors thrown in its try block
+pub const SWALLOWED_ERRORS_CODE: &str = "DIT011";
+/// Syntax error in the analyzed file
+pub const PARSE_ERROR_CODE: &str = "DIT012";
+
+/// Extra payload the language server reads to build a quick fix for a diagnostic
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticData {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub quick_fix_type: Option<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub error_types: Vec<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub insert_position: Option<DiagnosticPosition>, // Zero-based, unlike diagnostic ranges
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub append_to_existing_doc: Option<bool>, // Insert tags before the `*/` of an existing JSDoc block
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub related_information: Vec<RelatedInformation>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub throw_locations: Vec<DiagnosticRange>, // The throw statements a collapsed function diagnostic stands for
+}
+
+/// Context for a diagnostic: what a `@throws` tag says about a type, or where a callee throws it
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedInformation {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error_type: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>, // Text after the type in `@throws`, markdown links included
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub file: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub range: Option<DiagnosticRange>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DiagnosticRange {
+  pub start: DiagnosticPosition,
+  pub end: DiagnosticPosition,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DiagnosticPosition {
+  pub line: usize,
+  pub character: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum DiagnosticSeverity {
+  Error = 0,
+  Warning = 1,
+  Information = 2,
+  Hint = 3,
+}
+
+impl Serialize for DiagnosticSeverity {
+  fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_i32(*self as i32)
+  }
+}
+
+impl DiagnosticSeverity {
+  pub fn to_int(self) -> i32 {
+    match self {
+      DiagnosticSeverity::Error => 0,
+      DiagnosticSeverity::Warning => 1,
+      DiagnosticSeverity::Information => 2,
+      DiagnosticSeverity::Hint => 3,
+    }
+  }
+}
+
+impl FromStr for DiagnosticSeverity {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.trim().to_lowercase().as_str() {
+      "error" => Ok(DiagnosticSeverity::Error),
+      "warning" | "warn" => Ok(DiagnosticSeverity::Warning),
+      "information" | "info" => Ok(DiagnosticSeverity::Information),
+      "hint" => Ok(DiagnosticSeverity::Hint),
+      _ => Err(()),
+    }
+  }
+}
+
+/// Key in `error_type_severities` that applies to every custom error class without its own entry
+pub const CUSTOM_ERROR_SEVERITY_KEY: &str = "*Custom";
+
+/// Severity for throws with the given details (untyped throws count as `Error`).
+/// The most severe configured override wins; unlisted types fall back to `default`.
+fn severity_for_throw_details<'a, I>(
+  throw_details: I,
+  default: DiagnosticSeverity,
+  error_type_severities: &HashMap<String, DiagnosticSeverity>,
+) -> DiagnosticSeverity
+where
+  I: IntoIterator<Item = &'a ThrowDetails>,
+{
+  throw_details
+    .into_iter()
+    .filter_map(|detail| {
+      error_type_severities.get(detail.error_type.as_deref().unwrap_or("Error")).or_else(|| {
+        detail
+          .is_custom_error
+          .then(|| error_type_severities.get(CUSTOM_ERROR_SEVERITY_KEY))
+          .flatten()
+      })
+    })
+    .min_by_key(|severity| severity.to_int())
+    .copied()
+    .unwrap_or(default)
+}
+
+fn get_line_end_byte_pos(cm: &SourceMap, lo_byte_pos: BytePos, hi_byte_pos: BytePos) -> BytePos {
+  let src = cm
+    .span_to_snippet(Span::new(lo_byte_pos, hi_byte_pos, Default::default()))
+    .unwrap_or_default();
+
+  if let Some(newline_pos) = src.find('\n') {
+    lo_byte_pos + BytePos(newline_pos as u32)
+  } else {
+    // should never be true
+    hi_byte_pos
+  }
+}
+
+/// Range of a function's name, which function-level diagnostics underline. `None` when the name
+/// wasn't recorded, leaving the caller's range over the first line of the signature.
+fn function_name_range(cm: &SourceMap, fun: &ThrowMap) -> Option<DiagnosticRange> {
+  let name_span = fun.name_span.filter(|name_span| is_within_source_file(cm, name_span.lo()))?;
+  let (start_line, start_col) = safe_lookup_char_pos(cm, name_span.lo());
+  let (end_line, end_col) = safe_lookup_char_pos(cm, name_span.hi());
+  Some(DiagnosticRange {
+    start: DiagnosticPosition {
+      line: start_line,
+      character: start_col,
+    },
+    end: DiagnosticPosition {
+      line: end_line,
+      character: end_col,
+    },
+  })
+}
+
+fn get_line_start_byte_pos(cm: &SourceMap, lo_byte_pos: BytePos, hi_byte_pos: BytePos) -> BytePos {
+  let src = cm
+    .span_to_snippet(Span::new(lo_byte_pos, hi_byte_pos, Default::default()))
+    .unwrap_or_default();
+
+  if let Some(last_line) = src.lines().next_back() {
+    // The first non-blank character of the last line, or its start when it is all whitespace;
+    // counted in bytes, as the position is
+    let content = last_line.trim_start_matches([' ', '\t']);
+    let from_line_start = if content.is_empty() { last_line } else { content };
+    hi_byte_pos - BytePos(from_line_start.len() as u32)
+  } else {
+    // If there's no newline character, then we are at the start of the file
+    BytePos(0)
+  }
+}
+
+/// Start of the last character on the first line of `lo_byte_pos..hi_byte_pos`, where ranges over
+/// the first line of a multi-line span end. Found on char boundaries, as a position inside a
+/// multi-byte character has no column.
+fn get_first_line_last_char_byte_pos(cm: &SourceMap, lo_byte_pos: BytePos, hi_byte_pos: BytePos) -> BytePos {
+  let src = cm
+    .span_to_snippet(Span::new(lo_byte_pos, hi_byte_pos, Default::default()))
+    .unwrap_or_default();
+  let first_line = src.split('\n').next().unwrap_or_default().trim_end_matches('\r');
+  match first_line.char_indices().next_back() {
+    Some((index, _)) => lo_byte_pos + BytePos(index as u32),
+    None => lo_byte_pos,
+  }
+}
+
+fn get_relative_imports(import_sources: Vec<String>) -> Vec<String> {
+  let mut relative_imports: Vec<String> = Vec::new();
+  for import_source in import_sources {
+    if import_source.starts_with("./") || import_source.starts_with("../") {
+      relative_imports.push(import_source);
+    }
+  }
+  relative_imports
+}
+
+/// Order diagnostics by start position, then message, so output is stable across runs
+fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+  diagnostics.sort_by(|a, b| {
+    (a.range.start.line, a.range.start.character, &a.message)
+      .cmp(&(b.range.start.line, b.range.start.character, &b.message))
+  });
+}
+
+/// The `{A, B}` error type set in a diagnostic message, with the byte range it occupies
+fn message_error_types(message: &str) -> Option<(std::ops::Range<usize>, Vec<&str>)> {
+  let open = message.rfind('{')?;
+  let close = open + message[open..].find('}')?;
+  let error_types = message[open + 1..close].split(',').map(str::trim).filter(|t| !t.is_empty()).collect();
+  Some((open..close + 1, error_types))
+}
+
+/// Merges diagnostics raised on exactly the same range into one, so editors show a single squiggle.
+/// The merged diagnostic is the most severe of the group, preferring one whose message lists error
+/// types and then one that links to the throw sites, with the group's error types combined into that list.
+fn merge_overlapping_diagnostics(diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+  let range_key = |d: &Diagnostic| (d.range.start.line, d.range.start.character, d.range.end.line, d.range.end.character);
+  let mut groups: Vec<Vec<Diagnostic>> = Vec::new();
+  let mut group_index: HashMap<(usize, usize, usize, usize), usize> = HashMap::new();
+  for diagnostic in diagnostics {
+    match group_index.get(&range_key(&diagnostic)) {
+      Some(&index) => groups[index].push(diagnostic),
+      None => {
+        group_index.insert(range_key(&diagnostic), groups.len());
+        groups.push(vec![diagnostic]);
+      }
+    }
+  }
+
+  groups
+    .into_iter()
+    .map(|mut group| {
+      if group.len() == 1 {
+        return group.remove(0);
+      }
+      let mut error_types: Vec<String> = group
+        .iter()
+        .filter_map(|d| message_error_types(&d.message))
+        .flat_map(|(_, error_types)| error_types.into_iter().map(str::to_string))
+        .collect();
+      error_types.sort();
+      error_types.dedup();
+      let pos = group.iter().map(|d| d.pos).min().unwrap_or_default();
+      let base_index = (0..group.len())
+        .min_by_key(|&index| {
+          let diagnostic = &group[index];
+          (diagnostic.severity, message_error_types(&diagnostic.message).is_none(), diagnostic.data.is_none())
+        })
+        .unwrap_or_default();
+      let mut merged = group.swap_remove(base_index);
+      if let Some((set_range, _)) = message_error_types(&merged.message) {
+        merged.message.replace_range(set_range, &format!("{{{}}}", error_types.join(", ")));
+      }
+      merged.data = merged.data.or_else(|| group.into_iter().find_map(|d| d.data));
+      merged.pos = pos;
+      merged
+    })
+    .collect()
+}
+
+/// What a catch block does with the errors its try block can throw, for editor inlay hints
+#[derive(Serialize, Clone, Debug)]
+pub struct CatchSummary {
+  pub range: DiagnosticRange,
+  pub errors_thrown_in_try: Vec<String>,
+  pub errors_handled_in_catch: Vec<String>,
+  pub errors_effectively_caught: Vec<String>,
+  pub errors_propagated: Vec<String>,
+  pub has_escape_hatch: bool, // The catch rethrows its parameter, so unhandled errors still escape
+}
+
+/// One summary per catch block, sorted by position with each error list sorted and deduplicated
+fn catch_summaries(catch_analyses: &[CatchAnalysis], cm: &SourceMap) -> Vec<CatchSummary> {
+  let sorted_unique = |errors: &[String]| -> Vec<String> {
+    let mut errors = errors.to_vec();
+    errors.sort();
+    errors.dedup();
+    errors
+  };
+  let mut summaries: Vec<CatchSummary> = catch_analyses
+    .iter()
+    .map(|catch_analysis| {
+      let (start_line, start_col) = safe_lookup_char_pos(cm, catch_analysis.catch_span.lo());
+      let (end_line, end_col) = safe_lookup_char_pos(cm, catch_analysis.catch_span.hi());
+      CatchSummary {
+        range: DiagnosticRange {
+          start: DiagnosticPosition {
+            line: start_line,
+            character: start_col,
+          },
+          end: DiagnosticPosition {
+            line: end_line,
+            character: end_col,
+          },
+        },
+        errors_thrown_in_try: sorted_unique(&catch_analysis.errors_thrown_in_try),
+        errors_handled_in_catch: sorted_unique(&catch_analysis.errors_handled_in_catch),
+        errors_effectively_caught: sorted_unique(&catch_analysis.errors_effectively_caught),
+        errors_propagated: sorted_unique(&catch_analysis.errors_propagated),
+        has_escape_hatch: catch_analysis.has_escape_hatch,
+      }
+    })
+    .collect();
+  summaries.sort_by(|a, b| {
+    (a.range.start.line, a.range.start.character, a.range.end.line, a.range.end.character)
+      .cmp(&(b.range.start.line, b.range.start.character, b.range.end.line, b.range.end.character))
+  });
+  summaries
+}
+
+/// How well a throwing function's JSDoc covers what it throws
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentedStatus {
+  FullyDocumented,
+  PartiallyDocumented,
+  Undocumented,
+  Suppressed, // An `@it-throws` comment covers the whole function
+}
+
+/// Documentation status of a function that can throw, for editor gutter decorations. Unlike
+/// diagnostics these include fully documented functions.
+#[derive(Serialize, Clone, Debug)]
+pub struct FunctionThrowSummary {
+  pub id: String,
+  pub name: String,
+  pub range: DiagnosticRange,
+  pub thrown_types: Vec<String>, // Thrown directly or inherited from callees, after catch analysis
+  pub documented_types: Vec<String>, // From the function's JSDoc @throws tags
+  pub undocumented_types: Vec<String>, // Thrown types no @throws tag covers
+  pub status: DocumentedStatus,
+  pub insert_position: DiagnosticPosition, // Zero-based, where a "document all throws" action adds @throws tags
+  pub append_to_existing_doc: bool, // Insert tags before the `*/` of an existing JSDoc block
+}
+
+/// One summary per throwing function, direct or through its callees, sorted by position
+fn function_summaries(
+  functions_with_throws: &HashSet<ThrowMap>,
+  cm: &SourceMap,
+  comments: &Lrc<dyn Comments>,
+  ignore_statements: &[String],
+) -> Vec<FunctionThrowSummary> {
+  // A function may have several throw maps, e.g. its own throws and those of an inline callback
+  let mut functions: Vec<(&ThrowMap, Vec<&ThrowDetails>)> = Vec::new();
+  for throw_map in functions_with_throws {
+    match functions.iter_mut().find(|(fun, _)| fun.id == throw_map.id) {
+      Some((_, throw_details)) => throw_details.extend(&throw_map.throw_details),
+      None => functions.push((throw_map, throw_map.throw_details.iter().collect())),
+    }
+  }
+
+  let mut summaries: Vec<FunctionThrowSummary> = functions
+    .into_iter()
+    .map(|(fun, throw_details)| {
+      let annotation = fun.throws_annotation.as_ref();
+      let is_documented = |detail: &ThrowDetails| {
+        annotation.is_some_and(|annotation| {
+          annotation.covers_all || annotation.error_types.iter().any(|documented| detail.is_a(documented))
+        })
+      };
+      let documented_count = throw_details.iter().filter(|detail| is_documented(detail)).count();
+      let status = if has_it_throws_comment(comments, fun.function_span, ignore_statements) {
+        DocumentedStatus::Suppressed
+      } else if documented_count == throw_details.len() {
+        DocumentedStatus::FullyDocumented
+      } else if documented_count > 0 {
+        DocumentedStatus::PartiallyDocumented
+      } else {
+        DocumentedStatus::Undocumented
+      };
+
+      let mut thrown_types: Vec<String> = throw_details.iter().filter_map(|detail| detail.error_type.clone()).collect();
+      thrown_types.sort();
+      thrown_types.dedup();
+      let mut undocumented_types: Vec<String> = throw_details
+        .iter()
+        .filter(|detail| !is_documented(detail))
+        .filter_map(|detail| detail.error_type.clone())
+        .collect();
+      undocumented_types.sort();
+      undocumented_types.dedup();
+      let (insert_position, append_to_existing_doc) = throws_annotation_insert_position(cm, comments, fun.function_span);
+      let mut documented_types: Vec<String> = annotation.map(|annotation| annotation.error_types.clone()).unwrap_or_default();
+      documented_types.sort();
+      documented_types.dedup();
+      FunctionThrowSummary {
+        id: fun.id.clone(),
+        name: fun.function_or_method_name.clone(),
+        range: span_range(cm, fun.function_span),
+        thrown_types,
+        documented_types,
+        undocumented_types,
+        status,
+        insert_position,
+        append_to_existing_doc,
+      }
+    })
+    .collect();
+  summaries.sort_by(|a, b| {
+    (a.range.start.line, a.range.start.character, &a.id).cmp(&(b.range.start.line, b.range.start.character, &b.id))
+  });
+  summaries
+}
+
+/// Which functions call which, and the errors each can surface, for visualizing error flow
+#[derive(Serialize, Clone, Debug)]
+pub struct CallGraph {
+  pub nodes: Vec<CallGraphNode>,
+  pub edges: Vec<CallGraphEdge>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CallGraphNode {
+  pub id: String,
+  pub name: String,
+  pub class_name: Option<String>,
+  pub range: DiagnosticRange, // The function's name, or the whole function when it has none
+  pub error_types: Vec<String>, // Thrown directly or inherited from callees, after catch analysis
+  pub documented_types: Vec<String>, // From the function's JSDoc @throws tags
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CallGraphEdge {
+  pub from_id: String,
+  pub to_id: String,
+  pub call_range: DiagnosticRange,
+  pub propagated_error_types: Vec<String>, // The callee's errors that reach the caller through this call
+}
+
+fn span_range(cm: &SourceMap, span: Span) -> DiagnosticRange {
+  let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
+  let (end_line, end_col) = safe_lookup_char_pos(cm, span.hi());
+  DiagnosticRange {
+    start: DiagnosticPosition {
+      line: start_line,
+      character: start_col,
+    },
+    end: DiagnosticPosition {
+      line: end_line,
+      character: end_col,
+    },
+  }
+}
+
+const MAX_RELATED_THROW_SITES: usize = 5;
+
+/// Where a called function's throws are, so a call diagnostic can link to them. Throws the callee
+/// only inherits point at the callee itself and are left out, as are types suppressed on the call.
+fn throw_site_related_information(
+  cm: &SourceMap,
+  throw_map: &ThrowMap,
+  suppressed_error_types: &[String],
+) -> Vec<RelatedInformation> {
+  let mut seen_spans: Vec<Span> = Vec::new();
+  let mut related_information = Vec::new();
+  for (index, span) in throw_map.throw_spans.iter().enumerate() {
+    let error_type = throw_map.throw_details.get(index).and_then(|detail| detail.error_type.clone());
+    if span.is_dummy()
+      || *span == throw_map.function_span
+      || !is_within_source_file(cm, span.lo())
+      || seen_spans.contains(span)
+      || error_type.as_ref().is_some_and(|error_type| suppressed_error_types.contains(error_type))
+    {
+      continue;
+    }
+    seen_spans.push(*span);
+    related_information.push(RelatedInformation {
+      error_type,
+      description: None,
+      file: Some(cm.span_to_filename(*span).to_string()),
+      range: Some(span_range(cm, *span)),
+    });
+    if related_information.len() == MAX_RELATED_THROW_SITES {
+      break;
+    }
+  }
+  related_information
+}
+
+/// The graph of calls between user functions that can throw. Built from the filtered results, so
+/// calls whose errors are caught don't appear. Calls to builtins like `JSON.parse` are left out.
+fn call_graph(
+  functions_with_throws: &HashSet<ThrowMap>,
+  calls_to_throws: &HashSet<CallToThrowMap>,
+  all_functions: &HashSet<FunctionMap>,
+  cm: &SourceMap,
+) -> CallGraph {
+  let sorted_unique = |error_types: Vec<String>| -> Vec<String> {
+    let mut error_types = error_types;
+    error_types.sort();
+    error_types.dedup();
+    error_types
+  };
+  let thrown_types = |id: &str| -> Vec<String> {
+    sorted_unique(
+      functions_with_throws
+        .iter()
+        .filter(|throw_map| throw_map.id == id)
+        .flat_map(|throw_map| throw_map.throw_details.iter().filter_map(|detail| detail.error_type.clone()))
+        .collect(),
+    )
+  };
+  let documented_types = |annotation: &Option<ThrowsAnnotation>| -> Vec<String> {
+    sorted_unique(annotation.iter().flat_map(|annotation| annotation.error_types.clone()).collect())
+  };
+
+  let mut nodes: Vec<CallGraphNode> = all_functions
+    .iter()
+    .map(|fun| CallGraphNode {
+      id: fun.id.clone(),
+      name: fun.name.clone(),
+      class_name: fun.class_name.clone(),
+      range: span_range(cm, if is_within_source_file(cm, fun.name_span.lo()) { fun.name_span } else { fun.span }),
+      error_types: thrown_types(&fun.id),
+      documented_types: documented_types(&fun.throws_annotation),
+    })
+    .collect();
+  // Throwing functions the function finder doesn't record, like inline callbacks
+  for throw_map in functions_with_throws {
+    if nodes.iter().any(|node| node.id == throw_map.id) {
+      continue;
+    }
+    nodes.push(CallGraphNode {
+      id: throw_map.id.clone(),
+      name: throw_map.function_or_method_name.clone(),
+      class_name: throw_map.class_name.clone(),
+      range: span_range(cm, throw_map.name_span.unwrap_or(throw_map.function_span)),
+      error_types: thrown_types(&throw_map.id),
+      documented_types: documented_types(&throw_map.throws_annotation),
+    });
+  }
+
+  let mut edges: Vec<CallGraphEdge> = calls_to_throws
+    .iter()
+    .filter(|call| !call.is_builtin)
+    .map(|call| CallGraphEdge {
+      from_id: call.id.clone(),
+      to_id: call.throw_map.id.clone(),
+      call_range: span_range(cm, call.call_span),
+      propagated_error_types: sorted_unique(
+        call.propagated_throw_details().into_iter().filter_map(|detail| detail.error_type).collect(),
+      ),
+    })
+    .collect();
+
+  let position = |range: &DiagnosticRange| (range.start.line, range.start.character);
+  nodes.sort_by(|a, b| (position(&a.range), &a.id).cmp(&(position(&b.range), &b.id)));
+  nodes.dedup_by(|a, b| a.id == b.id && position(&a.range) == position(&b.range));
+  edges.sort_by(|a, b| (position(&a.call_range), &a.to_id).cmp(&(position(&b.call_range), &b.to_id)));
+  CallGraph { nodes, edges }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ImportedIdentifiers {
+  pub diagnostics: Vec<Diagnostic>,
+  pub id: String,
+}
+
+/// The types a `@throws` tag may name for this throw: its own type and every class it extends
+fn documentable_types(detail: &ThrowDetails) -> impl Iterator<Item = Option<&str>> {
+  std::iter::once(detail.error_type.as_deref()).chain(detail.ancestor_types.iter().map(|ancestor| Some(ancestor.as_str())))
+}
+
+/// `@throws` types a function documents but never throws, sorted. Only reported when every
+/// throw has a concrete type: an untyped, dynamic (`variable: err`) or union throw could be any
+/// of the documented ones. Documented unions are never reported either.
+fn stale_documented_types<'a>(
+  annotation: &ThrowsAnnotation,
+  thrown_types: impl IntoIterator<Item = Option<&'a str>>,
+) -> Option<Vec<String>> {
+  if annotation.covers_all {
+    return None;
+  }
+  let mut concrete_thrown_types = HashSet::new();
+  for error_type in thrown_types {
+    match error_type {
+      Some(error_type) if !error_type.contains(':') && !error_type.contains('|') => {
+        concrete_thrown_types.insert(error_type)
+      }
+      _ => return None,
+    };
+  }
+  let mut stale_types: Vec<String> = annotation
+    .error_types
+    .iter()
+    .filter(|error_type| !error_type.contains('|') && !concrete_thrown_types.contains(error_type.as_str()))
+    .cloned()
+    .collect();
+  stale_types.sort();
+  stale_types.dedup();
+  if stale_types.is_empty() {
+    None
+  } else {
+    Some(stale_types)
+  }
+}
+
+/// Flags `@throws` types a function documents but can never throw, directly or through the calls it
+/// makes, e.g. a `@throws {NetworkError}` left behind after a refactor. A documented function that
+/// throws nothing is only flagged when it calls no imported function the analysis can't see into.
+#[allow(clippy::too_many_arguments)]
+pub fn add_diagnostics_for_over_documented_functions(
+  diagnostics: &mut Vec<Diagnostic>,
+  functions_with_throws: &HashSet<ThrowMap>,
+  calls_to_throws: &HashSet<CallToThrowMap>,
+  all_functions: &HashSet<FunctionMap>,
+  imported_identifier_usages: &HashSet<IdentifierUsage>,
+  cm: &SourceMap,
+  severity: DiagnosticSeverity,
+  comments: &Lrc<dyn Comments>,
+  ignore_statements: &[String],
+) {
+  let mut documented_functions: Vec<(Span, &ThrowsAnnotation, Vec<Option<&str>>)> = Vec::new();
+  for fun in functions_with_throws {
+    let Some(annotation) = &fun.throws_annotation else {
+      continue;
+    };
+    // Throws recorded without details still count, just with an unknown error type
+    let mut thrown_types: Vec<Option<&str>> = if fun.throw_details.is_empty() {
+      vec![None; fun.throw_spans.len()]
+    } else {
+      fun.throw_details.iter().flat_map(documentable_types).collect()
+    };
+    thrown_types.extend(
+      calls_to_throws
+        .iter()
+        .filter(|call| call.id == fun.id)
+        .flat_map(|call| call.throw_map.throw_details.iter().flat_map(documentable_types)),
+    );
+    documented_functions.push((fun.function_span, annotation, thrown_types));
+  }
+  for fun in all_functions {
+    let Some(annotation) = &fun.throws_annotation else {
+      continue;
+    };
+    let throws = functions_with_throws.iter().any(|throw_map| throw_map.id == fun.id);
+    let calls_unknown_import =
+      imported_identifier_usages.iter().any(|usage| !usage.is_accessor && fun.span.contains(usage.usage_span));
+    if !throws && !calls_unknown_import {
+      documented_functions.push((fun.span, annotation, Vec::new()));
+    }
+  }
+
+  for (function_span, annotation, thrown_types) in documented_functions {
+    if annotation.no_throw.is_some() || has_it_throws_comment(comments, function_span, ignore_statements) {
+      continue;
+    }
+    let Some(stale_types) = stale_documented_types(annotation, thrown_types) else {
+      continue;
+    };
+    let (start_line, start_col) = safe_lookup_char_pos(cm, function_span.lo());
+    let last_char_byte_pos = get_first_line_last_char_byte_pos(cm, function_span.lo(), function_span.hi());
+    let (end_line, end_col) = safe_lookup_char_pos(cm, last_char_byte_pos);
+    diagnostics.push(Diagnostic {
+      severity: severity.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: start_line,
+          character: start_col,
+        },
+        end: DiagnosticPosition {
+          line: end_line,
+          character: end_col,
+        },
+      },
+      message: format!(
+        "JSDoc documents {} but {} never thrown",
+        stale_types.join(", "),
+        if stale_types.len() == 1 { "it is" } else { "they are" }
+      ),
+      code: Some(STALE_THROWS_DOC_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: Some(DiagnosticData {
+        quick_fix_type: Some("removeStaleThrows".to_string()),
+        error_types: stale_types,
+        insert_position: None,
+        append_to_existing_doc: None,
+        related_information: Vec::new(),
+        throw_locations: Vec::new(),
+      }),
+      pos: function_span.lo(),
+    });
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn add_diagnostics_for_functions_that_throw(
+  diagnostics: &mut Vec<Diagnostic>,
+  functions_with_throws: HashSet<ThrowMap>,
+  cm: &SourceMap,
+  debug: Option<bool>,
+  throw_statement_severity: DiagnosticSeverity,
+  function_throw_severity: DiagnosticSeverity,
+  error_type_severities: &HashMap<String, DiagnosticSeverity>,
+  expected_error_types: &[String],
+  comments: &Lrc<dyn Comments>,
+  ignore_statements: &[String],
+  class_ignore_comments: &[ClassIgnoreComment], // Already applied; a function doesn't get these as its own
+  strict_entry_functions: &[String],
+  collapse_throw_statements: bool, // Count throw statements in the function's message instead of reporting each
+) -> Vec<Span> { // Return the spans of @it-throws comments that were actually used
+  log("Starting add_diagnostics_for_functions_that_throw");
+  log(&format!("Processing {} functions with throws", functions_with_throws.len()));
+  
+  // Track which @it-throws comments are actually used for suppression
+  let mut used_it_throws_spans = Vec::new();
+  
+  // WORKAROUND: Convert HashSet to Vec immediately to avoid corrupted hash table cleanup
+  log("Converting HashSet to Vec to avoid hash table corruption...");
+  let functions_vec: Vec<ThrowMap> = functions_with_throws.into_iter().collect();
+  log(&format!("Successfully converted to Vec with {} functions", functions_vec.len()));
+  
+  log("About to iterate over functions_vec...");
+  for fun in &functions_vec {
+        log(&format!("Processing function: {}", fun.function_or_method_name));
+    
+    // Check if this function has @it-throws comment (will only suppress function-level diagnostics)
+    let it_throws_comment = it_throws_comment_with_types(comments, fun.function_span, ignore_statements)
+      .filter(|(comment_span, _)| !class_ignore_comments.iter().any(|class_comment| class_comment.comment_span == *comment_span));
+    // `// @it-throws ValidationError` only suppresses the listed types
+    let suppressed_error_types: Vec<String> = it_throws_comment
+      .as_ref()
+      .map(|(_, error_types)| error_types.clone())
+      .unwrap_or_default();
+    let has_function_it_throws = it_throws_comment.is_some() && suppressed_error_types.is_empty();
+
+    if let Some((comment_span, _)) = it_throws_comment {
+      // Track that this comment was actually used for suppression
+      used_it_throws_spans.push(comment_span);
+    }
+    if has_function_it_throws && debug == Some(true) {
+      log(&format!("Function {} has @it-throws comment - applying comprehensive suppression", fun.function_or_method_name));
+    }
+    
+    log("Checking if debug mode is enabled...");
+    if debug == Some(true) {
+      log(&format!("Processing function: {} (id: {})", fun.function_or_method_name, fun.id));
+      log(&format!("  Details count: {}, Spans count: {}", fun.throw_details.len(), fun.throw_spans.len()));
+    }
+    log("Debug check completed");
+
+    // Throws recorded without details are still reported, just with an unknown error type
+    let throw_details: Vec<ThrowDetails> = if fun.throw_details.is_empty() {
+      vec![ThrowDetails::default(); fun.throw_spans.len()]
+    } else {
+      fun.throw_details.clone()
+    };
+
+    // A throw from `finally` replaces whatever error was in flight, documented or not.
+    // Throws propagated from a callee only carry the function's own span, so skip those.
+    if !has_function_it_throws {
+      for (detail, span) in throw_details.iter().zip(fun.throw_spans.iter()) {
+        if !detail.in_finally || *span == fun.function_span {
+          continue;
+        }
+        let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
+        let (end_line, end_col) = safe_lookup_char_pos(cm, span.hi());
+        diagnostics.push(Diagnostic {
+          severity: DiagnosticSeverity::Warning.to_int(),
+          range: DiagnosticRange {
+            start: DiagnosticPosition {
+              line: start_line,
+              character: start_col,
+            },
+            end: DiagnosticPosition {
+              line: end_line,
+              character: end_col,
+            },
+          },
+          message: "Throw in finally block may mask the original error".to_string(),
+          code: Some(THROW_IN_FINALLY_CODE.to_string()),
+          source: "Does it Throw?".to_string(),
+          data: None,
+          pos: span.lo(),
+        });
+      }
+    }
+
+    // @nothrow functions may not throw at all, documented or propagated from a callee
+    if let Some(nothrow_tag) = fun.throws_annotation.as_ref().and_then(|annotation| annotation.no_throw.as_ref()) {
+      let mut thrown_types: Vec<String> = throw_details
+        .iter()
+        .map(|detail| detail.reported_error_type().unwrap_or_else(|| "Error".to_string()))
+        .collect();
+      thrown_types.sort();
+      thrown_types.dedup();
+      let (start_line, start_col) = safe_lookup_char_pos(cm, fun.function_span.lo());
+      let last_char_byte_pos = get_first_line_last_char_byte_pos(cm, fun.function_span.lo(), fun.function_span.hi());
+      let (end_line, end_col) = safe_lookup_char_pos(cm, last_char_byte_pos);
+      diagnostics.push(Diagnostic {
+        severity: DiagnosticSeverity::Error.to_int(),
+        range: function_name_range(cm, fun).unwrap_or(DiagnosticRange {
+          start: DiagnosticPosition {
+            line: start_line,
+            character: start_col,
+          },
+          end: DiagnosticPosition {
+            line: end_line,
+            character: end_col,
+          },
+        }),
+        message: format!(
+          "Function {} is marked {} but may throw: {{{}}}",
+          fun.function_or_method_name,
+          nothrow_tag,
+          thrown_types.join(", ")
+        ),
+        code: Some(NOTHROW_VIOLATION_CODE.to_string()),
+        source: "Does it Throw?".to_string(),
+        data: None,
+        pos: fun.function_span.lo(),
+      });
+      continue;
+    }
+
+    log("Performing defensive length check...");
+    // Defensive check: ensure throw_details and throw_spans have matching lengths
+    if throw_details.len() != fun.throw_spans.len() {
+      log(&format!("Vector length mismatch in {}: {} details vs {} spans",
+        fun.function_or_method_name,
+        throw_details.len(),
+        fun.throw_spans.len()
+      ));
+      // Skip this function to prevent panic
+      continue;
+    }
+    log("Length check passed");
+
+    log("Looking up function_start character position...");
+    let (function_start_line, function_start_col) = safe_lookup_char_pos(cm, fun.function_span.lo());
+    log("function_start looked up successfully");
+    
+    log("Getting last_char_byte_pos...");
+    let last_char_byte_pos = get_first_line_last_char_byte_pos(cm, fun.function_span.lo(), fun.function_span.hi());
+    log("last_char_byte_pos calculated successfully");
+
+    log("Looking up function_end character position...");
+    let (function_end_line, function_end_col) = safe_lookup_char_pos(cm, last_char_byte_pos);
+    log("function_end looked up successfully");
+
+    log("Getting start_character_byte_pos...");
+    let start_character_byte_pos =
+      get_line_start_byte_pos(cm, fun.function_span.lo(), fun.function_span.hi());
+    log("start_character_byte_pos calculated successfully");
+    
+    log("Looking up start_character position...");
+    let (_start_character_line, start_character_col) = safe_lookup_char_pos(cm, start_character_byte_pos);
+    log("start_character looked up successfully");
+
+    if debug == Some(true) {
+      log(&format!("Function throws: {}", fun.function_or_method_name));
+      log(&format!(
+        "From line {} column {} to line {} column {}",
+        function_start_line,
+        function_start_col,
+        function_end_line,
+        function_end_col
+      ));
+    }
+
+    // Filter throw_details based on throws_annotation
+    let (filtered_throw_details, filtered_throw_spans): (Vec<_>, Vec<_>) = if let Some(annotation) = fun.throws_annotation.as_ref().filter(|a| a.covers_all) {
+      // Wildcard @throws {*} / @throws {any} documents every throw, whatever else is listed
+      if debug == Some(true) {
+        log(&format!("{} documents all throws via wildcard: {:?}", fun.function_or_method_name, annotation.error_types));
+      }
+      (Vec::new(), Vec::new())
+    } else if let Some(annotation) = &fun.throws_annotation {
+      // Collect all error types from the annotation for easy lookup
+      let annotated_types: Vec<String> = annotation
+        .error_types.to_vec();
+
+      // Debug: log filtering process
+      if debug == Some(true) {
+        log(&format!("Filtering {} - documented types: {:?}", fun.function_or_method_name, annotated_types));
+        log(&format!("   Original throw details count: {}", throw_details.len()));
+        for (i, detail) in throw_details.iter().enumerate() {
+          log(&format!("   Detail {}: error_type={:?}", i, detail.error_type));
+        }
+      }
+
+      let filtered: Vec<_> = if throw_details.len() == fun.throw_spans.len() {
+        throw_details
+          .iter()
+          .zip(fun.throw_spans.iter())
+          .filter(|(detail, _span)| {
+            // Only keep throw_details whose error_type is NOT in the annotation
+            let is_documented = annotated_types.iter().any(|ann| detail.is_a(ann));
+            
+            if debug == Some(true) {
+              log(&format!("   Detail {:?} is documented: {} (keeping: {})", detail.error_type, is_documented, !is_documented));
+            }
+            
+            !is_documented
+          })
+          .map(|(detail, span)| (detail.clone(), *span))
+          .collect()
+      } else {
+        // Vector length mismatch - filter only details and duplicate the first span
+        log(&format!("Vector length mismatch during filtering in {}: {} details vs {} spans - using safe fallback", 
+          fun.function_or_method_name, 
+          throw_details.len(), 
+          fun.throw_spans.len()
+        ));
+        let fallback_span = fun.throw_spans.first().copied().unwrap_or(swc_common::DUMMY_SP);
+        throw_details
+          .iter()
+          .filter(|detail| {
+            let is_documented = annotated_types.iter().any(|ann| detail.is_a(ann));
+            !is_documented
+          })
+          .map(|detail| (detail.clone(), fallback_span))
+          .collect()
+      };
+
+      if debug == Some(true) {
+        log(&format!("   Filtered throw details count: {}", filtered.len()));
+      }
+
+      filtered.into_iter().unzip()
+    } else {
+      // No annotation, keep all
+      (throw_details.clone(), fun.throw_spans.clone())
+    };
+
+    // Project-wide expected error types never need reporting, documented or not
+    let (filtered_throw_details, filtered_throw_spans): (Vec<_>, Vec<_>) = if expected_error_types.is_empty() {
+      (filtered_throw_details, filtered_throw_spans)
+    } else {
+      filtered_throw_details
+        .into_iter()
+        .zip(filtered_throw_spans)
+        .filter(|(detail, _span)| {
+          !detail
+            .error_type
+            .as_ref()
+            .is_some_and(|error_type| expected_error_types.contains(error_type))
+        })
+        .unzip()
+    };
+
+    // Types listed in the function's @it-throws comment are suppressed; listed types it never throws are stale
+    let (filtered_throw_details, filtered_throw_spans): (Vec<_>, Vec<_>) = if suppressed_error_types.is_empty() {
+      (filtered_throw_details, filtered_throw_spans)
+    } else {
+      if let Some((comment_span, _)) = it_throws_comment {
+        for error_type in &suppressed_error_types {
+          if !throw_details.iter().any(|detail| detail.error_type.as_ref() == Some(error_type)) {
+            add_diagnostic_for_unused_it_throws_type(diagnostics, cm, comment_span, error_type);
+          }
+        }
+      }
+      filtered_throw_details
+        .into_iter()
+        .zip(filtered_throw_spans)
+        .filter(|(detail, _span)| {
+          !detail
+            .error_type
+            .as_ref()
+            .is_some_and(|error_type| suppressed_error_types.contains(error_type))
+        })
+        .unzip()
+    };
+
+    // Throws merged in from a callee next to the function's own throws only carry the function's
+    // span; the call is reported instead
+    let throw_statements: Vec<(&ThrowDetails, &Span)> = filtered_throw_details
+      .iter()
+      .zip(filtered_throw_spans.iter())
+      .filter(|(_, span)| **span != fun.function_span || fun.throw_statement == fun.function_span)
+      .collect();
+
+    // Only push a function-level diagnostic if there is at least one undocumented throw AND function doesn't have @it-throws
+    if !filtered_throw_details.is_empty() && !has_function_it_throws {
+      // Extract and format error type names for cleaner message
+      let mut error_types: Vec<String> = filtered_throw_details
+        .iter()
+        .filter_map(|detail| detail.reported_error_type())
+        .collect();
+      // Deduplicate and sort for stable output
+      error_types.sort();
+      error_types.dedup();
+      // Types that only leave the function through `throw e` in a catch are listed as rethrown
+      let (rethrown_types, thrown_types): (Vec<String>, Vec<String>) =
+        error_types.iter().cloned().partition(|error_type| {
+          filtered_throw_details
+            .iter()
+            .filter(|detail| detail.reported_error_type().as_ref() == Some(error_type))
+            .all(|detail| detail.is_rethrow)
+        });
+      let format_types = |types: &Vec<String>| -> String {
+        if types.is_empty() { "".to_string() } else { format!("{{{}}}", types.join(", ")) }
+      };
+
+      // Async functions never throw synchronously - their throws reject the returned promise,
+      // and so do throws inside a `new Promise` executor
+      let via_promise_executor = filtered_throw_details.iter().all(|detail| detail.via_promise_executor);
+      let verb = if fun.is_async || via_promise_executor { "reject" } else { "throw" };
+
+      // `throw "bad"` carries only a message; quote those so non-Error throws stand out
+      let mut string_literals: Vec<String> = Vec::new();
+      for detail in filtered_throw_details.iter().filter(|detail| detail.error_type.is_none()) {
+        if let Some(literal) = detail.error_message.as_ref().map(|message| format!("\"{}\"", message)) {
+          if !string_literals.contains(&literal) {
+            string_literals.push(literal);
+          }
+        }
+      }
+
+      // Prefer a friendlier message when the function name is "<anonymous>"
+      let subject = if fun.function_or_method_name == "<anonymous>" {
+        "Anonymous function".to_string()
+      } else {
+        format!("Function {}", fun.function_or_method_name)
+      };
+      // Collapsed throw statements are counted after the verb, e.g. `may throw (3 statements)`
+      let counted = |verb: &str| -> String {
+        match throw_statements.len() {
+          count if !collapse_throw_statements || count == 0 => verb.to_string(),
+          1 => format!("{} (1 statement)", verb),
+          count => format!("{} ({} statements)", verb, count),
+        }
+      };
+      // Async functions reject either way, so only a synchronous `throw e` reads as a rethrow
+      let types_clause = if verb == "throw" && !rethrown_types.is_empty() {
+        if thrown_types.is_empty() {
+          format!("{}: {}", counted("rethrow"), format_types(&rethrown_types))
+        } else {
+          format!("{}: {} and rethrow: {}", counted("throw"), format_types(&thrown_types), format_types(&rethrown_types))
+        }
+      } else {
+        format!("{}: {}", counted(verb), format_types(&error_types))
+      };
+      let message = match (error_types.is_empty(), string_literals.is_empty()) {
+        // Fallback for cases where error_type is None, or only an unresolved `throw e`
+        (true, true) if verb == "throw" && filtered_throw_details.iter().all(|detail| detail.is_rethrow) => {
+          format!("{} may {}", subject, counted("rethrow"))
+        }
+        (true, true) => format!("{} may {}", subject, counted(verb)),
+        (false, true) => format!("{} may {}", subject, types_clause),
+        (true, false) => format!("{} may {} a string literal: {}", subject, counted(verb), string_literals.join(", ")),
+        (false, false) => format!("{} may {} and a string literal: {}", subject, types_clause, string_literals.join(", ")),
+      };
+      let message = if via_promise_executor {
+        format!("{} (thrown inside Promise executor)", message)
+      } else {
+        message
+      };
+
+      // Entry functions must document or handle everything that reaches them
+      let severity = if is_strict_entry_function(&fun.function_or_method_name, strict_entry_functions) {
+        DiagnosticSeverity::Error
+      } else {
+        severity_for_throw_details(&filtered_throw_details, function_throw_severity, error_type_severities)
+      };
+
+      // Undocumented functions get what a "Document thrown errors" quick fix needs
+      let data = if fun.throws_annotation.is_none() && !error_types.is_empty() {
+        let (insert_position, append_to_existing_doc) =
+          throws_annotation_insert_position(cm, comments, fun.function_span);
+        Some(DiagnosticData {
+          quick_fix_type: Some("addThrowsAnnotation".to_string()),
+          error_types: error_types.clone(),
+          insert_position: Some(insert_position),
+          append_to_existing_doc: Some(append_to_existing_doc),
+          related_information: Vec::new(),
+          throw_locations: Vec::new(),
+        })
+      } else {
+        // Partially documented: pass along what the existing @throws tags say
+        let related_information: Vec<RelatedInformation> = fun
+          .throws_annotation
+          .iter()
+          .flat_map(|annotation| annotation.descriptions.iter())
+          .map(|(error_type, description)| RelatedInformation {
+            error_type: Some(error_type.clone()),
+            description: Some(description.clone()),
+            file: None,
+            range: None,
+          })
+          .collect();
+        if related_information.is_empty() {
+          None
+        } else {
+          Some(DiagnosticData {
+            quick_fix_type: None,
+            error_types: Vec::new(),
+            insert_position: None,
+            append_to_existing_doc: None,
+            related_information,
+            throw_locations: Vec::new(),
+          })
+        }
+      };
+
+      // The throw statements aren't reported on their own, so point at them from here
+      let data = if collapse_throw_statements && !throw_statements.is_empty() {
+        let throw_locations = throw_statements
+          .iter()
+          .map(|(_, span)| {
+            let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
+            let (end_line, end_col) = safe_lookup_char_pos(cm, span.hi());
+            DiagnosticRange {
+              start: DiagnosticPosition { line: start_line, character: start_col },
+              end: DiagnosticPosition { line: end_line, character: end_col },
+            }
+          })
+          .collect();
+        Some(DiagnosticData {
+          throw_locations,
+          ..data.unwrap_or_default()
+        })
+      } else {
+        data
+      };
+
+      diagnostics.push(Diagnostic {
+        severity: severity.to_int(),
+        range: function_name_range(cm, fun).unwrap_or(DiagnosticRange {
+          start: DiagnosticPosition {
+            line: function_start_line,
+            character: start_character_col,
+          },
+          end: DiagnosticPosition {
+            line: function_end_line,
+            character: function_end_col,
+          },
+        }),
+        message,
+        code: Some(FUNCTION_MAY_THROW_CODE.to_string()),
+        source: "Does it Throw?".to_string(),
+        data,
+        pos: fun.function_span.lo(),
+      });
+    }
+
+    // Push throw statement diagnostics for undocumented throws
+    // Apply comprehensive suppression: if function has @it-throws, suppress ALL diagnostics including throw statements
+    if !filtered_throw_details.is_empty() && !has_function_it_throws && !collapse_throw_statements {
+      for (detail, span) in throw_statements {
+        let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
+        let (end_line, end_col) = safe_lookup_char_pos(cm, span.hi());
+
+        let severity = severity_for_throw_details([detail], throw_statement_severity, error_type_severities);
+
+        diagnostics.push(Diagnostic {
+          severity: severity.to_int(),
+          range: DiagnosticRange {
+            start: DiagnosticPosition {
+              line: start_line,
+              character: start_col,
+            },
+            end: DiagnosticPosition {
+              line: end_line,
+              character: end_col,
+            },
+          },
+          message: throw_statement_message(cm, detail, *span),
+          code: Some(THROW_STATEMENT_CODE.to_string()),
+          source: "Does it Throw?".to_string(),
+          data: None,
+          pos: span.lo(),
+        });
+      }
+    }
+  }
+  log("Completed processing all functions, about to exit safely");
+  used_it_throws_spans
+}
+
+pub fn add_diagnostics_for_exhaustive_catches(
+  diagnostics: &mut Vec<Diagnostic>,
+  catch_analyses: &[CatchAnalysis],
+  cm: &SourceMap,
+  debug: Option<bool>,
+) {
+  for catch_analysis in catch_analyses {
+    let (pos_line, pos_col) = safe_lookup_char_pos(cm, catch_analysis.catch_span.lo());
+    
+    // Only show diagnostics for catches that have validation errors
+    if catch_analysis.has_validation_errors() {
+      let missing = catch_analysis.missing_handlers.join(", ");
+      let message = if catch_analysis.has_escape_hatch {
+        format!("Catch block uses escape hatch but still missing handlers for: {}", missing)
+      } else {
+        format!("Exhaustive catch is missing handlers for: {}. Add handlers or use 'throw e' as escape hatch.", missing)
+      };
+      
+      if debug == Some(true) {
+        log(&format!("Exhaustive catch error at line {}: {}", pos_line, message));
+      }
+
+      diagnostics.push(Diagnostic {
+        severity: DiagnosticSeverity::Error.to_int(),
+        range: DiagnosticRange {
+          start: DiagnosticPosition {
+            line: pos_line,
+            character: pos_col,
+          },
+          end: DiagnosticPosition {
+            line: pos_line,
+            character: pos_col + 10, // Highlight "catch" keyword
+          },
+        },
+        message,
+        code: Some(EXHAUSTIVE_CATCH_CODE.to_string()),
+        source: "Does it Throw?".to_string(),
+        data: None,
+        pos: catch_analysis.catch_span.lo(),
+      });
+    } else if catch_analysis.has_escape_hatch && debug == Some(true) {
+      // Info message for successful escape hatch usage
+      log(&format!("Catch at line {} uses escape hatch correctly", pos_line));
+    } else if catch_analysis.is_exhaustive() && debug == Some(true) {
+      // Info message for complete exhaustive catches
+      log(&format!("Catch at line {} is complete", pos_line));
+    }
+  }
+}
+
+/// Warns about empty catch blocks, like `catch (e) {}`, that discard errors their try block is
+/// known to throw. An ignore statement inside the catch or just above it suppresses the warning.
+/// Returns the spans of the ignore statements that did so.
+pub fn add_diagnostics_for_swallowed_errors(
+  diagnostics: &mut Vec<Diagnostic>,
+  catch_analyses: &[CatchAnalysis],
+  cm: &SourceMap,
+  comments: &Lrc<dyn Comments>,
+  ignore_statements: &[String],
+) -> Vec<Span> {
+  let mut used_it_throws_spans = Vec::new();
+  for catch_analysis in catch_analyses.iter().filter(|catch_analysis| catch_analysis.swallows_silently()) {
+    let catch_span = catch_analysis.catch_span;
+    let suppression = line_ignore_comment(cm, comments, catch_span, ignore_statements).or_else(|| {
+      (catch_span.lo.0..=catch_span.hi.0).find_map(|pos| {
+        comments.get_leading(BytePos(pos)).and_then(|leading| {
+          leading.iter().find_map(|comment| {
+            ignore_comment_error_types(&comment.text, ignore_statements).map(|error_types| (comment.span, error_types))
+          })
+        })
+      })
+    });
+    if let Some((comment_span, _)) = suppression {
+      used_it_throws_spans.push(comment_span);
+      continue;
+    }
+
+    let mut swallowed = catch_analysis.errors_thrown_in_try.clone();
+    swallowed.sort();
+    let (pos_line, pos_col) = safe_lookup_char_pos(cm, catch_span.lo());
+    diagnostics.push(Diagnostic {
+      severity: DiagnosticSeverity::Warning.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: pos_line,
+          character: pos_col,
+        },
+        end: DiagnosticPosition {
+          line: pos_line,
+          character: pos_col + 5, // Length of "catch"
+        },
+      },
+      message: format!("Catch block silently swallows: {{{}}}", swallowed.join(", ")),
+      code: Some(SWALLOWED_ERRORS_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: catch_span.lo(),
+    });
+  }
+  used_it_throws_spans
+}
+
+pub fn add_diagnostics_for_unused_it_throws_comments(
+  diagnostics: &mut Vec<Diagnostic>,
+  unused_comment_spans: &[swc_common::Span],
+  cm: &SourceMap,
+  debug: Option<bool>,
+) {
+  for span in unused_comment_spans {
+    let (pos_line, pos_col) = safe_lookup_char_pos(cm, span.lo);
+    
+    if debug == Some(true) {
+      log(&format!("Unused @it-throws comment at line {}", pos_line));
+    }
+
+    diagnostics.push(Diagnostic {
+      severity: DiagnosticSeverity::Information.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: pos_line,
+          character: pos_col,
+        },
+        end: DiagnosticPosition {
+          line: pos_line,
+          character: pos_col + 12, // Length of "// @it-throws"
+        },
+      },
+      message: "Unused @it-throws comment. This comment is not suppressing any diagnostics.".to_string(),
+      code: Some(UNUSED_IT_THROWS_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: span.lo,
+    });
+  }
+}
+
+/// Flags an error type listed in an `@it-throws <ErrorType>` comment that nothing it covers throws
+fn add_diagnostic_for_unused_it_throws_type(
+  diagnostics: &mut Vec<Diagnostic>,
+  cm: &SourceMap,
+  comment_span: Span,
+  error_type: &str,
+) {
+  let (start_line, start_col) = safe_lookup_char_pos(cm, comment_span.lo);
+  let (end_line, end_col) = safe_lookup_char_pos(cm, comment_span.hi);
+  diagnostics.push(Diagnostic {
+    severity: DiagnosticSeverity::Information.to_int(),
+    range: DiagnosticRange {
+      start: DiagnosticPosition {
+        line: start_line,
+        character: start_col,
+      },
+      end: DiagnosticPosition {
+        line: end_line,
+        character: end_col,
+      },
+    },
+    message: format!("Unused @it-throws type: {}. Nothing it covers throws this type.", error_type),
+    code: Some(UNUSED_IT_THROWS_CODE.to_string()),
+    source: "Does it Throw?".to_string(),
+    data: None,
+    pos: comment_span.lo,
+  });
+}
+
+/// Flags the error types of a class's `@it-throws <ErrorType>` comment that none of its methods throw
+/// or call something throwing. A comment covering nothing at all is reported as unused instead.
+fn add_diagnostics_for_unused_class_it_throws_types(
+  diagnostics: &mut Vec<Diagnostic>,
+  results: &AnalysisResult,
+  cm: &SourceMap,
+) {
+  for class_comment in results.class_ignore_comments.iter().filter(|class_comment| !class_comment.error_types.is_empty()) {
+    let throws_in_class = results
+      .functions_with_throws
+      .iter()
+      .filter(|fun| class_comment.class_span.contains(fun.function_span))
+      .flat_map(|fun| &fun.throw_details);
+    let calls_in_class = results
+      .calls_to_throws
+      .iter()
+      .filter(|call| class_comment.class_span.contains(call.call_span))
+      .flat_map(|call| &call.throw_map.throw_details);
+    let thrown_types: HashSet<&str> =
+      throws_in_class.chain(calls_in_class).filter_map(|detail| detail.error_type.as_deref()).collect();
+    if !class_comment.error_types.iter().any(|error_type| thrown_types.contains(error_type.as_str())) {
+      continue;
+    }
+    for error_type in &class_comment.error_types {
+      if !thrown_types.contains(error_type.as_str()) {
+        add_diagnostic_for_unused_it_throws_type(diagnostics, cm, class_comment.comment_span, error_type);
+      }
+    }
+  }
+}
+
+/// With `treat_unknown_imports_as_throwing`, flags every call to an imported function that the
+/// analysis has no definition for, unless the call is suppressed by an ignore statement
+pub fn add_diagnostics_for_unknown_imports(
+  diagnostics: &mut Vec<Diagnostic>,
+  identifier_usages: &HashSet<IdentifierUsage>,
+  all_functions: &HashSet<FunctionMap>,
+  cm: &SourceMap,
+  comments: &Lrc<dyn Comments>,
+  ignore_statements: &[String],
+  severity: DiagnosticSeverity,
+) {
+  // Reading a property of an import is only a call when it runs an accessor, which isn't known here
+  for identifier_usage in identifier_usages.iter().filter(|usage| !usage.is_accessor) {
+    // `lib.parse` resolves to `parse` when the imported module is part of the analysis
+    let function_name = identifier_usage.callee.rsplit('.').next().unwrap_or_default();
+    if all_functions.iter().any(|f| f.name == function_name) {
+      continue;
+    }
+    if line_has_ignore_comment(cm, comments, identifier_usage.usage_span, ignore_statements) {
+      continue;
+    }
+    let (start_line, start_col) = safe_lookup_char_pos(cm, identifier_usage.usage_span.lo());
+    let (end_line, end_col) = safe_lookup_char_pos(cm, identifier_usage.usage_span.hi());
+    diagnostics.push(Diagnostic {
+      severity: severity.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: start_line,
+          character: start_col,
+        },
+        end: DiagnosticPosition {
+          line: end_line,
+          character: end_col,
+        },
+      },
+      message: format!("Imported function '{}' has unknown throw behavior", identifier_usage.callee),
+      code: Some(UNKNOWN_IMPORT_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: identifier_usage.usage_span.lo(),
+    });
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn add_diagnostics_for_calls_to_throws(
+  diagnostics: &mut Vec<Diagnostic>,
+  calls_to_throws: HashSet<CallToThrowMap>,
+  functions_with_throws: &HashSet<ThrowMap>,
+  all_functions: &HashSet<FunctionMap>,
+  cm: &SourceMap,
+  debug: Option<bool>,
+  call_to_throw_severity: DiagnosticSeverity,
+  builtin_throw_severity: DiagnosticSeverity,
+  error_type_severities: &HashMap<String, DiagnosticSeverity>,
+  comments: &Lrc<dyn Comments>,
+  ignore_statements: &[String],
+  suppressed_functions: &HashSet<String>,
+  suppressed_error_types: &HashMap<String, Vec<String>>,
+) {
+  // `// @it-throws <ErrorType>` comments on call lines: the types they list and the types the calls they cover throw
+  let mut typed_call_comments: HashMap<Span, (Vec<String>, HashSet<String>)> = HashMap::new();
+  for call in &calls_to_throws {
+    // Check if this call is in a function that has @it-throws comment (comprehensive suppression)  
+    if suppressed_functions.contains(&call.call_function_or_method_name) {
+      if debug == Some(true) {
+        log(&format!("Skipping call diagnostic for {} due to @it-throws comment on calling function", call.call_function_or_method_name));
+      }
+      continue; // Skip this call diagnostic entirely
+    }
+
+    // Types listed in an @it-throws comment on the call or on the calling function are suppressed
+    let mut allowed_error_types: Vec<String> =
+      suppressed_error_types.get(&call.call_function_or_method_name).cloned().unwrap_or_default();
+    allowed_error_types.extend(call.suppressed_error_types.iter().cloned());
+    if let Some((comment_span, error_types)) = line_ignore_comment(cm, comments, call.call_span, ignore_statements) {
+      let (_, thrown_types) = typed_call_comments.entry(comment_span).or_insert_with(|| (error_types.clone(), HashSet::new()));
+      thrown_types.extend(call.throw_map.throw_details.iter().filter_map(|d| d.error_type.clone()));
+      allowed_error_types.extend(error_types);
+    }
+    let unsuppressed_details: Vec<&ThrowDetails> = call.throw_map.throw_details
+      .iter()
+      .filter(|d| !d.error_type.as_ref().is_some_and(|error_type| allowed_error_types.contains(error_type)))
+      .collect();
+    if !call.throw_map.throw_details.is_empty() && unsuppressed_details.is_empty() {
+      if debug == Some(true) {
+        log(&format!("Skipping call diagnostic for {} - every error type is listed in @it-throws", call.call_function_or_method_name));
+      }
+      continue;
+    }
+    
+    // Check if the calling function has JSDoc annotations that cover the called function's errors
+    let should_suppress_call = should_suppress_call_diagnostic(call, functions_with_throws, all_functions, debug);
+
+    if should_suppress_call {
+      if debug == Some(true) {
+        log(&format!(
+          "Suppressing call to {} - calling function has proper JSDoc documentation",
+          call.call_function_or_method_name
+        ));
+      }
+      continue; // Skip this call diagnostic
+    }
+
+    let (call_start_line, call_start_col) = safe_lookup_char_pos(cm, call.call_span.lo());
+    let last_char_byte_pos = get_first_line_last_char_byte_pos(cm, call.call_span.lo(), call.call_span.hi());
+    let (call_end_line, call_end_col) = safe_lookup_char_pos(cm, last_char_byte_pos);
+
+    if debug == Some(true) {
+      log(&format!(
+        "Function call may throw: {}",
+        call.call_function_or_method_name
+      ));
+      log(&format!(
+        "From line {} column {} to line {} column {}",
+        call_start_line, call_start_col, call_end_line, call_end_col
+      ));
+    }
+
+    // Include error types from the called function if available
+    let mut called_error_types: Vec<String> = unsuppressed_details
+      .iter()
+      .filter_map(|d| d.reported_error_type())
+      .collect();
+    called_error_types.sort();
+    called_error_types.dedup();
+    let called_types = if called_error_types.is_empty() {
+      "{Error}".to_string()
+    } else {
+      format!("{{{}}}", called_error_types.join(", "))
+    };
+    // Calls into async functions only raise when awaited; otherwise the rejection goes unhandled
+    let call_message = if call.is_builtin {
+      format!("Call to {} may throw: {}", call.throw_map.function_or_method_name, called_types)
+    } else if !call.throw_map.is_async {
+      format!("Function call may throw: {}.", called_types)
+    } else if call.is_awaited {
+      format!("Awaited call may throw: {}.", called_types)
+    } else {
+      format!("Unawaited call may cause an unhandled rejection: {}.", called_types)
+    };
+
+    let default_severity = if call.is_builtin { builtin_throw_severity } else { call_to_throw_severity };
+    let severity = if call.throw_map.throw_details.is_empty() {
+      severity_for_throw_details([&ThrowDetails::default()], default_severity, error_type_severities)
+    } else {
+      severity_for_throw_details(unsuppressed_details.iter().copied(), default_severity, error_type_severities)
+    };
+
+    // Link to the callee's throw statements; builtins have none in the analyzed code
+    let related_information = if call.is_builtin {
+      Vec::new()
+    } else {
+      throw_site_related_information(cm, &call.throw_map, &allowed_error_types)
+    };
+    let data = (!related_information.is_empty()).then(|| DiagnosticData {
+      quick_fix_type: None,
+      error_types: Vec::new(),
+      insert_position: None,
+      append_to_existing_doc: None,
+      related_information,
+      throw_locations: Vec::new(),
+    });
+
+    diagnostics.push(Diagnostic {
+      severity: severity.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: call_start_line,
+          character: call_start_col,
+        },
+        end: DiagnosticPosition {
+          line: call_end_line,
+          character: call_end_col,
+        },
+      },
+      message: call_message,
+      code: Some(CALL_MAY_THROW_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data,
+      pos: call.call_span.lo(),
+    });
+  }
+
+  for (comment_span, (listed_types, thrown_types)) in typed_call_comments {
+    for error_type in listed_types.iter().filter(|error_type| !thrown_types.contains(*error_type)) {
+      add_diagnostic_for_unused_it_throws_type(diagnostics, cm, comment_span, error_type);
+    }
+  }
+}
+
+/// Generate "Function X may throw" diagnostics for functions that call throwing functions
+/// This handles transitive throwing - functions that don't directly throw but call functions that do
+#[allow(clippy::too_many_arguments)]
+pub fn add_diagnostics_for_calling_functions_that_may_throw(
+  diagnostics: &mut Vec<Diagnostic>,
+  calls_to_throws: &HashSet<CallToThrowMap>,
+  functions_with_throws: &HashSet<ThrowMap>,
+  cm: &SourceMap,
+  function_throw_severity: DiagnosticSeverity,
+  debug: Option<bool>,
+  suppressed_functions: &HashSet<String>,
+  suppressed_error_types: &HashMap<String, Vec<String>>,
+  strict_entry_functions: &[String],
+) {
+  // Collect unique calling functions that call throwing functions
+  let mut calling_functions: std::collections::HashMap<String, (swc_common::Span, String)> = std::collections::HashMap::new();
+  
+  for call in calls_to_throws {
+    // Skip if the calling function is suppressed, or the call suppresses everything its callee throws
+    if suppressed_functions.contains(&call.call_function_or_method_name)
+      || (!call.throw_map.throw_details.is_empty() && call.propagated_throw_details().is_empty())
+    {
+      continue;
+    }
+    
+    // Skip if this function already has a direct throw (already covered by add_diagnostics_for_functions_that_throw)
+    let is_already_throwing = functions_with_throws.iter().any(|throw_map| {
+      throw_map.function_or_method_name == call.call_function_or_method_name
+    });
+    
+    if !is_already_throwing {
+      // Use the call span to represent the function (this will be the line where the call happens)
+      // For better UX, we could try to find the actual function declaration span, but call span works
+      calling_functions.insert(
+        call.call_function_or_method_name.clone(),
+        (call.call_span, call.call_function_or_method_name.clone())
+      );
+      
+      if debug == Some(true) {
+        log(&format!("Found calling function that may throw: {}", call.call_function_or_method_name));
+      }
+    }
+  }
+  
+  // Generate diagnostics for each unique calling function
+  for (function_name, (span, _)) in calling_functions {
+    let (start_line, start_col) = safe_lookup_char_pos(cm, span.lo());
+    let line_end_byte_pos = get_line_end_byte_pos(cm, span.lo(), span.hi());
+    let (end_line, end_col) = safe_lookup_char_pos(cm, line_end_byte_pos);
+    // Aggregate error types from calls within this function
+    let mut types: Vec<String> = calls_to_throws
+      .iter()
+      .filter(|c| c.call_function_or_method_name == function_name)
+      .flat_map(|c| c.propagated_throw_details().into_iter().filter_map(|d| d.reported_error_type()))
+      .collect();
+    types.sort();
+    types.dedup();
+    if let Some(suppressed_types) = suppressed_error_types.get(&function_name) {
+      if !types.is_empty() && types.iter().all(|error_type| suppressed_types.contains(error_type)) {
+        continue;
+      }
+      types.retain(|error_type| !suppressed_types.contains(error_type));
+    }
+    let message = if types.is_empty() {
+      format!("Function {} may throw", function_name)
+    } else {
+      format!("Function {} may throw: {{{}}}", function_name, types.join(", "))
+    };
+    
+    if debug == Some(true) {
+      log(&message);
+    }
+    let severity = if is_strict_entry_function(&function_name, strict_entry_functions) {
+      DiagnosticSeverity::Error
+    } else {
+      function_throw_severity
+    };
+    
+    diagnostics.push(Diagnostic {
+      severity: severity.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: start_line,
+          character: start_col,
+        },
+        end: DiagnosticPosition {
+          line: end_line,
+          character: end_col,
+        },
+      },
+      message,
+      code: Some(FUNCTION_MAY_THROW_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: span.lo(),
+    });
+  }
+}
+
+fn should_suppress_call_diagnostic(
+  call: &CallToThrowMap,
+  functions_with_throws: &HashSet<ThrowMap>,
+  all_functions: &HashSet<FunctionMap>,
+  debug: Option<bool>,
+) -> bool {
+  // Extract the calling function name from the call.id
+  // Format is typically "ClassName-functionName" or "NOT_SET-functionName"
+  let calling_function_name = if let Some(dash_pos) = call.id.rfind('-') {
+    &call.id[dash_pos + 1..]
+  } else {
+    &call.id
+  };
+
+  if debug == Some(true) {
+    log(&format!("Checking call suppression for calling function: {}", calling_function_name));
+  }
+
+  // Find the calling function in functions_with_throws
+  let calling_function = functions_with_throws
+    .iter()
+    .find(|f| f.function_or_method_name == calling_function_name);
+
+  if let Some(caller) = calling_function {
+    if let Some(caller_annotation) = &caller.throws_annotation {
+      // Get the error types that the called function can throw
+      let called_error_types: Vec<String> = call.throw_map.throw_details
+        .iter()
+        .filter_map(|d| d.error_type.clone())
+        .collect();
+
+      if debug == Some(true) {
+        log(&format!("  Caller documents: {:?}", caller_annotation.error_types));
+        log(&format!("  Called function throws: {:?}", called_error_types));
+      }
+
+      // Check if all called function's error types are documented by the caller
+      let all_errors_documented = caller_annotation.no_throw.is_none()
+        && (caller_annotation.covers_all || call.throw_map.throw_details.iter().filter(|d| d.error_type.is_some()).all(|detail| {
+          caller_annotation.error_types.iter().any(|documented| detail.is_a(documented))
+        }));
+
+      if debug == Some(true) {
+        log(&format!("  All errors documented: {}", all_errors_documented));
+      }
+
+      return all_errors_documented;
+    } else {
+      if debug == Some(true) {
+        log("  Calling function has no JSDoc annotation");
+      }
+    }
+  } else {
+    if debug == Some(true) {
+      log("  Calling function not found in throwing functions, checking all functions for JSDoc");
+    }
+    // If the calling function is not in functions_with_throws, it means it doesn't throw directly
+    // But it might still have JSDoc annotations covering the called errors
+    // Check in all_functions for JSDoc annotations
+    let calling_function_in_all = all_functions
+      .iter()
+      .find(|f| f.name == calling_function_name);
+      
+    if let Some(caller) = calling_function_in_all {
+      if let Some(caller_annotation) = &caller.throws_annotation {
+        // Get the error types that the called function can throw
+        let called_error_types: Vec<String> = call.throw_map.throw_details
+          .iter()
+          .filter_map(|d| d.error_type.clone())
+          .collect();
+
+        if debug == Some(true) {
+          log(&format!("  Non-throwing caller documents: {:?}", caller_annotation.error_types));
+          log(&format!("  Called function throws: {:?}", called_error_types));
+        }
+
+        // Check if all called function's error types are documented by the caller
+        let all_errors_documented = caller_annotation.no_throw.is_none()
+          && (caller_annotation.covers_all || call.throw_map.throw_details.iter().filter(|d| d.error_type.is_some()).all(|detail| {
+            caller_annotation.error_types.iter().any(|documented| detail.is_a(documented))
+          }));
+
+        if debug == Some(true) {
+          log(&format!("  All errors documented by non-throwing function: {}", all_errors_documented));
+        }
+
+        return all_errors_documented;
+      } else {
+        if debug == Some(true) {
+          log("  Non-throwing calling function has no JSDoc annotation");
+        }
+      }
+    } else {
+      if debug == Some(true) {
+        log("  Calling function not found in all functions either");
+      }
+    }
+  }
+
+  false
+}
+
+// Multiple calls to the same identifier can result in multiple diagnostics for the same identifier.
+// We want to return a diagnostic for all calls to the same identifier, so we need to combine the diagnostics for each identifier.
+pub fn identifier_usages_vec_to_combined_map(
+  identifier_usages: HashSet<IdentifierUsage>,
+  cm: &SourceMap,
+  debug: Option<bool>,
+  call_to_imported_throw_severity: DiagnosticSeverity,
+) -> HashMap<String, ImportedIdentifiers> {
+  let mut identifier_usages_map: HashMap<String, ImportedIdentifiers> = HashMap::new();
+  for identifier_usage in identifier_usages {
+    let identifier_name = identifier_usage.id.clone();
+    let (start_line, start_col) = safe_lookup_char_pos(cm, identifier_usage.usage_span.lo());
+    let (end_line, end_col) = safe_lookup_char_pos(cm, identifier_usage.usage_span.hi());
+
+    if debug == Some(true) {
+      log(&format!(
+        "Identifier usage: {}",
+        identifier_usage.id.clone()
+      ));
+      log(&format!(
+        "From line {} column {} to line {} column {}",
+        start_line, start_col, end_line, end_col
+      ));
+    }
+
+    let identifier_diagnostics =
+      identifier_usages_map
+        .entry(identifier_name)
+        .or_insert(ImportedIdentifiers {
+          diagnostics: Vec::new(),
+          id: identifier_usage.id,
+        });
+
+    identifier_diagnostics.diagnostics.push(Diagnostic {
+      severity: call_to_imported_throw_severity.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition {
+          line: start_line,
+          character: start_col,
+        },
+        end: DiagnosticPosition {
+          line: end_line,
+          character: end_col,
+        },
+      },
+      message: "Function imported may throw.".to_string(),
+      code: Some(CALL_MAY_THROW_CODE.to_string()),
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: identifier_usage.usage_span.lo(),
+    });
+  }
+  identifier_usages_map
+}
+
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of characters and `?` for one
+pub fn wildcard_matches(pattern: &str, name: &str) -> bool {
+  fn matches(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+      None => name.is_empty(),
+      Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+      Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+      Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+    }
+  }
+  matches(&pattern.chars().collect::<Vec<_>>(), &name.chars().collect::<Vec<_>>())
+}
+
+fn is_strict_entry_function(function_name: &str, strict_entry_functions: &[String]) -> bool {
+  strict_entry_functions.iter().any(|pattern| wildcard_matches(pattern, function_name))
+}
+
+/// Everything that shapes the diagnostics built from an analysis, apart from the analysis itself
+#[derive(Clone, Debug)]
+pub struct DiagnosticSettings {
+  pub throw_statement_severity: DiagnosticSeverity,
+  pub function_throw_severity: DiagnosticSeverity,
+  pub call_to_throw_severity: DiagnosticSeverity,
+  pub call_to_imported_throw_severity: DiagnosticSeverity,
+  pub builtin_throw_severity: DiagnosticSeverity, // Calls to builtins known to throw, like `JSON.parse`
+  pub over_documented_severity: DiagnosticSeverity, // `@throws` types the function can never throw
+  pub error_type_severities: HashMap<String, DiagnosticSeverity>, // Per error type overrides, see `CUSTOM_ERROR_SEVERITY_KEY`
+  pub strict_entry_functions: Vec<String>, // Name patterns like "handle*"; undocumented throws there are errors
+  pub collapse_throw_statements: bool, // One count in the function's diagnostic instead of one per throw statement
+  pub merge_overlapping_diagnostics: bool, // Merge diagnostics on the exact same range
+  pub treat_unknown_imports_as_throwing: bool,
+  pub include_call_graph: bool,
+  pub ignore_statements: Vec<String>,
+  pub expected_error_types: Vec<String>,
+  pub debug: bool,
+}
+
+impl Default for DiagnosticSettings {
+  fn default() -> Self {
+    Self {
+      throw_statement_severity: DiagnosticSeverity::Hint,
+      function_throw_severity: DiagnosticSeverity::Hint,
+      call_to_throw_severity: DiagnosticSeverity::Hint,
+      call_to_imported_throw_severity: DiagnosticSeverity::Hint,
+      builtin_throw_severity: DiagnosticSeverity::Hint,
+      over_documented_severity: DiagnosticSeverity::Information,
+      error_type_severities: HashMap::new(),
+      strict_entry_functions: Vec::new(),
+      collapse_throw_statements: false,
+      merge_overlapping_diagnostics: true,
+      treat_unknown_imports_as_throwing: false,
+      include_call_graph: false,
+      ignore_statements: Vec::new(),
+      expected_error_types: Vec::new(),
+      debug: false,
+    }
+  }
+}
+
+impl DiagnosticSettings {
+  /// Default severities, taking comment keywords, expected error types and `debug` from the analysis settings
+  pub fn from_user_settings(user_settings: &UserSettings) -> Self {
+    Self {
+      ignore_statements: user_settings.ignore_statements.clone(),
+      expected_error_types: user_settings.expected_error_types.clone(),
+      debug: user_settings.debug,
+      ..Default::default()
+    }
+  }
+}
+
+/// Diagnostics for an analysis along with the summaries built next to them
+#[derive(Clone, Debug)]
+pub struct DiagnosticReport {
+  pub diagnostics: Vec<Diagnostic>,
+  pub relative_imports: Vec<String>,
+  pub throw_ids: Vec<String>,
+  pub imported_identifiers_diagnostics: Vec<ImportedIdentifiers>,
+  pub catch_summaries: Vec<CatchSummary>,
+  pub function_summaries: Vec<FunctionThrowSummary>,
+  pub call_graph: Option<CallGraph>, // Only with `include_call_graph`
+  pub debug_log: Vec<String>, // Trace of building the report, only collected with `debug`
+}
+
+/// Diagnostics for the first file of `cm`, the one `analyze_code` analyzed.
+/// A file opting out with `@it-throws-disable` at the top gets none.
+pub fn build_diagnostics(
+  result: &AnalysisResult,
+  cm: &SourceMap,
+  comments: &Lrc<SingleThreadedComments>,
+  settings: &DiagnosticSettings,
+) -> Vec<Diagnostic> {
+  let source_file = match cm.files().first() {
+    Some(source_file) => source_file.clone(),
+    None => return Vec::new(),
+  };
+  if has_file_disable_comment(&source_file.src) {
+    return Vec::new();
+  }
+
+  let disable_regions = disable_regions(cm, comments, source_file.start_pos);
+  let comments_as_dyn: Lrc<dyn Comments> = comments.clone();
+  build_report(result, cm, &comments_as_dyn, settings, &result.throw_statement_spans, &disable_regions).diagnostics
+}
+
+/// Build the diagnostics and summaries for `results`. `@it-throws` comments directly above one of
+/// `all_throw_spans` count as used; diagnostics inside `disable_regions` are dropped.
+pub fn build_report(
+  results: &AnalysisResult,
+  cm: &SourceMap,
+  comments: &Lrc<dyn Comments>,
+  settings: &DiagnosticSettings,
+  all_throw_spans: &[Span],
+  disable_regions: &[DisableRegion],
+) -> DiagnosticReport {
+  let (mut report, debug_log) = ::debug_log::capture(settings.debug, || {
+    build_report_traced(results, cm, comments, settings, all_throw_spans, disable_regions)
+  });
+  report.debug_log = debug_log;
+  report
+}
+
+fn build_report_traced(
+  results: &AnalysisResult,
+  cm: &SourceMap,
+  comments: &Lrc<dyn Comments>,
+  settings: &DiagnosticSettings,
+  all_throw_spans: &[Span],
+  disable_regions: &[DisableRegion],
+) -> DiagnosticReport {
+  let debug = Some(settings.debug);
+  log("Accessing results.functions_with_throws...");
+  // First, extract data we need before consuming any parts of results
+  let throw_ids: Vec<String> = results.functions_with_throws.iter().map(|f| f.id.clone()).collect();
+  log("Successfully extracted throw_ids");
+  
+  let call_graph = settings.include_call_graph.then(|| {
+    call_graph(&results.functions_with_throws, &results.calls_to_throws, &results.all_functions, cm)
+  });
+
+  log("Accessing results.import_sources...");
+  let relative_imports = get_relative_imports(results.import_sources.iter().cloned().collect());
+  log("Successfully extracted relative_imports");
+  
+  log("Creating empty diagnostics vector...");
+  let mut diagnostics: Vec<Diagnostic> = Vec::new();
+  log("Created empty diagnostics vector");
+  
+  log("About to clone results.functions_with_throws...");
+  let functions_clone = without_class_suppressed_throws(
+    without_caught_nothrow_throws(results.functions_with_throws.clone(), &results.calls_to_throws, &results.catch_analyses),
+    &results.class_ignore_comments,
+  );
+  log("Successfully cloned functions_with_throws");
+  
+  log("Calling add_diagnostics_for_functions_that_throw...");
+  // Track which functions were suppressed by @it-throws for later use
+  let mut suppressed_functions = HashSet::new();
+  for fun in &functions_clone {
+    if has_it_throws_comment(comments, fun.function_span, &settings.ignore_statements) {
+      suppressed_functions.insert(fun.function_or_method_name.clone());
+    }
+  }
+  // Functions whose @it-throws comment lists error types only suppress those types
+  let mut suppressed_error_types: HashMap<String, Vec<String>> = HashMap::new();
+  for (name, span) in functions_clone
+    .iter()
+    .map(|fun| (&fun.function_or_method_name, fun.function_span))
+    .chain(results.all_functions.iter().map(|fun| (&fun.name, fun.span)))
+  {
+    if let Some((_, error_types)) = it_throws_comment_with_types(comments, span, &settings.ignore_statements) {
+      if !error_types.is_empty() {
+        suppressed_error_types.insert(name.clone(), error_types);
+      }
+    }
+  }
+  // So are the calls made by methods of a class with an @it-throws comment above it
+  for class_comment in &results.class_ignore_comments {
+    let methods = results.all_functions.iter().filter(|fun| class_comment.class_span.contains(fun.span));
+    for method in methods {
+      if class_comment.error_types.is_empty() {
+        suppressed_functions.insert(method.name.clone());
+      } else {
+        suppressed_error_types.entry(method.name.clone()).or_default().extend(class_comment.error_types.iter().cloned());
+      }
+    }
+  }
+  
+  let used_it_throws_spans = add_diagnostics_for_functions_that_throw(
+    &mut diagnostics,
+    functions_clone,
+    cm,
+    debug,
+    settings.throw_statement_severity,
+    settings.function_throw_severity,
+    &settings.error_type_severities,
+    &settings.expected_error_types,
+    comments,
+    &settings.ignore_statements,
+    &results.class_ignore_comments,
+    &settings.strict_entry_functions,
+    settings.collapse_throw_statements,
+  );
+  log("add_diagnostics_for_functions_that_throw completed successfully");
+  add_diagnostics_for_unused_class_it_throws_types(&mut diagnostics, results, cm);
+  
+  
+  log("About to call add_diagnostics_for_calls_to_throws...");
+  add_diagnostics_for_calls_to_throws(
+    &mut diagnostics,
+    results.calls_to_throws.clone(),
+    &results.functions_with_throws,
+    &results.all_functions,
+    cm,
+    debug,
+    settings.call_to_throw_severity,
+    settings.builtin_throw_severity,
+    &settings.error_type_severities,
+    comments,
+    &settings.ignore_statements,
+    &suppressed_functions,
+    &suppressed_error_types,
+  );
+  log("add_diagnostics_for_calls_to_throws completed successfully");
+  
+  log("About to call add_diagnostics_for_calling_functions_that_may_throw...");
+  add_diagnostics_for_calling_functions_that_may_throw(
+    &mut diagnostics,
+    &results.calls_to_throws,
+    &results.functions_with_throws,
+    cm,
+    settings.function_throw_severity,
+    debug,
+    &suppressed_functions,
+    &suppressed_error_types,
+    &settings.strict_entry_functions,
+  );
+  log("add_diagnostics_for_calling_functions_that_may_throw completed successfully");
+  
+  add_diagnostics_for_over_documented_functions(
+    &mut diagnostics,
+    &results.functions_with_throws,
+    &results.calls_to_throws,
+    &results.all_functions,
+    &results.imported_identifier_usages,
+    cm,
+    settings.over_documented_severity,
+    comments,
+    &settings.ignore_statements,
+  );
+
+  log("About to call add_diagnostics_for_exhaustive_catches...");
+  // Add exhaustive catch validation diagnostics
+  add_diagnostics_for_exhaustive_catches(
+    &mut diagnostics,
+    &results.catch_analyses,
+    cm,
+    debug,
+  );
+  log("add_diagnostics_for_exhaustive_catches completed successfully");
+  let swallowed_errors_comment_spans = add_diagnostics_for_swallowed_errors(
+    &mut diagnostics,
+    &results.catch_analyses,
+    cm,
+    comments,
+    &settings.ignore_statements,
+  );
+  let catch_summaries = catch_summaries(&results.catch_analyses, cm);
+  let function_summaries =
+    function_summaries(&results.functions_with_throws, cm, comments, &settings.ignore_statements);
+  
+  log("About to call add_diagnostics_for_unused_it_throws_comments...");
+  // Filter out @it-throws comments that were actually used for suppression
+  let original_count = results.unused_it_throws_comments.len();
+  
+  // For comprehensive suppression, be very conservative about marking @it-throws comments as unused
+  // Check multiple ways a comment could be "used":
+  // 1. Direct function-level suppression (existing logic)
+  // 2. Inline suppression of throw statements
+  // 3. Any comment near a function that has throws
+  let mut all_used_comment_spans = used_it_throws_spans.clone();
+  all_used_comment_spans.extend(swallowed_errors_comment_spans);
+  
+  // Add comment spans for ALL functions that have throws (comprehensive suppression approach)
+  for fun in &results.functions_with_throws {
+    if let Some(comment_span) = has_it_throws_comment_with_span(comments, fun.function_span, &settings.ignore_statements) {
+      if !all_used_comment_spans.contains(&comment_span) {
+        all_used_comment_spans.push(comment_span);
+      }
+    }
+  }
+  
+  // ADDITIONAL: Inline suppression proximity using ALL throw statements in the file
+  // Some throws are suppressed at the Rust layer and won't appear in functions_with_throws.
+  // Use every throw statement of the analysis so comments directly above suppressed throws are marked as used.
+  let mut additional_used_spans = Vec::new();
+  for comment_span in &results.unused_it_throws_comments {
+    // `@it-throws-next-line` covers exactly the line below it, which the analysis already checked
+    if cm.span_to_snippet(*comment_span).is_ok_and(|text| text.contains("-next-line")) {
+      continue;
+    }
+    let comment_line = cm.lookup_char_pos(comment_span.lo).line;
+    let mut used = false;
+    for throw_span in all_throw_spans {
+      let throw_line = cm.lookup_char_pos(throw_span.lo()).line;
+      // Consider the comment "used" if it is within 2 lines immediately above the throw
+      if comment_line < throw_line && throw_line - comment_line <= 2 {
+        used = true;
+        break;
+      }
+    }
+
+    if used
+      && !all_used_comment_spans.contains(comment_span) && !additional_used_spans.contains(comment_span) {
+        additional_used_spans.push(*comment_span);
+      }
+  }
+  
+  // Add the additional used spans
+  all_used_comment_spans.extend(additional_used_spans);
+  
+  let truly_unused_comments: Vec<swc_common::Span> = results.unused_it_throws_comments
+    .iter()
+    .filter(|span| !all_used_comment_spans.contains(span))
+    .copied()
+    .collect();
+  
+  log(&format!("Filtered unused comments: {} total -> {} truly unused", 
+    original_count, 
+    truly_unused_comments.len()
+  ));
+  
+  // Add unused @it-throws comment diagnostics
+  add_diagnostics_for_unused_it_throws_comments(
+    &mut diagnostics,
+    &truly_unused_comments,
+    cm,
+    debug,
+  );
+  log("add_diagnostics_for_unused_it_throws_comments completed successfully");
+  
+  let call_to_imported_throw_severity = settings.call_to_imported_throw_severity;
+  if settings.treat_unknown_imports_as_throwing {
+    add_diagnostics_for_unknown_imports(
+      &mut diagnostics,
+      &results.imported_identifier_usages,
+      &results.all_functions,
+      cm,
+      comments,
+      &settings.ignore_statements,
+      call_to_imported_throw_severity,
+    );
+  }
+
+  log("About to call identifier_usages_vec_to_combined_map...");
+  let imported_identifiers_map = identifier_usages_vec_to_combined_map(
+    results.imported_identifier_usages.clone(),
+    cm,
+    debug,
+    call_to_imported_throw_severity,
+  );
+  log("identifier_usages_vec_to_combined_map completed successfully");
+  
+  log("Converting HashMap to Vec to avoid drop corruption...");
+  let mut imported_identifiers_diagnostics: Vec<ImportedIdentifiers> = imported_identifiers_map.into_values().collect();
+  log("Successfully converted HashMap to Vec");
+
+  apply_disable_regions(&mut diagnostics, &mut imported_identifiers_diagnostics, disable_regions, cm);
+  // Syntax errors can't be disabled
+  diagnostics.extend(parse_error_diagnostics(&results.parse_errors, cm));
+  if settings.merge_overlapping_diagnostics {
+    diagnostics = merge_overlapping_diagnostics(diagnostics);
+  }
+
+  // Analysis results live in HashSets; sort everything so identical input gives identical output
+  sort_diagnostics(&mut diagnostics);
+  for imported_identifier in imported_identifiers_diagnostics.iter_mut() {
+    sort_diagnostics(&mut imported_identifier.diagnostics);
+  }
+  imported_identifiers_diagnostics.sort_by(|a, b| a.id.cmp(&b.id));
+  let mut throw_ids = throw_ids;
+  throw_ids.sort();
+  let mut relative_imports = relative_imports;
+  relative_imports.sort();
+  
+  log(&format!("Final data sizes - diagnostics: {}, throw_ids: {}, relative_imports: {}, imported_identifiers: {}", 
+    diagnostics.len(), 
+    throw_ids.len(), 
+    relative_imports.len(), 
+    imported_identifiers_diagnostics.len()
+  ));
+
+  DiagnosticReport {
+    diagnostics,
+    relative_imports,
+    throw_ids,
+    imported_identifiers_diagnostics,
+    catch_summaries,
+    function_summaries,
+    call_graph,
+    debug_log: Vec::new(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use self::swc_common::FileName;
+  use analyze_code;
+
+  fn user_settings() -> UserSettings {
+    UserSettings {
+      ignore_statements: vec!["@it-throws".to_string()],
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_get_line_end_byte_pos_with_newline() {
+    let cm = Lrc::new(SourceMap::default());
+    let source_file = cm.new_source_file(
+      FileName::Custom("test_file".into()),
+      "line 1\nline 2".into(),
+    );
+
+    let lo_byte_pos = source_file.start_pos;
+    let hi_byte_pos = BytePos(source_file.end_pos.0 + 10);
+
+    let result = get_line_end_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
+    assert_eq!(result, BytePos(24));
+  }
+
+  #[test]
+  fn test_get_line_end_byte_pos_without_newline() {
+    let cm = Lrc::new(SourceMap::default());
+    let source_file = cm.new_source_file(FileName::Custom("test_file".into()), "no newline".into());
+
+    let lo_byte_pos = source_file.start_pos;
+    let hi_byte_pos = BytePos(source_file.end_pos.0 + 10);
+
+    let result = get_line_end_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
+    assert_eq!(result, hi_byte_pos);
+  }
+
+  #[test]
+  fn test_get_line_end_byte_pos_none_snippet() {
+    let cm = Lrc::new(SourceMap::default());
+    let source_file = cm.new_source_file(FileName::Custom("test_file".into()), "".into());
+
+    let lo_byte_pos = source_file.start_pos;
+    let hi_byte_pos = BytePos(source_file.end_pos.0 + 10);
+
+    let result = get_line_end_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
+    assert_eq!(result, hi_byte_pos);
+  }
+
+  #[test]
+  fn test_get_line_start_byte_pos_with_content() {
+    let cm = Lrc::new(SourceMap::default());
+    cm.new_source_file(
+      FileName::Custom("test_file".into()),
+      "line 1\n    line 2\nline 3".into(),
+    );
+
+    let lo_byte_pos = BytePos(19);
+    let hi_byte_pos = BytePos(7);
+
+    let result = get_line_start_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
+    assert_eq!(result, BytePos(1));
+  }
+
+  #[test]
+  fn test_get_line_start_byte_pos_without_content() {
+    let cm = Lrc::new(SourceMap::default());
+    cm.new_source_file(
+      FileName::Custom("test_file".into()),
+      "line 1\n    \nline 3".into(),
+    );
+
+    let lo_byte_pos = BytePos(1);
+    let hi_byte_pos = BytePos(11);
+
+    let result = get_line_start_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
+    assert_eq!(result, BytePos(8));
+  }
+
+  #[test]
+  fn test_get_line_start_byte_pos_at_file_start() {
+    let cm = Lrc::new(SourceMap::default());
+    cm.new_source_file(
+      FileName::Custom("test_file".into()),
+      "line 1\nline 2\nline 3".into(),
+    );
+
+    let lo_byte_pos = BytePos(0);
+    let hi_byte_pos = BytePos(5);
+
+    let result = get_line_start_byte_pos(&cm, lo_byte_pos, hi_byte_pos);
+    assert_eq!(result, BytePos(0));
+  }
+
+  #[test]
+  fn test_get_relative_imports() {
+    let import_sources = vec![
+      "./relative/path".to_string(),
+      "../relative/path".to_string(),
+      "/absolute/path".to_string(),
+      "http://example.com".to_string(),
+      "https://example.com".to_string(),
+      "package".to_string(),
+    ];
+
+    let expected_relative_imports = vec![
+      "./relative/path".to_string(),
+      "../relative/path".to_string(),
+    ];
+
+    let relative_imports = get_relative_imports(import_sources);
+    assert_eq!(relative_imports, expected_relative_imports);
+  }
+
+  #[test]
+  fn test_safe_lookup_char_pos_accepts_first_character() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let first = cm.new_source_file(FileName::Anon, "throw new Error(\"first\")\n".to_string());
+    let second = cm.new_source_file(FileName::Anon, "const s = \"😀\"; throw new Error(\"x\")\n".to_string());
+
+    assert_eq!(safe_lookup_char_pos(&cm, first.start_pos), (1, 0));
+    assert_eq!(safe_lookup_char_pos(&cm, first.start_pos + BytePos(6)), (1, 6));
+    assert_eq!(safe_lookup_char_pos(&cm, second.start_pos), (1, 0));
+
+    // A multi-byte emoji earlier on the line counts as its two UTF-16 code units
+    let throw_pos = second.start_pos + BytePos(second.src.find("throw").unwrap() as u32);
+    assert_eq!(safe_lookup_char_pos(&cm, throw_pos), (1, 16));
+
+    // Dummy positions and positions past the last file fall back instead of panicking
+    assert_eq!(safe_lookup_char_pos(&cm, BytePos(0)), (1, 0));
+    assert_eq!(safe_lookup_char_pos(&cm, second.end_pos + BytePos(10)), (1, 0));
+  }
+
+  #[test]
+  fn test_merge_overlapping_diagnostics_on_same_range() {
+    let diagnostic = |severity: DiagnosticSeverity, start: usize, message: &str| Diagnostic {
+      severity: severity.to_int(),
+      range: DiagnosticRange {
+        start: DiagnosticPosition { line: 4, character: start },
+        end: DiagnosticPosition { line: 4, character: 20 },
+      },
+      message: message.to_string(),
+      code: None,
+      source: "Does it Throw?".to_string(),
+      data: None,
+      pos: BytePos(start as u32),
+    };
+    let merged = merge_overlapping_diagnostics(vec![
+      diagnostic(DiagnosticSeverity::Hint, 2, "Function call may throw: {TypeError}."),
+      diagnostic(DiagnosticSeverity::Warning, 2, "Function parse may throw: {RangeError, TypeError}"),
+      diagnostic(DiagnosticSeverity::Information, 2, "Throw statement."),
+      diagnostic(DiagnosticSeverity::Hint, 3, "Function call may throw: {Error}."),
+    ]);
+
+    assert_eq!(merged.len(), 2, "{:?}", merged);
+    assert_eq!(merged[0].message, "Function parse may throw: {RangeError, TypeError}");
+    assert_eq!(merged[0].severity, DiagnosticSeverity::Warning.to_int());
+    assert_eq!(merged[1].message, "Function call may throw: {Error}.");
+
+    let merged = merge_overlapping_diagnostics(vec![
+      diagnostic(DiagnosticSeverity::Hint, 2, "Function call may throw: {TypeError}."),
+      diagnostic(DiagnosticSeverity::Hint, 2, "Function call may throw: {Error}."),
+    ]);
+    assert_eq!(merged.len(), 1, "{:?}", merged);
+    assert_eq!(merged[0].message, "Function call may throw: {Error, TypeError}.");
+  }
+
+  #[test]
+  fn test_build_diagnostics_without_wasm() {
+    let code = r#"function parse(input) {
+  if (!input) throw new TypeError("bad input");
+}
+function run() {
+  parse("");
+}
+"#;
+    let user_settings = user_settings();
+    let (result, cm, comments) = analyze_code(code, Default::default(), &user_settings);
+    let settings = DiagnosticSettings {
+      call_to_throw_severity: DiagnosticSeverity::Warning,
+      ..DiagnosticSettings::from_user_settings(&user_settings)
+    };
+    let diagnostics = build_diagnostics(&result, &cm, &comments, &settings);
+
+    let call = diagnostics
+      .iter()
+      .find(|d| d.message == "Function call may throw: {TypeError}.")
+      .unwrap_or_else(|| panic!("no call diagnostic: {:?}", diagnostics));
+    assert_eq!(call.range.start.line, 5);
+    assert_eq!(call.severity, DiagnosticSeverity::Warning.to_int());
+    assert_eq!(call.code.as_deref(), Some(CALL_MAY_THROW_CODE));
+    assert!(diagnostics.iter().any(|d| d.code.as_deref() == Some(THROW_STATEMENT_CODE) && d.range.start.line == 2));
+
+    let disabled = format!("// @it-throws-disable\n{}", code);
+    let (result, cm, comments) = analyze_code(&disabled, Default::default(), &user_settings);
+    assert!(build_diagnostics(&result, &cm, &comments, &settings).is_empty());
+  }
+
+  #[test]
+  fn test_debug_trace_is_ascii_and_messages_keep_unicode() {
+    let code = r#"function load() {
+  throw new Error("エラー: 設定が見つかりません");
+}
+function run() {
+  load();
+}
+"#;
+    let user_settings = UserSettings { debug: true, ..user_settings() };
+    let (result, cm, comments) = analyze_code(code, Default::default(), &user_settings);
+    let comments_as_dyn: Lrc<dyn Comments> = comments;
+    let settings = DiagnosticSettings::from_user_settings(&user_settings);
+    let report = build_report(&result, &cm, &comments_as_dyn, &settings, &result.throw_statement_spans, &[]);
+
+    // The trace is plain ASCII; only text quoted from the analyzed code may be anything else
+    assert!(!report.debug_log.is_empty());
+    for line in result.debug_log.iter().chain(&report.debug_log) {
+      assert!(line.is_ascii(), "{}", line);
+    }
+    let round_tripped: serde_json::Value = serde_json::from_str(&serde_json::to_string(&report.diagnostics).unwrap()).unwrap();
+    let messages: Vec<&str> = round_tripped.as_array().unwrap().iter().map(|d| d["message"].as_str().unwrap()).collect();
+    assert!(messages.contains(&"Throw: new Error(\"エラー: 設定が見つかりません\")"), "{:?}", messages);
+  }
+
+  #[test]
+  fn test_diagnostic_columns_count_utf16_code_units() {
+    let ranges = |code: &str| -> Vec<(String, (usize, usize, usize, usize))> {
+      let user_settings = user_settings();
+      let (result, cm, comments) = analyze_code(code, Default::default(), &user_settings);
+      let settings = DiagnosticSettings::from_user_settings(&user_settings);
+      build_diagnostics(&result, &cm, &comments, &settings)
+        .into_iter()
+        .map(|d| (d.message, (d.range.start.line, d.range.start.character, d.range.end.line, d.range.end.character)))
+        .collect()
+    };
+
+    // The emoji is two UTF-16 code units before the throw, `é` one
+    let emoji = ranges("function emoji() { const s = \"😀\"; throw new Error(\"x\"); }\n");
+    assert!(emoji.contains(&("Throw: new Error(\"x\")".to_string(), (1, 35, 1, 56))), "{:?}", emoji);
+    let accented = ranges("function héllo() { const s = \"héllo\"; throw new Error(\"x\");\n}\n");
+    assert!(accented.contains(&("Function héllo may throw: {Error}".to_string(), (1, 9, 1, 14))), "{:?}", accented);
+    assert!(accented.contains(&("Throw: new Error(\"x\")".to_string(), (1, 38, 1, 59))), "{:?}", accented);
+
+    // Wide CJK characters are one code unit each, not their display width of two
+    let cjk = ranges("function 検証する() {\n  throw new Error(\"x\");\n}\n");
+    assert!(cjk.contains(&("Function 検証する may throw: {Error}".to_string(), (1, 9, 1, 13))), "{:?}", cjk);
+
+    // A first line ending in a multi-byte character ends the range on that character, not inside it
+    let end_of_line = ranges("function tail() { // 注意\n  throw new Error(\"x\"); // 😀\n}\nfunction run() { tail(); // 😀\n}\n");
+    assert!(end_of_line.contains(&("Function call may throw: {Error}.".to_string(), (4, 17, 4, 22))), "{:?}", end_of_line);
+    assert!(end_of_line.contains(&("Throw: new Error(\"x\")".to_string(), (2, 2, 2, 23))), "{:?}", end_of_line);
+  }
+
+  #[test]
+  fn test_get_first_line_last_char_byte_pos() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let file = cm.new_source_file(FileName::Anon, "call(); // 注意\r\nnext\nf()".to_string());
+    let span_end = file.start_pos + BytePos(file.src.find("next").unwrap() as u32 + 4);
+    // Stops before the line break, on the first byte of the three-byte character
+    let last_char = get_first_line_last_char_byte_pos(&cm, file.start_pos, span_end);
+    assert_eq!(last_char, file.start_pos + BytePos(file.src.find('意').unwrap() as u32));
+    assert_eq!(cm.lookup_char_pos(last_char).col.0, 12);
+
+    let single_line = file.start_pos + BytePos(file.src.find("f()").unwrap() as u32);
+    assert_eq!(get_first_line_last_char_byte_pos(&cm, single_line, file.end_pos), file.end_pos - BytePos(1));
+  }
+}
@@ -0,0 +1,348 @@
+extern crate swc_ecma_ast;
+extern crate swc_ecma_visit;
+
+use std::collections::{HashMap, HashSet};
+
+use self::swc_ecma_ast::{
+  Decl, ExportAll, ExportDecl, ExportSpecifier, ImportDecl, ImportSpecifier, ModuleExportName, NamedExport, Pat,
+};
+use self::swc_ecma_visit::Visit;
+
+/// `export { imported as exported } from 'source'`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reexport {
+  pub source: String,
+  pub imported: String,
+  pub exported: String,
+}
+
+/// `import { imported as local } from 'source'`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamedImport {
+  pub source: String,
+  pub imported: String,
+  pub local: String,
+}
+
+/// What a module exports and imports by name, for following re-exports across files
+#[derive(Clone, Debug, Default)]
+pub struct ExportFinder {
+  pub local_exports: HashMap<String, String>, // Exported name -> name declared in this module
+  pub reexports: Vec<Reexport>,               // `export { a as b } from './x'`
+  pub export_all_sources: Vec<String>,        // `export * from './x'`
+  pub named_imports: Vec<NamedImport>,
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
+impl Visit for ExportFinder {
+  fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
+    let names: Vec<String> = match &export_decl.decl {
+      Decl::Fn(fn_decl) => vec![fn_decl.ident.sym.to_string()],
+      Decl::Class(class_decl) => vec![class_decl.ident.sym.to_string()],
+      Decl::Var(var_decl) => var_decl
+        .decls
+        .iter()
+        .filter_map(|declarator| match &declarator.name {
+          Pat::Ident(ident) => Some(ident.id.sym.to_string()),
+          _ => None,
+        })
+        .collect(),
+      _ => vec![],
+    };
+    for name in names {
+      self.local_exports.insert(name.clone(), name);
+    }
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport) {
+    if named_export.type_only {
+      return;
+    }
+    for specifier in &named_export.specifiers {
+      let ExportSpecifier::Named(named) = specifier else {
+        continue;
+      };
+      let orig = module_export_name(&named.orig);
+      let exported = named.exported.as_ref().map(module_export_name).unwrap_or_else(|| orig.clone());
+      match &named_export.src {
+        Some(src) => self.reexports.push(Reexport {
+          source: src.value.to_string(),
+          imported: orig,
+          exported,
+        }),
+        None => {
+          self.local_exports.insert(exported, orig);
+        }
+      }
+    }
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll) {
+    if !export_all.type_only {
+      self.export_all_sources.push(export_all.src.value.to_string());
+    }
+  }
+
+  fn visit_import_decl(&mut self, import: &ImportDecl) {
+    for specifier in &import.specifiers {
+      if let ImportSpecifier::Named(named_spec) = specifier {
+        let local = named_spec.local.sym.to_string();
+        self.named_imports.push(NamedImport {
+          source: import.src.value.to_string(),
+          imported: named_spec.imported.as_ref().map(module_export_name).unwrap_or_else(|| local.clone()),
+          local,
+        });
+      }
+    }
+  }
+}
+
+const RESOLVED_EXTENSIONS: [&str; 8] = ["", ".ts", ".tsx", ".d.ts", ".js", "/index.ts", "/index.tsx", "/index.js"];
+
+/// Path of the file a relative import in `from_path` points at, if it is one of `paths`
+pub fn resolve_relative_import(from_path: &str, source: &str, paths: &[&str]) -> Option<usize> {
+  if !source.starts_with('.') {
+    return None;
+  }
+  let mut segments: Vec<&str> = from_path.split('/').collect();
+  segments.pop();
+  for segment in source.split('/') {
+    match segment {
+      "." | "" => {}
+      ".." => {
+        segments.pop();
+      }
+      segment => segments.push(segment),
+    }
+  }
+  let base = segments.join("/");
+  RESOLVED_EXTENSIONS
+    .iter()
+    .find_map(|extension| paths.iter().position(|path| path.trim_start_matches("./") == format!("{}{}", base, extension)))
+}
+
+/// How many re-exporting modules a chain may pass through before it is given up on
+pub const MAX_REEXPORT_DEPTH: usize = 16;
+
+/// The `source` a module at `from_path` would import the module at `to_path` by, without the extension
+pub fn relative_specifier(from_path: &str, to_path: &str) -> String {
+  let mut from_dir: Vec<&str> = from_path.trim_start_matches("./").split('/').collect();
+  from_dir.pop();
+  let to_path = to_path.trim_start_matches("./");
+  let to_path = [".d.ts", ".tsx", ".ts", ".jsx", ".js"]
+    .iter()
+    .find_map(|extension| to_path.strip_suffix(extension))
+    .unwrap_or(to_path);
+  let to_segments: Vec<&str> = to_path.split('/').collect();
+  let shared = from_dir.iter().zip(&to_segments).take_while(|(from, to)| from == to).count();
+  let up = from_dir.len() - shared;
+  let prefix = if up == 0 { "./".to_string() } else { "../".repeat(up) };
+  format!("{}{}", prefix, to_segments[shared..].join("/"))
+}
+
+/// Modules reached by following the re-exports of the modules `sources` point at, from module
+/// `index`, not counting those modules themselves
+pub fn reexported_modules<'a>(
+  modules: &[(&str, &ExportFinder)],
+  index: usize,
+  sources: impl IntoIterator<Item = &'a str>,
+) -> Vec<usize> {
+  let paths: Vec<&str> = modules.iter().map(|(path, _)| *path).collect();
+  let imported: Vec<usize> = sources
+    .into_iter()
+    .filter_map(|source| resolve_relative_import(paths[index], source, &paths))
+    .collect();
+  let mut visited: HashSet<usize> = imported.iter().copied().collect();
+  visited.insert(index);
+  let mut reached = Vec::new();
+  let mut frontier = imported;
+  for _ in 0..MAX_REEXPORT_DEPTH {
+    let mut next = Vec::new();
+    for module in frontier {
+      let (path, exports) = modules[module];
+      let reexport_sources = exports.reexports.iter().map(|reexport| &reexport.source).chain(&exports.export_all_sources);
+      for source in reexport_sources {
+        if let Some(source_index) = resolve_relative_import(path, source, &paths) {
+          if visited.insert(source_index) {
+            reached.push(source_index);
+            next.push(source_index);
+          }
+        }
+      }
+    }
+    if next.is_empty() {
+      break;
+    }
+    frontier = next;
+  }
+  reached
+}
+
+/// Follows re-exports (`export { a as b } from`, `export * from`) from `name` as exported by
+/// module `index` to the name it is declared under, through at most `MAX_REEXPORT_DEPTH` modules
+fn resolve_export(
+  modules: &[(&str, &ExportFinder)],
+  index: usize,
+  name: &str,
+  depth: usize,
+  visited: &mut HashSet<(usize, String)>,
+) -> Option<String> {
+  if depth > MAX_REEXPORT_DEPTH || !visited.insert((index, name.to_string())) {
+    return None;
+  }
+  let (path, exports) = modules[index];
+  if let Some(declared) = exports.local_exports.get(name) {
+    return Some(declared.clone());
+  }
+  let paths: Vec<&str> = modules.iter().map(|(path, _)| *path).collect();
+  for reexport in exports.reexports.iter().filter(|reexport| reexport.exported == name) {
+    if let Some(source_index) = resolve_relative_import(path, &reexport.source, &paths) {
+      if let Some(declared) = resolve_export(modules, source_index, &reexport.imported, depth + 1, visited) {
+        return Some(declared);
+      }
+    }
+  }
+  exports.export_all_sources.iter().find_map(|source| {
+    let source_index = resolve_relative_import(path, source, &paths)?;
+    resolve_export(modules, source_index, name, depth + 1, visited)
+  })
+}
+
+/// For each module, the imported names that are declared under a different name in the module
+/// they come from, through aliases and re-exporting barrel files: local name -> declared name
+pub fn resolve_import_aliases(modules: &[(&str, &ExportFinder)]) -> Vec<HashMap<String, String>> {
+  let paths: Vec<&str> = modules.iter().map(|(path, _)| *path).collect();
+  modules
+    .iter()
+    .map(|(path, exports)| {
+      exports
+        .named_imports
+        .iter()
+        .filter_map(|import| {
+          let source_index = resolve_relative_import(path, &import.source, &paths)?;
+          let declared = resolve_export(modules, source_index, &import.imported, 0, &mut HashSet::new())?;
+          (declared != import.local).then(|| (import.local.clone(), declared))
+        })
+        .collect()
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_common::{sync::Lrc, FileName, SourceMap};
+  use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+  use swc_ecma_visit::VisitWith;
+
+  fn exports_for(code: &str) -> ExportFinder {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Custom("test.ts".into()), code.into());
+    let lexer = Lexer::new(
+      Syntax::Typescript(TsConfig::default()),
+      Default::default(),
+      StringInput::from(&*fm),
+      None,
+    );
+    let module = Parser::new_from(lexer).parse_module().expect("Failed to parse module");
+    let mut finder = ExportFinder::default();
+    module.visit_with(&mut finder);
+    finder
+  }
+
+  #[test]
+  fn test_collects_exports_and_reexports() {
+    let finder = exports_for(
+      r#"
+      export function fail() {}
+      const helper = () => {};
+      export { helper as assist };
+      export { fail as explode } from './errors';
+      export * from './more';
+      export type { Shape } from './types';
+      "#,
+    );
+    assert_eq!(finder.local_exports.get("fail"), Some(&"fail".to_string()));
+    assert_eq!(finder.local_exports.get("assist"), Some(&"helper".to_string()));
+    assert_eq!(
+      finder.reexports,
+      vec![Reexport {
+        source: "./errors".to_string(),
+        imported: "fail".to_string(),
+        exported: "explode".to_string(),
+      }]
+    );
+    assert_eq!(finder.export_all_sources, vec!["./more".to_string()]);
+  }
+
+  #[test]
+  fn test_resolves_relative_imports() {
+    let paths = ["src/errors.ts", "src/lib/index.ts", "main.ts"];
+    assert_eq!(resolve_relative_import("src/app.ts", "./errors", &paths), Some(0));
+    assert_eq!(resolve_relative_import("src/app.ts", "./lib", &paths), Some(1));
+    assert_eq!(resolve_relative_import("src/lib/index.ts", "../../main", &paths), Some(2));
+    assert_eq!(resolve_relative_import("main.ts", "errors", &paths), None);
+  }
+
+  #[test]
+  fn test_aliases_follow_barrels() {
+    let errors = exports_for("export function failHard() {}\nexport function explode() {}");
+    let barrel = exports_for("export { failHard as fail } from './errors';\nexport * from './errors';");
+    let main = exports_for("import { fail, explode, failHard as hard } from './index';");
+    let modules = [("errors.ts", &errors), ("index.ts", &barrel), ("main.ts", &main)];
+
+    let aliases = resolve_import_aliases(&modules);
+    assert!(aliases[0].is_empty());
+    assert_eq!(aliases[2].get("fail"), Some(&"failHard".to_string()));
+    assert_eq!(aliases[2].get("hard"), Some(&"failHard".to_string()));
+    // Imported under the name it is declared with, so nothing to alias
+    assert_eq!(aliases[2].get("explode"), None);
+  }
+
+  #[test]
+  fn test_relative_specifier() {
+    assert_eq!(relative_specifier("src/app.ts", "src/lib/validate.ts"), "./lib/validate");
+    assert_eq!(relative_specifier("src/lib/index.ts", "main.ts"), "../../main");
+    assert_eq!(relative_specifier("src/a/app.ts", "src/b/types.d.ts"), "../b/types");
+    assert_eq!(relative_specifier("app.ts", "errors.js"), "./errors");
+  }
+
+  #[test]
+  fn test_reexported_modules_follow_chains_once() {
+    let validate = exports_for("export function validate() {}");
+    let more = exports_for("export * from './index';\nexport function explode() {}");
+    let barrel = exports_for("export { validate as checkInput } from './validate';\nexport * from './more';");
+    let main = exports_for("import { checkInput } from './lib';");
+    let modules = [("lib/validate.ts", &validate), ("lib/more.ts", &more), ("lib/index.ts", &barrel), ("main.ts", &main)];
+
+    // `more` re-exports the barrel back; the cycle ends there
+    let mut reached = reexported_modules(&modules, 3, ["./lib"]);
+    reached.sort();
+    assert_eq!(reached, vec![0, 1]);
+    assert_eq!(resolve_import_aliases(&modules)[3].get("checkInput"), Some(&"validate".to_string()));
+  }
+
+  #[test]
+  fn test_reexport_chains_stop_at_max_depth() {
+    let aliases_through = |hops: usize| {
+      // hop0 re-exports `v1` from hop1 as `v0`, and so on down to the hop declaring its `v`
+      let mut sources: Vec<(String, String)> = (0..hops)
+        .map(|hop| (format!("hop{}.ts", hop), format!("export {{ v{} as v{} }} from './hop{}';", hop + 1, hop, hop + 1)))
+        .collect();
+      sources.push((format!("hop{}.ts", hops), format!("export function v{}() {{}}", hops)));
+      sources.push(("main.ts".to_string(), "import { v0 } from './hop0';".to_string()));
+      let exports: Vec<ExportFinder> = sources.iter().map(|(_, code)| exports_for(code)).collect();
+      let modules: Vec<(&str, &ExportFinder)> = sources.iter().map(|(path, _)| path.as_str()).zip(&exports).collect();
+      resolve_import_aliases(&modules)[hops + 1].get("v0").cloned()
+    };
+
+    assert_eq!(aliases_through(MAX_REEXPORT_DEPTH), Some(format!("v{}", MAX_REEXPORT_DEPTH)));
+    assert_eq!(aliases_through(MAX_REEXPORT_DEPTH + 1), None);
+  }
+}
\ No newline at end of file
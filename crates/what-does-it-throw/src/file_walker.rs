@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_EXTENSIONS: [&str; 4] = ["ts", "tsx", "js", "jsx"];
+
+#[derive(Clone, Debug)]
+pub struct WalkOptions {
+  pub extensions: Vec<String>, // Without the leading dot, e.g. ["ts", "tsx"]
+  pub include_hidden: bool,    // Descend into `node_modules` and dot-directories too
+}
+
+impl Default for WalkOptions {
+  fn default() -> Self {
+    Self {
+      extensions: DEFAULT_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+      include_hidden: false,
+    }
+  }
+}
+
+/// Whether a target names a glob (`src/**/*.ts`) rather than a plain file or directory
+pub fn is_glob(target: &str) -> bool {
+  target.contains('*') || target.contains('?')
+}
+
+/// Resolve a file, directory or glob into the sorted list of source files to analyze.
+/// Directories are walked recursively and filtered by extension; globs select files by
+/// pattern alone.
+pub fn collect_source_files(target: &str, options: &WalkOptions) -> Vec<PathBuf> {
+  let mut files = Vec::new();
+  if is_glob(target) {
+    let pattern: Vec<&str> = target.split('/').filter(|part| !part.is_empty() && *part != ".").collect();
+    let literal_prefix: Vec<&str> = pattern.iter().take_while(|part| !is_glob(part)).cloned().collect();
+    let root = if literal_prefix.is_empty() {
+      if target.starts_with('/') { PathBuf::from("/") } else { PathBuf::from(".") }
+    } else {
+      let joined = literal_prefix.join("/");
+      if target.starts_with('/') { PathBuf::from(format!("/{}", joined)) } else { PathBuf::from(joined) }
+    };
+    let mut candidates = Vec::new();
+    walk_directory(&root, options, &mut candidates);
+    for candidate in candidates {
+      let relative = candidate.strip_prefix(&root).unwrap_or(&candidate);
+      let mut components: Vec<String> = literal_prefix.iter().map(|part| part.to_string()).collect();
+      components.extend(relative.components().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+      let components: Vec<&str> = components.iter().map(|c| c.as_str()).collect();
+      if glob_matches(&pattern, &components) {
+        files.push(candidate);
+      }
+    }
+  } else {
+    let path = Path::new(target);
+    if path.is_dir() {
+      walk_directory(path, options, &mut files);
+      files.retain(|file| has_extension(file, &options.extensions));
+    } else {
+      files.push(path.to_path_buf());
+    }
+  }
+  files.sort();
+  files
+}
+
+fn walk_directory(dir: &Path, options: &WalkOptions, files: &mut Vec<PathBuf>) {
+  let entries = match fs::read_dir(dir) {
+    Ok(entries) => entries,
+    Err(_) => return,
+  };
+  for entry in entries.flatten() {
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().into_owned();
+    let file_type = match entry.file_type() {
+      Ok(file_type) => file_type,
+      Err(_) => continue,
+    };
+    if file_type.is_dir() {
+      if !options.include_hidden && (name == "node_modules" || name.starts_with('.')) {
+        continue;
+      }
+      walk_directory(&path, options, files);
+    } else if file_type.is_file() {
+      files.push(path);
+    }
+  }
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+  path
+    .extension()
+    .map(|ext| extensions.iter().any(|wanted| ext.to_string_lossy() == wanted.as_str()))
+    .unwrap_or(false)
+}
+
+/// Match path components against glob components: `**` spans any number of directories,
+/// `*` and `?` match within a single component
+pub(crate) fn glob_matches(pattern: &[&str], path: &[&str]) -> bool {
+  match pattern.split_first() {
+    None => path.is_empty(),
+    Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_matches(rest, &path[skip..])),
+    Some((first, rest)) => match path.split_first() {
+      Some((component, path_rest)) => {
+        wildcard_matches(first.as_bytes(), component.as_bytes()) && glob_matches(rest, path_rest)
+      }
+      None => false,
+    },
+  }
+}
+
+fn wildcard_matches(pattern: &[u8], text: &[u8]) -> bool {
+  match pattern.split_first() {
+    None => text.is_empty(),
+    Some((b'*', rest)) => (0..=text.len()).any(|skip| wildcard_matches(rest, &text[skip..])),
+    Some((b'?', rest)) => !text.is_empty() && wildcard_matches(rest, &text[1..]),
+    Some((c, rest)) => text.first() == Some(c) && wildcard_matches(rest, &text[1..]),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fixture_tree(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("what-does-it-throw-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for dir in ["src/nested", "node_modules/dep", ".cache"].iter() {
+      fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    for file in ["src/a.ts", "src/nested/b.tsx", "src/nested/c.js", "src/readme.md", "node_modules/dep/index.js", ".cache/d.ts"].iter() {
+      fs::write(root.join(file), "export {};").unwrap();
+    }
+    root
+  }
+
+  fn relative_names(root: &Path, files: &[PathBuf]) -> Vec<String> {
+    files
+      .iter()
+      .map(|file| file.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+      .collect()
+  }
+
+  #[test]
+  fn test_directory_walk_skips_node_modules_and_hidden() {
+    let root = fixture_tree("walk");
+    let files = collect_source_files(root.to_str().unwrap(), &WalkOptions::default());
+    assert_eq!(relative_names(&root, &files), vec!["src/a.ts", "src/nested/b.tsx", "src/nested/c.js"]);
+
+    let options = WalkOptions {
+      extensions: vec!["ts".to_string()],
+      include_hidden: true,
+    };
+    let files = collect_source_files(root.to_str().unwrap(), &options);
+    assert_eq!(relative_names(&root, &files), vec![".cache/d.ts", "src/a.ts"]);
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn test_glob_selects_matching_files() {
+    let root = fixture_tree("glob");
+    let pattern = format!("{}/src/**/*.ts*", root.to_str().unwrap());
+    let files = collect_source_files(&pattern, &WalkOptions::default());
+    assert_eq!(relative_names(&root, &files), vec!["src/a.ts", "src/nested/b.tsx"]);
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn test_glob_matching() {
+    assert!(glob_matches(&["src", "**", "*.ts"], &["src", "a.ts"]));
+    assert!(glob_matches(&["src", "**", "*.ts"], &["src", "x", "y", "a.ts"]));
+    assert!(!glob_matches(&["src", "*.ts"], &["src", "x", "a.ts"]));
+    assert!(glob_matches(&["a?c.js"], &["abc.js"]));
+  }
+}
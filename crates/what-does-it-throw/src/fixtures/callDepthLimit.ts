@@ -0,0 +1,43 @@
+class DbError extends Error {}
+
+function query() {
+  throw new DbError('connection lost')
+}
+
+function loadUser() {
+  return query()
+}
+
+function loadProfile() {
+  return loadUser()
+}
+
+export function renderPage() {
+  return loadProfile()
+}
+
+function loadUserSafely() {
+  try {
+    return query()
+  } catch (e) {
+    if (e instanceof DbError) {
+      return null
+    }
+    throw e
+  }
+}
+
+export function renderFallback() {
+  return loadUserSafely()
+}
+
+function validateAndLoad(id) {
+  if (!id) {
+    throw new TypeError('id is required')
+  }
+  return query()
+}
+
+export function handleRequest(id) {
+  return validateAndLoad(id)
+}
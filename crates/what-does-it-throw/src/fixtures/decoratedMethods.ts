@@ -0,0 +1,44 @@
+class NotFoundError extends Error {}
+class ValidationError extends Error {}
+
+function Log() {
+  return (target: any, key: string, descriptor: PropertyDescriptor) => descriptor
+}
+
+function Retry(times: number) {
+  return (target: any, key: string, descriptor: PropertyDescriptor) => descriptor
+}
+
+class UserService {
+  /**
+   * @throws {NotFoundError}
+   */
+  @Log()
+  findUser(id: string) {
+    throw new NotFoundError(id)
+  }
+
+  /**
+   * @throws {ValidationError} when the input is empty
+   */
+  @Log()
+  @Retry(3)
+  async saveUser(name: string) {
+    if (!name) {
+      throw new ValidationError('name is required')
+    }
+  }
+
+  @Log()
+  /**
+   * @throws {NotFoundError}
+   */
+  archiveUser(id: string) {
+    throw new NotFoundError(id)
+  }
+
+  @Log()
+  deleteUser(id: string) {
+    throw new NotFoundError(id)
+  }
+}
@@ -0,0 +1,26 @@
+class ConfigError extends Error {}
+
+function checkInvariants(config) {
+  if (process.env.NODE_ENV !== 'production') {
+    throw new Error('invariants only checked in development')
+  }
+  return config
+}
+
+export function loadConfig(config) {
+  return checkInvariants(config)
+}
+
+function readPort(config) {
+  if (process.env['STRICT'] === 'true') {
+    throw new Error('strict mode')
+  }
+  if (!config.port) {
+    throw new ConfigError('missing port')
+  }
+  return config.port
+}
+
+export function start(config) {
+  return readPort(config)
+}
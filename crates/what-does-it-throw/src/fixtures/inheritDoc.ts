@@ -0,0 +1,42 @@
+class IOError extends Error {}
+class ParseError extends Error {}
+
+class A {
+  /**
+   * @throws {IOError}
+   */
+  doIt() {
+    throw new IOError('disk unavailable')
+  }
+}
+
+class B extends A {
+  /**
+   * @inheritdoc
+   */
+  doIt() {
+    throw new IOError('cache unavailable')
+  }
+}
+
+class C extends A {
+  /**
+   * {@inheritDoc}
+   * @throws {ParseError}
+   */
+  doIt() {
+    if (Math.random() > 0.5) {
+      throw new ParseError('bad header')
+    }
+    throw new IOError('cache unavailable')
+  }
+}
+
+class D {
+  /**
+   * @inheritdoc
+   */
+  doIt() {
+    throw new IOError('no base to inherit from')
+  }
+}
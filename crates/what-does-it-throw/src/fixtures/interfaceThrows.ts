@@ -0,0 +1,37 @@
+class NotFoundError extends Error {}
+class ConflictError extends Error {}
+
+interface Closeable {
+  /**
+   * @throws {Error} When already closed
+   */
+  close(): void;
+}
+
+interface UserStore extends Closeable {
+  /**
+   * @throws {NotFoundError} When no user has the id
+   */
+  find(id: string): string;
+  save(user: string): void;
+}
+
+class MemoryUserStore implements UserStore {
+  find(id: string): string {
+    throw new NotFoundError(id)
+  }
+
+  save(user: string): void {
+    throw new ConflictError(user)
+  }
+
+  close(): void {
+    throw new Error('already closed')
+  }
+}
+
+class UnrelatedStore {
+  find(id: string): string {
+    throw new NotFoundError(id)
+  }
+}
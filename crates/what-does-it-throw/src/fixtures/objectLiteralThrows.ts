@@ -0,0 +1,26 @@
+/**
+ * @throws {TimeoutError} when the upstream service is too slow
+ */
+function callUpstream(url: string) {
+  if (!url) throw { name: 'TimeoutError', code: 504, message: 'upstream timeout' }
+}
+
+function legacyFailure() {
+  throw { code: 500, message: 'something broke' }
+}
+
+export function withRetry(url: string) {
+  try {
+    callUpstream(url)
+  } catch (err) {
+    if (err.name === 'TimeoutError') return null
+  }
+}
+
+export function inlineObjectThrow(input: string) {
+  try {
+    if (!input) throw { name: 'BadRequestError', message: 'input is required' }
+  } catch (err) {
+    return null
+  }
+}
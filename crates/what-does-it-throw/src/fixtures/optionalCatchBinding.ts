@@ -0,0 +1,34 @@
+class ConfigError extends Error {}
+
+function fails() {
+  throw new TypeError('bad input')
+}
+
+export function swallowDirect() {
+  try {
+    throw new Error('boom')
+  } catch {
+    log()
+  }
+}
+
+export function swallowCall(config: unknown) {
+  try {
+    fails()
+  } catch {
+    if (config instanceof ConfigError) log()
+  }
+}
+
+export function rethrowCall() {
+  try {
+    fails()
+  } catch {
+    log()
+    throw new RangeError('wrapped')
+  }
+}
+
+export function callsSwallow() {
+  swallowCall(null)
+}
@@ -0,0 +1,79 @@
+class ValidationError extends Error {}
+class NetworkError extends Error {}
+
+function validate(input: string) {
+  if (!input) throw new ValidationError('input is required')
+}
+
+function fetchRemote(url: string) {
+  if (!url.startsWith('https://')) throw new NetworkError('insecure url')
+}
+
+export function switchTrueDispatch(input: string) {
+  try {
+    validate(input)
+    fetchRemote(input)
+  } catch (e) {
+    switch (true) {
+      case e instanceof ValidationError:
+        return 'invalid'
+      case e instanceof NetworkError:
+        return 'offline'
+    }
+  }
+}
+
+export function switchConstructorDispatch(input: string) {
+  try {
+    validate(input)
+    fetchRemote(input)
+  } catch (err) {
+    switch (err.constructor) {
+      case ValidationError:
+        return 'invalid'
+      case NetworkError:
+        return 'offline'
+    }
+  }
+}
+
+export function constructorEqualityDispatch(input: string) {
+  try {
+    validate(input)
+    fetchRemote(input)
+  } catch (err) {
+    if (err.constructor === ValidationError) return 'invalid'
+    if (NetworkError === err.constructor) return 'offline'
+  }
+}
+
+export function nameEqualityDispatch(input: string) {
+  try {
+    validate(input)
+    fetchRemote(input)
+  } catch (e) {
+    if (e.name === 'ValidationError') {
+      return 'invalid'
+    } else if ('NetworkError' == e.name) {
+      return 'offline'
+    } else {
+      throw e
+    }
+  }
+}
+
+export function switchNameDispatch(input: string) {
+  try {
+    validate(input)
+    fetchRemote(input)
+  } catch (err) {
+    switch (err.name) {
+      case 'ValidationError':
+        return 'invalid'
+      case 'NetworkError':
+        return 'offline'
+      default:
+        throw err
+    }
+  }
+}
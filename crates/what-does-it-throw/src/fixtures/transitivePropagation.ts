@@ -0,0 +1,33 @@
+function c() {
+  throw new TypeError('deep')
+}
+
+function b() {
+  c()
+}
+
+export function a() {
+  b()
+}
+
+function isEven(n: number): boolean {
+  if (n < 0) throw new RangeError('negative')
+  return n === 0 ? true : isOdd(n - 1)
+}
+
+function isOdd(n: number): boolean {
+  if (n > 1000) throw new SyntaxError('too deep')
+  return n === 0 ? false : isEven(n - 1)
+}
+
+export function parity(n: number) {
+  return isEven(n)
+}
+
+export function guarded() {
+  try {
+    a()
+  } catch {
+    return null
+  }
+}
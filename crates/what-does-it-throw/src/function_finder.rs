@@ -6,16 +6,65 @@ use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
 use swc_ecma_ast::{
-  AssignExpr, ClassDecl, ClassMethod, Constructor, Decl,
-  ExportDecl, FnDecl, ObjectLit, PatOrExpr, Prop, PropName, PropOrSpread,
-  VarDeclarator,
+  ArrowExpr, AssignExpr, BlockStmt, BlockStmtOrExpr, ClassDecl, ClassMethod, Constructor, Decl,
+  ExportDecl, FnDecl, Function, MethodKind, ObjectLit, PatOrExpr, Prop, PropName, PropOrSpread, ReturnStmt,
+  TsKeywordTypeKind, TsType, VarDeclarator,
 };
 
 use self::swc_common::{comments::Comments, sync::Lrc, Span, BytePos, Spanned};
 use self::swc_ecma_ast::Expr;
 use self::swc_ecma_visit::Visit;
 
-use crate::throw_finder::ThrowsAnnotation;
+use crate::throw_finder::{
+  class_member_name, class_method_doc_start, comments_since_statement_boundary, default_throws_tag_aliases, is_built_in_error, is_inheritdoc_tag, nothrow_tag, is_wildcard_throws_type,
+  normalize_jsdoc_line, parse_throws_tag, statement_boundaries, throws_tag_text, ThrowDetails, ThrowMap, ThrowsAnnotation,
+  ThrowsTagAliases,
+};
+
+/// Whether a function's return type is annotated `never`, so a call to it can only end by throwing
+fn returns_never(function: &Function) -> bool {
+  match function.return_type.as_deref().map(|return_type| &*return_type.type_ann) {
+    Some(TsType::TsKeywordType(keyword)) => keyword.kind == TsKeywordTypeKind::TsNeverKeyword,
+    _ => false,
+  }
+}
+
+/// `X` for `new X(...)`
+fn constructed_type(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::New(new_expr) => match &*new_expr.callee {
+      Expr::Ident(ident) => Some(ident.sym.to_string()),
+      _ => None,
+    },
+    Expr::Paren(paren) => constructed_type(&paren.expr),
+    _ => None,
+  }
+}
+
+/// Collects what each `return` of a function body constructs, ignoring nested functions
+struct ReturnedTypeFinder {
+  returned_types: Vec<Option<String>>,
+}
+
+impl Visit for ReturnedTypeFinder {
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt) {
+    self.returned_types.push(return_stmt.arg.as_ref().and_then(|arg| constructed_type(arg)));
+  }
+  fn visit_function(&mut self, _function: &Function) {}
+  fn visit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr) {}
+}
+
+/// The error type a factory always returns, e.g. `function makeError() { return new NotFoundError() }`
+fn returned_error_type(body: &BlockStmt) -> Option<String> {
+  let mut finder = ReturnedTypeFinder { returned_types: Vec::new() };
+  finder.visit_block_stmt(body);
+  let first = finder.returned_types.first()?.clone()?;
+  if finder.returned_types.iter().all(|returned| returned.as_deref() == Some(first.as_str())) {
+    Some(first)
+  } else {
+    None
+  }
+}
 
 fn prop_name_to_string(prop_name: &PropName) -> String {
   match prop_name {
@@ -44,6 +93,8 @@ pub struct FunctionMap {
   pub id: String,
   pub throws_annotation: Option<ThrowsAnnotation>,
   pub function_type: FunctionType,
+  pub is_async: bool,
+  pub name_span: Span, // The function's name, where diagnostics about it are anchored
 }
 
 impl PartialEq for FunctionMap {
@@ -73,6 +124,16 @@ pub struct FunctionFinder {
   pub param_throws: std::collections::HashMap<String, Vec<Vec<String>>>,
   // Optional map of callback typedef/callback names -> their @throws annotation types
   pub callback_type_throws: std::collections::HashMap<String, Vec<String>>,
+  // Map of class name -> the class it extends, for inheriting @throws from base methods
+  pub class_parents: std::collections::HashMap<String, String>,
+  // Map of function name -> the error type it always returns, for resolving `throw makeError()`
+  pub error_factories: std::collections::HashMap<String, String>,
+  // Limits how far back a function's @throws is looked for
+  pub statement_boundaries: Vec<BytePos>,
+  // Bodyless declarations (`declare function`, `.d.ts` stubs) standing in for their @throws contract
+  pub ambient_throws: HashSet<ThrowMap>,
+  // JSDoc tags documenting thrown errors, e.g. `throws` and `exception`
+  pub throws_tag_aliases: Vec<String>,
 }
 
 impl FunctionFinder {
@@ -86,6 +147,11 @@ impl FunctionFinder {
       current_method_name: None,
       param_throws: std::collections::HashMap::new(),
       callback_type_throws: std::collections::HashMap::new(),
+      class_parents: std::collections::HashMap::new(),
+      error_factories: std::collections::HashMap::new(),
+      statement_boundaries: Vec::new(),
+      ambient_throws: HashSet::new(),
+      throws_tag_aliases: default_throws_tag_aliases(),
     }
   }
 
@@ -95,77 +161,54 @@ impl FunctionFinder {
     self
   }
 
-  fn extract_throws_annotation(&self, function_span: Span) -> Option<ThrowsAnnotation> {
-    // Strategy 1: Direct leading comments at function start
-    if let Some(comments) = self.comments.get_leading(function_span.lo()) {
-      for comment in comments {
-        if let Some(annotation) = self.parse_throws_comment(&comment.text) {
-          #[cfg(debug_assertions)]
-          eprintln!("   ✅ Found throws annotation in direct leading comment: {:?}", annotation);
-          return Some(annotation);
-        }
-      }
-    }
-    
-    // Strategy 2: Search backwards for comments (to catch variable declaration comments)
-    for offset in 1..100 {
-      let search_pos = if function_span.lo().0 >= offset {
-        function_span.lo() - swc_common::BytePos(offset)
-      } else {
-        swc_common::BytePos(0)
-      };
-      
-      if let Some(comments) = self.comments.get_leading(search_pos) {
-        for comment in comments {
-          if let Some(annotation) = self.parse_throws_comment(&comment.text) {
-            #[cfg(debug_assertions)]
-            eprintln!("   ✅ Found throws annotation in backward search at -{}: {:?}", offset, annotation);
-            return Some(annotation);
-          }
-        }
-      }
-      
-      if search_pos.0 == 0 {
-        break;
-      }
-    }
-
-    #[cfg(debug_assertions)]
-    eprintln!("   ❌ No throws annotation found for function span {:?}", function_span);
-    
-    None
+  fn extract_throws_annotation(&self, start: BytePos) -> Option<ThrowsAnnotation> {
+    comments_since_statement_boundary(&*self.comments, &self.statement_boundaries, start)
+      .iter()
+      .find_map(|comment| self.parse_throws_comment(&comment.text))
   }
 
   fn parse_throws_comment(&self, comment_text: &str) -> Option<ThrowsAnnotation> {
     let text = comment_text.trim();
     let mut error_types: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut descriptions: Vec<(String, String)> = Vec::new();
+    let mut no_throw = None; // Set by @nothrow and its aliases
+    let mut inherit_doc = false; // Set by @inheritdoc
 
     let lines: Vec<&str> = text.lines()
-      .map(|line| line.trim().trim_start_matches('*').trim())
+      .map(normalize_jsdoc_line)
       .collect();
 
     for line in &lines {
-      if line.to_lowercase().contains("@throws") {
-        if let Some(throws_pos) = line.to_lowercase().find("@throws") {
-          let after_throws = &line[throws_pos + 7..].trim();
-
-          // Only handle @throws {Type} syntax
-          if let Some(start_brace) = after_throws.find('{') {
-            if let Some(end_brace) = after_throws.find('}') {
-              let type_name = &after_throws[start_brace + 1..end_brace].trim();
-              if !type_name.is_empty() {
-                error_types.insert(type_name.to_string());
-              }
+      if is_inheritdoc_tag(line) {
+        inherit_doc = true;
+      }
+      if let Some(tag) = nothrow_tag(line) {
+        no_throw = Some(tag.to_string());
+        continue;
+      }
+      if let Some(after_tag) = throws_tag_text(line, &self.throws_tag_aliases) {
+        // Only handle @throws {Type} syntax
+        if after_tag.contains('{') {
+          let (types, description) = parse_throws_tag(after_tag);
+          for error_type in types {
+            if let Some(description) = &description {
+              descriptions.push((error_type.clone(), description.clone()));
             }
+            error_types.insert(error_type);
           }
         }
       }
     }
 
-    if !error_types.is_empty() {
+    if !error_types.is_empty() || no_throw.is_some() || inherit_doc {
+      let covers_all = error_types.iter().any(|t| is_wildcard_throws_type(t));
       Some(ThrowsAnnotation { 
         error_types: error_types.into_iter().collect(),
-        is_documented: true 
+        is_documented: true,
+        covers_all,
+        no_throw,
+        inherit_doc,
+        descriptions,
       })
     } else {
       None
@@ -213,12 +256,12 @@ impl FunctionFinder {
     if let Some(comments) = self.comments.get_leading(function_span.lo()) {
       for comment in comments {
         let text = comment.text.trim();
-        let lines: Vec<&str> = text.lines().map(|l| l.trim().trim_start_matches('*').trim()).collect();
+        let lines: Vec<&str> = text.lines().map(normalize_jsdoc_line).collect();
         for line in &lines {
           // Match lines like: @param {TypeName} paramName - desc
           if line.starts_with("@param") {
             // Find type in braces
-            let type_name = if let Some(start) = line.find('{') { if let Some(end) = line[start+1..].find('}') { Some(line[start+1..start+1+end].trim().to_string()) } else { None } } else { None };
+            let type_name = if let Some(start) = line.find('{') { line[start+1..].find('}').map(|end| line[start+1..start+1+end].trim().to_string()) } else { None };
             if let Some(type_name) = type_name {
               // Extract the parameter name following the type
               let after_brace = &line.split('}').nth(1).unwrap_or("").trim();
@@ -240,13 +283,46 @@ impl FunctionFinder {
     result
   }
 
+  fn record_class_parent(&mut self, class_decl: &ClassDecl) {
+    if let Some(super_class) = &class_decl.class.super_class {
+      if let Expr::Ident(ident) = &**super_class {
+        self.class_parents.insert(class_decl.ident.sym.to_string(), ident.sym.to_string());
+      }
+    }
+  }
+
+  fn record_error_factory(&mut self, name: &str, error_type: Option<String>) {
+    // Only top-level functions can be referenced by a bare call
+    if self.current_class_name.is_some() {
+      return;
+    }
+    if let Some(error_type) = error_type {
+      self.error_factories.insert(name.to_string(), error_type);
+    }
+  }
+
   fn register_function(
     &mut self,
     span: Span,
     name: String,
+    name_span: Span,
     function_type: FunctionType,
+    is_async: bool,
   ) {
-    let throws_annotation = self.extract_throws_annotation(span);
+    self.register_function_documented_at(span, span.lo(), name, name_span, function_type, is_async);
+  }
+
+  /// `register_function` for a function whose JSDoc search starts at `doc_start`, like a decorated method
+  fn register_function_documented_at(
+    &mut self,
+    span: Span,
+    doc_start: BytePos,
+    name: String,
+    name_span: Span,
+    function_type: FunctionType,
+    is_async: bool,
+  ) {
+    let throws_annotation = self.extract_throws_annotation(doc_start);
     
     let function_map = FunctionMap {
       span,
@@ -259,16 +335,89 @@ impl FunctionFinder {
       ),
       throws_annotation,
       function_type,
+      is_async,
+      name_span,
     };
 
     #[cfg(debug_assertions)]
-    eprintln!("🔍 Registered function: {} (id: {}, type: {:?})", name, function_map.id, function_map.function_type);
+    debug_log!("Registered function: {} (id: {}, type: {:?})", name, function_map.id, function_map.function_type);
 
     self.functions.insert(function_map);
   }
+
+  /// A declaration without a body has no throw statements to find, so its `@throws` types are
+  /// recorded as the throws callers must handle. A `never` return type without `@throws` types
+  /// stands for an `Error`.
+  fn record_ambient_throws(&mut self, span: Span, name: &str, name_span: Span, is_async: bool, returns_never: bool) {
+    let annotation = match self.extract_throws_annotation(span.lo()) {
+      Some(annotation) if annotation.no_throw.is_some() => return,
+      Some(annotation) if !(returns_never && annotation.error_types.is_empty()) => annotation,
+      Some(annotation) => ThrowsAnnotation { error_types: vec!["Error".to_string()], ..annotation },
+      None if returns_never => ThrowsAnnotation {
+        error_types: vec!["Error".to_string()],
+        is_documented: true,
+        covers_all: false,
+        no_throw: None,
+        inherit_doc: false,
+        descriptions: Vec::new(),
+      },
+      None => return,
+    };
+    let mut error_types = annotation.error_types.clone();
+    error_types.sort();
+    let throw_details: Vec<ThrowDetails> = error_types
+      .iter()
+      .map(|error_type| ThrowDetails {
+        error_type: Some(error_type.clone()).filter(|t| !is_wildcard_throws_type(t)),
+        is_custom_error: !is_wildcard_throws_type(error_type) && !is_built_in_error(error_type),
+        ..Default::default()
+      })
+      .collect();
+    self.ambient_throws.insert(ThrowMap {
+      throw_spans: vec![span; throw_details.len()],
+      throw_statement: span,
+      function_span: span,
+      function_or_method_name: name.to_string(),
+      class_name: self.current_class_name.clone(),
+      id: format!("{}-{}", self.current_class_name.clone().unwrap_or_else(|| "NOT_SET".to_string()), name),
+      throw_details,
+      throws_annotation: Some(annotation),
+      is_async,
+      name_span: Some(name_span),
+      accessor_kind: None,
+    });
+  }
+
+  /// Ambient `@throws` contracts for functions no visited module implements. A real body
+  /// declared under the same name wins, since its throws are analyzed directly.
+  pub fn unimplemented_ambient_throws(&self) -> Vec<ThrowMap> {
+    let ambient_spans: HashSet<Span> = self.ambient_throws.iter().map(|throw_map| throw_map.throw_statement).collect();
+    self
+      .ambient_throws
+      .iter()
+      .filter(|throw_map| {
+        !self
+          .functions
+          .iter()
+          .any(|function| function.id == throw_map.id && !ambient_spans.contains(&function.span))
+      })
+      .cloned()
+      .collect()
+  }
+}
+
+impl ThrowsTagAliases for FunctionFinder {
+  fn throws_tag_aliases_mut(&mut self) -> &mut Vec<String> {
+    &mut self.throws_tag_aliases
+  }
 }
 
 impl Visit for FunctionFinder {
+  fn visit_module(&mut self, module: &swc_ecma_ast::Module) {
+    self.statement_boundaries = statement_boundaries(module);
+    swc_ecma_visit::visit_module(self, module);
+  }
+
   fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
     let function_name = fn_decl.ident.sym.to_string();
     self.function_name_stack.push(function_name.clone());
@@ -283,7 +432,18 @@ impl Visit for FunctionFinder {
       }
     }
 
-    self.register_function(fn_decl.function.span, function_name, FunctionType::Declaration);
+    let error_type = fn_decl.function.body.as_ref().and_then(returned_error_type);
+    self.record_error_factory(&function_name, error_type);
+    if fn_decl.function.body.is_none() {
+      self.record_ambient_throws(
+        fn_decl.function.span,
+        &function_name,
+        fn_decl.ident.span,
+        fn_decl.function.is_async,
+        returns_never(&fn_decl.function),
+      );
+    }
+    self.register_function(fn_decl.function.span, function_name, fn_decl.ident.span, FunctionType::Declaration, fn_decl.function.is_async);
 
     swc_ecma_visit::visit_fn_decl(self, fn_decl);
     self.function_name_stack.pop();
@@ -310,7 +470,9 @@ impl Visit for FunctionFinder {
                 self.param_throws.insert(function_id, per_param);
               }
             }
-            self.register_function(fn_expr.function.span, function_name, FunctionType::Declaration);
+            let error_type = fn_expr.function.body.as_ref().and_then(returned_error_type);
+            self.record_error_factory(&function_name, error_type);
+            self.register_function(fn_expr.function.span, function_name, ident.id.span, FunctionType::Declaration, fn_expr.function.is_async);
             self.visit_function(&fn_expr.function);
             self.function_name_stack.pop();
             // Don't call default visitor as we handled the function
@@ -331,7 +493,12 @@ impl Visit for FunctionFinder {
                 self.param_throws.insert(function_id, per_param);
               }
             }
-            self.register_function(arrow_expr.span, function_name, FunctionType::Arrow);
+            let error_type = match &*arrow_expr.body {
+              BlockStmtOrExpr::BlockStmt(body) => returned_error_type(body),
+              BlockStmtOrExpr::Expr(expr) => constructed_type(expr),
+            };
+            self.record_error_factory(&function_name, error_type);
+            self.register_function(arrow_expr.span, function_name, ident.id.span, FunctionType::Arrow, arrow_expr.is_async);
             // Capture per-parameter @throws annotations for callbacks
             let mut per_param: Vec<Vec<String>> = Vec::new();
             for param in &arrow_expr.params {
@@ -372,25 +539,21 @@ impl Visit for FunctionFinder {
     let function_name = match &assign_expr.left {
       PatOrExpr::Expr(expr) => {
         if let Expr::Ident(ident) = &**expr {
-          Some(ident.sym.to_string())
+          Some((ident.sym.to_string(), ident.span))
         } else {
           None
         }
       }
       PatOrExpr::Pat(pat) => {
-        if let Some(ident) = pat.as_ident() {
-          Some(ident.sym.to_string())
-        } else {
-          None
-        }
+        pat.as_ident().map(|ident| (ident.sym.to_string(), ident.id.span))
       }
     };
 
-    if let Some(function_name) = function_name {
+    if let Some((function_name, name_span)) = function_name {
       match &*assign_expr.right {
         Expr::Fn(fn_expr) => {
           self.function_name_stack.push(function_name.clone());
-          self.register_function(fn_expr.function.span, function_name, FunctionType::Declaration);
+          self.register_function(fn_expr.function.span, function_name, name_span, FunctionType::Declaration, fn_expr.function.is_async);
           self.visit_function(&fn_expr.function);
           self.function_name_stack.pop();
           // Don't call default visitor for function expressions as we handled them
@@ -399,7 +562,7 @@ impl Visit for FunctionFinder {
         }
         Expr::Arrow(arrow_expr) => {
           self.function_name_stack.push(function_name.clone());
-          self.register_function(arrow_expr.span, function_name, FunctionType::Arrow);
+          self.register_function(arrow_expr.span, function_name, name_span, FunctionType::Arrow, arrow_expr.is_async);
           self.visit_arrow_expr(arrow_expr);
           self.function_name_stack.pop();
           // Don't call default visitor for arrow expressions as we handled them
@@ -419,13 +582,11 @@ impl Visit for FunctionFinder {
         PropOrSpread::Prop(prop) => {
           match &**prop {
             Prop::Method(method_prop) => {
-              if let Some(method_name) = &method_prop.key.as_ident() {
-                let method_name = method_name.sym.to_string();
-                self.function_name_stack.push(method_name.clone());
-                self.register_function(method_prop.function.span, method_name, FunctionType::ObjectMethod);
-                self.visit_function(&method_prop.function);
-                self.function_name_stack.pop();
-              }
+              let method_name = prop_name_to_string(&method_prop.key);
+              self.function_name_stack.push(method_name.clone());
+              self.register_function(method_prop.function.span, method_name, method_prop.key.span(), FunctionType::ObjectMethod, method_prop.function.is_async);
+              self.visit_function(&method_prop.function);
+              self.function_name_stack.pop();
             }
             Prop::KeyValue(key_value_prop) => {
               let property_name = prop_name_to_string(&key_value_prop.key);
@@ -433,13 +594,13 @@ impl Visit for FunctionFinder {
               match &*key_value_prop.value {
                 Expr::Fn(fn_expr) => {
                   self.function_name_stack.push(property_name.clone());
-                  self.register_function(fn_expr.function.span, property_name, FunctionType::ObjectProperty);
+                  self.register_function(fn_expr.function.span, property_name, key_value_prop.key.span(), FunctionType::ObjectProperty, fn_expr.function.is_async);
                   self.visit_function(&fn_expr.function);
                   self.function_name_stack.pop();
                 }
                 Expr::Arrow(arrow_expr) => {
                   self.function_name_stack.push(property_name.clone());
-                  self.register_function(arrow_expr.span, property_name, FunctionType::ObjectProperty);
+                  self.register_function(arrow_expr.span, property_name, key_value_prop.key.span(), FunctionType::ObjectProperty, arrow_expr.is_async);
                   self.visit_arrow_expr(arrow_expr);
                   self.function_name_stack.pop();
                 }
@@ -450,9 +611,9 @@ impl Visit for FunctionFinder {
               }
             }
             Prop::Getter(getter_prop) => {
-              let getter_name = prop_name_to_string(&getter_prop.key);
+              let getter_name = class_member_name(MethodKind::Getter, &prop_name_to_string(&getter_prop.key));
               self.function_name_stack.push(getter_name.clone());
-              self.register_function(getter_prop.span, getter_name, FunctionType::ObjectMethod);
+              self.register_function(getter_prop.span, getter_name, getter_prop.key.span(), FunctionType::ObjectMethod, false);
               
               if let Some(body) = &getter_prop.body {
                 for stmt in &body.stmts {
@@ -462,9 +623,9 @@ impl Visit for FunctionFinder {
               self.function_name_stack.pop();
             }
             Prop::Setter(setter_prop) => {
-              let setter_name = prop_name_to_string(&setter_prop.key);
+              let setter_name = class_member_name(MethodKind::Setter, &prop_name_to_string(&setter_prop.key));
               self.function_name_stack.push(setter_name.clone());
-              self.register_function(setter_prop.span, setter_name, FunctionType::ObjectMethod);
+              self.register_function(setter_prop.span, setter_name, setter_prop.key.span(), FunctionType::ObjectMethod, false);
               
               if let Some(body) = &setter_prop.body {
                 for stmt in &body.stmts {
@@ -490,22 +651,30 @@ impl Visit for FunctionFinder {
 
   fn visit_constructor(&mut self, constructor: &Constructor) {
     self.current_method_name = Some("<constructor>".to_string());
-    self.register_function(constructor.span, "<constructor>".to_string(), FunctionType::Constructor);
+    self.register_function(constructor.span, "<constructor>".to_string(), constructor.key.span(), FunctionType::Constructor, false);
     swc_ecma_visit::visit_constructor(self, constructor);
     self.current_method_name = None;
   }
 
   fn visit_class_method(&mut self, class_method: &ClassMethod) {
     if let Some(method_name) = &class_method.key.as_ident() {
-      let method_name = method_name.sym.to_string();
+      let method_name = class_member_name(class_method.kind, &method_name.sym);
       self.function_name_stack.push(method_name.clone());
-      self.register_function(class_method.span, method_name, FunctionType::Method);
+      self.register_function_documented_at(
+        class_method.span,
+        class_method_doc_start(class_method),
+        method_name,
+        class_method.key.span(),
+        FunctionType::Method,
+        class_method.function.is_async,
+      );
       self.function_name_stack.pop();
     }
     swc_ecma_visit::visit_class_method(self, class_method);
   }
 
   fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+    self.record_class_parent(class_decl);
     self.class_name_stack.push(self.current_class_name.clone());
     self.current_class_name = Some(class_decl.ident.sym.to_string());
     self.visit_class(&class_decl.class);
@@ -516,6 +685,7 @@ impl Visit for FunctionFinder {
     match &export_decl.decl {
       Decl::Class(class_decl) => {
         let class_name = class_decl.ident.sym.to_string();
+        self.record_class_parent(class_decl);
         
         self.class_name_stack.push(self.current_class_name.clone());
         self.current_class_name = Some(class_name);
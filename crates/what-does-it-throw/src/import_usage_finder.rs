@@ -5,9 +5,9 @@ extern crate swc_ecma_visit;
 
 use std::collections::HashSet;
 
-use swc_ecma_ast::Callee;
+use swc_ecma_ast::{AssignExpr, AssignOp, Callee, MemberExpr, MethodKind, Pat, PatOrExpr};
 
-use crate::throw_finder::IdentifierUsage;
+use crate::throw_finder::{class_member_name, IdentifierUsage};
 
 use self::swc_ecma_ast::{CallExpr, Expr, MemberProp};
 
@@ -21,7 +21,58 @@ pub struct ImportUsageFinder {
   pub function_name_stack: Vec<String>,
 }
 
+impl ImportUsageFinder {
+  /// Record a read or write of `imported.prop` under the id its getter or setter is exported with,
+  /// e.g. `config-get value`, so it is flagged when that accessor throws
+  fn record_accessor_usage(&mut self, member_expr: &MemberExpr, kind: MethodKind) {
+    let (Expr::Ident(object_ident), MemberProp::Ident(prop_ident)) = (&*member_expr.obj, &member_expr.prop) else {
+      return;
+    };
+    let object_name = object_ident.sym.to_string();
+    if !self.imported_identifiers.contains(&object_name) {
+      return;
+    }
+    let accessor_name = class_member_name(kind, &prop_ident.sym);
+    self.imported_identifier_usages.insert(IdentifierUsage {
+      is_accessor: true,
+      ..IdentifierUsage::new(
+        member_expr.span,
+        object_name.clone(),
+        accessor_name.clone(),
+        format!("{}-{}", object_name, accessor_name),
+        format!("{}.{}", object_name, prop_ident.sym),
+      )
+    });
+  }
+}
+
 impl Visit for ImportUsageFinder {
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr) {
+    self.record_accessor_usage(member_expr, MethodKind::Getter);
+    swc_ecma_visit::visit_member_expr(self, member_expr);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => expr.as_member(),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => expr.as_member(),
+        _ => None,
+      },
+    };
+    if let Some(member_expr) = target {
+      // Writing through a setter; compound assignments (`+=`, ...) also read through the getter
+      self.record_accessor_usage(member_expr, MethodKind::Setter);
+      if assign_expr.op != AssignOp::Assign {
+        self.record_accessor_usage(member_expr, MethodKind::Getter);
+      }
+      self.visit_expr(&member_expr.obj);
+      self.visit_expr(&assign_expr.right);
+      return;
+    }
+    swc_ecma_visit::visit_assign_expr(self, assign_expr);
+  }
+
   fn visit_call_expr(&mut self, call: &CallExpr) {
     if let Callee::Expr(expr) = &call.callee {
       match &**expr {
@@ -54,6 +105,7 @@ impl Visit for ImportUsageFinder {
                 current_class_name.clone(),
                 usage_context.clone(),
                 id.clone(),
+                format!("{}.{}", current_class_name, current_method_name),
               );
               self.imported_identifier_usages.insert(usage_map);
             }
@@ -98,6 +150,7 @@ impl Visit for ImportUsageFinder {
               called_function_name.clone(),
               usage_context.clone(),
               id.clone(),
+              called_function_name.clone(),
             );
             self.imported_identifier_usages.insert(usage_map);
           }
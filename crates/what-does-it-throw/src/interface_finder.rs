@@ -0,0 +1,209 @@
+extern crate swc_common;
+extern crate swc_ecma_ast;
+extern crate swc_ecma_visit;
+
+use std::collections::HashMap;
+use swc_ecma_ast::{Class, ClassDecl, Expr, ExportDefaultDecl, DefaultDecl, TsExprWithTypeArgs, TsInterfaceDecl, TsTypeElement};
+use self::swc_common::{comments::Comments, sync::Lrc, BytePos};
+use self::swc_ecma_visit::Visit;
+
+use crate::throw_finder::{
+  default_throws_tag_aliases, is_wildcard_throws_type, normalize_jsdoc_line, nothrow_tag, parse_throws_tag,
+  throws_tag_text, ThrowsAnnotation, ThrowsTagAliases,
+};
+
+/// Finder for @throws JSDoc annotations on interface method signatures
+///
+/// Records what each interface documents its methods to throw, and which interfaces each class
+/// implements, so an implementing method is documented without repeating the tag.
+///
+/// Example usage:
+/// ```typescript
+/// interface UserStore {
+///   /** @throws {NotFoundError} When no user has the id */
+///   find(id: string): User;
+/// }
+///
+/// class DbUserStore implements UserStore {
+///   find(id: string): User {
+///     throw new NotFoundError(id); // Documented by UserStore.find
+///   }
+/// }
+/// ```
+pub struct InterfaceFinder {
+  pub comments: Lrc<dyn Comments>,
+  pub method_throws: HashMap<(String, String), ThrowsAnnotation>, // (interface, method) -> the method's @throws
+  pub class_interfaces: HashMap<String, Vec<String>>, // `class X implements I, J` as X -> [I, J]
+  pub interface_parents: HashMap<String, Vec<String>>, // `interface I extends J` as I -> [J]
+  pub throws_tag_aliases: Vec<String>, // JSDoc tags documenting thrown errors, e.g. `throws`
+}
+
+impl InterfaceFinder {
+  pub fn new(comments: Lrc<dyn Comments>) -> Self {
+    Self {
+      comments,
+      method_throws: HashMap::new(),
+      class_interfaces: HashMap::new(),
+      interface_parents: HashMap::new(),
+      throws_tag_aliases: default_throws_tag_aliases(),
+    }
+  }
+
+  fn extract_throws_annotation(&self, start: BytePos) -> Option<ThrowsAnnotation> {
+    self
+      .comments
+      .get_leading(start)?
+      .iter()
+      .rev()
+      .find_map(|comment| self.parse_throws_comment(&comment.text))
+  }
+
+  fn parse_throws_comment(&self, comment_text: &str) -> Option<ThrowsAnnotation> {
+    let mut error_types: Vec<String> = Vec::new();
+    let mut descriptions: Vec<(String, String)> = Vec::new();
+    let mut no_throw = None;
+
+    for line in comment_text.trim().lines().map(normalize_jsdoc_line) {
+      if let Some(tag) = nothrow_tag(line) {
+        no_throw = Some(tag.to_string());
+        continue;
+      }
+      if let Some(after_tag) = throws_tag_text(line, &self.throws_tag_aliases) {
+        if !after_tag.contains('{') {
+          continue;
+        }
+        let (types, description) = parse_throws_tag(after_tag);
+        for error_type in types {
+          if let Some(description) = &description {
+            descriptions.push((error_type.clone(), description.clone()));
+          }
+          if !error_types.contains(&error_type) {
+            error_types.push(error_type);
+          }
+        }
+      }
+    }
+
+    if error_types.is_empty() && no_throw.is_none() {
+      return None;
+    }
+    Some(ThrowsAnnotation {
+      covers_all: error_types.iter().any(|error_type| is_wildcard_throws_type(error_type)),
+      error_types,
+      is_documented: true,
+      no_throw,
+      inherit_doc: false,
+      descriptions,
+    })
+  }
+
+  fn record_implements(&mut self, class_name: String, class: &Class) {
+    let interfaces = type_names(&class.implements);
+    if !interfaces.is_empty() {
+      self.class_interfaces.insert(class_name, interfaces);
+    }
+  }
+}
+
+/// The names of the interfaces in an `implements` or `extends` list, e.g. `UserStore` of `UserStore<User>`
+fn type_names(types: &[TsExprWithTypeArgs]) -> Vec<String> {
+  types
+    .iter()
+    .filter_map(|type_with_args| match &*type_with_args.expr {
+      Expr::Ident(ident) => Some(ident.sym.to_string()),
+      _ => None,
+    })
+    .collect()
+}
+
+impl ThrowsTagAliases for InterfaceFinder {
+  fn throws_tag_aliases_mut(&mut self) -> &mut Vec<String> {
+    &mut self.throws_tag_aliases
+  }
+}
+
+impl Visit for InterfaceFinder {
+  fn visit_ts_interface_decl(&mut self, interface: &TsInterfaceDecl) {
+    let interface_name = interface.id.sym.to_string();
+    for element in &interface.body.body {
+      let TsTypeElement::TsMethodSignature(method) = element else {
+        continue;
+      };
+      let Expr::Ident(key) = &*method.key else {
+        continue;
+      };
+      if let Some(annotation) = self.extract_throws_annotation(method.span.lo) {
+        self.method_throws.insert((interface_name.clone(), key.sym.to_string()), annotation);
+      }
+    }
+    let parents = type_names(&interface.extends);
+    if !parents.is_empty() {
+      self.interface_parents.insert(interface_name, parents);
+    }
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+    self.record_implements(class_decl.ident.sym.to_string(), &class_decl.class);
+    swc_ecma_visit::visit_class_decl(self, class_decl);
+  }
+
+  fn visit_export_default_decl(&mut self, export: &ExportDefaultDecl) {
+    if let DefaultDecl::Class(class_expr) = &export.decl {
+      if let Some(ident) = &class_expr.ident {
+        self.record_implements(ident.sym.to_string(), &class_expr.class);
+      }
+    }
+    swc_ecma_visit::visit_export_default_decl(self, export);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use swc_common::{comments::SingleThreadedComments, FileName, SourceMap};
+  use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+
+  fn find_interfaces(code: &str) -> InterfaceFinder {
+    let cm: Lrc<SourceMap> = Default::default();
+    let comments: Lrc<dyn Comments> = Lrc::new(SingleThreadedComments::default());
+    let fm = cm.new_source_file(FileName::Custom("test.ts".into()), code.into());
+    let lexer = Lexer::new(
+      Syntax::Typescript(TsConfig::default()),
+      Default::default(),
+      StringInput::from(&*fm),
+      Some(&comments),
+    );
+    let module = Parser::new_from(lexer).parse_module().expect("test code should parse");
+    let mut finder = InterfaceFinder::new(comments);
+    finder.visit_module(&module);
+    finder
+  }
+
+  #[test]
+  fn test_interface_method_throws_and_implementing_classes() {
+    let finder = find_interfaces(
+      r#"
+      interface Store<T> extends Closeable {
+        /**
+         * Looks a record up
+         * @throws {NotFoundError} When nothing has the id
+         */
+        find(id: string): T;
+        // Not JSDoc about throwing
+        save(record: T): void;
+      }
+
+      export class DbStore implements Store<User>, Disposable {}
+      export default class MemoryStore implements Store<User> {}
+      "#,
+    );
+
+    let find = &finder.method_throws[&("Store".to_string(), "find".to_string())];
+    assert_eq!(find.error_types, vec!["NotFoundError".to_string()]);
+    assert_eq!(find.description_for("NotFoundError"), Some("When nothing has the id"));
+    assert_eq!(finder.method_throws.len(), 1);
+    assert_eq!(finder.class_interfaces["DbStore"], vec!["Store".to_string(), "Disposable".to_string()]);
+    assert_eq!(finder.class_interfaces["MemoryStore"], vec!["Store".to_string()]);
+    assert_eq!(finder.interface_parents["Store"], vec!["Closeable".to_string()]);
+  }
+}
@@ -1,78 +1,147 @@
+#[macro_use]
+mod debug_log;
 pub mod call_finder;
 pub mod import_usage_finder;
+pub mod export_finder;
 pub mod throw_finder;
 pub mod function_finder;
 pub mod try_catch_finder;
 pub mod callback_finder;
 pub mod typedef_finder;
 pub mod param_finder;
-use call_finder::{CallFinder, CallToThrowMap};
+pub mod interface_finder;
+pub mod file_walker;
+pub mod report;
+pub mod diagnostics;
+pub use report::{diff_reports, resolve_analysis, ResolvedReport};
+use call_finder::{CallEdge, CallFinder, CallToThrowMap};
 use import_usage_finder::ImportUsageFinder;
 use function_finder::{FunctionFinder, FunctionMap};
 use callback_finder::CallbackFinder;
 use typedef_finder::TypedefFinder;
 use param_finder::ParamFinder;
+use interface_finder::InterfaceFinder;
 use swc_common::comments::{SingleThreadedComments, Comments};
 use swc_common::Spanned;
-use throw_finder::{IdentifierUsage, ThrowAnalyzer, ThrowMap, ThrowFinderSettings, TypeRegistry};
+use throw_finder::{
+  assertion_config, builtin_thrower, constructed_error_type, ignore_comment_error_types, is_built_in_error, is_env_guard_test, next_line_comments, object_literal_error_fields, IdentifierUsage, NextLineComment, ThrowAnalyzer, ThrowDetails, ThrowMap, ThrowFinderSettings,
+  ThrowsTagAliases, TypeRegistry, ERROR_FACTORY_CALL_PREFIX,
+};
+pub use throw_finder::{
+  default_assertion_functions, default_terminator_functions, default_throws_tag_aliases, BuiltinThrowCall, EnvGuardedThrows,
+  AssertionConfig, ErrorFactoryConfig, DEFAULT_TERMINATOR_FUNCTIONS, DEFAULT_THROWS_TAG_ALIASES,
+};
 use try_catch_finder::{TryCatchFinder, CatchAnalysis};
 extern crate swc_common;
 extern crate swc_ecma_ast;
 extern crate swc_ecma_parser;
 extern crate swc_ecma_visit;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
 
 use std::vec;
 
-use self::swc_common::{sync::Lrc, SourceMap};
-use self::swc_ecma_ast::EsVersion;
+use self::swc_common::{sync::Lrc, SourceFile, SourceMap};
+use self::swc_ecma_ast::{EsVersion, Module};
 use self::swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
 use self::swc_ecma_visit::{Visit, VisitWith};
-use swc_common::Span;
+use swc_common::{BytePos, Span};
 
-/// Visitor to analyze function calls and direct throws within a specific try block
-struct TryBlockCallAnalyzer {
+/// Function calls and direct throws inside one try block, not counting nested try statements
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TryBlockContents {
   function_calls: Vec<String>,
-  direct_throws: Vec<String>, // Track direct throw statements
+  direct_throws: Vec<String>,
 }
 
-impl TryBlockCallAnalyzer {
-  fn new() -> Self {
+impl TryBlockContents {
+  fn add_direct_throw(&mut self, error_type: String) {
+    if !self.direct_throws.contains(&error_type) {
+      self.direct_throws.push(error_type);
+    }
+  }
+}
+
+/// Collects the contents of every try block in a single pass over the module, keyed by the
+/// block's span, so catch analysis doesn't re-walk the module for each try statement
+struct TryBlockIndex<'a> {
+  blocks: HashMap<(BytePos, BytePos), TryBlockContents>,
+  open_blocks: Vec<Option<TryBlockContents>>, // None while in a nested try's catch or finally
+  skip_env_guarded_throws: bool, // Ignore throws behind `process.env` checks, per `EnvGuardedThrows::Exclude`
+  in_env_guard: bool,
+  assertion_functions: &'a [AssertionConfig], // Calls to these throw from the try block like a `throw` does
+  flag_json_parse: bool, // Whether `JSON.parse` throws from the try block, per `UserSettings::flag_json_parse`
+}
+
+impl<'a> TryBlockIndex<'a> {
+  fn new(skip_env_guarded_throws: bool, assertion_functions: &'a [AssertionConfig], flag_json_parse: bool) -> Self {
     Self {
-      function_calls: Vec::new(),
-      direct_throws: Vec::new(),
+      blocks: HashMap::new(),
+      open_blocks: Vec::new(),
+      skip_env_guarded_throws,
+      in_env_guard: false,
+      assertion_functions,
+      flag_json_parse,
     }
   }
+
+  fn get(&self, try_span: &Span) -> Option<&TryBlockContents> {
+    self.blocks.get(&(try_span.lo(), try_span.hi()))
+  }
+
+  fn current_block(&mut self) -> Option<&mut TryBlockContents> {
+    self.open_blocks.last_mut().and_then(Option::as_mut)
+  }
 }
 
-impl Visit for TryBlockCallAnalyzer {
+impl Visit for TryBlockIndex<'_> {
   fn visit_call_expr(&mut self, call_expr: &swc_ecma_ast::CallExpr) {
-    // Extract function name from call expression
-    if let swc_ecma_ast::Callee::Expr(expr) = &call_expr.callee {
-      if let swc_ecma_ast::Expr::Ident(ident) = &**expr {
-        self.function_calls.push(ident.sym.to_string());
+    let assertion = assertion_config(call_expr, self.assertion_functions).filter(|_| !self.in_env_guard);
+    let builtin = builtin_thrower(call_expr, self.flag_json_parse);
+    if let Some(contents) = self.current_block() {
+      // Extract function name from call expression
+      if let swc_ecma_ast::Callee::Expr(expr) = &call_expr.callee {
+        if let swc_ecma_ast::Expr::Ident(ident) = &**expr {
+          contents.function_calls.push(ident.sym.to_string());
+        }
+      }
+      // Builtins like `JSON.parse` throw from inside the try block just like a direct throw
+      if let Some((_, error_type)) = builtin {
+        contents.add_direct_throw(error_type.to_string());
+      }
+      if let Some(assertion) = assertion {
+        contents.add_direct_throw(assertion.error_type.clone());
       }
     }
     // Continue visiting child nodes
     call_expr.visit_children_with(self);
   }
   
+  fn visit_if_stmt(&mut self, if_stmt: &swc_ecma_ast::IfStmt) {
+    let in_env_guard = self.in_env_guard || (self.skip_env_guarded_throws && is_env_guard_test(&if_stmt.test));
+    let previous_in_env_guard = std::mem::replace(&mut self.in_env_guard, in_env_guard);
+    if_stmt.visit_children_with(self);
+    self.in_env_guard = previous_in_env_guard;
+  }
+
   fn visit_throw_stmt(&mut self, throw_stmt: &swc_ecma_ast::ThrowStmt) {
-    // Extract error type from direct throw statement
-    match &*throw_stmt.arg {
-      swc_ecma_ast::Expr::New(new_expr) => {
-        if let swc_ecma_ast::Expr::Ident(ident) = &*new_expr.callee {
-          let error_type = ident.sym.to_string();
-          if !self.direct_throws.contains(&error_type) {
-            self.direct_throws.push(error_type);
+    if !self.in_env_guard {
+      if let Some(contents) = self.current_block() {
+        // Extract error type from direct throw statement
+        match &*throw_stmt.arg {
+          swc_ecma_ast::Expr::New(new_expr) => {
+            if let Some(error_type) = constructed_error_type(new_expr) {
+              contents.add_direct_throw(error_type);
+            }
           }
-        }
-      }
-      _ => {
-        // For other types of throws (strings, variables), add a generic "Error" type
-        if !self.direct_throws.contains(&"Error".to_string()) {
-          self.direct_throws.push("Error".to_string());
+          swc_ecma_ast::Expr::Object(object) => {
+            // `throw { name: 'TimeoutError', ... }` counts as that error type
+            let (name, _) = object_literal_error_fields(object);
+            contents.add_direct_throw(name.unwrap_or_else(|| "Error".to_string()));
+          }
+          // For other types of throws (strings, variables), add a generic "Error" type
+          _ => contents.add_direct_throw("Error".to_string()),
         }
       }
     }
@@ -80,9 +149,33 @@ impl Visit for TryBlockCallAnalyzer {
     throw_stmt.visit_children_with(self);
   }
   
-  // Don't visit nested try blocks - we only want calls directly in this try block
-  fn visit_try_stmt(&mut self, _try_stmt: &swc_ecma_ast::TryStmt) {
-    // Don't visit children to avoid analyzing nested try blocks
+  fn visit_try_stmt(&mut self, try_stmt: &swc_ecma_ast::TryStmt) {
+    // Each try block is analyzed on its own, so a guard around the whole statement doesn't apply
+    let previous_in_env_guard = std::mem::replace(&mut self.in_env_guard, false);
+
+    self.open_blocks.push(Some(TryBlockContents::default()));
+    try_stmt.block.visit_with(self);
+    if let Some(Some(contents)) = self.open_blocks.pop() {
+      // A try without a catch clause handles nothing; `finally` only runs cleanup, so whatever
+      // the block throws or calls reaches the enclosing try block as if written there directly
+      if try_stmt.handler.is_none() {
+        if let Some(enclosing) = self.current_block() {
+          enclosing.function_calls.extend(contents.function_calls.iter().cloned());
+          for error_type in &contents.direct_throws {
+            enclosing.add_direct_throw(error_type.clone());
+          }
+        }
+      }
+      self.blocks.insert((try_stmt.block.span.lo(), try_stmt.block.span.hi()), contents);
+    }
+
+    // Nested try statements belong to their own block, catch and finally included
+    self.open_blocks.push(None);
+    try_stmt.handler.visit_with(self);
+    try_stmt.finalizer.visit_with(self);
+    self.open_blocks.pop();
+
+    self.in_env_guard = previous_in_env_guard;
   }
 }
 
@@ -92,9 +185,13 @@ fn populate_catch_analyses_with_throws(
   mut catch_analyses: Vec<CatchAnalysis>,
   functions_with_throws: &HashSet<ThrowMap>,
   module: &swc_ecma_ast::Module,
+  env_guarded_throws: EnvGuardedThrows,
+  type_registry: &TypeRegistry,
+  assertion_functions: &[AssertionConfig],
+  flag_json_parse: bool,
 ) -> Vec<CatchAnalysis> {
   
-  println!("🔧 Analyzing {} catch blocks for error flow using real call graph data", catch_analyses.len());
+  debug_log!("Analyzing {} catch blocks for error flow using real call graph data", catch_analyses.len());
   
   // Create a lookup map from function names to their thrown error types
   let mut function_error_map: HashMap<String, Vec<String>> = HashMap::new();
@@ -110,70 +207,70 @@ fn populate_catch_analyses_with_throws(
     }
   }
   
-  println!("  📋 Built function error map with {} throwing functions", function_error_map.len());
+  debug_log!("  Built function error map with {} throwing functions", function_error_map.len());
   for (func_name, errors) in &function_error_map {
-    println!("    - {}: {:?}", func_name, errors);
+    debug_log!("    - {}: {:?}", func_name, errors);
   }
   
-  // Find all try statements in the module and match them with catch analyses
-  let mut try_finder = TryStatementFinder::new();
-  try_finder.visit_module(module);
+  // Index every try block once, then match them with catch analyses
+  let mut try_block_index = TryBlockIndex::new(env_guarded_throws == EnvGuardedThrows::Exclude, assertion_functions, flag_json_parse);
+  try_block_index.visit_module(module);
   
   for catch_analysis in &mut catch_analyses {
     let try_span = catch_analysis.try_span;
     let handled_errors = catch_analysis.errors_handled_in_catch.clone();
-    println!("  🎯 Analyzing try block span: {:?}", try_span);
+    debug_log!("  Analyzing try block span: {:?}", try_span);
     
-    // Find the corresponding try statement for this catch analysis
-    if let Some(try_block) = try_finder.find_try_block_by_span(&try_span) {
-      // Analyze function calls within this specific try block
-      let mut call_analyzer = TryBlockCallAnalyzer::new();
-      call_analyzer.visit_block_stmt(try_block);
-      
-      println!("    🔍 Found {} function calls in try block: {:?}", 
-        call_analyzer.function_calls.len(), 
-        call_analyzer.function_calls
+    // Find the corresponding try block for this catch analysis
+    if let Some(try_block) = try_block_index.get(&try_span) {
+      debug_log!("    Found {} function calls in try block: {:?}", 
+        try_block.function_calls.len(), 
+        try_block.function_calls
       );
-      println!("    🔍 Found {} direct throws in try block: {:?}", 
-        call_analyzer.direct_throws.len(), 
-        call_analyzer.direct_throws
+      debug_log!("    Found {} direct throws in try block: {:?}", 
+        try_block.direct_throws.len(), 
+        try_block.direct_throws
       );
       
       // Look up what errors these function calls can throw
       let mut thrown_errors = Vec::new();
-      for function_call in &call_analyzer.function_calls {
+      for function_call in &try_block.function_calls {
         if let Some(errors) = function_error_map.get(function_call) {
           for error in errors {
             if !thrown_errors.contains(error) {
               thrown_errors.push(error.clone());
             }
           }
-          println!("    ✅ {} can throw: {:?}", function_call, errors);
+          debug_log!("    {} can throw: {:?}", function_call, errors);
         } else {
-          println!("    ⚠️  {} not found in error map (might not throw)", function_call);
+          debug_log!("    {} not found in error map (might not throw)", function_call);
         }
       }
       
       // Add direct throws from the try block
-      for direct_throw in &call_analyzer.direct_throws {
+      for direct_throw in &try_block.direct_throws {
         if !thrown_errors.contains(direct_throw) {
           thrown_errors.push(direct_throw.clone());
         }
-        println!("    ✅ Direct throw found: {}", direct_throw);
+        debug_log!("    Direct throw found: {}", direct_throw);
       }
       
       // Add the actual thrown errors to the catch analysis
       for error_type in thrown_errors {
+        let ancestors = type_registry.ancestors_of(&error_type);
+        if !ancestors.is_empty() {
+          catch_analysis.error_ancestors.insert(error_type.clone(), ancestors);
+        }
         catch_analysis.add_thrown_error(error_type);
       }
     } else {
-      println!("    ❌ Could not find try block for this catch analysis");
+      debug_log!("    Could not find try block for this catch analysis");
       
       // Fallback: use the catch handlers as indicators of what errors are thrown
       for error_type in &handled_errors {
         if function_error_map.values().any(|errors| errors.contains(error_type)) {
           catch_analysis.add_thrown_error(error_type.clone());
-          println!("    ✅ Fallback: Confirmed {} is actually thrown by some function", error_type);
+          debug_log!("    Fallback: Confirmed {} is actually thrown by some function", error_type);
         }
       }
     }
@@ -181,78 +278,197 @@ fn populate_catch_analyses_with_throws(
     // Recalculate error flow with the real data
     catch_analysis.calculate_error_flow();
     
-    println!("    📊 After calculation:");
-    println!("      - Thrown: {:?}", catch_analysis.errors_thrown_in_try);
-    println!("      - Handled: {:?}", catch_analysis.errors_handled_in_catch);
-    println!("      - Effectively caught: {:?}", catch_analysis.errors_effectively_caught);
-    println!("      - Propagated: {:?}", catch_analysis.errors_propagated);
+    debug_log!("    After calculation:");
+    debug_log!("      - Thrown: {:?}", catch_analysis.errors_thrown_in_try);
+    debug_log!("      - Handled: {:?}", catch_analysis.errors_handled_in_catch);
+    debug_log!("      - Effectively caught: {:?}", catch_analysis.errors_effectively_caught);
+    debug_log!("      - Propagated: {:?}", catch_analysis.errors_propagated);
   }
 
   catch_analyses
 }
 
-/// Visitor to find all try statements in the module and map them by span
-struct TryStatementFinder {
-  try_blocks: Vec<(Span, swc_ecma_ast::BlockStmt)>,
+/// A syntax error in an analyzed file, as the parser reported it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+  pub message: String,
+  pub span: Span,
 }
 
-impl TryStatementFinder {
-  fn new() -> Self {
-    Self {
-      try_blocks: Vec::new(),
+impl From<&swc_ecma_parser::error::Error> for ParseError {
+  fn from(error: &swc_ecma_parser::error::Error) -> Self {
+    ParseError {
+      message: error.kind().msg().to_string(),
+      span: error.span(),
     }
   }
-  
-  fn find_try_block_by_span(&self, target_span: &Span) -> Option<&swc_ecma_ast::BlockStmt> {
-    self.try_blocks
-      .iter()
-      .find(|(span, _)| span.lo() == target_span.lo() && span.hi() == target_span.hi())
-      .map(|(_, block)| block)
-  }
 }
 
-impl Visit for TryStatementFinder {
-  fn visit_try_stmt(&mut self, try_stmt: &swc_ecma_ast::TryStmt) {
-    self.try_blocks.push((try_stmt.block.span, try_stmt.block.clone()));
-    // Continue visiting child nodes
-    try_stmt.visit_children_with(self);
-  }
+/// An ignore statement just above a class declaration, e.g. `// @it-throws` over `class Legacy {`,
+/// which covers every method of the class
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassIgnoreComment {
+  pub comment_span: Span,
+  pub class_span: Span,
+  pub error_types: Vec<String>, // Empty when it suppresses everything, e.g. a bare `// @it-throws`
 }
 
+impl ClassIgnoreComment {
+  /// Whether this comment's suppression reaches an `error_type` thrown or called inside the class
+  pub fn covers(&self, error_type: Option<&str>) -> bool {
+    self.error_types.is_empty() || error_type.is_some_and(|error_type| self.error_types.iter().any(|listed| listed == error_type))
+  }
+}
 
 #[derive(Default)]
 pub struct AnalysisResult {
   pub functions_with_throws: HashSet<ThrowMap>,
   pub calls_to_throws: HashSet<CallToThrowMap>,
-  pub json_parse_calls: Vec<String>,
-  pub fs_access_calls: Vec<String>,
+  pub builtin_throw_calls: Vec<BuiltinThrowCall>, // Calls to builtins known to throw, like `JSON.parse`
   pub import_sources: HashSet<String>,
   pub imported_identifiers: Vec<String>,
   pub imported_identifier_usages: HashSet<IdentifierUsage>,
   pub catch_analyses: Vec<CatchAnalysis>, // New: error flow analysis for try-catch blocks
   pub unused_it_throws_comments: Vec<Span>, // Track unused @it-throws comments
+  pub class_ignore_comments: Vec<ClassIgnoreComment>, // Ignore statements above class declarations, covering all their methods
+  pub throw_statement_spans: Vec<Span>, // Every throw statement, suppressed or not, for telling which @it-throws comments are used
   pub all_functions: HashSet<FunctionMap>, // All functions (throwing and non-throwing) for JSDoc checking
   pub inline_callback_allowed_throws: std::collections::HashMap<Span, Vec<String>>, // Map inline callback span -> allowed throws
+  pub parse_errors: Vec<ParseError>, // Every syntax error, including those the parser recovered from to analyze the rest
+  pub debug_log: Vec<String>, // Analysis trace, only collected when `UserSettings::debug` is set
+  pub incremental: IncrementalState, // What `analyze_code_incremental` reuses when the file is edited
+}
+
+/// Where an analysis spent its time and how much it found, for diagnosing slow files
+#[derive(Clone, Debug, Default)]
+pub struct AnalysisStats {
+  pub parse_time: Duration,
+  pub visitor_times: Vec<(&'static str, Duration)>, // In the order the visitors ran
+  pub function_count: usize,
+  pub throw_count: usize, // Throw statements found directly in function bodies, before propagation
+  pub propagation_iterations: usize, // Worklist steps taken pushing throws up the call graph
+}
+
+impl AnalysisStats {
+  /// Run one visitor pass, recording how long it took under `visitor`
+  fn time_visitor<T>(&mut self, visitor: &'static str, pass: impl FnOnce() -> T) -> T {
+    let (result, elapsed) = timed(pass);
+    self.visitor_times.push((visitor, elapsed));
+    result
+  }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn timed<T>(run: impl FnOnce() -> T) -> (T, Duration) {
+  let started = std::time::Instant::now();
+  let result = run();
+  (result, started.elapsed())
+}
+
+// There is no clock on wasm32-unknown-unknown, so durations read as zero there
+#[cfg(target_arch = "wasm32")]
+fn timed<T>(run: impl FnOnce() -> T) -> (T, Duration) {
+  (run(), Duration::ZERO)
+}
+
+/// An edit between two versions of a file: bytes `start..old_end` of the old content were
+/// replaced by bytes `start..new_end` of the new content. Offsets count from the start of the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+  pub start: u32,
+  pub old_end: u32,
+  pub new_end: u32,
+}
+
+/// ThrowFinder's findings for a file, before error factories, ancestors, and propagation are applied
+#[derive(Clone, Default)]
+pub struct IncrementalState {
+  pub file_start: BytePos, // Where the file started in the `SourceMap` the spans below point into
+  pub direct_throws: HashSet<ThrowMap>,
+  pub used_it_throws_comments: HashSet<Span>,
+  pub reanalyzed_functions: Vec<String>, // Ids of the functions ThrowFinder looked at; all of them on a full analysis
 }
 
 
 pub struct UserSettings {
   pub include_try_statement_throws: bool,
   pub ignore_statements: Vec<String>,
+  pub debug: bool, // Collect analysis trace output into `AnalysisResult::debug_log`
+  pub error_factories: Vec<ErrorFactoryConfig>, // Helpers whose argument names the thrown error type
+  pub assertion_functions: Vec<AssertionConfig>, // `assert(cond, msg)`-style helpers whose calls throw when the condition fails
+  pub max_call_depth: usize, // How many caller levels a throw climbs; values below 1 act as 1
+  pub expected_error_types: Vec<String>, // Error types the project throws on purpose; never reported
+  pub treat_env_guarded_throws_as: EnvGuardedThrows, // Whether throws behind `process.env` checks are analyzed
+  pub terminator_functions: Vec<String>, // `process.exit`-style calls; a catch that always makes one swallows its errors
+  pub include_catch_rethrows: bool, // Whether `throw e` in a catch passes the caught error on to the function's callers
+  pub throws_tag_aliases: Vec<String>, // JSDoc tags, without the `@`, that document thrown errors, e.g. `exception`
+  pub ignore_imports: Vec<String>, // Module specifiers or patterns like `@acme/*` whose imports are never reported
+  pub flag_json_parse: bool, // Whether `JSON.parse` calls throw a `SyntaxError` into their enclosing function
+}
+
+/// Caller levels a throw climbs when no `max_call_depth` is configured
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 5;
+
+/// The extension's defaults: the built-in assertion and terminator functions, `@throws` and its
+/// aliases, and rethrows passed on to callers
+impl Default for UserSettings {
+  fn default() -> Self {
+    Self {
+      include_try_statement_throws: false,
+      ignore_statements: Vec::new(),
+      debug: false,
+      error_factories: Vec::new(),
+      assertion_functions: default_assertion_functions(),
+      max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+      expected_error_types: Vec::new(),
+      treat_env_guarded_throws_as: EnvGuardedThrows::Include,
+      terminator_functions: default_terminator_functions(),
+      include_catch_rethrows: true,
+      throws_tag_aliases: default_throws_tag_aliases(),
+      ignore_imports: Vec::new(),
+      flag_json_parse: false,
+    }
+  }
 }
 
 
 
-/// Simple propagation without catch analysis filtering - used when include_try_statement_throws is true
+/// Calls ordered by position, so merging propagated throws does not depend on HashSet order
+fn calls_in_source_order(calls_to_throws: &HashSet<CallToThrowMap>) -> Vec<&CallToThrowMap> {
+  let mut calls: Vec<&CallToThrowMap> = calls_to_throws.iter().collect();
+  calls.sort_by(|a, b| (a.call_span.lo, a.call_span.hi, &a.id).cmp(&(b.call_span.lo, b.call_span.hi, &b.id)));
+  calls
+}
+
+/// Simple propagation without catch analysis filtering - used when include_try_statement_throws is true.
+/// Also returns the propagation iterations, as `propagate_throws_transitively` does
 fn propagate_throws_to_callers_without_catch_filtering(
   functions_with_throws: HashSet<ThrowMap>,
   calls_to_throws: &HashSet<CallToThrowMap>,
+  call_edges: &[CallEdge],
   all_functions: &HashSet<FunctionMap>,
-) -> HashSet<ThrowMap> {
-  let mut result_functions = functions_with_throws;
+  max_call_depth: usize,
+  include_catch_rethrows: bool,
+) -> (HashSet<ThrowMap>, usize) {
+  let direct_throws = direct_throw_types(&functions_with_throws);
+  let mut result_functions: HashSet<ThrowMap> = functions_with_throws
+    .into_iter()
+    .filter_map(|mut throw_map| {
+      if !include_catch_rethrows {
+        retain_throw_details(&mut throw_map, |detail| !detail.is_rethrow);
+      }
+      (!throw_map.throw_details.is_empty()).then_some(throw_map)
+    })
+    .collect();
   
   // Simple propagation from called functions to callers
-  for call in calls_to_throws {
+  for call in calls_in_source_order(calls_to_throws) {
+    let mut propagated_throws = call.propagated_throw_details();
+    if propagated_throws.is_empty() {
+      continue;
+    }
+    // Try statements are ignored here, so nothing the callee throws is the caller's rethrow
+    mark_rethrows_at_call(&mut propagated_throws, call.call_span, &[]);
     // Find the function information for the caller
     if let Some(function_info) = all_functions.iter().find(|f| f.id == call.id) {
       // Check if this function already has throws
@@ -263,17 +479,24 @@ fn propagate_throws_to_callers_without_catch_filtering(
         if let Some(mut existing_throw_map) = result_functions.take(&ThrowMap {
           throw_spans: vec![function_info.span],
           throw_statement: function_info.span,
+          function_span: function_info.span,
           function_or_method_name: function_info.name.clone(),
           class_name: function_info.class_name.clone(),
           id: function_info.id.clone(),
           throw_details: vec![], // dummy for lookup
           throws_annotation: None, // dummy for lookup
+          is_async: false,
+          name_span: None,
+          accessor_kind: None,
         }) {
           // Merge propagated throws with existing ones
-          for propagated_throw in &call.throw_map.throw_details {
+          for propagated_throw in &propagated_throws {
             if !existing_throw_map.throw_details.iter().any(|existing| {
               existing.error_type == propagated_throw.error_type
             }) {
+              if existing_throw_map.throw_spans.len() == existing_throw_map.throw_details.len() {
+                existing_throw_map.throw_spans.push(function_info.span);
+              }
               existing_throw_map.throw_details.push(propagated_throw.clone());
             }
           }
@@ -284,11 +507,15 @@ fn propagate_throws_to_callers_without_catch_filtering(
         let new_throw_map = ThrowMap {
           throw_spans: vec![function_info.span],
           throw_statement: function_info.span,
+          function_span: function_info.span,
           function_or_method_name: function_info.name.clone(),
           class_name: function_info.class_name.clone(),
           id: function_info.id.clone(),
-          throw_details: call.throw_map.throw_details.clone(),
+          throw_details: propagated_throws,
           throws_annotation: function_info.throws_annotation.clone(),
+          is_async: function_info.is_async,
+          name_span: Some(function_info.name_span),
+          accessor_kind: None,
         };
         
         result_functions.insert(new_throw_map);
@@ -296,19 +523,24 @@ fn propagate_throws_to_callers_without_catch_filtering(
     }
   }
   
-  result_functions
+  propagate_throws_transitively(result_functions, call_edges, all_functions, &[], &direct_throws, max_call_depth)
 }
 
 /// Propagates throws from called functions to calling functions using complete function information
 /// This creates ThrowMap entries for functions that don't directly throw but call functions that do
-/// Now enhanced with catch analysis to filter out effectively caught errors
+/// Now enhanced with catch analysis to filter out effectively caught errors.
+/// Also returns the propagation iterations, as `propagate_throws_transitively` does
 fn propagate_throws_to_callers(
   mut functions_with_throws: HashSet<ThrowMap>,
   calls_to_throws: &HashSet<CallToThrowMap>,
+  call_edges: &[CallEdge],
   all_functions: &HashSet<FunctionMap>,
   catch_analyses: &[CatchAnalysis],
-) -> HashSet<ThrowMap> {
-  
+  max_call_depth: usize,
+  include_catch_rethrows: bool,
+) -> (HashSet<ThrowMap>, usize) {
+  let direct_throws = direct_throw_types(&functions_with_throws);
+
   // First, filter existing direct throws through catch analysis
   let mut filtered_functions_with_throws = HashSet::new();
   
@@ -320,40 +552,42 @@ fn propagate_throws_to_callers(
         catch_analyses
       );
       
-      println!("🔧 Filtering original function: {} ({})", function_info.name, function_info.id);
-      println!("   📍 Function span: {:?}", function_info.span);
-      println!("   🎯 Effectively caught errors: {:?}", effectively_caught_errors);
-      println!("   📝 Original throw details: {:?}", throw_map.throw_details.iter().map(|d| &d.error_type).collect::<Vec<_>>());
+      debug_log!("Filtering original function: {} ({})", function_info.name, function_info.id);
+      debug_log!("   Function span: {:?}", function_info.span);
+      debug_log!("   Effectively caught errors: {:?}", effectively_caught_errors);
+      debug_log!("   Original throw details: {:?}", throw_map.throw_details.iter().map(|d| &d.error_type).collect::<Vec<_>>());
       
-      // Filter the throw details to exclude effectively caught errors
-      let mut filtered_throw_details = throw_map.throw_details.clone();
-      filtered_throw_details.retain(|throw_detail| {
-        if let Some(ref error_type) = throw_detail.error_type {
+      // Filter the throw details to exclude effectively caught errors. A rethrow leaves the catch
+      // that caught it, so only `include_catch_rethrows` decides whether it is kept
+      let mut filtered_throw_map = throw_map.clone();
+      retain_throw_details(&mut filtered_throw_map, |throw_detail| {
+        if throw_detail.is_rethrow {
+          debug_log!("     Rethrow of {:?}: keep={}", throw_detail.error_type, include_catch_rethrows);
+          include_catch_rethrows
+        } else if let Some(ref error_type) = throw_detail.error_type {
           // Don't keep errors that are effectively caught
           let should_keep = !effectively_caught_errors.contains(error_type);
-          println!("     🔍 Error type '{}': keep={}", error_type, should_keep);
+          debug_log!("     Error type '{}': keep={}", error_type, should_keep);
           should_keep
         } else {
-          println!("     🔍 String/other throw: keeping");
+          debug_log!("     String/other throw: keeping");
           true // Keep string throws and other types
         }
       });
       
-      println!("   📝 Filtered throw details: {:?}", filtered_throw_details.iter().map(|d| &d.error_type).collect::<Vec<_>>());
+      debug_log!("   Filtered throw details: {:?}", filtered_throw_map.throw_details.iter().map(|d| &d.error_type).collect::<Vec<_>>());
       
       // Only keep the function if it has unhandled throws
-      if !filtered_throw_details.is_empty() {
-        let mut filtered_throw_map = throw_map.clone();
-        filtered_throw_map.throw_details = filtered_throw_details;
+      if !filtered_throw_map.throw_details.is_empty() {
         filtered_functions_with_throws.insert(filtered_throw_map);
-        println!("   ✅ Kept function (has unhandled throws)");
+        debug_log!("   Kept function (has unhandled throws)");
       } else {
-        println!("   ❌ Filtered out function (all throws effectively caught)");
+        debug_log!("   Filtered out function (all throws effectively caught)");
       }
     } else {
       // If we can't find function info, we need to check if this function's throws
       // are all within try blocks by looking at the throw spans directly
-      println!("🔧 No function info found for: {}, checking throws directly", throw_map.function_or_method_name);
+      debug_log!("No function info found for: {}, checking throws directly", throw_map.function_or_method_name);
       
       let mut has_unhandled_throws = false;
       for (i, throw_detail) in throw_map.throw_details.iter().enumerate() {
@@ -371,10 +605,10 @@ fn propagate_throws_to_callers(
           
           if !is_within_try_block {
             has_unhandled_throws = true;
-            println!("    🔍 Found unhandled throw at span {:?}", throw_span);
+            debug_log!("    Found unhandled throw at span {:?}", throw_span);
             break;
           } else {
-            println!("    ❌ Throw at span {:?} is within try block and effectively caught", throw_span);
+            debug_log!("    Throw at span {:?} is within try block and effectively caught", throw_span);
           }
         } else {
           // No span info, conservatively keep it
@@ -384,16 +618,16 @@ fn propagate_throws_to_callers(
       }
       
       if has_unhandled_throws {
-        println!("    ✅ Keeping function (has unhandled throws)");
+        debug_log!("    Keeping function (has unhandled throws)");
         filtered_functions_with_throws.insert(throw_map);
       } else {
-        println!("    ❌ Filtering out function (all throws effectively caught)");
+        debug_log!("    Filtering out function (all throws effectively caught)");
       }
     }
   }
   
   // Now handle propagation from called functions
-  for call in calls_to_throws {
+  for call in calls_in_source_order(calls_to_throws) {
     // Find the function information for the caller
     if let Some(function_info) = all_functions.iter().find(|f| f.id == call.id) {
       // Check if this function already has (filtered) throws
@@ -406,7 +640,7 @@ fn propagate_throws_to_callers(
       );
       
       // Filter the called function's throws to exclude effectively caught errors
-      let mut propagated_throws = call.throw_map.throw_details.clone();
+      let mut propagated_throws = call.propagated_throw_details();
       propagated_throws.retain(|throw_detail| {
         if let Some(ref error_type) = throw_detail.error_type {
           // Don't propagate errors that are effectively caught
@@ -415,6 +649,7 @@ fn propagate_throws_to_callers(
           true // Keep string throws and other types
         }
       });
+      mark_rethrows_at_call(&mut propagated_throws, call.call_span, catch_analyses);
       
       if !propagated_throws.is_empty() {
         if has_existing_throws {
@@ -422,19 +657,27 @@ fn propagate_throws_to_callers(
           if let Some(mut existing_throw_map) = filtered_functions_with_throws.take(&ThrowMap {
             throw_spans: vec![function_info.span],
             throw_statement: function_info.span,
+            function_span: function_info.span,
             function_or_method_name: function_info.name.clone(),
             class_name: function_info.class_name.clone(),
             id: function_info.id.clone(),
             throw_details: vec![], // dummy for lookup
             throws_annotation: None, // dummy for lookup
+            is_async: false,
+            name_span: None,
+            accessor_kind: None,
           }) {
             // Merge propagated throws with existing ones
             for propagated_throw in propagated_throws {
-              if !existing_throw_map.throw_details.iter().any(|existing| {
+              if existing_throw_map.throw_details.iter().any(|existing| {
                 existing.error_type == propagated_throw.error_type
               }) {
-                existing_throw_map.throw_details.push(propagated_throw);
+                continue;
+              }
+              if existing_throw_map.throw_spans.len() == existing_throw_map.throw_details.len() {
+                existing_throw_map.throw_spans.push(function_info.span);
               }
+              existing_throw_map.throw_details.push(propagated_throw);
             }
             filtered_functions_with_throws.insert(existing_throw_map);
           }
@@ -443,14 +686,18 @@ fn propagate_throws_to_callers(
           let new_throw_map = ThrowMap {
             throw_spans: vec![function_info.span],
             throw_statement: function_info.span,
+            function_span: function_info.span,
             function_or_method_name: function_info.name.clone(),
             class_name: function_info.class_name.clone(),
             id: function_info.id.clone(),
             throw_details: propagated_throws,
             throws_annotation: function_info.throws_annotation.clone(),
+            is_async: function_info.is_async,
+            name_span: Some(function_info.name_span),
+            accessor_kind: None,
           };
           
-          println!("🚀 Propagated throws to caller: {} ({})", 
+          debug_log!("Propagated throws to caller: {} ({})", 
             function_info.name, 
             function_info.id
           );
@@ -461,7 +708,213 @@ fn propagate_throws_to_callers(
     }
   }
   
-  filtered_functions_with_throws
+  propagate_throws_transitively(
+    filtered_functions_with_throws,
+    call_edges,
+    all_functions,
+    catch_analyses,
+    &direct_throws,
+    max_call_depth,
+  )
+}
+
+/// Whether `detail` is a `throw e` of a catch parameter whose type wasn't known from the try block
+fn is_rethrow_placeholder(detail: &ThrowDetails) -> bool {
+  detail.is_rethrow && detail.error_type.is_some() && detail.reported_error_type().is_none()
+}
+
+/// A `throw e` whose type wasn't known from the try block stands in for what the calls in the try
+/// throw, so it takes the first type such a call rethrows; propagation adds the others. Calls to the
+/// rethrowing function are refreshed too, so its callers see the resolved type, never the placeholder.
+fn resolve_rethrow_placeholders(
+  functions_with_throws: HashSet<ThrowMap>,
+  calls_to_throws: HashSet<CallToThrowMap>,
+  catch_analyses: &[CatchAnalysis],
+) -> (HashSet<ThrowMap>, HashSet<CallToThrowMap>) {
+  let mut functions: Vec<ThrowMap> = functions_with_throws.into_iter().collect();
+  for call in calls_in_source_order(&calls_to_throws) {
+    let Some(catch_analysis) = enclosing_catch(call.call_span, catch_analyses) else {
+      continue;
+    };
+    let Some(caller) = functions.iter_mut().find(|throw_map| throw_map.id == call.id) else {
+      continue;
+    };
+    let Some(index) = caller.throw_details.iter().position(is_rethrow_placeholder) else {
+      continue;
+    };
+    let rethrown = call
+      .propagated_throw_details()
+      .into_iter()
+      .find(|detail| detail.error_type.as_deref().is_some_and(|error_type| catch_analysis.rethrows(error_type)));
+    if let Some(rethrown) = rethrown {
+      let arg_span = caller.throw_details[index].arg_span;
+      caller.throw_details[index] = ThrowDetails { arg_span, is_rethrow: true, ..rethrown };
+    }
+  }
+
+  let calls_to_throws = calls_to_throws
+    .into_iter()
+    .map(|mut call| {
+      if call.throw_map.throw_details.iter().any(is_rethrow_placeholder) {
+        if let Some(resolved) = functions.iter().find(|throw_map| throw_map.id == call.throw_map.id) {
+          call.throw_map = resolved.clone();
+        }
+      }
+      call
+    })
+    .collect();
+  (functions.into_iter().collect(), calls_to_throws)
+}
+
+/// The error types each function throws itself, keyed by function id, before any are inherited
+/// from its callees
+fn direct_throw_types(functions_with_throws: &HashSet<ThrowMap>) -> HashSet<(String, Option<String>)> {
+  functions_with_throws
+    .iter()
+    .flat_map(|throw_map| {
+      throw_map.throw_details.iter().map(move |detail| (throw_map.id.clone(), detail.error_type.clone()))
+    })
+    .collect()
+}
+
+/// Keeps pushing throws up the call graph until nothing changes, so in `a -> b -> c` a throw in
+/// `c` reaches `a` even though only the `b -> c` call was known to throw when calls were collected.
+/// Each caller takes on each error type at most once, which also ends the walk on recursion.
+/// The walk is breadth-first so every error type reaches a function at its shortest distance
+/// from where it was thrown, and stops once a throw has climbed `max_call_depth` levels. Also
+/// returns how many worklist steps the walk took.
+fn propagate_throws_transitively(
+  mut functions_with_throws: HashSet<ThrowMap>,
+  call_edges: &[CallEdge],
+  all_functions: &HashSet<FunctionMap>,
+  catch_analyses: &[CatchAnalysis],
+  direct_throws: &HashSet<(String, Option<String>)>,
+  max_call_depth: usize,
+) -> (HashSet<ThrowMap>, usize) {
+  let mut iterations = 0;
+  // How many levels each function's error types have climbed; types a function only picked up
+  // from its direct calls are already one level up, even when it throws others itself
+  let mut depths: HashMap<(String, Option<String>), usize> = HashMap::new();
+  for throw_map in &functions_with_throws {
+    for detail in &throw_map.throw_details {
+      let key = (throw_map.id.clone(), detail.error_type.clone());
+      let depth = if direct_throws.contains(&key) { 0 } else { 1 };
+      depths.insert(key, depth);
+    }
+  }
+  let mut initial: Vec<(usize, String)> = depths
+    .iter()
+    .map(|((function_id, _), depth)| (*depth, function_id.clone()))
+    .collect();
+  initial.sort();
+  initial.dedup();
+  let mut worklist: VecDeque<(usize, String)> = initial.into_iter().collect();
+
+  while let Some((depth, callee_id)) = worklist.pop_front() {
+    iterations += 1;
+    if depth >= max_call_depth.max(1) {
+      continue;
+    }
+    let callee = match functions_with_throws.iter().find(|throw_map| throw_map.id == callee_id) {
+      Some(callee) => callee.clone(),
+      None => continue,
+    };
+    for edge in call_edges {
+      let calls_callee = match &edge.callee_id {
+        Some(id) => *id == callee.id,
+        None => edge.callee_name == callee.function_or_method_name,
+      };
+      if !calls_callee {
+        continue;
+      }
+      let function_info = match all_functions.iter().find(|f| f.id == edge.caller_id) {
+        Some(function_info) => function_info,
+        None => continue,
+      };
+      let effectively_caught_errors = get_effectively_caught_errors_for_function(function_info.span, catch_analyses);
+      let mut propagated_throws = Vec::new();
+      // Only the callee's types that reached it at this depth climb on from here
+      let throw_details_at_depth: Vec<&ThrowDetails> = callee
+        .throw_details
+        .iter()
+        .filter(|detail| depths.get(&(callee.id.clone(), detail.error_type.clone())) == Some(&depth))
+        .collect();
+      for throw_detail in throw_details_at_depth {
+        let is_caught = throw_detail
+          .error_type
+          .as_ref()
+          .is_some_and(|error_type| effectively_caught_errors.contains(error_type) || edge.suppressed_error_types.contains(error_type))
+          || catch_analyses.iter().any(|catch_analysis| {
+            edge.call_span.lo() >= catch_analysis.try_span.lo()
+              && edge.call_span.hi() <= catch_analysis.try_span.hi()
+              && catch_analysis.catches(throw_detail.error_type.as_deref())
+          });
+        let key = (function_info.id.clone(), throw_detail.error_type.clone());
+        if !is_caught && !depths.contains_key(&key) {
+          depths.insert(key, depth + 1);
+          propagated_throws.push(throw_detail.clone());
+        }
+      }
+      if propagated_throws.is_empty() {
+        continue;
+      }
+      mark_rethrows_at_call(&mut propagated_throws, edge.call_span, catch_analyses);
+
+      debug_log!("Propagated throws transitively: {} -> {} {:?}",
+        callee.id,
+        function_info.id,
+        propagated_throws.iter().map(|d| &d.error_type).collect::<Vec<_>>()
+      );
+      let caller_throw_map = match functions_with_throws.iter().find(|f| f.id == function_info.id).cloned() {
+        Some(existing_throw_map) => {
+          functions_with_throws.remove(&existing_throw_map);
+          let mut merged_throw_map = existing_throw_map;
+          // Propagated throws point at the caller itself, keeping a span for every detail
+          if merged_throw_map.throw_spans.len() == merged_throw_map.throw_details.len() {
+            merged_throw_map.throw_spans.extend(propagated_throws.iter().map(|_| function_info.span));
+          }
+          merged_throw_map.throw_details.extend(propagated_throws);
+          merged_throw_map
+        }
+        None => ThrowMap {
+          throw_spans: vec![function_info.span],
+          throw_statement: function_info.span,
+          function_span: function_info.span,
+          function_or_method_name: function_info.name.clone(),
+          class_name: function_info.class_name.clone(),
+          id: function_info.id.clone(),
+          throw_details: propagated_throws,
+          throws_annotation: function_info.throws_annotation.clone(),
+          is_async: function_info.is_async,
+          name_span: Some(function_info.name_span),
+          accessor_kind: None,
+        },
+      };
+      functions_with_throws.insert(caller_throw_map);
+      worklist.push_back((depth + 1, function_info.id.clone()));
+    }
+  }
+
+  (functions_with_throws, iterations)
+}
+
+/// A callee's throw reaches the caller as a rethrow only when the caller's own catch passes it
+/// along with `throw e`; otherwise the caller simply throws it, whatever the callee did with it
+fn mark_rethrows_at_call(throw_details: &mut [ThrowDetails], call_span: Span, catch_analyses: &[CatchAnalysis]) {
+  let catch_analysis = enclosing_catch(call_span, catch_analyses);
+  for throw_detail in throw_details {
+    throw_detail.is_rethrow = catch_analysis.is_some_and(|catch_analysis| {
+      throw_detail.error_type.as_deref().is_some_and(|error_type| catch_analysis.rethrows(error_type))
+    });
+  }
+}
+
+/// The innermost catch whose try block contains `span`
+fn enclosing_catch(span: Span, catch_analyses: &[CatchAnalysis]) -> Option<&CatchAnalysis> {
+  catch_analyses
+    .iter()
+    .filter(|catch_analysis| catch_analysis.try_span.lo() <= span.lo() && span.hi() <= catch_analysis.try_span.hi())
+    .min_by_key(|catch_analysis| catch_analysis.try_span.hi() - catch_analysis.try_span.lo())
 }
 
 /// Get all error types that are effectively caught (handled with instanceof and not re-thrown)
@@ -495,23 +948,23 @@ fn filter_functions_exclude_try_block_throws(
 ) -> HashSet<ThrowMap> {
   let mut filtered_functions = HashSet::new();
   
-  println!("🔧 Filtering functions to exclude try block throws:");
-  println!("  - Input: {} functions with throws", functions_with_throws.len());
-  println!("  - Total catch analyses: {}", catch_analyses.len());
+  debug_log!("Filtering functions to exclude try block throws:");
+  debug_log!("  - Input: {} functions with throws", functions_with_throws.len());
+  debug_log!("  - Total catch analyses: {}", catch_analyses.len());
 
   for throw_map in functions_with_throws {
-    println!("  🔍 Processing function: {} ({})", throw_map.function_or_method_name, throw_map.id);
+    debug_log!("  Processing function: {} ({})", throw_map.function_or_method_name, throw_map.id);
     // Find the function info to get the function span
     if let Some(function_info) = all_functions.iter().find(|f| f.id == throw_map.id) {
       // Filter throw details to exclude those within try blocks (unless effectively caught was already handled)
       let mut filtered_throw_details = Vec::new();
       
       for (i, throw_detail) in throw_map.throw_details.iter().enumerate() {
-        println!("    📝 Checking throw detail {}: {:?}", i, throw_detail.error_type);
+        debug_log!("    Checking throw detail {}: {:?}", i, throw_detail.error_type);
         // Check if any of the throw spans are within try blocks
         let corresponding_span = throw_map.throw_spans.get(i);
         if let Some(throw_span) = corresponding_span {
-          println!("      📍 Throw span: {:?}", throw_span);
+          debug_log!("      Throw span: {:?}", throw_span);
           let is_within_try_block = catch_analyses.iter().any(|catch_analysis| {
             let span_within_try = throw_span.lo() >= catch_analysis.try_span.lo() 
               && throw_span.hi() <= catch_analysis.try_span.hi();
@@ -519,25 +972,25 @@ fn filter_functions_exclude_try_block_throws(
               && catch_analysis.try_span.hi() <= function_info.span.hi();
             let result = span_within_try && try_within_function;
             if result {
-              println!("      ✅ Found matching try block: try_span={:?} within function_span={:?}", 
+              debug_log!("      Found matching try block: try_span={:?} within function_span={:?}", 
                 catch_analysis.try_span, function_info.span);
             }
             result
           });
           
-          println!("      🔍 is_within_try_block: {}", is_within_try_block);
+          debug_log!("      is_within_try_block: {}", is_within_try_block);
           
           if !is_within_try_block {
             // Keep throws that are not within try blocks
             filtered_throw_details.push(throw_detail.clone());
-            println!("      ✅ Kept throw detail (not in try block)");
+            debug_log!("      Kept throw detail (not in try block)");
           } else {
-            println!("      ❌ Filtered out throw detail (in try block)");
+            debug_log!("      Filtered out throw detail (in try block)");
           }
         } else {
           // No corresponding span, keep the throw detail
           filtered_throw_details.push(throw_detail.clone());
-          println!("      ✅ Kept throw detail (no span info)");
+          debug_log!("      Kept throw detail (no span info)");
         }
       }
       
@@ -580,10 +1033,10 @@ fn filter_calls_through_catch_analysis_and_function_availability(
 
   for call in calls_to_throws {
     // First check if the called function is still available (not filtered out)
-    let called_function_still_throws = available_throwing_functions.contains(&call.throw_map.id);
+    let called_function_still_throws = call.is_builtin || available_throwing_functions.contains(&call.throw_map.id);
     
     if !called_function_still_throws {
-      println!("🔧 Filtering out call to {} because function was filtered out (all throws effectively caught)", 
+      debug_log!("Filtering out call to {} because function was filtered out (all throws effectively caught)", 
         call.throw_map.function_or_method_name);
       continue;
     }
@@ -611,7 +1064,7 @@ fn filter_calls_through_catch_analysis_and_function_availability(
         // Only keep calls that are not effectively caught
         filtered_calls.insert(call);
       } else {
-        println!("🔧 Filtering out call to {} because it's effectively caught", 
+        debug_log!("Filtering out call to {} because it's effectively caught", 
           call.throw_map.function_or_method_name);
       }
     } else {
@@ -623,11 +1076,380 @@ fn filter_calls_through_catch_analysis_and_function_availability(
   filtered_calls
 }
 
+/// Replace `call: makeError` placeholders with the error type the factory is known to return
+pub fn resolve_error_factory_throws(
+  functions_with_throws: HashSet<ThrowMap>,
+  error_factories: &HashMap<String, String>,
+) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .map(|mut throw_map| {
+      for detail in throw_map.throw_details.iter_mut() {
+        let resolved = detail
+          .error_type
+          .as_deref()
+          .and_then(|error_type| error_type.strip_prefix(ERROR_FACTORY_CALL_PREFIX))
+          .and_then(|factory| error_factories.get(factory));
+        if let Some(error_type) = resolved {
+          detail.is_custom_error = !is_built_in_error(error_type);
+          detail.error_type = Some(error_type.clone());
+        }
+      }
+      throw_map
+    })
+    .collect()
+}
+
+/// Record the classes each thrown error type extends, so `@throws {HttpError}` and
+/// `instanceof HttpError` cover a `NotFoundError extends HttpError`
+pub fn resolve_error_ancestors(
+  functions_with_throws: HashSet<ThrowMap>,
+  type_registry: &TypeRegistry,
+) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .map(|mut throw_map| {
+      for detail in throw_map.throw_details.iter_mut() {
+        if let Some(error_type) = &detail.error_type {
+          detail.ancestor_types = type_registry.ancestors_of(error_type);
+        }
+      }
+      throw_map
+    })
+    .collect()
+}
+
+/// Keeps the throws `keep` accepts, along with their spans while the two still line up
+fn retain_throw_details(throw_map: &mut ThrowMap, mut keep: impl FnMut(&ThrowDetails) -> bool) {
+  if throw_map.throw_spans.len() == throw_map.throw_details.len() {
+    let (throw_spans, throw_details) = throw_map
+      .throw_spans
+      .iter()
+      .zip(&throw_map.throw_details)
+      .filter(|(_, detail)| keep(detail))
+      .map(|(span, detail)| (*span, detail.clone()))
+      .unzip();
+    throw_map.throw_spans = throw_spans;
+    throw_map.throw_details = throw_details;
+  } else {
+    throw_map.throw_details.retain(keep);
+  }
+}
+
+/// Drops throws found behind `process.env` checks, along with functions left with no other throws.
+/// Run before call analysis so neither callers nor catch blocks see them.
+pub fn exclude_env_guarded_throws(functions_with_throws: HashSet<ThrowMap>) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .filter_map(|mut throw_map| {
+      if !throw_map.throw_details.iter().any(|detail| detail.is_env_guarded) {
+        return Some(throw_map);
+      }
+      retain_throw_details(&mut throw_map, |detail| !detail.is_env_guarded);
+      if throw_map.throw_details.is_empty() {
+        None
+      } else {
+        Some(throw_map)
+      }
+    })
+    .collect()
+}
+
+/// Points each throwing function at its name as found by `FunctionFinder`, so diagnostics can
+/// start there instead of at the start of the declaration.
+pub fn attach_function_name_spans(
+  functions_with_throws: HashSet<ThrowMap>,
+  all_functions: &HashSet<FunctionMap>,
+) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .map(|mut throw_map| {
+      if throw_map.name_span.is_none() {
+        // `const f = () => ...` is recorded at the declarator, which encloses the arrow itself
+        throw_map.name_span = all_functions
+          .iter()
+          .filter(|f| {
+            f.id == throw_map.id
+              && throw_map.function_span.lo() <= f.span.lo()
+              && f.span.hi() <= throw_map.function_span.hi()
+          })
+          .min_by_key(|f| f.span.lo())
+          .map(|f| f.name_span);
+      }
+      throw_map
+    })
+    .collect()
+}
+
+/// Lets overriding class methods inherit the `@throws` annotation of the same-named method on
+/// a base class. `class_parents` maps each class to the class it extends. An annotation written
+/// on the override always wins, so an override documenting only a subset keeps its own list.
+/// Overrides marked `@inheritdoc` add the base method's types to any they document themselves.
+pub fn apply_inherited_throws_annotations(
+  functions_with_throws: HashSet<ThrowMap>,
+  all_functions: &HashSet<FunctionMap>,
+  class_parents: &HashMap<String, String>,
+) -> HashSet<ThrowMap> {
+  functions_with_throws
+    .into_iter()
+    .map(|mut throw_map| {
+      if throw_map.throws_annotation.as_ref().is_some_and(|annotation| !annotation.inherit_doc) {
+        return throw_map;
+      }
+      let mut annotation = throw_map.throws_annotation.take();
+      let mut visited: HashSet<&String> = HashSet::new();
+      let mut parent = throw_map.class_name.as_ref().and_then(|class_name| class_parents.get(class_name));
+      while let Some(parent_class) = parent {
+        // Guard against cyclic `extends` chains in malformed input
+        if !visited.insert(parent_class) {
+          break;
+        }
+        let inherited = all_functions.iter().find_map(|f| {
+          f.throws_annotation
+            .as_ref()
+            .filter(|_| f.class_name.as_ref() == Some(parent_class) && f.name == throw_map.function_or_method_name)
+        });
+        if let Some(base_annotation) = inherited {
+          // `@inheritdoc` keeps the override's own @throws alongside the base method's
+          annotation = Some(match annotation {
+            Some(mut own) => {
+              for error_type in &base_annotation.error_types {
+                if !own.error_types.contains(error_type) {
+                  own.error_types.push(error_type.clone());
+                  if let Some(description) = base_annotation.description_for(error_type) {
+                    own.descriptions.push((error_type.clone(), description.to_string()));
+                  }
+                }
+              }
+              own.covers_all |= base_annotation.covers_all;
+              own.inherit_doc = base_annotation.inherit_doc;
+              own
+            }
+            None => base_annotation.clone(),
+          });
+          // A base that itself says `@inheritdoc` defers further up the chain
+          if !base_annotation.inherit_doc {
+            break;
+          }
+        }
+        parent = class_parents.get(parent_class);
+      }
+      // `@inheritdoc` with nothing to inherit leaves the method undocumented
+      throw_map.throws_annotation = annotation.filter(|annotation| !annotation.error_types.is_empty() || annotation.no_throw.is_some());
+      throw_map
+    })
+    .collect()
+}
+
+/// Merges throws from inline callbacks passed to higher-order array methods (`map`, `forEach`, ...)
+/// into the function enclosing the call. Throws covered by a `@callback` typedef parameter
+/// (recorded in `inline_callback_allowed_throws`) count as documented and are dropped from both the
+/// callback and the enclosing function. Calls are refreshed so call sites see the merged throws.
+pub fn merge_higher_order_callback_throws(
+  functions_with_throws: HashSet<ThrowMap>,
+  calls_to_throws: HashSet<CallToThrowMap>,
+  higher_order_callbacks: &HashSet<Span>,
+  inline_callback_allowed_throws: &HashMap<Span, Vec<String>>,
+) -> (HashSet<ThrowMap>, HashSet<CallToThrowMap>) {
+  let mut functions: Vec<ThrowMap> = functions_with_throws.into_iter().collect();
+
+  for callback_span in higher_order_callbacks {
+    let callback = match functions.iter().find(|f| f.function_span == *callback_span) {
+      Some(callback) if callback.throw_spans.len() == callback.throw_details.len() => callback.clone(),
+      _ => continue,
+    };
+    let allowed_throws = inline_callback_allowed_throws.get(callback_span);
+    let (documented, undocumented): (Vec<_>, Vec<_>) = callback
+      .throw_spans
+      .iter()
+      .zip(callback.throw_details.iter())
+      .partition(|(_, detail)| match (allowed_throws, &detail.error_type) {
+        (Some(allowed), Some(error_type)) => allowed.contains(error_type),
+        _ => false,
+      });
+    let documented_spans: Vec<Span> = documented.into_iter().map(|(span, _)| *span).collect();
+
+    // The innermost other function containing the callback is the one that runs it
+    let enclosing_index = functions
+      .iter()
+      .enumerate()
+      .filter(|(_, f)| f.function_span != *callback_span && f.function_span.contains(*callback_span))
+      .min_by_key(|(_, f)| f.function_span.hi.0 - f.function_span.lo.0)
+      .map(|(index, _)| index);
+
+    for (index, function) in functions.iter_mut().enumerate() {
+      let is_affected = function.function_span == *callback_span || Some(index) == enclosing_index;
+      if !is_affected || function.throw_spans.len() != function.throw_details.len() {
+        continue;
+      }
+      let (spans, details): (Vec<_>, Vec<_>) = function
+        .throw_spans
+        .iter()
+        .cloned()
+        .zip(function.throw_details.iter().cloned())
+        .filter(|(span, _)| !documented_spans.contains(span))
+        .unzip();
+      function.throw_spans = spans;
+      function.throw_details = details;
+    }
+
+    if let Some(index) = enclosing_index {
+      let enclosing = &mut functions[index];
+      for (span, detail) in undocumented {
+        if !enclosing.throw_spans.contains(span) {
+          enclosing.throw_spans.push(*span);
+          enclosing.throw_details.push(detail.clone());
+        }
+      }
+    }
+  }
+
+  let functions: HashSet<ThrowMap> = functions
+    .into_iter()
+    .filter(|f| !f.throw_spans.is_empty())
+    .collect();
+  let calls = calls_to_throws
+    .into_iter()
+    .filter_map(|mut call| {
+      if call.is_builtin {
+        return Some(call);
+      }
+      let updated = functions.get(&call.throw_map)?.clone();
+      call.throw_map = updated;
+      Some(call)
+    })
+    .collect();
+
+  (functions, calls)
+}
+
+/// Whether an import's module specifier matches an `ignore_imports` pattern: `**` spans any
+/// number of path segments, `*` and `?` match within one, e.g. `@acme/*` or `./legacy/**`
+fn import_source_matches(pattern: &str, source: &str) -> bool {
+  let pattern_segments: Vec<&str> = pattern.split('/').collect();
+  let source_segments: Vec<&str> = source.split('/').collect();
+  file_walker::glob_matches(&pattern_segments, &source_segments)
+}
+
+/// Drops usages of identifiers imported from modules matched by `ignore_imports`, or by an import
+/// declaration carrying an ignore statement (`// @it-throws` above it or at the end of its line).
+/// Returns the ignore statements that dropped at least one usage.
+fn exclude_ignored_import_usages(
+  imported_identifier_usages: &mut HashSet<IdentifierUsage>,
+  module: &Module,
+  comments: &Lrc<SingleThreadedComments>,
+  user_settings: &UserSettings,
+) -> HashSet<Span> {
+  let mut used_comments = HashSet::new();
+  for item in &module.body {
+    let swc_ecma_ast::ModuleItem::ModuleDecl(swc_ecma_ast::ModuleDecl::Import(import)) = item else {
+      continue;
+    };
+    let source = import.src.value.to_string();
+    let ignored_by_setting = user_settings
+      .ignore_imports
+      .iter()
+      .any(|pattern| import_source_matches(pattern, &source));
+    let ignore_comment = comments
+      .get_leading(import.span.lo())
+      .into_iter()
+      .chain(comments.get_trailing(import.span.hi()))
+      .flatten()
+      .find(|comment| ignore_comment_error_types(&comment.text, &user_settings.ignore_statements).is_some())
+      .map(|comment| comment.span);
+    if !ignored_by_setting && ignore_comment.is_none() {
+      continue;
+    }
+
+    // Usages are recorded under the imported name for named imports and the local one otherwise
+    let mut names = HashSet::new();
+    for specifier in &import.specifiers {
+      match specifier {
+        swc_ecma_ast::ImportSpecifier::Named(named) => {
+          names.insert(named.local.sym.to_string());
+          if let Some(imported) = &named.imported {
+            names.insert(match imported {
+              swc_ecma_ast::ModuleExportName::Ident(ident) => ident.sym.to_string(),
+              swc_ecma_ast::ModuleExportName::Str(str) => str.value.to_string(),
+            });
+          }
+        }
+        swc_ecma_ast::ImportSpecifier::Default(default) => {
+          names.insert(default.local.sym.to_string());
+        }
+        swc_ecma_ast::ImportSpecifier::Namespace(namespace) => {
+          names.insert(namespace.local.sym.to_string());
+        }
+      }
+    }
+    let usages_before = imported_identifier_usages.len();
+    imported_identifier_usages.retain(|usage| !names.contains(&usage.identifier_name));
+    if let Some(comment_span) = ignore_comment {
+      if !ignored_by_setting && imported_identifier_usages.len() < usages_before {
+        used_comments.insert(comment_span);
+      }
+    }
+  }
+  used_comments
+}
+
+/// The ignore statements immediately above class declarations, exported or not, with the classes they cover
+pub fn class_ignore_comments(module: &Module, comments: &dyn Comments, ignore_statements: &[String]) -> Vec<ClassIgnoreComment> {
+  struct ClassIgnoreCommentFinder<'a> {
+    comments: &'a dyn Comments,
+    ignore_statements: &'a [String],
+    found: Vec<ClassIgnoreComment>,
+  }
+
+  impl<'a> ClassIgnoreCommentFinder<'a> {
+    // `export` comes before the class, so comments above an exported class lead the export
+    fn check(&mut self, comment_pos: BytePos, class_span: Span) {
+      let found = self.comments.get_leading(comment_pos).into_iter().flatten().find_map(|comment| {
+        ignore_comment_error_types(&comment.text, self.ignore_statements).map(|error_types| ClassIgnoreComment {
+          comment_span: comment.span,
+          class_span,
+          error_types,
+        })
+      });
+      self.found.extend(found);
+    }
+  }
+
+  impl<'a> Visit for ClassIgnoreCommentFinder<'a> {
+    fn visit_module_decl(&mut self, module_decl: &swc_ecma_ast::ModuleDecl) {
+      match module_decl {
+        swc_ecma_ast::ModuleDecl::ExportDecl(export) => {
+          if let swc_ecma_ast::Decl::Class(class_decl) = &export.decl {
+            self.check(export.span.lo(), class_decl.class.span);
+          }
+        }
+        swc_ecma_ast::ModuleDecl::ExportDefaultDecl(export) => {
+          if let swc_ecma_ast::DefaultDecl::Class(class_expr) = &export.decl {
+            self.check(export.span.lo(), class_expr.class.span);
+          }
+        }
+        _ => {}
+      }
+      swc_ecma_visit::visit_module_decl(self, module_decl);
+    }
+
+    fn visit_class_decl(&mut self, class_decl: &swc_ecma_ast::ClassDecl) {
+      self.check(class_decl.class.span.lo(), class_decl.class.span);
+      swc_ecma_visit::visit_class_decl(self, class_decl);
+    }
+  }
+
+  let mut finder = ClassIgnoreCommentFinder { comments, ignore_statements, found: Vec::new() };
+  finder.visit_module(module);
+  finder.found
+}
+
 /// Find unused @it-throws comments by checking if they actually suppress any diagnostics
 fn find_unused_it_throws_comments(
   comments: &Lrc<SingleThreadedComments>,
   module: &swc_ecma_ast::Module,
   ignore_statements: &[String],
+  next_line_comments: &[NextLineComment],
   throw_analyzer: &ThrowAnalyzer,
   call_finder: &CallFinder,
 ) -> Vec<Span> {
@@ -654,7 +1476,7 @@ fn find_unused_it_throws_comments(
       if let Some(leading_comments) = self.comments.get_leading(pos) {
         for comment in leading_comments.iter() {
           let comment_text = comment.text.trim();
-          if self.ignore_statements.iter().any(|keyword| comment_text.trim() == keyword) {
+          if ignore_comment_error_types(comment_text, &self.ignore_statements).is_some() {
             self.all_it_throws_comments.insert(comment.span);
           }
         }
@@ -664,7 +1486,7 @@ fn find_unused_it_throws_comments(
       if let Some(trailing_comments) = self.comments.get_trailing(pos) {
         for comment in trailing_comments.iter() {
           let comment_text = comment.text.trim();
-          if self.ignore_statements.iter().any(|keyword| comment_text.trim() == keyword) {
+          if ignore_comment_error_types(comment_text, &self.ignore_statements).is_some() {
             self.all_it_throws_comments.insert(comment.span);
           }
         }
@@ -679,6 +1501,24 @@ fn find_unused_it_throws_comments(
       swc_ecma_visit::visit_module(self, module);
     }
 
+    fn visit_module_decl(&mut self, module_decl: &swc_ecma_ast::ModuleDecl) {
+      // Import declarations can carry an ignore statement above them or at the end of their line,
+      // exported classes one above the `export`
+      match module_decl {
+        swc_ecma_ast::ModuleDecl::Import(import) => {
+          self.check_comments_at_position(import.span.lo());
+          self.check_comments_at_position(import.span.hi());
+        }
+        swc_ecma_ast::ModuleDecl::ExportDecl(swc_ecma_ast::ExportDecl { span, decl: swc_ecma_ast::Decl::Class(_) })
+        | swc_ecma_ast::ModuleDecl::ExportDefaultDecl(swc_ecma_ast::ExportDefaultDecl {
+          span,
+          decl: swc_ecma_ast::DefaultDecl::Class(_),
+        }) => self.check_comments_at_position(span.lo()),
+        _ => {}
+      }
+      swc_ecma_visit::visit_module_decl(self, module_decl);
+    }
+
     fn visit_stmt(&mut self, stmt: &swc_ecma_ast::Stmt) {
       // Check comments at the statement level
       self.check_comments_at_position(stmt.span().lo());
@@ -694,6 +1534,7 @@ fn find_unused_it_throws_comments(
 
   let mut collector = CommentCollector::new(ignore_statements, comments);
   collector.visit_module(module);
+  collector.all_it_throws_comments.extend(next_line_comments.iter().map(|comment| comment.comment_span));
   let all_it_throws_comments: Vec<Span> = collector.all_it_throws_comments.into_iter().collect();
 
   // Step 2: Collect used comments from analyzers
@@ -725,8 +1566,75 @@ pub fn analyze_code(
   cm: Lrc<SourceMap>,
   user_settings: &UserSettings,
 ) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
-  // Debug output removed for cleaner logs
+  let (result, cm, comments, _) = analyze_code_with_stats(content, cm, user_settings);
+  (result, cm, comments)
+}
+
+/// Like `analyze_code`, also reporting how long parsing and each visitor took and how much was found
+pub fn analyze_code_with_stats(
+  content: &str,
+  cm: Lrc<SourceMap>,
+  user_settings: &UserSettings,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>, AnalysisStats) {
+  let mut stats = AnalysisStats::default();
+  let (result, cm, comments) = analyze_content(content, cm, user_settings, None, &mut stats);
+  (result, cm, comments, stats)
+}
+
+/// Analyze `content` after `edit`, only running ThrowFinder over the top-level items the edit
+/// touched and reusing `previous`'s throws for the others. Calls, catches, and propagation are
+/// still worked out for the whole file. `previous` must be the analysis of the content before the
+/// edit, made with the same settings; edits to `@typedef`/`@callback` definitions or interface
+/// `@throws` other functions rely on need a full `analyze_code`.
+pub fn analyze_code_incremental(
+  content: &str,
+  cm: Lrc<SourceMap>,
+  user_settings: &UserSettings,
+  previous: &AnalysisResult,
+  edit: TextEdit,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
+  if !previous.parse_errors.is_empty() {
+    return analyze_code(content, cm, user_settings);
+  }
+  analyze_content(content, cm, user_settings, Some((&previous.incremental, edit)), &mut AnalysisStats::default())
+}
+
+fn analyze_content(
+  content: &str,
+  cm: Lrc<SourceMap>,
+  user_settings: &UserSettings,
+  previous: Option<(&IncrementalState, TextEdit)>,
+  stats: &mut AnalysisStats,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
   let fm = cm.new_source_file(swc_common::FileName::Anon, content.into());
+  let (parsed, parse_time) = timed(|| parse_source_file(&fm));
+  stats.parse_time = parse_time;
+  match parsed {
+    Ok((module, comments, recovered_errors)) => {
+      let (mut result, cm, comments) =
+        analyze_module_with_previous(&module, cm, comments, user_settings, previous, stats);
+      result.parse_errors = recovered_errors;
+      (result, cm, comments)
+    }
+    Err(e) => {
+      if user_settings.debug {
+        eprintln!("Failed to parse module: {:?}", e);
+      }
+      // Return empty analysis result on parse failure
+      let result = AnalysisResult {
+        parse_errors: vec![e],
+        ..Default::default()
+      };
+      (result, cm, Lrc::new(SingleThreadedComments::default()))
+    }
+  }
+}
+
+/// Parse a source file already registered in a `SourceMap`, collecting its comments and the
+/// syntax errors the parser recovered from. Those leave a partial module that can still be analyzed.
+pub fn parse_source_file(
+  fm: &SourceFile,
+) -> Result<(Module, Lrc<SingleThreadedComments>, Vec<ParseError>), ParseError> {
   let comments = Lrc::new(SingleThreadedComments::default());
   let lexer = Lexer::new(
     Syntax::Typescript(swc_ecma_parser::TsConfig {
@@ -737,28 +1645,62 @@ pub fn analyze_code(
       disallow_ambiguous_jsx_like: false,
     }),
     EsVersion::latest(),
-    StringInput::from(&*fm),
+    StringInput::from(fm),
     Some(&comments),
   );
 
   let mut parser = Parser::new_from(lexer);
-  let module = match parser.parse_module() {
-    Ok(module) => module,
-    Err(e) => {
-      eprintln!("❌ Failed to parse module: {:?}", e);
-      // Return empty analysis result on parse failure
-      return (AnalysisResult::default(), cm, Lrc::new(SingleThreadedComments::default()));
-    }
-  };
+  let module = parser.parse_module().map_err(|error| ParseError::from(&error))?;
+  let recovered_errors = parser.take_errors().iter().map(ParseError::from).collect();
+  Ok((module, comments, recovered_errors))
+}
+
+/// Analyze an already parsed module. `cm` must be the `SourceMap` its spans point into and
+/// `comments` the comments collected while parsing it.
+pub fn analyze_module(
+  module: &Module,
+  cm: Lrc<SourceMap>,
+  comments: Lrc<SingleThreadedComments>,
+  user_settings: &UserSettings,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
+  analyze_module_with_previous(module, cm, comments, user_settings, None, &mut AnalysisStats::default())
+}
+
+fn analyze_module_with_previous(
+  module: &Module,
+  cm: Lrc<SourceMap>,
+  comments: Lrc<SingleThreadedComments>,
+  user_settings: &UserSettings,
+  previous: Option<(&IncrementalState, TextEdit)>,
+  stats: &mut AnalysisStats,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
+  let ((mut result, cm, comments), trace) = debug_log::capture(user_settings.debug, || {
+    analyze_parsed_module(module, cm, comments, user_settings, previous, stats)
+  });
+  result.debug_log = trace;
+  (result, cm, comments)
+}
+
+fn analyze_parsed_module(
+  module: &Module,
+  cm: Lrc<SourceMap>,
+  comments: Lrc<SingleThreadedComments>,
+  user_settings: &UserSettings,
+  previous: Option<(&IncrementalState, TextEdit)>,
+  stats: &mut AnalysisStats,
+) -> (AnalysisResult, Lrc<SourceMap>, Lrc<SingleThreadedComments>) {
   // Create and populate type registry from JSDoc definitions
-  let mut callback_finder = CallbackFinder::new(comments.clone());
-  callback_finder.analyze_module(&module);
+  let mut callback_finder = CallbackFinder::new(comments.clone()).with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  stats.time_visitor("CallbackFinder", || callback_finder.analyze_module(module));
   
-  let mut typedef_finder = TypedefFinder::new(comments.clone());
-  typedef_finder.analyze_module(&module);
+  let mut typedef_finder = TypedefFinder::new(comments.clone()).with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  stats.time_visitor("TypedefFinder", || typedef_finder.analyze_module(module));
   
-  let mut param_finder = ParamFinder::new(comments.clone());
-  param_finder.visit_module(&module);
+  let mut param_finder = ParamFinder::new(comments.clone()).with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  stats.time_visitor("ParamFinder", || param_finder.visit_module(module));
+
+  let mut interface_finder = InterfaceFinder::new(comments.clone()).with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  stats.time_visitor("InterfaceFinder", || interface_finder.visit_module(module));
   
   // Build type registry from callback and typedef definitions
   let mut type_registry = TypeRegistry::new();
@@ -773,61 +1715,81 @@ pub fn analyze_code(
     type_registry.typedefs.insert(name.clone(), typedef_def.clone());
   }
 
+  // Add the @throws of interface methods, for the classes implementing them
+  type_registry.interface_method_throws = interface_finder.method_throws;
+  type_registry.class_interfaces = interface_finder.class_interfaces;
+  type_registry.interface_parents = interface_finder.interface_parents;
+
+  let next_line_comments = next_line_comments(&cm, &comments, &user_settings.ignore_statements);
+  let throwfinder_settings = ThrowFinderSettings {
+    ignore_statements: &user_settings.ignore_statements.clone(),
+    include_try_statements: &user_settings.include_try_statement_throws.clone(),
+    error_factories: &user_settings.error_factories,
+    assertion_functions: &user_settings.assertion_functions,
+    throws_tag_aliases: &user_settings.throws_tag_aliases,
+    next_line_comments: &next_line_comments,
+  };
   let mut throw_collector = ThrowAnalyzer {
-    comments: comments.clone(),
-    functions_with_throws: HashSet::new(),
-    json_parse_calls: vec![],
-    fs_access_calls: vec![],
-    import_sources: HashSet::new(),
-    imported_identifiers: Vec::new(),
-    function_name_stack: vec![],
-    current_class_name: None,
-    current_method_name: None,
-    throwfinder_settings: ThrowFinderSettings {
-      ignore_statements: &user_settings.ignore_statements.clone(),
-      include_try_statements: &user_settings.include_try_statement_throws.clone(),
-    },
-    used_it_throws_comments: HashSet::new(),
     type_registry,
+    ..ThrowAnalyzer::new(comments.clone(), throwfinder_settings, user_settings)
   };
-  throw_collector.visit_module(&module);
-  
-  let mut call_collector = CallFinder::new(comments.clone());
-  call_collector.functions_with_throws = throw_collector.functions_with_throws.clone();
-  call_collector.visit_module(&module);
-
-  // Mark function-level @it-throws comments as used
-  throw_collector.mark_function_it_throws_comments_as_used();
-
-  // Find all @it-throws comments and determine which are unused
-  let unused_comments = find_unused_it_throws_comments(
-    &comments,
-    &module,
-    &user_settings.ignore_statements,
-    &throw_collector,
-    &call_collector,
-  );
-
-  let mut import_usages_collector = ImportUsageFinder {
-    imported_identifiers: throw_collector.imported_identifiers.clone(),
-    imported_identifier_usages: HashSet::new(),
-    current_class_name: None,
-    current_method_name: None,
-    function_name_stack: vec![],
+  let file_start = cm.lookup_source_file(module.span.lo).start_pos;
+  let reused_items = match previous {
+    Some((previous, edit)) => reuse_unchanged_items(module, file_start, previous, edit, &mut throw_collector),
+    None => Vec::new(),
   };
-  import_usages_collector.visit_module(&module);
-
+  stats.time_visitor("ThrowAnalyzer", || {
+    throw_collector.visit_module_items_where(module, |item| !reused_items.contains(&item.span()))
+  });
+  let direct_throws = throw_collector.functions_with_throws.clone();
+  stats.throw_count = direct_throws.iter().map(|throw_map| throw_map.throw_spans.len()).sum();
+  let direct_used_it_throws_comments = throw_collector.used_it_throws_comments.clone();
+  
   // Build a map of callback typedef names -> their throws types for parameter mapping
-  let callback_type_throws: std::collections::HashMap<String, Vec<String>> = typedef_finder
+  let mut callback_type_throws: std::collections::HashMap<String, Vec<String>> = typedef_finder
     .get_callback_typedefs()
     .into_iter()
     .filter_map(|(name, def)| def.throws_annotation.as_ref().map(|ann| (name, ann.error_types.clone())))
     .collect();
+  // `@callback` definitions document parameter types the same way `@typedef {function}` does
+  for (name, callback_def) in callback_finder.get_all_callbacks() {
+    if let Some(annotation) = &callback_def.throws_annotation {
+      callback_type_throws
+        .entry(name.clone())
+        .or_insert_with(|| annotation.error_types.clone());
+    }
+  }
 
-  let mut function_collector = FunctionFinder::new(comments.clone()).with_callback_types(callback_type_throws);
-  function_collector.visit_module(&module);
+  let mut function_collector = FunctionFinder::new(comments.clone())
+    .with_callback_types(callback_type_throws)
+    .with_throws_tag_aliases(user_settings.throws_tag_aliases.clone());
+  stats.time_visitor("FunctionFinder", || function_collector.visit_module(module));
+  stats.function_count = function_collector.functions.len();
+  let mut reanalyzed_functions: Vec<&FunctionMap> = function_collector
+    .functions
+    .iter()
+    .filter(|function| !reused_items.iter().any(|item| item.contains(function.span)))
+    .collect();
+  reanalyzed_functions.sort_by_key(|function| (function.span.lo, function.span.hi));
+  let reanalyzed_functions: Vec<String> = reanalyzed_functions.into_iter().map(|function| function.id.clone()).collect();
+  throw_collector.functions_with_throws = resolve_error_factory_throws(
+    throw_collector.functions_with_throws,
+    &function_collector.error_factories,
+  );
+  // Ambient declarations contribute their documented @throws in place of a body
+  throw_collector.functions_with_throws.extend(function_collector.unimplemented_ambient_throws());
+  throw_collector.type_registry.class_parents = function_collector.class_parents.clone();
+  throw_collector.functions_with_throws =
+    resolve_error_ancestors(throw_collector.functions_with_throws, &throw_collector.type_registry);
+  throw_collector.functions_with_throws =
+    attach_function_name_spans(throw_collector.functions_with_throws, &function_collector.functions);
+  if user_settings.treat_env_guarded_throws_as == EnvGuardedThrows::Exclude {
+    throw_collector.functions_with_throws = exclude_env_guarded_throws(throw_collector.functions_with_throws);
+  }
   // Pass parameter-level throws metadata from function finder to call finder
+  let mut call_collector = CallFinder::new(comments.clone());
   call_collector.param_throws = function_collector.param_throws.clone();
+  call_collector.next_line_comments = next_line_comments.clone();
   
   // Integrate parameter throws information from the new param finder
   // This provides more detailed parameter-level @throws analysis
@@ -849,37 +1811,98 @@ pub fn analyze_code(
       
       // Add the throws information for this parameter
       param_throws_vec[param_info.param_index] = param_info.throws_annotation.error_types.clone();
+      call_collector
+        .param_indices
+        .entry(function_id.clone())
+        .or_default()
+        .insert(param_info.param_name.clone(), param_info.param_index);
     }
     
     call_collector.param_throws.insert(function_id.clone(), param_throws_vec);
   }
   
-  println!("🔧 Registered functions:");
+  call_collector.functions_with_throws = throw_collector.functions_with_throws.clone();
+  call_collector.builtin_throw_calls = throw_collector.builtin_throw_calls.clone();
+  stats.time_visitor("CallFinder", || call_collector.visit_module(module));
+
+  // Fold throws from inline callbacks of map/forEach/... into their enclosing functions
+  let (merged_functions_with_throws, merged_calls) = merge_higher_order_callback_throws(
+    throw_collector.functions_with_throws,
+    call_collector.calls,
+    &call_collector.higher_order_callbacks,
+    &call_collector.inline_callback_allowed_throws,
+  );
+  // Overrides without their own @throws inherit the base-class method's annotation
+  throw_collector.functions_with_throws = apply_inherited_throws_annotations(
+    merged_functions_with_throws,
+    &function_collector.functions,
+    &function_collector.class_parents,
+  );
+  call_collector.calls = merged_calls;
+
+  // Mark function-level @it-throws comments as used
+  throw_collector.mark_function_it_throws_comments_as_used();
+  let class_ignore_comments = class_ignore_comments(module, &*comments, &user_settings.ignore_statements);
+  throw_collector.mark_class_it_throws_comments_as_used(&class_ignore_comments, &call_collector.calls);
+
+  let mut import_usages_collector = ImportUsageFinder {
+    imported_identifiers: throw_collector.imported_identifiers.clone(),
+    imported_identifier_usages: HashSet::new(),
+    current_class_name: None,
+    current_method_name: None,
+    function_name_stack: vec![],
+  };
+  stats.time_visitor("ImportUsageFinder", || import_usages_collector.visit_module(module));
+  let used_import_comments = exclude_ignored_import_usages(
+    &mut import_usages_collector.imported_identifier_usages,
+    module,
+    &comments,
+    user_settings,
+  );
+
+  // Find all @it-throws comments and determine which are unused
+  let mut unused_comments = find_unused_it_throws_comments(
+    &comments,
+    module,
+    &user_settings.ignore_statements,
+    &next_line_comments,
+    &throw_collector,
+    &call_collector,
+  );
+  unused_comments.retain(|comment_span| !used_import_comments.contains(comment_span));
+
+  debug_log!("Registered functions:");
   for func in &function_collector.functions {
-    println!("  - {} ({})", func.name, func.id);
+    debug_log!("  - {} ({})", func.name, func.id);
   }
 
   // Create and populate catch analyses with actual thrown errors
   let mut try_catch_finder = TryCatchFinder::new(comments.clone());
-  try_catch_finder.visit_module(&module);
+  try_catch_finder.terminator_functions = user_settings.terminator_functions.clone();
+  try_catch_finder.include_catch_rethrows = user_settings.include_catch_rethrows;
+  stats.time_visitor("TryCatchFinder", || try_catch_finder.visit_module(module));
   
   // Populate catch analyses with actual thrown errors found by ThrowFinder
   let populated_catch_analyses = populate_catch_analyses_with_throws(
     try_catch_finder.all_catches, 
     &throw_collector.functions_with_throws,
-    &module,
+    module,
+    user_settings.treat_env_guarded_throws_as,
+    &throw_collector.type_registry,
+    &user_settings.assertion_functions,
+    user_settings.flag_json_parse,
   );
   
-  println!("🔧 Catch analysis populated:");
+  debug_log!("Catch analysis populated:");
   for (i, catch_analysis) in populated_catch_analyses.iter().enumerate() {
-    println!("  [{}] Try block has {} thrown errors: {:?}", 
+    debug_log!("  [{}] Try block has {} thrown errors: {:?}", 
       i, 
       catch_analysis.errors_thrown_in_try.len(),
       catch_analysis.errors_thrown_in_try
     );
-    println!("  [{}] Catch handles: {:?}", i, catch_analysis.errors_handled_in_catch);
-    println!("  [{}] Effectively caught: {:?}", i, catch_analysis.errors_effectively_caught);
-    println!("  [{}] Propagated: {:?}", i, catch_analysis.errors_propagated);
+    debug_log!("  [{}] Catch handles: {:?}", i, catch_analysis.errors_handled_in_catch);
+    debug_log!("  [{}] Effectively caught: {:?}", i, catch_analysis.errors_effectively_caught);
+    debug_log!("  [{}] Propagated: {:?}", i, catch_analysis.errors_propagated);
   }
 
   // First, get the preliminary filtered functions (before propagation)
@@ -894,7 +1917,9 @@ pub fn analyze_code(
         
         let mut filtered_throw_details = throw_map.throw_details.clone();
         filtered_throw_details.retain(|throw_detail| {
-          if let Some(ref error_type) = throw_detail.error_type {
+          if throw_detail.is_rethrow {
+            user_settings.include_catch_rethrows
+          } else if let Some(ref error_type) = throw_detail.error_type {
             !effectively_caught_errors.contains(error_type)
           } else {
             true
@@ -914,12 +1939,21 @@ pub fn analyze_code(
   // Handle different logic based on include_try_statement_throws setting
   let (final_functions_with_throws, filtered_calls_to_throws) = if user_settings.include_try_statement_throws {
     // When including try statement throws, use original calls and simple propagation
-    let final_functions = propagate_throws_to_callers_without_catch_filtering(
+    let (functions_with_throws, calls) = resolve_rethrow_placeholders(
       throw_collector.functions_with_throws,
-      &call_collector.calls,
+      call_collector.calls,
+      &populated_catch_analyses,
+    );
+    let (final_functions, propagation_iterations) = propagate_throws_to_callers_without_catch_filtering(
+      functions_with_throws,
+      &calls,
+      &call_collector.call_edges,
       &function_collector.functions,
+      user_settings.max_call_depth,
+      user_settings.include_catch_rethrows,
     );
-    (final_functions, call_collector.calls)
+    stats.propagation_iterations = propagation_iterations;
+    (final_functions, calls)
   } else {
     // When excluding try statement throws, filter calls and use enhanced catch analysis
     let filtered_calls = filter_calls_through_catch_analysis_and_function_availability(
@@ -928,13 +1962,19 @@ pub fn analyze_code(
       &populated_catch_analyses,
       &preliminary_filtered_functions,
     );
+    let (functions_with_throws, filtered_calls) =
+      resolve_rethrow_placeholders(throw_collector.functions_with_throws, filtered_calls, &populated_catch_analyses);
     
-    let final_functions = propagate_throws_to_callers(
-      throw_collector.functions_with_throws,
+    let (final_functions, propagation_iterations) = propagate_throws_to_callers(
+      functions_with_throws,
       &filtered_calls,
+      &call_collector.call_edges,
       &function_collector.functions,
       &populated_catch_analyses,
+      user_settings.max_call_depth,
+      user_settings.include_catch_rethrows,
     );
+    stats.propagation_iterations = propagation_iterations;
     
     (final_functions, filtered_calls)
   };
@@ -942,37 +1982,478 @@ pub fn analyze_code(
   // Apply include_try_statements setting: filter out throws that are within try blocks
   // if the user has disabled include_try_statements
   let final_functions_with_throws = if user_settings.include_try_statement_throws {
-    println!("🔧 include_try_statement_throws is true, keeping all {} functions with throws", final_functions_with_throws.len());
+    debug_log!("include_try_statement_throws is true, keeping all {} functions with throws", final_functions_with_throws.len());
     final_functions_with_throws
   } else {
-    println!("🔧 include_try_statement_throws is false, filtering {} functions with throws", final_functions_with_throws.len());
+    debug_log!("include_try_statement_throws is false, filtering {} functions with throws", final_functions_with_throws.len());
     let filtered = filter_functions_exclude_try_block_throws(
       final_functions_with_throws,
       &function_collector.functions,
       &populated_catch_analyses,
     );
-    println!("🔧 After filtering: {} functions remain", filtered.len());
+    debug_log!("After filtering: {} functions remain", filtered.len());
     for func in &filtered {
-      println!("  - Remaining function: {} ({})", func.function_or_method_name, func.id);
+      debug_log!("  - Remaining function: {} ({})", func.function_or_method_name, func.id);
     }
     filtered
   };
 
-  println!("🔧 Final result summary:");  
-  println!("  - functions_with_throws: {}", final_functions_with_throws.len());
-  println!("  - calls_to_throws: {}", filtered_calls_to_throws.len());
+  debug_log!("Final result summary:");  
+  debug_log!("  - functions_with_throws: {}", final_functions_with_throws.len());
+  debug_log!("  - calls_to_throws: {}", filtered_calls_to_throws.len());
   
   (AnalysisResult {
     functions_with_throws: final_functions_with_throws,
     calls_to_throws: filtered_calls_to_throws, // Use filtered calls instead of raw calls
-    json_parse_calls: throw_collector.json_parse_calls,
-    fs_access_calls: throw_collector.fs_access_calls,
+    builtin_throw_calls: throw_collector.builtin_throw_calls,
     import_sources: throw_collector.import_sources,
     imported_identifiers: throw_collector.imported_identifiers,
     imported_identifier_usages: import_usages_collector.imported_identifier_usages,
     catch_analyses: populated_catch_analyses, // Use populated catch analyses
     unused_it_throws_comments: unused_comments,
+    class_ignore_comments,
+    throw_statement_spans: diagnostics::throw_statement_spans(module),
     all_functions: function_collector.functions, // Include all functions for JSDoc checking
     inline_callback_allowed_throws: call_collector.inline_callback_allowed_throws, // Pass inline callback allowed throws
+    parse_errors: Vec::new(),
+    debug_log: Vec::new(),
+    incremental: IncrementalState {
+      file_start,
+      direct_throws,
+      used_it_throws_comments: direct_used_it_throws_comments,
+      reanalyzed_functions,
+    },
   }, cm, comments)
 }
+
+/// Carry `previous` throws over to `throw_collector` for the top-level items of `module` that `edit`
+/// didn't touch, returning their spans. An item's leading comments count as part of it.
+fn reuse_unchanged_items(
+  module: &Module,
+  file_start: BytePos,
+  previous: &IncrementalState,
+  edit: TextEdit,
+  throw_collector: &mut ThrowAnalyzer,
+) -> Vec<Span> {
+  let mut reused_items = Vec::new();
+  let mut region_lo = file_start;
+  for item in &module.body {
+    let item_span = item.span();
+    let (lo, hi) = ((region_lo - file_start).0, (item_span.hi - file_start).0);
+    region_lo = item_span.hi;
+    let old_lo = if hi < edit.start {
+      lo
+    } else if lo > edit.new_end {
+      lo - edit.new_end + edit.old_end
+    } else {
+      continue;
+    };
+    let old_region = Span::new(
+      previous.file_start + BytePos(old_lo),
+      previous.file_start + BytePos(old_lo + hi - lo),
+      Default::default(),
+    );
+    let new_region_lo = file_start + BytePos(lo);
+    let rebase = |span: Span| {
+      Span::new(span.lo - old_region.lo + new_region_lo, span.hi - old_region.lo + new_region_lo, span.ctxt)
+    };
+
+    for throw_map in previous.direct_throws.iter().filter(|throw_map| old_region.contains(throw_map.function_span)) {
+      let mut throw_map = throw_map.clone();
+      throw_map.throw_spans = throw_map.throw_spans.into_iter().map(rebase).collect();
+      throw_map.throw_statement = rebase(throw_map.throw_statement);
+      throw_map.function_span = rebase(throw_map.function_span);
+      throw_map.name_span = throw_map.name_span.map(rebase);
+      for detail in &mut throw_map.throw_details {
+        detail.arg_span = detail.arg_span.map(rebase);
+      }
+      throw_collector.functions_with_throws.insert(throw_map);
+    }
+    for comment_span in previous.used_it_throws_comments.iter().filter(|span| old_region.contains(**span)) {
+      throw_collector.used_it_throws_comments.insert(rebase(*comment_span));
+    }
+    reused_items.push(item_span);
+  }
+  reused_items
+}
+
+/// What a function throws and what its JSDoc documents, for editor hovers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThrowSummary {
+  pub name: String,
+  pub thrown_types: Vec<String>, // Thrown directly or inherited from callees, after catch analysis
+  pub documented_types: Vec<String>, // From the function's JSDoc @throws tags
+}
+
+/// The throws of the innermost function containing a position of a single file's analysis.
+/// `line` is 1-based and `col` 0-based, like `swc_common::Loc`. `None` when the position is
+/// outside every function, or the innermost function containing it doesn't throw.
+pub fn throws_at_position(result: &AnalysisResult, cm: &SourceMap, line: usize, col: usize) -> Option<ThrowSummary> {
+  let contains = |span: Span| {
+    let (lo, hi) = (cm.lookup_char_pos(span.lo), cm.lookup_char_pos(span.hi));
+    (lo.line, lo.col.0) <= (line, col) && (line, col) < (hi.line, hi.col.0)
+  };
+  // Inline callbacks only have a throw map; every other function has a function map
+  let (id, name, span, throws_annotation) = result
+    .all_functions
+    .iter()
+    .map(|fun| (&fun.id, &fun.name, fun.span, &fun.throws_annotation))
+    .chain(
+      result
+        .functions_with_throws
+        .iter()
+        .map(|fun| (&fun.id, &fun.function_or_method_name, fun.function_span, &fun.throws_annotation)),
+    )
+    .filter(|(_, _, span, _)| !span.is_dummy() && contains(*span))
+    .min_by_key(|(id, _, span, _)| (span.hi - span.lo, *id))?;
+
+  let throw_details: Vec<&ThrowDetails> = result
+    .functions_with_throws
+    .iter()
+    .filter(|fun| fun.id == *id && fun.function_span == span)
+    .flat_map(|fun| &fun.throw_details)
+    .collect();
+  if throw_details.is_empty() {
+    return None;
+  }
+  let mut thrown_types: Vec<String> = throw_details.iter().filter_map(|detail| detail.error_type.clone()).collect();
+  thrown_types.sort();
+  thrown_types.dedup();
+  let mut documented_types: Vec<String> =
+    throws_annotation.as_ref().map(|annotation| annotation.error_types.clone()).unwrap_or_default();
+  documented_types.sort();
+  documented_types.dedup();
+  Some(ThrowSummary {
+    name: name.clone(),
+    thrown_types,
+    documented_types,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::{Duration, Instant};
+
+  struct TryStmtCollector {
+    try_stmts: Vec<swc_ecma_ast::TryStmt>,
+  }
+
+  impl Visit for TryStmtCollector {
+    fn visit_try_stmt(&mut self, try_stmt: &swc_ecma_ast::TryStmt) {
+      self.try_stmts.push(try_stmt.clone());
+      try_stmt.visit_children_with(self);
+    }
+  }
+
+  // ~40 handlers, each with a nested try, an env-guarded throw and a builtin call, padded to ~6k lines
+  fn large_source() -> String {
+    let mut code = String::new();
+    for i in 0..40 {
+      code.push_str(&format!(
+        r#"
+class NetworkError{i} extends Error {{}}
+class ValidationError{i} extends Error {{}}
+
+function fetch{i}(id) {{
+  if (!id) throw new NetworkError{i}("missing");
+  return id;
+}}
+
+function validate{i}(value) {{
+  if (value < 0) throw new ValidationError{i}("negative");
+  return value;
+}}
+
+function handle{i}(input) {{
+  try {{
+    const parsed = JSON.parse(input);
+    fetch{i}(parsed.id);
+    try {{
+      validate{i}(parsed.value);
+    }} catch (inner) {{
+      console.log(inner);
+    }}
+    if (process.env.NODE_ENV !== "production") {{
+      throw new RangeError("dev only");
+    }}
+  }} catch (e) {{
+    if (e instanceof NetworkError{i}) {{
+      return null;
+    }}
+    throw e;
+  }}
+}}
+"#
+      ));
+      code.push_str(&format!("\nfunction filler{i}() {{\n"));
+      for j in 0..120 {
+        code.push_str(&format!("  const value{j} = compute({j});\n"));
+      }
+      code.push_str("}\n");
+    }
+    code
+  }
+
+  fn settings(treat_env_guarded_throws_as: EnvGuardedThrows) -> UserSettings {
+    UserSettings {
+      ignore_statements: vec!["@it-throws".to_string()],
+      treat_env_guarded_throws_as,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_try_block_index_matches_per_block_analysis() {
+    let code = large_source();
+    assert!(code.lines().count() > 6000);
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(swc_common::FileName::Anon, code);
+    let (module, _, _) = parse_source_file(&fm).expect("large source parses");
+
+    for skip_env_guarded_throws in [false, true] {
+      let mut index = TryBlockIndex::new(skip_env_guarded_throws, &[], true);
+      index.visit_module(&module);
+
+      let mut collector = TryStmtCollector { try_stmts: Vec::new() };
+      collector.visit_module(&module);
+      assert_eq!(collector.try_stmts.len(), 80);
+      assert_eq!(index.blocks.len(), 80);
+
+      // Indexing a try statement on its own is what the analysis used to do for every catch
+      for try_stmt in &collector.try_stmts {
+        let mut single = TryBlockIndex::new(skip_env_guarded_throws, &[], true);
+        single.visit_try_stmt(try_stmt);
+        assert_eq!(index.get(&try_stmt.block.span), single.get(&try_stmt.block.span));
+      }
+    }
+  }
+
+  #[test]
+  fn test_large_file_catch_analysis_is_fast() {
+    let code = large_source();
+    for env_guarded_throws in [EnvGuardedThrows::Include, EnvGuardedThrows::Exclude] {
+      let started = Instant::now();
+      let settings = UserSettings { flag_json_parse: true, ..settings(env_guarded_throws) };
+      let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &settings);
+      assert!(
+        started.elapsed() < Duration::from_secs(10),
+        "analysis took {:?}",
+        started.elapsed()
+      );
+
+      assert_eq!(result.catch_analyses.len(), 80);
+      for i in 0..40 {
+        let thrown_in = |catch_param: &str, error_type: &str| {
+          result
+            .catch_analyses
+            .iter()
+            .filter(|analysis| analysis.catch_param.as_deref() == Some(catch_param))
+            .any(|analysis| analysis.errors_thrown_in_try.contains(&error_type.to_string()))
+        };
+        assert!(thrown_in("e", &format!("NetworkError{}", i)));
+        assert!(thrown_in("inner", &format!("ValidationError{}", i)));
+      }
+      let outer_thrown: Vec<&Vec<String>> = result
+        .catch_analyses
+        .iter()
+        .filter(|analysis| analysis.catch_param.as_deref() == Some("e"))
+        .map(|analysis| &analysis.errors_thrown_in_try)
+        .collect();
+      assert!(outer_thrown.iter().all(|thrown| thrown.contains(&"SyntaxError".to_string())));
+      // The nested try handles its own errors
+      assert!(outer_thrown.iter().all(|thrown| !thrown.iter().any(|t| t.starts_with("ValidationError"))));
+      let expect_range_error = env_guarded_throws == EnvGuardedThrows::Include;
+      assert!(outer_thrown
+        .iter()
+        .all(|thrown| thrown.contains(&"RangeError".to_string()) == expect_range_error));
+    }
+  }
+
+  #[test]
+  fn test_throws_at_position_prefers_innermost_function() {
+    let code = r#"/**
+ * @throws {TypeError}
+ */
+function outer(input) {
+  if (!input) {
+    throw new TypeError("missing");
+  }
+  function inner() {
+    throw new RangeError("inner");
+  }
+  const safe = () => {
+    return 1;
+  };
+  return inner() + safe();
+}
+
+const top = 1;
+"#;
+    let cm: Lrc<SourceMap> = Default::default();
+    let (result, cm, _) = analyze_code(code, cm, &settings(EnvGuardedThrows::Include));
+
+    let outer = throws_at_position(&result, &cm, 6, 4).expect("inside outer");
+    assert_eq!(outer.name, "outer");
+    assert_eq!(outer.thrown_types, vec!["RangeError", "TypeError"]);
+    assert_eq!(outer.documented_types, vec!["TypeError"]);
+
+    let inner = throws_at_position(&result, &cm, 9, 4).expect("inside inner");
+    assert_eq!(inner.name, "inner");
+    assert_eq!(inner.thrown_types, vec!["RangeError"]);
+    assert!(inner.documented_types.is_empty());
+
+    // The innermost function doesn't throw, even though the one around it does
+    assert_eq!(throws_at_position(&result, &cm, 12, 4), None);
+    // Outside every function
+    assert_eq!(throws_at_position(&result, &cm, 17, 0), None);
+  }
+
+  #[test]
+  fn test_object_literal_methods_are_registered_under_the_object_name() {
+    let code = r#"const api = {
+  fetch() {
+    throw new TypeError('down');
+  },
+  'delete'() {
+    throw new RangeError('gone');
+  },
+};
+function run() {
+  api.fetch();
+  api.delete();
+}
+"#;
+    let (result, _, _) = analyze_code(code, Lrc::new(SourceMap::default()), &settings(EnvGuardedThrows::Include));
+
+    let mut throwing_ids: Vec<&str> = result.functions_with_throws.iter().map(|f| f.id.as_str()).collect();
+    throwing_ids.sort();
+    assert!(throwing_ids.contains(&"api-fetch"), "{:?}", throwing_ids);
+    assert!(throwing_ids.contains(&"api-delete"), "{:?}", throwing_ids);
+
+    let mut calls: Vec<(&str, &str)> = result
+      .calls_to_throws
+      .iter()
+      .map(|c| (c.id.as_str(), c.throw_map.id.as_str()))
+      .collect();
+    calls.sort();
+    assert_eq!(calls, vec![("NOT_SET-run", "api-delete"), ("NOT_SET-run", "api-fetch")]);
+  }
+
+  #[test]
+  fn test_incremental_analysis_only_revisits_edited_functions() {
+    fn summary(result: &AnalysisResult) -> Vec<String> {
+      let mut lines: Vec<String> = result
+        .functions_with_throws
+        .iter()
+        .map(|f| {
+          let mut types: Vec<&str> = f.throw_details.iter().filter_map(|d| d.error_type.as_deref()).collect();
+          types.sort();
+          types.dedup();
+          format!("{} throws {:?} at {:?}", f.id, types, f.throw_spans)
+        })
+        .chain(
+          result
+            .calls_to_throws
+            .iter()
+            .map(|c| format!("{} calls {} at {:?}", c.id, c.throw_map.id, c.call_span)),
+        )
+        .collect();
+      lines.sort();
+      lines
+    }
+
+    let settings = settings(EnvGuardedThrows::Include);
+    let code = large_source();
+    let (previous, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &settings);
+    assert!(previous.incremental.reanalyzed_functions.len() > 120);
+
+    // Swap the error validate7 throws, which moves every function after it
+    let old_throw = "throw new ValidationError7(\"negative\")";
+    let start = code.find(old_throw).unwrap();
+    let edited = code.replacen(old_throw, "throw new TypeError(\"negative!\")", 1);
+    let edit = TextEdit {
+      start: start as u32,
+      old_end: (start + old_throw.len()) as u32,
+      new_end: (start + "throw new TypeError(\"negative!\")".len()) as u32,
+    };
+    let (incremental, _, _) =
+      analyze_code_incremental(&edited, Lrc::new(SourceMap::default()), &settings, &previous, edit);
+    let (full, _, _) = analyze_code(&edited, Lrc::new(SourceMap::default()), &settings);
+
+    assert_eq!(incremental.incremental.reanalyzed_functions, vec!["NOT_SET-validate7"]);
+    assert_eq!(summary(&incremental), summary(&full));
+    assert!(summary(&incremental)
+      .iter()
+      .any(|line| line.starts_with("NOT_SET-validate7 throws [\"TypeError\"]")));
+
+    // A function added at the end, reusing the incremental result in turn
+    let added = "\nfunction retry() {\n  fetch39(1);\n  throw new URIError(\"again\");\n}\n";
+    let appended = format!("{}{}", edited, added);
+    let edit = TextEdit {
+      start: edited.len() as u32,
+      old_end: edited.len() as u32,
+      new_end: appended.len() as u32,
+    };
+    let (incremental, _, _) =
+      analyze_code_incremental(&appended, Lrc::new(SourceMap::default()), &settings, &incremental, edit);
+    let (full, _, _) = analyze_code(&appended, Lrc::new(SourceMap::default()), &settings);
+
+    assert_eq!(incremental.incremental.reanalyzed_functions, vec!["NOT_SET-retry"]);
+    assert_eq!(summary(&incremental), summary(&full));
+  }
+
+  #[test]
+  fn test_import_source_matches_patterns() {
+    assert!(import_source_matches("@acme/*", "@acme/errors"));
+    assert!(!import_source_matches("@acme/*", "@acme/errors/sub"));
+    assert!(import_source_matches("./legacy/**", "./legacy/v1/convert"));
+    assert!(!import_source_matches("./legacy/**", "../legacy/convert"));
+    assert!(import_source_matches("lodash", "lodash"));
+    assert!(!import_source_matches("lodash", "lodash-es"));
+  }
+
+  #[test]
+  fn test_class_it_throws_comments_are_used_when_a_method_throws() {
+    let code = "// @it-throws\nexport class Legacy {\n  a() {\n    return 1;\n  }\n  b() {\n    throw new TypeError('b');\n  }\n}\n// @it-throws\nclass Quiet {\n  a() {\n    return 1;\n  }\n}\n";
+    let (result, cm, _) = analyze_code(code, Lrc::new(SourceMap::default()), &settings(EnvGuardedThrows::Include));
+
+    let class_lines: Vec<(usize, usize)> = result
+      .class_ignore_comments
+      .iter()
+      .map(|class_comment| (cm.lookup_char_pos(class_comment.comment_span.lo).line, cm.lookup_char_pos(class_comment.class_span.lo).line))
+      .collect();
+    assert_eq!(class_lines, vec![(1, 2), (10, 11)]);
+    let unused_lines: Vec<usize> =
+      result.unused_it_throws_comments.iter().map(|span| cm.lookup_char_pos(span.lo).line).collect();
+    assert_eq!(unused_lines, vec![10]);
+  }
+
+  #[test]
+  fn test_analysis_stats_for_fixture() {
+    let code = include_str!("fixtures/transitivePropagation.ts");
+    let (result, _, _, stats) =
+      analyze_code_with_stats(code, Lrc::new(SourceMap::default()), &settings(EnvGuardedThrows::Include));
+
+    // c, b, a, isEven, isOdd, parity and guarded; only c, isEven and isOdd throw themselves
+    assert_eq!(stats.function_count, 7);
+    assert_eq!(stats.function_count, result.all_functions.len());
+    assert_eq!(stats.throw_count, 3);
+    assert!(stats.propagation_iterations > 0);
+    let visitors: Vec<&str> = stats.visitor_times.iter().map(|(visitor, _)| *visitor).collect();
+    assert_eq!(
+      visitors,
+      vec![
+        "CallbackFinder",
+        "TypedefFinder",
+        "ParamFinder",
+        "InterfaceFinder",
+        "ThrowAnalyzer",
+        "FunctionFinder",
+        "CallFinder",
+        "ImportUsageFinder",
+        "TryCatchFinder",
+      ]
+    );
+  }
+}
@@ -1,23 +1,142 @@
 extern crate what_does_it_throw;
+extern crate serde_json;
 extern crate swc_common;
 extern crate swc_ecma_ast;
 extern crate swc_ecma_parser;
 extern crate swc_ecma_visit;
-use std::{fs, env};
+use std::{fs, env, process};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
-use self::swc_common::{sync::Lrc, SourceMap};
-use what_does_it_throw::{analyze_code, UserSettings};
+use self::swc_common::{sync::Lrc, FileName, SourceMap};
+use what_does_it_throw::{
+  analyze_code, analyze_module, diff_reports, parse_source_file, resolve_analysis, ResolvedReport, AnalysisResult, UserSettings,
+};
+use what_does_it_throw::file_walker::{collect_source_files, is_glob, WalkOptions};
+use what_does_it_throw::report::{
+  catalog_entries, diagnostic_records, documentation_status, files_to_json, AnalysisReport, CatalogEntry, CatalogFormat,
+  DocumentationStatus, FailOn, FileReport,
+};
+
+/// Flags that take the following argument as their value
+const VALUE_FLAGS: [&str; 6] = ["--format", "--fail-on", "--ext", "--baseline", "--report", "--report-format"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+  Text,
+  Json,   // One pretty-printed document once every file is analyzed
+  Ndjson, // One diagnostic per line, written as each file is analyzed
+}
+
+/// Value following a `--flag value` pair on the command line
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+  args.iter()
+    .position(|arg| arg == flag)
+    .and_then(|index| args.get(index + 1))
+    .map(|value| value.as_str())
+}
+
+/// The file, directory or glob to analyze: the first argument that is neither a flag nor a flag's value
+fn target_arg(args: &[String]) -> Option<&str> {
+  let mut rest = args.iter().skip(1);
+  while let Some(arg) = rest.next() {
+    if VALUE_FLAGS.contains(&arg.as_str()) {
+      rest.next();
+    } else if !arg.starts_with("--") {
+      return Some(arg);
+    }
+  }
+  None
+}
 
 pub fn main() {
   let args: Vec<String> = env::args().collect();
+  let format = match flag_value(&args, "--format") {
+    None | Some("text") => OutputFormat::Text,
+    Some("json") => OutputFormat::Json,
+    Some("ndjson") => OutputFormat::Ndjson,
+    Some(other) => {
+      eprintln!("Unknown --format '{}', expected 'text', 'json' or 'ndjson'", other);
+      process::exit(2);
+    }
+  };
   
   // Check if a file path was provided as argument
-  if args.len() > 1 {
-    let file_path = &args[1];
-    let include_try_statements = args.iter().any(|arg| arg == "--include-try-statements");
+  if let Some(file_path) = target_arg(&args) {
+    let user_settings = UserSettings {
+      include_try_statement_throws: args.iter().any(|arg| arg == "--include-try-statements"),
+      debug: args.iter().any(|arg| arg == "--debug"),
+      ..Default::default()
+    };
+    let fail_on = flag_value(&args, "--fail-on").map(|value| {
+      FailOn::parse(value).unwrap_or_else(|| {
+        eprintln!("Unknown --fail-on '{}', expected 'undocumented', 'partial' or 'any'", value);
+        process::exit(2);
+      })
+    });
+    
+    let quiet = args.iter().any(|arg| arg == "--quiet");
+    let mut walk_options = WalkOptions {
+      include_hidden: args.iter().any(|arg| arg == "--include-hidden"),
+      ..Default::default()
+    };
+    if let Some(extensions) = flag_value(&args, "--ext") {
+      walk_options.extensions = extensions
+        .split(',')
+        .map(|ext| ext.trim().trim_start_matches('.').to_string())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    }
+
+    // Only report what is new since the baseline, as a resolved report
+    if let Some(baseline_path) = flag_value(&args, "--baseline") {
+      let files = if is_glob(file_path) || Path::new(file_path).is_dir() {
+        collect_source_files(file_path, &walk_options)
+      } else {
+        vec![PathBuf::from(file_path)]
+      };
+      let delta = diff_reports(&load_baseline(baseline_path), &resolve_files(&files, &user_settings));
+      println!("{}", delta.to_json());
+      if fail_on.is_some_and(|fail_on| delta.has_failures(fail_on)) {
+        process::exit(1);
+      }
+      return;
+    }
     
-    println!("=== Analyzing File: {} ===\n", file_path);
-    analyze_specific_file(file_path, include_try_statements);
+    if let Some(report_path) = flag_value(&args, "--report") {
+      let report_format = match flag_value(&args, "--report-format") {
+        None => CatalogFormat::Markdown,
+        Some(value) => CatalogFormat::parse(value).unwrap_or_else(|| {
+          eprintln!("Unknown --report-format '{}', expected 'markdown' or 'html'", value);
+          process::exit(2);
+        }),
+      };
+      let files = if is_glob(file_path) || Path::new(file_path).is_dir() {
+        collect_source_files(file_path, &walk_options)
+      } else {
+        vec![PathBuf::from(file_path)]
+      };
+      if let Err(error) = fs::write(report_path, report_format.render(&catalog_files(&files, &user_settings))) {
+        eprintln!("{}: could not write report: {}", report_path, error);
+        process::exit(2);
+      }
+    }
+
+    let reports = if is_glob(file_path) || Path::new(file_path).is_dir() {
+      let files = collect_source_files(file_path, &walk_options);
+      analyze_many_files(&files, &user_settings, format, quiet)
+    } else {
+      if format == OutputFormat::Text {
+        println!("=== Analyzing File: {} ===\n", file_path);
+      }
+      vec![analyze_specific_file(file_path, &user_settings, format)]
+    };
+    if fail_on.is_some_and(|fail_on| reports.iter().any(|report| report.has_failures(fail_on))) {
+      process::exit(1);
+    }
+  } else if flag_value(&args, "--format").is_some() {
+    eprintln!("--format needs a file, directory or glob to analyze");
+    process::exit(2);
   } else {
     println!("=== JSDoc @throws Analysis Demo ===\n");
     demo_jsdoc_throws_analysis();
@@ -27,74 +146,219 @@ pub fn main() {
   }
 }
 
-fn analyze_specific_file(file_path: &str, include_try_statements: bool) {
+/// Read and analyze one file, failing when it cannot be read or doesn't parse at all. Syntax errors
+/// the parser recovered from stay in `parse_errors` next to the analysis of the rest of the file.
+fn analyze_file(file_path: &str, user_settings: &UserSettings) -> Result<(AnalysisResult, Lrc<SourceMap>), String> {
   let code = fs::read_to_string(file_path)
-    .unwrap_or_else(|_| panic!("Could not read file: {}", file_path));
+    .map_err(|error| format!("could not read file: {}", error))?;
   
   let cm: Lrc<SourceMap> = Default::default();
-  let user_settings = UserSettings {
-    include_try_statement_throws: include_try_statements,
-    ignore_statements: vec![], // No ignore statements for file analysis
+  let fm = cm.new_source_file(FileName::Anon, code);
+  let (module, comments, recovered_errors) =
+    parse_source_file(&fm).map_err(|parse_error| format!("failed to parse: {}", parse_error.message))?;
+  let (mut result, cm, _comments) = analyze_module(&module, cm, comments, user_settings);
+  result.parse_errors = recovered_errors;
+  for line in &result.debug_log {
+    eprintln!("{}", line);
+  }
+  Ok((result, cm))
+}
+
+/// The resolved report a `--baseline` run compares against. A baseline that doesn't exist yet is empty,
+/// so the first run reports everything and its output can be saved as the baseline.
+fn load_baseline(baseline_path: &str) -> ResolvedReport {
+  let contents = match fs::read_to_string(baseline_path) {
+    Ok(contents) => contents,
+    Err(error) if error.kind() == io::ErrorKind::NotFound => return ResolvedReport::default(),
+    Err(error) => {
+      eprintln!("{}: could not read baseline: {}", baseline_path, error);
+      process::exit(2);
+    }
   };
-  
-  let (result, _cm, _comments) = analyze_code(&code, cm, &user_settings);
-  
+  serde_json::from_str(&contents).unwrap_or_else(|error| {
+    eprintln!("{}: not a resolved report: {}", baseline_path, error);
+    process::exit(2);
+  })
+}
+
+/// One resolved report for every file, skipping files that fail to analyze
+fn resolve_files(files: &[PathBuf], user_settings: &UserSettings) -> ResolvedReport {
+  let mut resolved = ResolvedReport::default();
+  for file in files {
+    let path = file.display().to_string();
+    match analyze_file(&path, user_settings) {
+      Ok((result, cm)) => resolved.extend(resolve_analysis(&path, &result, &cm)),
+      Err(error) => eprintln!("{}: {}", path, error),
+    }
+  }
+  resolved
+}
+
+/// The error catalog entries of every file, skipping files that fail to analyze
+fn catalog_files(files: &[PathBuf], user_settings: &UserSettings) -> Vec<CatalogEntry> {
+  let mut entries = Vec::new();
+  for file in files {
+    let path = file.display().to_string();
+    match analyze_file(&path, user_settings) {
+      Ok((result, cm)) => entries.extend(catalog_entries(&path, &result, &cm)),
+      Err(error) => eprintln!("{}: {}", path, error),
+    }
+  }
+  entries
+}
+
+fn analyze_specific_file(file_path: &str, user_settings: &UserSettings, format: OutputFormat) -> AnalysisReport {
+  let (result, cm) = analyze_file(file_path, user_settings).unwrap_or_else(|error| {
+    eprintln!("{}: {}", file_path, error);
+    process::exit(2);
+  });
+  let report = AnalysisReport::from_analysis(&result, &cm);
+  match format {
+    OutputFormat::Text => print_file_report(&result, &cm),
+    OutputFormat::Json => println!("{}", report.to_json()),
+    OutputFormat::Ndjson => stream_diagnostics(file_path, &result, &cm),
+  }
+  report
+}
+
+/// Write one JSON line per diagnostic, flushing each so consumers see them as they are found
+fn stream_diagnostics(file_path: &str, result: &AnalysisResult, cm: &SourceMap) {
+  // Recovered syntax errors aren't documentation diagnostics, so they go to stderr
+  for parse_error in &result.parse_errors {
+    let pos = cm.lookup_char_pos(parse_error.span.lo());
+    eprintln!("{}:{}:{}: parse error: {}", file_path, pos.line, pos.col_display, parse_error.message);
+  }
+  let stdout = io::stdout();
+  let mut out = stdout.lock();
+  for record in diagnostic_records(file_path, result, cm) {
+    let written = writeln!(out, "{}", record.to_json_line()).and_then(|_| out.flush());
+    if written.is_err() {
+      // The reader went away (e.g. `| head`); nothing left to report to
+      process::exit(0);
+    }
+  }
+}
+
+/// Analyze every file, keep going past failures and finish with an aggregated summary
+fn analyze_many_files(files: &[PathBuf], user_settings: &UserSettings, format: OutputFormat, quiet: bool) -> Vec<AnalysisReport> {
+  let mut file_reports = Vec::new();
+  let mut failures = Vec::new();
+
+  for file in files {
+    let path = file.display().to_string();
+    match analyze_file(&path, user_settings) {
+      Ok((result, cm)) => {
+        match format {
+          OutputFormat::Text if !quiet => {
+            println!("=== Analyzing File: {} ===\n", path);
+            print_file_report(&result, &cm);
+            println!();
+          }
+          OutputFormat::Ndjson => stream_diagnostics(&path, &result, &cm),
+          _ => {}
+        }
+        file_reports.push(FileReport {
+          path,
+          report: AnalysisReport::from_analysis(&result, &cm),
+        });
+      }
+      Err(error) => failures.push((path, error)),
+    }
+  }
+
+  match format {
+    OutputFormat::Text => print_summary(&file_reports, &failures),
+    OutputFormat::Json => println!("{}", files_to_json(&file_reports)),
+    OutputFormat::Ndjson => {}
+  }
+  if format != OutputFormat::Text {
+    for (path, error) in &failures {
+      eprintln!("{}: {}", path, error);
+    }
+  }
+
+  file_reports.into_iter().map(|file_report| file_report.report).collect()
+}
+
+fn print_summary(file_reports: &[FileReport], failures: &[(String, String)]) {
+  let total_functions: usize = file_reports.iter().map(|file| file.report.functions.len()).sum();
+  let count_status = |report: &AnalysisReport, status: DocumentationStatus| {
+    report.functions.iter().filter(|function| function.status == status).count()
+  };
+
+  println!("=== Summary ===\n");
+  println!("📊 Files analyzed: {}", file_reports.len());
+  println!("📊 Functions with throws: {}", total_functions);
+  println!();
+  println!("  {:<50} {:>9} {:>11} {:>8} {:>13}", "File", "Functions", "Documented", "Partial", "Undocumented");
+  for file in file_reports.iter().filter(|file| !file.report.functions.is_empty()) {
+    println!(
+      "  {:<50} {:>9} {:>11} {:>8} {:>13}",
+      file.path,
+      file.report.functions.len(),
+      count_status(&file.report, DocumentationStatus::Documented),
+      count_status(&file.report, DocumentationStatus::Partial),
+      count_status(&file.report, DocumentationStatus::Undocumented)
+    );
+  }
+
+  // Undocumented functions that throw the most come first
+  let mut undocumented: Vec<_> = file_reports
+    .iter()
+    .flat_map(|file| file.report.functions.iter().map(move |function| (file, function)))
+    .filter(|(_, function)| function.status == DocumentationStatus::Undocumented)
+    .collect();
+  undocumented.sort_by_key(|(_, function)| std::cmp::Reverse(function.throws.len()));
+  if !undocumented.is_empty() {
+    println!("\n❌ TOP UNDOCUMENTED FUNCTIONS:");
+    for (file, function) in undocumented.iter().take(10) {
+      let error_types: Vec<&str> = function.throws
+        .iter()
+        .filter_map(|throw| throw.error_type.as_deref())
+        .collect();
+      println!("  📍 {} ({}:{}:{}) throws: {}",
+        function.name,
+        file.path,
+        function.line,
+        function.column,
+        error_types.join(", ")
+      );
+    }
+  }
+
+  if !failures.is_empty() {
+    println!("\n⚠️  FAILED TO ANALYZE {} FILE(S):", failures.len());
+    for (path, error) in failures {
+      println!("  {}: {}", path, error);
+    }
+  }
+}
+
+fn print_file_report(result: &AnalysisResult, cm: &SourceMap) {
   println!("📊 Analysis Summary:");
   println!("  - Functions with throws: {}", result.functions_with_throws.len());
   println!("  - Functions calls to throws: {}", result.calls_to_throws.len());
   println!();
 
+  if !result.parse_errors.is_empty() {
+    println!("⚠️  PARSE ERRORS (the rest of the file was still analyzed):");
+    for parse_error in &result.parse_errors {
+      let pos = cm.lookup_char_pos(parse_error.span.lo());
+      println!("  📍 {}:{} {}", pos.line, pos.col_display, parse_error.message);
+    }
+    println!();
+  }
+
   // Analyze functions by documentation status
   let mut documented_functions = Vec::new();
   let mut undocumented_functions = Vec::new();
   let mut partially_documented_functions = Vec::new();
 
   for function in &result.functions_with_throws {
-    let has_annotation = function.throws_annotation.is_some();
-    let documented_types: Vec<String> = function.throws_annotation
-      .as_ref()
-      .map(|ann| ann.error_types.clone())
-      .unwrap_or_default();
-    
-    let actual_error_types: Vec<String> = function.throw_details
-      .iter()
-      .filter_map(|detail| detail.error_type.clone())
-      .collect();
-
-    if has_annotation {
-      // Check if all actual throws are documented
-      let all_documented = actual_error_types.iter()
-        .all(|actual_type| {
-          // Handle "variable: name" format
-          if actual_type.starts_with("variable: ") {
-            return false; // Variables are never documented by type
-          }
-          
-          // Handle union types by checking if all individual types are documented
-          if actual_type.starts_with("union: ") {
-            let union_content = &actual_type[7..]; // Remove "union: " prefix
-            let individual_types: Vec<&str> = union_content
-              .split(" | ")
-              .map(|t| t.trim())
-              .collect();
-            
-            // Check if all individual types in the union are documented
-            return individual_types.iter().all(|individual_type| {
-              documented_types.contains(&individual_type.to_string())
-            });
-          }
-          
-          // Regular type checking
-          documented_types.contains(actual_type)
-        });
-      
-      if all_documented {
-        documented_functions.push(function);
-      } else {
-        partially_documented_functions.push(function);
-      }
-    } else {
-      undocumented_functions.push(function);
+    match documentation_status(function) {
+      DocumentationStatus::Documented => documented_functions.push(function),
+      DocumentationStatus::Partial => partially_documented_functions.push(function),
+      DocumentationStatus::Undocumented => undocumented_functions.push(function),
     }
   }
 
@@ -106,7 +370,7 @@ fn analyze_specific_file(file_path: &str, include_try_statements: bool) {
   if !documented_functions.is_empty() {
     println!("\n✅ FULLY DOCUMENTED FUNCTIONS:");
     for function in documented_functions.iter().take(3) {
-      let pos = _cm.lookup_char_pos(function.throw_statement.lo());
+      let pos = cm.lookup_char_pos(function.throw_statement.lo());
       println!("  📍 {} ({}:{})", 
         function.function_or_method_name,
         pos.line,
@@ -131,7 +395,7 @@ fn analyze_specific_file(file_path: &str, include_try_statements: bool) {
   if !partially_documented_functions.is_empty() {
     println!("⚠️  PARTIALLY DOCUMENTED FUNCTIONS:");
     for function in partially_documented_functions.iter().take(3) {
-      let pos = _cm.lookup_char_pos(function.throw_statement.lo());
+      let pos = cm.lookup_char_pos(function.throw_statement.lo());
       println!("  📍 {} ({}:{})", 
         function.function_or_method_name,
         pos.line,
@@ -163,8 +427,7 @@ fn analyze_specific_file(file_path: &str, include_try_statements: bool) {
             }
             
             // Handle union types
-            if error_type.starts_with("union: ") {
-              let union_content = &error_type[7..]; // Remove "union: " prefix
+            if let Some(union_content) = error_type.strip_prefix("union: ") {
               let individual_types: Vec<&str> = union_content
                 .split(" | ")
                 .map(|t| t.trim())
@@ -206,7 +469,7 @@ fn analyze_specific_file(file_path: &str, include_try_statements: bool) {
   if !undocumented_functions.is_empty() {
     println!("❌ UNDOCUMENTED FUNCTIONS:");
     for function in undocumented_functions.iter().take(5) {
-      let pos = _cm.lookup_char_pos(function.throw_statement.lo());
+      let pos = cm.lookup_char_pos(function.throw_statement.lo());
       println!("  📍 {} ({}:{})", 
         function.function_or_method_name,
         pos.line,
@@ -270,7 +533,7 @@ fn analyze_specific_file(file_path: &str, include_try_statements: bool) {
   if !result.calls_to_throws.is_empty() {
     println!("  Examples:");
     for call in result.calls_to_throws.iter().take(3) {
-      let pos = _cm.lookup_char_pos(call.call_span.lo());
+      let pos = cm.lookup_char_pos(call.call_span.lo());
       println!("    📞 {} calls {} ({}:{})", 
         call.call_function_or_method_name,
         call.throw_map.function_or_method_name,
@@ -288,7 +551,7 @@ fn demo_jsdoc_throws_analysis() {
   let cm: Lrc<SourceMap> = Default::default();
   let user_settings = UserSettings {
     include_try_statement_throws: true,
-    ignore_statements: vec![], // No ignore statements for this demo
+    ..Default::default()
   };
   
   let (result, _cm, _comments) = analyze_code(&jsdoc_code, cm, &user_settings);
@@ -325,8 +588,7 @@ fn demo_jsdoc_throws_analysis() {
           }
           
           // Handle union types by checking if all individual types are documented
-          if actual_type.starts_with("union: ") {
-            let union_content = &actual_type[7..]; // Remove "union: " prefix
+          if let Some(union_content) = actual_type.strip_prefix("union: ") {
             let individual_types: Vec<&str> = union_content
               .split(" | ")
               .map(|t| t.trim())
@@ -490,8 +752,8 @@ fn demo_original_sample_analysis() {
     .expect("Something went wrong reading the file");
   let cm: Lrc<SourceMap> = Default::default();
   let user_settings = UserSettings {
-    include_try_statement_throws: false,
     ignore_statements: vec!["@it-throws".to_string()],
+    ..Default::default()
   };
   let (result, _cm, _comments) = analyze_code(&sample_code, cm, &user_settings);
   for import in result.import_sources.into_iter() {
@@ -9,7 +9,7 @@ use swc_ecma_ast::Module;
 use self::swc_common::{comments::Comments, sync::Lrc, Span, BytePos, Spanned};
 use self::swc_ecma_visit::Visit;
 
-use crate::throw_finder::ThrowsAnnotation;
+use crate::throw_finder::{default_throws_tag_aliases, ends_throws_type_list, is_wildcard_throws_type, throws_tag_text, ThrowsAnnotation, ThrowsTagAliases};
 
 /// Represents parameter-level throws information
 #[derive(Clone, Debug)]
@@ -51,6 +51,7 @@ pub struct ParamFinder {
   pub param_throws: HashMap<String, Vec<ParamThrowsInfo>>,
   pub function_name_stack: Vec<String>,
   pub current_class_name: Option<String>,
+  pub throws_tag_aliases: Vec<String>, // JSDoc tags documenting thrown errors, e.g. `throws`
 }
 
 impl ParamFinder {
@@ -60,6 +61,7 @@ impl ParamFinder {
       param_throws: HashMap::new(),
       function_name_stack: Vec::new(),
       current_class_name: None,
+      throws_tag_aliases: default_throws_tag_aliases(),
     }
   }
 
@@ -80,8 +82,12 @@ impl ParamFinder {
       
       if !throws_types.is_empty() {
         let throws_annotation = ThrowsAnnotation {
+          covers_all: throws_types.iter().any(|t| is_wildcard_throws_type(t)),
           error_types: throws_types,
           is_documented: true,
+          no_throw: None,
+          inherit_doc: false,
+          descriptions: Vec::new(),
         };
         
         let param_throws_info = ParamThrowsInfo {
@@ -92,7 +98,7 @@ impl ParamFinder {
         };
         
         #[cfg(debug_assertions)]
-        eprintln!("🔍 Found parameter throws: {} (index: {}) -> {:?}", 
+        debug_log!("Found parameter throws: {} (index: {}) -> {:?}", 
                   param_name, index, param_throws_info.throws_annotation.error_types);
         
         param_throws_list.push(param_throws_info);
@@ -121,8 +127,12 @@ impl ParamFinder {
       
       if !throws_types.is_empty() {
         let throws_annotation = ThrowsAnnotation {
+          covers_all: throws_types.iter().any(|t| is_wildcard_throws_type(t)),
           error_types: throws_types,
           is_documented: true,
+          no_throw: None,
+          inherit_doc: false,
+          descriptions: Vec::new(),
         };
         
         let param_throws_info = ParamThrowsInfo {
@@ -133,7 +143,7 @@ impl ParamFinder {
         };
         
         #[cfg(debug_assertions)]
-        eprintln!("🔍 Found arrow parameter throws: {} (index: {}) -> {:?}", 
+        debug_log!("Found arrow parameter throws: {} (index: {}) -> {:?}", 
                   param_name, index, param_throws_info.throws_annotation.error_types);
         
         param_throws_list.push(param_throws_info);
@@ -167,8 +177,12 @@ impl ParamFinder {
           
           if !throws_types.is_empty() {
             let throws_annotation = ThrowsAnnotation {
+              covers_all: throws_types.iter().any(|t| is_wildcard_throws_type(t)),
               error_types: throws_types,
               is_documented: true,
+              no_throw: None,
+              inherit_doc: false,
+              descriptions: Vec::new(),
             };
             
             let param_throws_info = ParamThrowsInfo {
@@ -179,7 +193,7 @@ impl ParamFinder {
             };
             
             #[cfg(debug_assertions)]
-            eprintln!("🔍 Found constructor parameter throws: {} (index: {}) -> {:?}", 
+            debug_log!("Found constructor parameter throws: {} (index: {}) -> {:?}", 
                       param_name, index, param_throws_info.throws_annotation.error_types);
             
             param_throws_list.push(param_throws_info);
@@ -205,7 +219,7 @@ impl ParamFinder {
       Pat::Array(_) => "destructured_array".to_string(),
       Pat::Rest(rest) => {
         if let Some(ident) = rest.arg.as_ident() {
-          format!("...{}", ident.id.sym.to_string())
+          format!("...{}", ident.id.sym)
         } else {
           "...rest".to_string()
         }
@@ -259,6 +273,16 @@ impl ParamFinder {
         parse_comments(self.comments.get_leading(pos).as_deref());
         parse_comments(self.comments.get_trailing(pos).as_deref());
       }
+      // A comment after the last parameter, e.g. `cb: () => void /* @throws {E} */` followed by a
+      // line break, leads the closing `)`, which can be further away than the comment itself
+      let closing_comments = (17..=256u32)
+        .find_map(|offset| self.comments.get_leading(BytePos(span.hi().0.saturating_add(offset))))
+        .unwrap_or_default();
+      let nearby: Vec<_> = closing_comments
+        .into_iter()
+        .filter(|comment| comment.span.lo().0 <= span.hi().0.saturating_add(16))
+        .collect();
+      parse_comments(Some(&nearby));
     }
     
     // Also check for inline comments within the parameter span only (no broad window)
@@ -277,46 +301,47 @@ impl ParamFinder {
     let mut error_types: std::collections::HashSet<String> = std::collections::HashSet::new();
 
     // Handle single-line inline comments like /** @throws {Error} */
-    if text.to_lowercase().contains("@throws") {
-      if let Some(throws_pos) = text.to_lowercase().find("@throws") {
-        let after_throws = &text[throws_pos + 7..].trim(); // Skip "@throws"
-
-        // Handle @throws {Type} syntax
-        if let Some(start_brace) = after_throws.find('{') {
-          if let Some(end_brace) = after_throws.find('}') {
-            let content = &after_throws[start_brace + 1..end_brace];
-            for t in content.split(',') {
-              let t = t.trim();
-              if !t.is_empty() {
-                error_types.insert(t.to_string());
-              }
+    if let Some(after_throws) = throws_tag_text(text, &self.throws_tag_aliases) {
+      let after_throws = &after_throws.trim();
+
+      // Handle @throws {Type} syntax
+      if let Some(start_brace) = after_throws.find('{') {
+        if let Some(end_brace) = after_throws.find('}') {
+          let content = &after_throws[start_brace + 1..end_brace];
+          for t in content.split(',') {
+            let t = t.trim();
+            if !t.is_empty() {
+              error_types.insert(t.to_string());
             }
           }
-        } else {
-          // Handle @throws Type1, Type2 (without braces)
-          let type_section = after_throws
-            .split_whitespace()
-            .take_while(|word| {
-              !["when", "if", "where", "that", "which", "while", "because", "since"].contains(&word.to_lowercase().as_str())
-            })
-            .collect::<Vec<_>>()
-            .join(" ");
-          let types: Vec<String> = type_section
-            .split(',')
-            .map(|s| s.trim().trim_end_matches(',').trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
-          for error_type in types {
-            error_types.insert(error_type);
-          }
+        }
+      } else {
+        // Handle @throws Type1, Type2 (without braces)
+        let type_section = after_throws
+          .split_whitespace()
+          .take_while(|word| !ends_throws_type_list(word))
+          .collect::<Vec<_>>()
+          .join(" ");
+        let types: Vec<String> = type_section
+          .split(',')
+          .map(|s| s.trim().trim_end_matches(',').trim().to_string())
+          .filter(|s| !s.is_empty())
+          .collect();
+        for error_type in types {
+          error_types.insert(error_type);
         }
       }
     }
 
     if !error_types.is_empty() {
+      let covers_all = error_types.iter().any(|t| is_wildcard_throws_type(t));
       Some(ThrowsAnnotation { 
         error_types: error_types.into_iter().collect(),
-        is_documented: true 
+        is_documented: true,
+        covers_all,
+        no_throw: None,
+        inherit_doc: false,
+        descriptions: Vec::new(),
       })
     } else {
       None
@@ -359,6 +384,12 @@ impl ParamFinder {
   }
 }
 
+impl ThrowsTagAliases for ParamFinder {
+  fn throws_tag_aliases_mut(&mut self) -> &mut Vec<String> {
+    &mut self.throws_tag_aliases
+  }
+}
+
 impl Visit for ParamFinder {
   fn visit_fn_decl(&mut self, fn_decl: &swc_ecma_ast::FnDecl) {
     let function_name = fn_decl.ident.sym.to_string();
@@ -408,11 +439,7 @@ impl Visit for ParamFinder {
         }
       }
       swc_ecma_ast::PatOrExpr::Pat(pat) => {
-        if let Some(ident) = pat.as_ident() {
-          Some(ident.sym.to_string())
-        } else {
-          None
-        }
+        pat.as_ident().map(|ident| ident.sym.to_string())
       }
     };
 
@@ -835,4 +862,34 @@ mod tests {
     assert!(finder.get_param_throws_by_name(function_id, "options").is_none());
     assert!(finder.get_param_throws_by_name(function_id, "config").is_none());
   }
+
+  #[test]
+  fn test_typed_function_parameter_throws() {
+    let code = r#"
+      function parseAll(
+        parse: (text: string) => number /* @throws {ParseError} */,
+        texts: string[],
+        report: (error: Error) => void /** @throws {NetworkError} */
+      ) {
+        texts.forEach((text) => report(parse(text)));
+      }
+    "#;
+
+    let (module, comments) = parse_code_with_comments(code);
+    let mut finder = ParamFinder::new(comments);
+    finder.visit_module(&module);
+
+    let function_id = "NOT_SET-parseAll";
+    let param_throws = finder.get_param_throws(function_id).unwrap();
+    assert_eq!(param_throws.len(), 2);
+    assert_eq!(
+      finder.get_param_throws_by_index(function_id, 0).unwrap().error_types,
+      vec!["ParseError"]
+    );
+    assert!(finder.get_param_throws_by_name(function_id, "texts").is_none());
+    assert_eq!(
+      finder.get_param_throws_by_name(function_id, "report").unwrap().error_types,
+      vec!["NetworkError"]
+    );
+  }
 }
@@ -0,0 +1,1225 @@
+extern crate serde;
+extern crate serde_json;
+extern crate swc_common;
+
+use std::collections::HashSet;
+
+use self::serde::{Deserialize, Serialize};
+use self::swc_common::{SourceMap, Span};
+
+use call_finder::CallToThrowMap;
+use diagnostics::{parse_error_diagnostics, Diagnostic};
+use throw_finder::{ThrowDetails, ThrowMap, ThrowsAnnotation};
+use try_catch_finder::CatchAnalysis;
+use AnalysisResult;
+
+/// How completely a throwing function's `@throws` annotation covers what it actually throws
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentationStatus {
+  Documented,
+  Partial,
+  Undocumented,
+}
+
+/// Which functions make `--fail-on` exit with a non-zero status
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailOn {
+  Undocumented, // Only functions without any `@throws` annotation
+  Partial,      // Functions whose annotation misses some thrown types
+  Any,          // Anything that is not fully documented
+}
+
+impl FailOn {
+  pub fn parse(value: &str) -> Option<FailOn> {
+    match value {
+      "undocumented" => Some(FailOn::Undocumented),
+      "partial" => Some(FailOn::Partial),
+      "any" => Some(FailOn::Any),
+      _ => None,
+    }
+  }
+
+  pub fn matches(self, status: DocumentationStatus) -> bool {
+    match self {
+      FailOn::Undocumented => status == DocumentationStatus::Undocumented,
+      FailOn::Partial => status == DocumentationStatus::Partial,
+      FailOn::Any => status != DocumentationStatus::Documented,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThrowReport {
+  pub error_type: Option<String>,
+  pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnnotationReport {
+  pub error_types: Vec<String>,
+  pub covers_all: bool,
+  pub no_throw: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionReport {
+  pub name: String,
+  pub class_name: Option<String>,
+  pub line: usize,   // 1-based
+  pub column: usize, // 0-based, matching `col_display`
+  pub throws: Vec<ThrowReport>,
+  pub throws_annotation: Option<AnnotationReport>,
+  pub status: DocumentationStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallReport {
+  pub caller: String,
+  pub callee: String,
+  pub callee_id: String,
+  pub line: usize,
+  pub column: usize,
+  pub is_awaited: bool,
+}
+
+/// Machine-readable analysis of a single file, ordered by source position so output is stable
+#[derive(Debug, Serialize)]
+pub struct AnalysisReport {
+  pub functions: Vec<FunctionReport>,
+  pub calls_to_throws: Vec<CallReport>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub parse_errors: Vec<Diagnostic>, // Syntax errors the parser recovered from; the rest of the report still holds
+}
+
+impl AnalysisReport {
+  pub fn from_analysis(result: &AnalysisResult, cm: &SourceMap) -> AnalysisReport {
+    let mut functions: Vec<FunctionReport> = result
+      .functions_with_throws
+      .iter()
+      .map(|function| function_report(function, cm))
+      .collect();
+    functions.sort_by(|a, b| (a.line, a.column, &a.name).cmp(&(b.line, b.column, &b.name)));
+
+    let mut calls_to_throws: Vec<CallReport> = result
+      .calls_to_throws
+      .iter()
+      .map(|call| call_report(call, cm))
+      .collect();
+    calls_to_throws.sort_by(|a, b| {
+      (a.line, a.column, &a.callee_id).cmp(&(b.line, b.column, &b.callee_id))
+    });
+
+    AnalysisReport {
+      functions,
+      calls_to_throws,
+      parse_errors: parse_error_diagnostics(&result.parse_errors, cm),
+    }
+  }
+
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("analysis report is always serializable")
+  }
+
+  pub fn has_failures(&self, fail_on: FailOn) -> bool {
+    self.functions.iter().any(|function| fail_on.matches(function.status))
+  }
+}
+
+/// Report for one file of a multi-file run
+#[derive(Debug, Serialize)]
+pub struct FileReport {
+  pub path: String,
+  #[serde(flatten)]
+  pub report: AnalysisReport,
+}
+
+pub fn files_to_json(file_reports: &[FileReport]) -> String {
+  serde_json::to_string_pretty(file_reports).expect("analysis report is always serializable")
+}
+
+/// Severity of a streamed diagnostic record
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordSeverity {
+  Error,
+  Warning,
+}
+
+/// One line of `--format ndjson` output: a single diagnostic, self-contained so it can be
+/// filtered without the rest of the run
+#[derive(Debug, Serialize)]
+pub struct DiagnosticRecord {
+  pub file: String,
+  pub line: usize, // 1-based
+  pub col: usize,  // 0-based, matching `col_display`
+  pub severity: RecordSeverity,
+  pub message: String,
+  pub rule: DocumentationStatus,
+}
+
+impl DiagnosticRecord {
+  pub fn to_json_line(&self) -> String {
+    serde_json::to_string(self).expect("diagnostic record is always serializable")
+  }
+}
+
+/// Diagnostics for every throwing function that is not fully documented, in source order
+pub fn diagnostic_records(file: &str, result: &AnalysisResult, cm: &SourceMap) -> Vec<DiagnosticRecord> {
+  let mut records: Vec<DiagnosticRecord> = result
+    .functions_with_throws
+    .iter()
+    .filter_map(|function| {
+      let status = documentation_status(function);
+      let (severity, message) = match (status, &function.throws_annotation) {
+        (DocumentationStatus::Documented, _) => return None,
+        (DocumentationStatus::Undocumented, _) | (_, None) => (
+          RecordSeverity::Error,
+          format!(
+            "{} throws {} but has no @throws documentation",
+            function.function_or_method_name,
+            thrown_types_description(function)
+          ),
+        ),
+        (DocumentationStatus::Partial, Some(ThrowsAnnotation { no_throw: Some(tag), .. })) => (
+          RecordSeverity::Warning,
+          format!(
+            "{} is marked {} but throws {}",
+            function.function_or_method_name,
+            tag,
+            thrown_types_description(function)
+          ),
+        ),
+        (DocumentationStatus::Partial, Some(annotation)) => (
+          RecordSeverity::Warning,
+          format!(
+            "{} is missing @throws documentation for {}",
+            function.function_or_method_name,
+            missing_throws_types(function, annotation).join(", ")
+          ),
+        ),
+      };
+      let pos = cm.lookup_char_pos(function.throw_statement.lo());
+      Some(DiagnosticRecord {
+        file: file.to_string(),
+        line: pos.line,
+        col: pos.col_display,
+        severity,
+        message,
+        rule: status,
+      })
+    })
+    .collect();
+  records.sort_by(|a, b| (a.line, a.col, &a.message).cmp(&(b.line, b.col, &b.message)));
+  records
+}
+
+fn thrown_types_description(function: &ThrowMap) -> String {
+  let mut error_types: Vec<&str> = Vec::new();
+  for error_type in function.throw_details.iter().filter_map(|detail| detail.error_type.as_deref()) {
+    if !error_types.contains(&error_type) {
+      error_types.push(error_type);
+    }
+  }
+  if error_types.is_empty() {
+    "an error".to_string()
+  } else {
+    error_types.join(", ")
+  }
+}
+
+/// Classify a throwing function by how well its `@throws` annotation matches its throws
+pub fn documentation_status(function: &ThrowMap) -> DocumentationStatus {
+  match &function.throws_annotation {
+    None => DocumentationStatus::Undocumented,
+    Some(annotation) if annotation.no_throw.is_some() => DocumentationStatus::Partial,
+    Some(annotation) if missing_throws_types(function, annotation).is_empty() => DocumentationStatus::Documented,
+    Some(_) => DocumentationStatus::Partial,
+  }
+}
+
+/// Thrown types that the annotation does not document, with unions split into their members
+pub fn missing_throws_types(function: &ThrowMap, annotation: &ThrowsAnnotation) -> Vec<String> {
+  if annotation.covers_all {
+    return Vec::new();
+  }
+  let mut missing: Vec<String> = Vec::new();
+  for actual_type in function.throw_details.iter().filter_map(|detail| detail.error_type.as_ref()) {
+    // Variables are never documented by type
+    let candidates: Vec<&str> = if actual_type.starts_with("variable: ") {
+      vec![actual_type.as_str()]
+    } else if let Some(union_content) = actual_type.strip_prefix("union: ") {
+      union_content.split(" | ").map(|t| t.trim()).collect()
+    } else {
+      vec![actual_type.as_str()]
+    };
+    for candidate in candidates {
+      let documented = !candidate.starts_with("variable: ")
+        && annotation.error_types.iter().any(|t| t == candidate);
+      if !documented && !missing.iter().any(|m| m == candidate) {
+        missing.push(candidate.to_string());
+      }
+    }
+  }
+  missing
+}
+
+fn function_report(function: &ThrowMap, cm: &SourceMap) -> FunctionReport {
+  let pos = cm.lookup_char_pos(function.throw_statement.lo());
+  FunctionReport {
+    name: function.function_or_method_name.clone(),
+    class_name: function.class_name.clone(),
+    line: pos.line,
+    column: pos.col_display,
+    throws: function.throw_details.iter().map(throw_report).collect(),
+    throws_annotation: function.throws_annotation.as_ref().map(annotation_report),
+    status: documentation_status(function),
+  }
+}
+
+fn throw_report(detail: &ThrowDetails) -> ThrowReport {
+  ThrowReport {
+    error_type: detail.error_type.clone(),
+    message: detail.error_message.clone(),
+  }
+}
+
+fn annotation_report(annotation: &ThrowsAnnotation) -> AnnotationReport {
+  AnnotationReport {
+    error_types: annotation.error_types.clone(),
+    covers_all: annotation.covers_all,
+    no_throw: annotation.no_throw.is_some(),
+  }
+}
+
+fn call_report(call: &CallToThrowMap, cm: &SourceMap) -> CallReport {
+  let pos = cm.lookup_char_pos(call.call_span.lo());
+  CallReport {
+    caller: call.call_function_or_method_name.clone(),
+    callee: call.throw_map.function_or_method_name.clone(),
+    callee_id: call.throw_map.id.clone(),
+    line: pos.line,
+    column: pos.col_display,
+    is_awaited: call.is_awaited,
+  }
+}
+
+/// A span resolved to a human-facing position
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedLocation {
+  pub file: String,
+  pub line: usize,   // 1-based
+  pub column: usize, // 0-based
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedThrow {
+  pub error_type: Option<String>,
+  pub message: Option<String>,
+  pub is_rejection: bool,
+  pub location: ResolvedLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedFunction {
+  pub file: String, // The analyzed file's path, as given to `resolve_analysis`
+  pub id: String,
+  pub name: String,
+  pub class_name: Option<String>,
+  pub is_async: bool,
+  pub location: ResolvedLocation,
+  pub throws: Vec<ResolvedThrow>,
+  pub documented_types: Vec<String>,
+  pub missing_types: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedCall {
+  pub file: String, // The analyzed file's path, as given to `resolve_analysis`
+  pub caller: String,
+  pub callee: String,
+  pub callee_id: String,
+  pub is_awaited: bool,
+  pub error_types: Vec<String>,
+  pub location: ResolvedLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedCatch {
+  pub try_location: ResolvedLocation,
+  pub catch_location: ResolvedLocation,
+  pub catch_param: Option<String>,
+  pub errors_thrown_in_try: Vec<String>,
+  pub errors_handled_in_catch: Vec<String>,
+  pub errors_effectively_caught: Vec<String>,
+  pub errors_propagated: Vec<String>,
+  pub missing_handlers: Vec<String>,
+  pub has_escape_hatch: bool,
+}
+
+/// Analysis with every span resolved, for tools that want data rather than editor diagnostics
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedReport {
+  pub undocumented_functions: Vec<ResolvedFunction>,
+  pub incomplete_functions: Vec<ResolvedFunction>, // Annotated, but missing some thrown types
+  pub throwing_calls: Vec<ResolvedCall>,
+  pub catch_analyses: Vec<ResolvedCatch>,
+}
+
+impl ResolvedReport {
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("resolved report is always serializable")
+  }
+
+  pub fn has_failures(&self, fail_on: FailOn) -> bool {
+    (!self.undocumented_functions.is_empty() && fail_on.matches(DocumentationStatus::Undocumented))
+      || (!self.incomplete_functions.is_empty() && fail_on.matches(DocumentationStatus::Partial))
+  }
+
+  /// Append `other`'s entries, e.g. to cover several files in one report
+  pub fn extend(&mut self, other: ResolvedReport) {
+    self.undocumented_functions.extend(other.undocumented_functions);
+    self.incomplete_functions.extend(other.incomplete_functions);
+    self.throwing_calls.extend(other.throwing_calls);
+    self.catch_analyses.extend(other.catch_analyses);
+  }
+}
+
+pub fn resolve_analysis(file: &str, result: &AnalysisResult, cm: &SourceMap) -> ResolvedReport {
+  let mut undocumented_functions = Vec::new();
+  let mut incomplete_functions = Vec::new();
+  for function in &result.functions_with_throws {
+    match documentation_status(function) {
+      DocumentationStatus::Undocumented => undocumented_functions.push(resolve_function(file, function, cm)),
+      DocumentationStatus::Partial => incomplete_functions.push(resolve_function(file, function, cm)),
+      DocumentationStatus::Documented => {}
+    }
+  }
+  let by_location = |a: &ResolvedFunction, b: &ResolvedFunction| {
+    (&a.location.line, &a.location.column, &a.id).cmp(&(&b.location.line, &b.location.column, &b.id))
+  };
+  undocumented_functions.sort_by(by_location);
+  incomplete_functions.sort_by(by_location);
+
+  let mut throwing_calls: Vec<ResolvedCall> =
+    result.calls_to_throws.iter().map(|call| resolve_call(file, call, cm)).collect();
+  throwing_calls.sort_by(|a, b| {
+    (a.location.line, a.location.column, &a.callee_id).cmp(&(b.location.line, b.location.column, &b.callee_id))
+  });
+
+  ResolvedReport {
+    undocumented_functions,
+    incomplete_functions,
+    throwing_calls,
+    catch_analyses: result.catch_analyses.iter().map(|catch| resolve_catch(catch, cm)).collect(),
+  }
+}
+
+/// What `current` reports that `baseline` doesn't, for failing only on throws added since a baseline
+/// snapshot. Throws match by file, function and error type rather than location, so code that merely moved
+/// within its file isn't reported again; calls match by file, caller, callee and error type. A function is kept with just its
+/// new throws, or for an incomplete one its new missing types. Catch analyses aren't diffed and are left out.
+pub fn diff_reports(baseline: &ResolvedReport, current: &ResolvedReport) -> ResolvedReport {
+  // The problems the baseline already accepts: every throw of an undocumented function and the
+  // types an incomplete one leaves out
+  let mut known_throws: HashSet<(&str, Option<&str>, &str, Option<&str>)> = HashSet::new();
+  for function in &baseline.undocumented_functions {
+    for throw in &function.throws {
+      known_throws.insert((&function.file, function.class_name.as_deref(), &function.name, throw.error_type.as_deref()));
+    }
+  }
+  for function in &baseline.incomplete_functions {
+    for error_type in &function.missing_types {
+      known_throws.insert((&function.file, function.class_name.as_deref(), &function.name, Some(error_type)));
+    }
+  }
+  let is_new = |function: &ResolvedFunction, error_type: Option<&str>| {
+    !known_throws.contains(&(function.file.as_str(), function.class_name.as_deref(), function.name.as_str(), error_type))
+  };
+
+  let undocumented_functions = current
+    .undocumented_functions
+    .iter()
+    .filter_map(|function| {
+      let throws: Vec<ResolvedThrow> =
+        function.throws.iter().filter(|throw| is_new(function, throw.error_type.as_deref())).cloned().collect();
+      (!throws.is_empty()).then(|| ResolvedFunction { throws, ..function.clone() })
+    })
+    .collect();
+  let incomplete_functions = current
+    .incomplete_functions
+    .iter()
+    .filter_map(|function| {
+      let missing_types: Vec<String> =
+        function.missing_types.iter().filter(|error_type| is_new(function, Some(error_type))).cloned().collect();
+      let throws = function
+        .throws
+        .iter()
+        .filter(|throw| throw.error_type.as_ref().is_some_and(|error_type| missing_types.contains(error_type)))
+        .cloned()
+        .collect();
+      (!missing_types.is_empty()).then(|| ResolvedFunction { throws, missing_types, ..function.clone() })
+    })
+    .collect();
+
+  // Calls whose callee's errors are all untyped match on the call alone
+  let call_error_types = |call: &ResolvedCall| -> Vec<Option<String>> {
+    if call.error_types.is_empty() {
+      vec![None]
+    } else {
+      call.error_types.iter().cloned().map(Some).collect()
+    }
+  };
+  let known_calls: HashSet<(&str, &str, &str, Option<String>)> = baseline
+    .throwing_calls
+    .iter()
+    .flat_map(|call| {
+      call_error_types(call)
+        .into_iter()
+        .map(move |error_type| (call.file.as_str(), call.caller.as_str(), call.callee_id.as_str(), error_type))
+    })
+    .collect();
+  let throwing_calls = current
+    .throwing_calls
+    .iter()
+    .filter_map(|call| {
+      let new_error_types: Vec<Option<String>> = call_error_types(call)
+        .into_iter()
+        .filter(|error_type| {
+          !known_calls.contains(&(call.file.as_str(), call.caller.as_str(), call.callee_id.as_str(), error_type.clone()))
+        })
+        .collect();
+      (!new_error_types.is_empty())
+        .then(|| ResolvedCall { error_types: new_error_types.into_iter().flatten().collect(), ..call.clone() })
+    })
+    .collect();
+
+  ResolvedReport {
+    undocumented_functions,
+    incomplete_functions,
+    throwing_calls,
+    catch_analyses: Vec::new(),
+  }
+}
+
+fn resolve_span(span: Span, cm: &SourceMap) -> ResolvedLocation {
+  let pos = cm.lookup_char_pos(span.lo());
+  ResolvedLocation {
+    file: pos.file.name.to_string(),
+    line: pos.line,
+    column: pos.col_display,
+  }
+}
+
+fn resolve_function(file: &str, function: &ThrowMap, cm: &SourceMap) -> ResolvedFunction {
+  let throws = function
+    .throw_details
+    .iter()
+    .enumerate()
+    .map(|(index, detail)| ResolvedThrow {
+      error_type: detail.error_type.clone(),
+      message: detail.error_message.clone(),
+      is_rejection: detail.is_rejection,
+      // Propagated details have no span of their own; fall back to the function
+      location: resolve_span(*function.throw_spans.get(index).unwrap_or(&function.throw_statement), cm),
+    })
+    .collect();
+  ResolvedFunction {
+    file: file.to_string(),
+    id: function.id.clone(),
+    name: function.function_or_method_name.clone(),
+    class_name: function.class_name.clone(),
+    is_async: function.is_async,
+    location: resolve_span(function.throw_statement, cm),
+    throws,
+    documented_types: function.throws_annotation.as_ref().map(|a| a.error_types.clone()).unwrap_or_default(),
+    missing_types: function.throws_annotation.as_ref().map(|a| missing_throws_types(function, a)).unwrap_or_default(),
+  }
+}
+
+fn resolve_call(file: &str, call: &CallToThrowMap, cm: &SourceMap) -> ResolvedCall {
+  ResolvedCall {
+    file: file.to_string(),
+    caller: call.call_function_or_method_name.clone(),
+    callee: call.throw_map.function_or_method_name.clone(),
+    callee_id: call.throw_map.id.clone(),
+    is_awaited: call.is_awaited,
+    error_types: call.throw_map.throw_details.iter().filter_map(|detail| detail.error_type.clone()).collect(),
+    location: resolve_span(call.call_span, cm),
+  }
+}
+
+fn resolve_catch(catch: &CatchAnalysis, cm: &SourceMap) -> ResolvedCatch {
+  ResolvedCatch {
+    try_location: resolve_span(catch.try_span, cm),
+    catch_location: resolve_span(catch.catch_span, cm),
+    catch_param: catch.catch_param.clone(),
+    errors_thrown_in_try: catch.errors_thrown_in_try.clone(),
+    errors_handled_in_catch: catch.errors_handled_in_catch.clone(),
+    errors_effectively_caught: catch.errors_effectively_caught.clone(),
+    errors_propagated: catch.errors_propagated.clone(),
+    missing_handlers: catch.missing_handlers.clone(),
+    has_escape_hatch: catch.has_escape_hatch,
+  }
+}
+
+/// A throwing function in the error catalog, with the calls its errors surface through
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatalogEntry {
+  pub file: String,
+  pub function: ResolvedFunction,
+  pub status: DocumentationStatus,
+  pub surfaced_through: Vec<ResolvedCall>,
+}
+
+/// What `--report` writes the error catalog as
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatalogFormat {
+  Markdown,
+  Html, // A standalone page
+}
+
+impl CatalogFormat {
+  pub fn parse(value: &str) -> Option<CatalogFormat> {
+    match value {
+      "markdown" | "md" => Some(CatalogFormat::Markdown),
+      "html" => Some(CatalogFormat::Html),
+      _ => None,
+    }
+  }
+
+  pub fn render(self, entries: &[CatalogEntry]) -> String {
+    match self {
+      CatalogFormat::Markdown => render_catalog_markdown(entries),
+      CatalogFormat::Html => render_catalog_html(entries),
+    }
+  }
+}
+
+/// Every throwing function of `file`, documented or not, for the error catalog
+pub fn catalog_entries(file: &str, result: &AnalysisResult, cm: &SourceMap) -> Vec<CatalogEntry> {
+  result
+    .functions_with_throws
+    .iter()
+    .map(|function| {
+      let mut surfaced_through: Vec<ResolvedCall> = result
+        .calls_to_throws
+        .iter()
+        .filter(|call| call.throw_map.id == function.id)
+        .map(|call| resolve_call(file, call, cm))
+        .collect();
+      surfaced_through.sort_by_key(|call| (call.location.line, call.location.column));
+      CatalogEntry {
+        file: file.to_string(),
+        function: resolve_function(file, function, cm),
+        status: documentation_status(function),
+        surfaced_through,
+      }
+    })
+    .collect()
+}
+
+/// Files, then classes within a file with free functions first, then position
+fn catalog_order(entry: &CatalogEntry) -> (&str, Option<&str>, usize, usize) {
+  let location = &entry.function.location;
+  (&entry.file, entry.function.class_name.as_deref(), location.line, location.column)
+}
+
+/// A class's catalog entries, or the free functions' for `None`
+type ClassGroup<'a> = (Option<&'a str>, Vec<&'a CatalogEntry>);
+
+/// The entries grouped by file and then by class, in catalog order
+fn catalog_groups(entries: &[CatalogEntry]) -> Vec<(&str, Vec<ClassGroup<'_>>)> {
+  let mut sorted: Vec<&CatalogEntry> = entries.iter().collect();
+  sorted.sort_by(|a, b| catalog_order(a).cmp(&catalog_order(b)));
+  let mut groups: Vec<(&str, Vec<ClassGroup>)> = Vec::new();
+  for entry in sorted {
+    if groups.last().map(|(file, _)| *file) != Some(entry.file.as_str()) {
+      groups.push((&entry.file, Vec::new()));
+    }
+    let classes = &mut groups.last_mut().expect("a group was just pushed").1;
+    let class_name = entry.function.class_name.as_deref();
+    if classes.last().map(|(class, _)| *class) != Some(class_name) {
+      classes.push((class_name, Vec::new()));
+    }
+    classes.last_mut().expect("a class was just pushed").1.push(entry);
+  }
+  groups
+}
+
+/// Documentation coverage of some catalog entries: functions, documented, partial, undocumented, percent documented
+fn catalog_coverage<'a>(entries: impl IntoIterator<Item = &'a CatalogEntry>) -> (usize, usize, usize, usize, String) {
+  let (mut total, mut documented, mut partial, mut undocumented) = (0, 0, 0, 0);
+  for entry in entries {
+    total += 1;
+    match entry.status {
+      DocumentationStatus::Documented => documented += 1,
+      DocumentationStatus::Partial => partial += 1,
+      DocumentationStatus::Undocumented => undocumented += 1,
+    }
+  }
+  let percent = if total == 0 { 100.0 } else { documented as f64 * 100.0 / total as f64 };
+  (total, documented, partial, undocumented, format!("{:.0}%", percent))
+}
+
+fn catalog_function_name(function: &ResolvedFunction) -> String {
+  match &function.class_name {
+    Some(class_name) => format!("{}.{}", class_name, function.name),
+    None => function.name.clone(),
+  }
+}
+
+/// A stable fragment identifier for a function's section, e.g. `src-app-ts-parser-parse`
+fn catalog_anchor(entry: &CatalogEntry) -> String {
+  let mut anchor = String::new();
+  for c in format!("{}-{}", entry.file, catalog_function_name(&entry.function)).chars() {
+    if c.is_ascii_alphanumeric() {
+      anchor.push(c.to_ascii_lowercase());
+    } else if !anchor.is_empty() && !anchor.ends_with('-') {
+      anchor.push('-');
+    }
+  }
+  anchor.trim_end_matches('-').to_string()
+}
+
+fn status_label(status: DocumentationStatus) -> &'static str {
+  match status {
+    DocumentationStatus::Documented => "documented",
+    DocumentationStatus::Partial => "partially documented",
+    DocumentationStatus::Undocumented => "undocumented",
+  }
+}
+
+fn markdown_cell(text: &str) -> String {
+  text.replace('|', "\\|").replace('<', "&lt;").replace('>', "&gt;").replace('\n', " ")
+}
+
+/// The anchor of the catalog section of `caller`, when the caller throws too and so has one
+fn caller_anchor(entries: &[CatalogEntry], file: &str, caller: &str) -> Option<String> {
+  entries
+    .iter()
+    .find(|entry| entry.file == file && entry.function.name == caller)
+    .map(catalog_anchor)
+}
+
+/// The error catalog as Markdown: a coverage table, then each file's functions grouped by class
+pub fn render_catalog_markdown(entries: &[CatalogEntry]) -> String {
+  let groups = catalog_groups(entries);
+  let mut out = String::from("# Thrown errors\n\n## Documentation coverage\n\n");
+  out.push_str("| File | Functions | Documented | Partial | Undocumented | Coverage |\n");
+  out.push_str("| --- | ---: | ---: | ---: | ---: | ---: |\n");
+  let coverage_row = |label: &str, coverage: (usize, usize, usize, usize, String)| {
+    format!("| {} | {} | {} | {} | {} | {} |\n", label, coverage.0, coverage.1, coverage.2, coverage.3, coverage.4)
+  };
+  for (file, classes) in &groups {
+    let file_entries = classes.iter().flat_map(|(_, class_entries)| class_entries.iter().copied());
+    out.push_str(&coverage_row(&format!("`{}`", markdown_cell(file)), catalog_coverage(file_entries)));
+  }
+  out.push_str(&coverage_row("**Total**", catalog_coverage(entries)));
+
+  for (file, classes) in &groups {
+    out.push_str(&format!("\n## `{}`\n", file));
+    for (class_name, class_entries) in classes {
+      match class_name {
+        Some(class_name) => out.push_str(&format!("\n### Class `{}`\n", class_name)),
+        None => out.push_str("\n### Functions\n"),
+      }
+      for entry in class_entries {
+        let function = &entry.function;
+        out.push_str(&format!(
+          "\n#### <a id=\"{}\"></a>`{}`\n\n",
+          catalog_anchor(entry),
+          catalog_function_name(function)
+        ));
+        out.push_str(&format!("- Status: {}\n", status_label(entry.status)));
+        out.push_str(&format!("- Location: `{}:{}`\n", entry.file, function.location.line));
+        if !function.documented_types.is_empty() {
+          out.push_str(&format!("- Documented: {}\n", code_list(&function.documented_types)));
+        }
+        if !function.missing_types.is_empty() {
+          out.push_str(&format!("- Missing from `@throws`: {}\n", code_list(&function.missing_types)));
+        }
+        out.push_str("\n| Error | Message | Line |\n| --- | --- | ---: |\n");
+        for throw in &function.throws {
+          out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            throw.error_type.as_ref().map(|error_type| format!("`{}`", markdown_cell(error_type))).unwrap_or_else(|| "unknown".to_string()),
+            throw.message.as_deref().map(markdown_cell).unwrap_or_default(),
+            throw.location.line
+          ));
+        }
+        if !entry.surfaced_through.is_empty() {
+          out.push_str("\nSurfaced through:\n\n");
+          for call in &entry.surfaced_through {
+            let caller = match caller_anchor(entries, &entry.file, &call.caller) {
+              Some(anchor) => format!("[`{}`](#{})", call.caller, anchor),
+              None => format!("`{}`", call.caller),
+            };
+            out.push_str(&format!("- {} at `{}:{}`\n", caller, entry.file, call.location.line));
+          }
+        }
+      }
+    }
+  }
+  out
+}
+
+fn code_list(items: &[String]) -> String {
+  items.iter().map(|item| format!("`{}`", item)).collect::<Vec<_>>().join(", ")
+}
+
+fn escape_html(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The error catalog as a standalone HTML page, laid out like the Markdown one
+pub fn render_catalog_html(entries: &[CatalogEntry]) -> String {
+  let groups = catalog_groups(entries);
+  let mut out = String::from(
+    "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Thrown errors</title>\n\
+     <style>body { font-family: sans-serif; max-width: 60rem; margin: auto; } \
+     table { border-collapse: collapse; } th, td { border: 1px solid #ccc; padding: 0.2rem 0.5rem; text-align: left; }</style>\n\
+     </head>\n<body>\n<h1>Thrown errors</h1>\n<h2>Documentation coverage</h2>\n<table>\n\
+     <tr><th>File</th><th>Functions</th><th>Documented</th><th>Partial</th><th>Undocumented</th><th>Coverage</th></tr>\n",
+  );
+  let coverage_row = |label: &str, coverage: (usize, usize, usize, usize, String)| {
+    format!(
+      "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+      label, coverage.0, coverage.1, coverage.2, coverage.3, coverage.4
+    )
+  };
+  for (file, classes) in &groups {
+    let file_entries = classes.iter().flat_map(|(_, class_entries)| class_entries.iter().copied());
+    out.push_str(&coverage_row(&format!("<code>{}</code>", escape_html(file)), catalog_coverage(file_entries)));
+  }
+  out.push_str(&coverage_row("<strong>Total</strong>", catalog_coverage(entries)));
+  out.push_str("</table>\n");
+
+  for (file, classes) in &groups {
+    out.push_str(&format!("<h2><code>{}</code></h2>\n", escape_html(file)));
+    for (class_name, class_entries) in classes {
+      match class_name {
+        Some(class_name) => out.push_str(&format!("<h3>Class <code>{}</code></h3>\n", escape_html(class_name))),
+        None => out.push_str("<h3>Functions</h3>\n"),
+      }
+      for entry in class_entries {
+        let function = &entry.function;
+        let anchor = catalog_anchor(entry);
+        out.push_str(&format!(
+          "<h4 id=\"{}\"><a href=\"#{}\"><code>{}</code></a></h4>\n<ul>\n",
+          anchor,
+          anchor,
+          escape_html(&catalog_function_name(function))
+        ));
+        out.push_str(&format!("<li>Status: {}</li>\n", status_label(entry.status)));
+        out.push_str(&format!("<li>Location: <code>{}:{}</code></li>\n", escape_html(&entry.file), function.location.line));
+        if !function.documented_types.is_empty() {
+          out.push_str(&format!("<li>Documented: {}</li>\n", html_code_list(&function.documented_types)));
+        }
+        if !function.missing_types.is_empty() {
+          out.push_str(&format!("<li>Missing from <code>@throws</code>: {}</li>\n", html_code_list(&function.missing_types)));
+        }
+        out.push_str("</ul>\n<table>\n<tr><th>Error</th><th>Message</th><th>Line</th></tr>\n");
+        for throw in &function.throws {
+          out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            throw.error_type.as_ref().map(|error_type| format!("<code>{}</code>", escape_html(error_type))).unwrap_or_else(|| "unknown".to_string()),
+            throw.message.as_deref().map(escape_html).unwrap_or_default(),
+            throw.location.line
+          ));
+        }
+        out.push_str("</table>\n");
+        if !entry.surfaced_through.is_empty() {
+          out.push_str("<p>Surfaced through:</p>\n<ul>\n");
+          for call in &entry.surfaced_through {
+            let caller = match caller_anchor(entries, &entry.file, &call.caller) {
+              Some(anchor) => format!("<a href=\"#{}\"><code>{}</code></a>", anchor, escape_html(&call.caller)),
+              None => format!("<code>{}</code>", escape_html(&call.caller)),
+            };
+            out.push_str(&format!("<li>{} at <code>{}:{}</code></li>\n", caller, escape_html(&entry.file), call.location.line));
+          }
+          out.push_str("</ul>\n");
+        }
+      }
+    }
+  }
+  out.push_str("</body>\n</html>\n");
+  out
+}
+
+fn html_code_list(items: &[String]) -> String {
+  items.iter().map(|item| format!("<code>{}</code>", escape_html(item))).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use self::swc_common::{sync::Lrc, SourceMap};
+  use {analyze_code, UserSettings};
+
+  fn report_for(code: &str) -> AnalysisReport {
+    let cm: Lrc<SourceMap> = Default::default();
+    let user_settings = UserSettings::default();
+    let (result, cm, _comments) = analyze_code(code, cm, &user_settings);
+    AnalysisReport::from_analysis(&result, &cm)
+  }
+
+  #[test]
+  fn test_report_serialization_shape() {
+    let code = r#"
+/**
+ * @throws {TypeError}
+ */
+function documented() {
+  throw new TypeError("bad type");
+}
+
+function undocumented() {
+  throw new Error("boom");
+}
+
+function caller() {
+  undocumented();
+}
+"#;
+    let report = report_for(code);
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+    let functions = json["functions"].as_array().unwrap();
+    let documented = &functions[0];
+    assert_eq!(documented["name"], "documented");
+    assert_eq!(documented["status"], "documented");
+    assert_eq!(documented["throws"][0]["error_type"], "TypeError");
+    assert_eq!(documented["throws"][0]["message"], "bad type");
+    assert_eq!(documented["throws_annotation"]["error_types"][0], "TypeError");
+    assert!(documented["line"].is_u64());
+    assert!(documented["column"].is_u64());
+
+    let undocumented = functions.iter().find(|f| f["name"] == "undocumented").unwrap();
+    assert_eq!(undocumented["status"], "undocumented");
+    assert!(undocumented["throws_annotation"].is_null());
+
+    let calls = json["calls_to_throws"].as_array().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0]["caller"], "caller");
+    assert_eq!(calls[0]["callee"], "undocumented");
+  }
+
+  #[test]
+  fn test_partial_documentation_and_fail_on() {
+    let code = r#"
+/**
+ * @throws {TypeError}
+ */
+function partial(flag) {
+  if (flag) {
+    throw new TypeError("bad type");
+  }
+  throw new RangeError("out of range");
+}
+"#;
+    let report = report_for(code);
+    assert_eq!(report.functions.len(), 1);
+    assert_eq!(report.functions[0].status, DocumentationStatus::Partial);
+
+    assert!(report.has_failures(FailOn::Partial));
+    assert!(report.has_failures(FailOn::Any));
+    assert!(!report.has_failures(FailOn::Undocumented));
+    assert_eq!(FailOn::parse("any"), Some(FailOn::Any));
+    assert_eq!(FailOn::parse("sometimes"), None);
+  }
+
+  #[test]
+  fn test_diagnostic_records_stream_shape() {
+    let code = r#"
+/**
+ * @throws {TypeError}
+ */
+function documented() {
+  throw new TypeError("bad type");
+}
+
+/**
+ * @throws {TypeError}
+ */
+function partial(flag) {
+  if (flag) {
+    throw new TypeError("bad type");
+  }
+  throw new RangeError("out of range");
+}
+
+function undocumented() {
+  throw new Error("boom");
+}
+"#;
+    let cm: Lrc<SourceMap> = Default::default();
+    let user_settings = UserSettings::default();
+    let (result, cm, _comments) = analyze_code(code, cm, &user_settings);
+    let records = diagnostic_records("src/a.ts", &result, &cm);
+
+    assert_eq!(records.len(), 2);
+    let lines: Vec<serde_json::Value> = records
+      .iter()
+      .map(|record| {
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        serde_json::from_str(&line).unwrap()
+      })
+      .collect();
+    assert_eq!(lines[0]["file"], "src/a.ts");
+    assert_eq!(lines[0]["rule"], "partial");
+    assert_eq!(lines[0]["severity"], "warning");
+    assert_eq!(lines[0]["message"], "partial is missing @throws documentation for RangeError");
+    assert_eq!(lines[1]["rule"], "undocumented");
+    assert_eq!(lines[1]["severity"], "error");
+    assert_eq!(lines[1]["message"], "undocumented throws Error but has no @throws documentation");
+    assert_eq!(lines[1]["line"], 19);
+    assert!(lines[1]["col"].is_u64());
+  }
+
+  #[test]
+  fn test_resolved_report_round_trip() {
+    let code = r#"
+/**
+ * @throws {TypeError}
+ */
+function incomplete(flag) {
+  if (flag) {
+    throw new TypeError("bad type");
+  }
+  throw new RangeError("out of range");
+}
+
+function undocumented() {
+  throw new Error("boom");
+}
+
+function caller() {
+  try {
+    undocumented();
+  } catch (e) {
+    console.log(e);
+  }
+}
+"#;
+    let cm: Lrc<SourceMap> = Default::default();
+    let user_settings = UserSettings::default();
+    let (result, cm, _comments) = analyze_code(code, cm, &user_settings);
+    let resolved = resolve_analysis("service.ts", &result, &cm);
+
+    assert_eq!(resolved.undocumented_functions.len(), 1);
+    let undocumented = &resolved.undocumented_functions[0];
+    assert_eq!(undocumented.name, "undocumented");
+    assert_eq!(undocumented.throws[0].location.line, 13);
+    assert_eq!(undocumented.throws[0].location.column, 2);
+
+    assert_eq!(resolved.incomplete_functions.len(), 1);
+    assert_eq!(resolved.incomplete_functions[0].documented_types, vec!["TypeError"]);
+    assert_eq!(resolved.incomplete_functions[0].missing_types, vec!["RangeError"]);
+    assert_eq!(resolved.catch_analyses.len(), 1);
+    assert_eq!(resolved.catch_analyses[0].try_location.line, 17);
+
+    let json = serde_json::to_string(&resolved).unwrap();
+    let round_tripped: ResolvedReport = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_tripped, resolved);
+  }
+
+  fn resolved_for(code: &str) -> ResolvedReport {
+    resolved_for_file("service.ts", code)
+  }
+
+  fn resolved_for_file(file: &str, code: &str) -> ResolvedReport {
+    let cm: Lrc<SourceMap> = Default::default();
+    let user_settings = UserSettings::default();
+    let (result, cm, _comments) = analyze_code(code, cm, &user_settings);
+    resolve_analysis(file, &result, &cm)
+  }
+
+  #[test]
+  fn test_diff_reports_only_reports_new_throws() {
+    let baseline = resolved_for(r#"
+function parse(input) {
+  throw new TypeError("bad input");
+}
+
+/**
+ * @throws {RangeError}
+ */
+function load(size) {
+  if (size > 10) throw new RangeError("too big");
+  throw new SyntaxError("bad header");
+}
+
+function save() {
+  throw new Error("disk full");
+}
+
+function main() {
+  parse("");
+}
+"#);
+    // parse moved down and gained a throw; save's throw is gone; load's missing type is unchanged
+    let current = resolved_for(r#"
+/**
+ * @throws {RangeError}
+ */
+function load(size) {
+  if (size > 10) throw new RangeError("too big");
+  throw new SyntaxError("bad header");
+}
+
+function save() {
+  return true;
+}
+
+
+function parse(input) {
+  if (!input) throw new TypeError("bad input");
+  throw new URIError("bad encoding");
+}
+
+function main() {
+  parse("");
+}
+"#);
+    let delta = diff_reports(&baseline, &current);
+
+    // The added throw is reported on its own, in parse and in main it propagates to
+    let new_throws: Vec<(&str, Vec<Option<&str>>)> = delta
+      .undocumented_functions
+      .iter()
+      .map(|function| (function.name.as_str(), function.throws.iter().map(|throw| throw.error_type.as_deref()).collect()))
+      .collect();
+    assert_eq!(new_throws, vec![("parse", vec![Some("URIError")]), ("main", vec![Some("URIError")])]);
+    assert_eq!(delta.throwing_calls.len(), 1);
+    assert_eq!(delta.throwing_calls[0].error_types, vec!["URIError"]);
+    // The moved throws and the removed one are not
+    assert!(delta.incomplete_functions.is_empty(), "{:?}", delta.incomplete_functions);
+    assert!(diff_reports(&current, &current).undocumented_functions.is_empty());
+    assert!(!delta.has_failures(FailOn::Partial));
+    assert!(delta.has_failures(FailOn::Undocumented));
+  }
+
+  #[test]
+  fn test_diff_reports_matches_throws_within_their_file() {
+    let code = r#"
+function parse(input) {
+  throw new TypeError("bad input");
+}
+
+function main() {
+  parse("");
+}
+"#;
+    let baseline = resolved_for_file("src/a.ts", code);
+    // The same function names and error types in another file are new problems, not the baseline's
+    let mut current = resolved_for_file("src/a.ts", code);
+    current.extend(resolved_for_file("src/b.ts", code));
+    let delta = diff_reports(&baseline, &current);
+
+    let new_functions: Vec<(&str, &str)> =
+      delta.undocumented_functions.iter().map(|function| (function.file.as_str(), function.name.as_str())).collect();
+    assert_eq!(new_functions, vec![("src/b.ts", "parse"), ("src/b.ts", "main")]);
+    let new_calls: Vec<(&str, &str)> =
+      delta.throwing_calls.iter().map(|call| (call.file.as_str(), call.caller.as_str())).collect();
+    assert_eq!(new_calls, vec![("src/b.ts", "main")]);
+  }
+
+  fn catalog_for(file: &str, code: &str) -> Vec<CatalogEntry> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let user_settings = UserSettings::default();
+    let (result, cm, _comments) = analyze_code(code, cm, &user_settings);
+    catalog_entries(file, &result, &cm)
+  }
+
+  const CATALOG_CODE: &str = r#"/**
+ * @throws {TypeError}
+ */
+function parse(input) {
+  throw new TypeError("empty | input");
+}
+
+class Store {
+  save() {
+    throw new Error("disk <full>");
+  }
+}
+
+function run() {
+  parse("x");
+}
+"#;
+
+  #[test]
+  fn test_catalog_markdown_snapshot() {
+    let markdown = render_catalog_markdown(&catalog_for("src/app.ts", CATALOG_CODE));
+    let expected = r#"# Thrown errors
+
+## Documentation coverage
+
+| File | Functions | Documented | Partial | Undocumented | Coverage |
+| --- | ---: | ---: | ---: | ---: | ---: |
+| `src/app.ts` | 3 | 1 | 0 | 2 | 33% |
+| **Total** | 3 | 1 | 0 | 2 | 33% |
+
+## `src/app.ts`
+
+### Functions
+
+#### <a id="src-app-ts-parse"></a>`parse`
+
+- Status: documented
+- Location: `src/app.ts:4`
+- Documented: `TypeError`
+
+| Error | Message | Line |
+| --- | --- | ---: |
+| `TypeError` | empty \| input | 5 |
+
+Surfaced through:
+
+- [`run`](#src-app-ts-run) at `src/app.ts:15`
+
+#### <a id="src-app-ts-run"></a>`run`
+
+- Status: undocumented
+- Location: `src/app.ts:14`
+
+| Error | Message | Line |
+| --- | --- | ---: |
+| `TypeError` | empty \| input | 14 |
+
+### Class `Store`
+
+#### <a id="src-app-ts-store-save"></a>`Store.save`
+
+- Status: undocumented
+- Location: `src/app.ts:9`
+
+| Error | Message | Line |
+| --- | --- | ---: |
+| `Error` | disk &lt;full&gt; | 10 |
+"#;
+    assert_eq!(markdown, expected);
+  }
+
+  #[test]
+  fn test_catalog_html_groups_files_and_escapes() {
+    let mut entries = catalog_for("src/b.ts", "function late() {\n  throw new RangeError(\"a & b\");\n}\n");
+    entries.extend(catalog_for("src/app.ts", CATALOG_CODE));
+    let html = render_catalog_html(&entries);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</html>\n"));
+    // Files come in order, whatever order their entries were collected in
+    let app = html.find("<h2><code>src/app.ts</code></h2>").unwrap();
+    let b = html.find("<h2><code>src/b.ts</code></h2>").unwrap();
+    assert!(app < b);
+    assert!(html.contains("<tr><td><strong>Total</strong></td><td>4</td><td>1</td><td>0</td><td>3</td><td>25%</td></tr>"));
+    assert!(html.contains("<h4 id=\"src-app-ts-store-save\">"));
+    assert!(html.contains("<td>disk &lt;full&gt;</td>"));
+    assert!(html.contains("<td>a &amp; b</td>"));
+    assert_eq!(CatalogFormat::parse("html").map(|format| format.render(&entries)), Some(html));
+  }
+}
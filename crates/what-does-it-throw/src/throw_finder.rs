@@ -1,3 +1,4 @@
+extern crate serde;
 extern crate swc_common;
 extern crate swc_ecma_ast;
 extern crate swc_ecma_parser;
@@ -7,21 +8,30 @@ use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::vec;
 
+use call_finder::CallToThrowMap;
+use ClassIgnoreComment;
+use UserSettings;
+
 use swc_ecma_ast::{
-  ArrowExpr, AssignExpr, Callee, ClassDecl, ClassMethod, Constructor, Decl,
-  ExportDecl, FnDecl, ObjectLit, PatOrExpr, Prop, PropName, PropOrSpread, Stmt, 
-  VarDeclarator,
+  ArrowExpr, AssignExpr, BinExpr, BinaryOp, Callee, ClassDecl, ClassMethod, Constructor, Decl,
+  ExportDecl, ExportDefaultExpr, FnDecl, MethodKind, ObjectLit, Param, PatOrExpr, Prop, PropName, PropOrSpread,
+  Stmt, SwitchStmt, VarDecl, VarDeclarator,
 };
 
-use self::swc_common::{comments::Comments, sync::Lrc, Span};
+use self::swc_common::{
+  comments::{Comments, SingleThreadedComments},
+  sync::Lrc,
+  BytePos, SourceMap, Span, Spanned,
+};
 use self::swc_ecma_ast::{
-  CallExpr, Expr, Function, ImportDecl, ImportSpecifier, MemberProp, ModuleExportName, ThrowStmt,
+  CallExpr, Expr, Function, ImportDecl, ImportSpecifier, MemberProp, ModuleExportName, NewExpr, ThrowStmt,
   Lit,
 };
 
+use self::serde::{Deserialize, Serialize};
 use self::swc_ecma_visit::Visit;
 
-fn prop_name_to_string(prop_name: &PropName) -> String {
+pub(crate) fn prop_name_to_string(prop_name: &PropName) -> String {
   match prop_name {
     PropName::Ident(ident) => ident.sym.to_string(),
     PropName::Str(str_) => str_.value.to_string(),
@@ -30,20 +40,29 @@ fn prop_name_to_string(prop_name: &PropName) -> String {
   }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ThrowDetails {
   pub error_type: Option<String>,    // "Error", "TypeError", etc.
   pub error_message: Option<String>, // Literal string if available
   pub is_custom_error: bool,         // true for custom classes
+  pub is_rejection: bool,            // true for `Promise.reject(...)` rather than `throw`
+  pub via_promise_executor: bool,    // Thrown inside a `new Promise(...)` executor, so it rejects instead
+  pub in_finally: bool,              // Thrown from a `finally` block, masking any error already in flight
+  pub is_env_guarded: bool,          // Only reachable behind a `process.env.X` comparison, e.g. a NODE_ENV check
+  pub arg_span: Option<Span>,        // The thrown/rejected expression, for quoting it in diagnostics
+  pub ancestor_types: Vec<String>,   // Classes `error_type` extends, nearest first, e.g. ["HttpError", "Error"]
+  pub is_rethrow: bool,              // `throw e` of a catch parameter, passing the caught error along
 }
 
-impl Default for ThrowDetails {
-  fn default() -> Self {
-    ThrowDetails {
-      error_type: None,
-      error_message: None,
-      is_custom_error: false,
-    }
+impl ThrowDetails {
+  /// Whether this error is an `error_type`: that exact type or a subclass of it
+  pub fn is_a(&self, error_type: &str) -> bool {
+    self.error_type.as_deref() == Some(error_type) || self.ancestor_types.iter().any(|ancestor| ancestor == error_type)
+  }
+
+  /// The type to name in a message; a `throw e` whose catch parameter type never resolved has none
+  pub fn reported_error_type(&self) -> Option<String> {
+    self.error_type.clone().filter(|error_type| !error_type.starts_with("variable: "))
   }
 }
 
@@ -51,6 +70,292 @@ impl Default for ThrowDetails {
 pub struct ThrowsAnnotation {
   pub error_types: Vec<String>,          // ["Error", "TypeError"] 
   pub is_documented: bool,               // Has throws annotation
+  pub covers_all: bool,                  // `@throws {*}` / `@throws {any}` documents every throw
+  pub no_throw: Option<String>,          // `@nothrow` or the alias used: any throw is an error
+  pub inherit_doc: bool,                 // `@inheritdoc`: the overridden base method's @throws apply too
+  pub descriptions: Vec<(String, String)>, // (error type, text after it), e.g. `see [docs](https://...)`
+}
+
+impl ThrowsAnnotation {
+  /// What the `@throws` tag for `error_type` says about it, if anything
+  pub fn description_for(&self, error_type: &str) -> Option<&str> {
+    self
+      .descriptions
+      .iter()
+      .find(|(documented_type, _)| documented_type == error_type)
+      .map(|(_, description)| description.as_str())
+  }
+}
+
+/// Prefix of the placeholder type recorded for `throw someFactory(...)` until the factory is resolved
+pub const ERROR_FACTORY_CALL_PREFIX: &str = "call: ";
+
+/// Name of a called function: `makeError` or `errors.notFound`
+pub fn callee_name(callee: &Expr) -> Option<String> {
+  match callee {
+    Expr::Ident(ident) => Some(ident.sym.to_string()),
+    Expr::Member(member) => match (&*member.obj, &member.prop) {
+      (Expr::Ident(obj), MemberProp::Ident(prop)) => Some(format!("{}.{}", obj.sym, prop.sym)),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Error type constructed by `new NotFound()`, `new NotFound` or `new errors.NotFound()`
+pub fn constructed_error_type(new_expr: &NewExpr) -> Option<String> {
+  match &*new_expr.callee {
+    Expr::Ident(ident) => Some(ident.sym.to_string()),
+    Expr::Member(member) => match &member.prop {
+      MemberProp::Ident(prop) => Some(prop.sym.to_string()),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Built-in calls known to throw, and the error type each throws
+pub const BUILTIN_THROWERS: [(&str, &str); 9] = [
+  ("JSON.parse", "SyntaxError"),
+  ("decodeURI", "URIError"),
+  ("decodeURIComponent", "URIError"),
+  ("encodeURI", "URIError"),
+  ("encodeURIComponent", "URIError"),
+  ("fs.readFileSync", "Error"),
+  ("fs.writeFileSync", "Error"),
+  ("fs.readdirSync", "Error"),
+  ("fs.statSync", "Error"),
+];
+
+/// The builtin name and error type if `call` is to one of `BUILTIN_THROWERS`.
+/// `JSON.parse` only counts when `flag_json_parse` opts in to it.
+pub fn builtin_thrower(call: &CallExpr, flag_json_parse: bool) -> Option<(&'static str, &'static str)> {
+  let name = call.callee.as_expr().and_then(|callee| callee_name(callee))?;
+  if name == "JSON.parse" && !flag_json_parse {
+    return None;
+  }
+  BUILTIN_THROWERS.iter().find(|(builtin, _)| *builtin == name).copied()
+}
+
+/// String-literal `name` and `message` of a thrown object literal, as in
+/// `throw { name: 'TimeoutError', code: 504, message: 'upstream timeout' }`
+pub(crate) fn object_literal_error_fields(object: &ObjectLit) -> (Option<String>, Option<String>) {
+  let mut name = None;
+  let mut message = None;
+  for prop in &object.props {
+    if let PropOrSpread::Prop(prop) = prop {
+      if let Prop::KeyValue(key_value) = &**prop {
+        let key = match &key_value.key {
+          PropName::Ident(ident) => ident.sym.to_string(),
+          PropName::Str(str_lit) => str_lit.value.to_string(),
+          _ => continue,
+        };
+        if let Expr::Lit(Lit::Str(value)) = &*key_value.value {
+          match key.as_str() {
+            "name" => name = Some(value.value.to_string()),
+            "message" => message = Some(value.value.to_string()),
+            _ => {}
+          }
+        }
+      }
+    }
+  }
+  (name, message)
+}
+
+/// Whether a documented throws type is a wildcard (`*` or `any`) rather than a concrete error
+pub fn is_wildcard_throws_type(error_type: &str) -> bool {
+  error_type == "*" || error_type == "any"
+}
+
+/// The types a `@throws` tag names and its description, from the text after `@throws`:
+/// `{HttpError} see [docs](https://...)` or `HttpError, TimeoutError when the request fails`
+pub fn parse_throws_tag(after_throws: &str) -> (Vec<String>, Option<String>) {
+  let after_throws = after_throws.trim();
+  let description = |rest: &str| {
+    let rest = rest.trim().trim_start_matches('-').trim();
+    (!rest.is_empty()).then(|| rest.to_string())
+  };
+  if let Some(start_brace) = after_throws.find('{') {
+    let Some(end_brace) = after_throws.find('}').filter(|end_brace| *end_brace > start_brace) else {
+      return (Vec::new(), None);
+    };
+    let type_name = after_throws[start_brace + 1..end_brace].trim();
+    if type_name.is_empty() {
+      return (Vec::new(), None);
+    }
+    return (vec![type_name.to_string()], description(&after_throws[end_brace + 1..]));
+  }
+
+  // A comma-separated list of names, up to the first word that doesn't continue it
+  let mut types = Vec::new();
+  let mut rest = after_throws;
+  let mut list_continues = true;
+  while let Some(word) = rest.split_whitespace().next() {
+    if !list_continues || ends_throws_type_list(word) {
+      break;
+    }
+    list_continues = word.ends_with(',');
+    rest = rest.trim_start()[word.len()..].trim_start();
+    let error_type = word.trim_end_matches(',');
+    if !error_type.is_empty() {
+      types.push(error_type.to_string());
+    }
+  }
+  (types, description(rest))
+}
+
+/// Whether a word after a brace-less `@throws` starts the description rather than naming a type:
+/// a connective like "when", or the `-` TypeDoc puts before it (`@throws E, F - message`)
+pub fn ends_throws_type_list(word: &str) -> bool {
+  word.starts_with('-')
+    || ["when", "if", "where", "that", "which", "while", "because", "since"].contains(&word.to_lowercase().as_str())
+}
+
+/// A JSDoc line without its leading `*` and any `-` bullet, e.g. ` * - @throws {E}` -> `@throws {E}`
+pub fn normalize_jsdoc_line(line: &str) -> &str {
+  line.trim().trim_start_matches('*').trim().trim_start_matches('-').trim()
+}
+
+/// Name a class or object literal member is recorded under: accessors get a `get `/`set `
+/// prefix so they don't collide with a method of the same name (e.g. `get value`)
+pub fn class_member_name(kind: MethodKind, name: &str) -> String {
+  match kind {
+    MethodKind::Method => name.to_string(),
+    MethodKind::Getter => format!("get {}", name),
+    MethodKind::Setter => format!("set {}", name),
+  }
+}
+
+/// `kind` if it is an accessor's, for `ThrowMap::accessor_kind`
+pub fn accessor_kind(kind: MethodKind) -> Option<MethodKind> {
+  match kind {
+    MethodKind::Method => None,
+    MethodKind::Getter | MethodKind::Setter => Some(kind),
+  }
+}
+
+/// The text after the first of `tags` in a JSDoc line, matched case-insensitively:
+/// `{HttpError} when offline` for `@exception {HttpError} when offline` with `exception` among them.
+/// A tag only matches as a whole word, so `throws` doesn't match `@throws_async`.
+pub fn throws_tag_text<'line>(line: &'line str, tags: &[String]) -> Option<&'line str> {
+  let lowercase = line.to_ascii_lowercase();
+  tags
+    .iter()
+    .filter_map(|tag| {
+      let tag = format!("@{}", tag.trim_start_matches('@').to_ascii_lowercase());
+      lowercase
+        .match_indices(&tag)
+        .map(|(start, _)| (start, start + tag.len()))
+        .find(|(_, end)| !line[*end..].starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '-'))
+    })
+    .min()
+    .map(|(_, end)| &line[end..])
+}
+
+/// The tag of a JSDoc line declaring the function must never throw (`@nothrow`, `@no-throw`,
+/// `@throws-free` or `@throws-never`), so messages can name the spelling the author used
+pub fn nothrow_tag(line: &str) -> Option<&'static str> {
+  let line = line.to_lowercase();
+  ["@nothrow", "@no-throw", "@throws-free", "@throws-never"].iter().copied().find(|tag| line.contains(*tag))
+}
+
+/// `@inheritdoc` / `{@inheritDoc}`: take the documentation of the overridden base method
+pub fn is_inheritdoc_tag(line: &str) -> bool {
+  line.to_lowercase().contains("@inheritdoc")
+}
+
+/// Positions a comment search must not cross: the end of every statement, class member and
+/// object property, and the opening of every block, namespace body, class body and object literal
+pub fn statement_boundaries(module: &swc_ecma_ast::Module) -> Vec<BytePos> {
+  let mut collector = StatementBoundaryCollector { boundaries: Vec::new() };
+  collector.visit_module(module);
+  let mut boundaries = collector.boundaries;
+  boundaries.sort();
+  boundaries.dedup();
+  boundaries
+}
+
+struct StatementBoundaryCollector {
+  boundaries: Vec<BytePos>,
+}
+
+impl Visit for StatementBoundaryCollector {
+  fn visit_module(&mut self, module: &swc_ecma_ast::Module) {
+    // The module span starts at its first token, which may carry the first function's JSDoc
+    self.boundaries.push(BytePos(module.span.lo.0.saturating_sub(1)));
+    swc_ecma_visit::visit_module(self, module);
+  }
+
+  fn visit_module_item(&mut self, item: &swc_ecma_ast::ModuleItem) {
+    self.boundaries.push(item.span().hi());
+    swc_ecma_visit::visit_module_item(self, item);
+  }
+
+  fn visit_stmt(&mut self, stmt: &Stmt) {
+    self.boundaries.push(stmt.span().hi());
+    swc_ecma_visit::visit_stmt(self, stmt);
+  }
+
+  fn visit_block_stmt(&mut self, block: &swc_ecma_ast::BlockStmt) {
+    self.boundaries.push(block.span.lo);
+    swc_ecma_visit::visit_block_stmt(self, block);
+  }
+
+  fn visit_ts_module_block(&mut self, block: &swc_ecma_ast::TsModuleBlock) {
+    self.boundaries.push(block.span.lo);
+    swc_ecma_visit::visit_ts_module_block(self, block);
+  }
+
+  fn visit_class(&mut self, class: &swc_ecma_ast::Class) {
+    self.boundaries.push(class.span.lo);
+    swc_ecma_visit::visit_class(self, class);
+  }
+
+  fn visit_class_member(&mut self, member: &swc_ecma_ast::ClassMember) {
+    self.boundaries.push(member.span().hi());
+    swc_ecma_visit::visit_class_member(self, member);
+  }
+
+  fn visit_object_lit(&mut self, object_lit: &ObjectLit) {
+    self.boundaries.push(object_lit.span.lo);
+    for prop in &object_lit.props {
+      self.boundaries.push(prop.span().hi());
+    }
+    swc_ecma_visit::visit_object_lit(self, object_lit);
+  }
+}
+
+/// Leading and trailing comments between `start` and the nearest statement boundary before it,
+/// nearest first. Comments at the boundary itself belong to the previous statement.
+pub fn comments_since_statement_boundary(
+  comments: &dyn Comments,
+  boundaries: &[BytePos],
+  start: BytePos,
+) -> Vec<swc_common::comments::Comment> {
+  let boundary = match boundaries.binary_search(&start) {
+    Ok(0) | Err(0) => BytePos(0),
+    Ok(index) | Err(index) => boundaries[index - 1],
+  };
+  let mut found = Vec::new();
+  let mut pos = start;
+  while pos > boundary {
+    found.extend(comments.get_leading(pos).unwrap_or_default());
+    found.extend(comments.get_trailing(pos).unwrap_or_default());
+    pos = pos - BytePos(1);
+  }
+  found
+}
+
+/// Where the comment search for a class method's JSDoc starts. A decorated method's span begins
+/// at its first decorator, so starting after the decorators also finds a JSDoc block placed
+/// between them and the method; the search still skips back over the decorators to the block above.
+pub fn class_method_doc_start(class_method: &ClassMethod) -> BytePos {
+  if class_method.function.decorators.is_empty() {
+    class_method.span.lo
+  } else {
+    class_method.key.span().lo()
+  }
 }
 
 // New structures for @typedef and @callback support
@@ -73,6 +378,16 @@ pub struct TypedefDefinition {
 pub struct TypeRegistry {
   pub callbacks: std::collections::HashMap<String, CallbackDefinition>,
   pub typedefs: std::collections::HashMap<String, TypedefDefinition>,
+  pub class_parents: std::collections::HashMap<String, String>, // `class X extends Y` as X -> Y
+  pub interface_method_throws: std::collections::HashMap<(String, String), ThrowsAnnotation>, // (interface, method) -> its @throws
+  pub class_interfaces: std::collections::HashMap<String, Vec<String>>, // `class X implements I` as X -> [I]
+  pub interface_parents: std::collections::HashMap<String, Vec<String>>, // `interface I extends J` as I -> [J]
+}
+
+impl Default for TypeRegistry {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 impl TypeRegistry {
@@ -80,7 +395,45 @@ impl TypeRegistry {
     Self {
       callbacks: std::collections::HashMap::new(),
       typedefs: std::collections::HashMap::new(),
+      class_parents: std::collections::HashMap::new(),
+      interface_method_throws: std::collections::HashMap::new(),
+      class_interfaces: std::collections::HashMap::new(),
+      interface_parents: std::collections::HashMap::new(),
+    }
+  }
+
+  /// The @throws an interface `class_name` implements documents for `method_name`, looking
+  /// through the interfaces those extend when they don't declare the method themselves
+  pub fn implemented_throws_annotation(&self, class_name: &str, method_name: &str) -> Option<&ThrowsAnnotation> {
+    let mut queue: Vec<&String> = self.class_interfaces.get(class_name)?.iter().collect();
+    let mut visited: HashSet<&String> = HashSet::new();
+    while !queue.is_empty() {
+      let interface = queue.remove(0);
+      // Guard against cyclic `extends` chains in malformed input
+      if !visited.insert(interface) {
+        continue;
+      }
+      if let Some(annotation) = self.interface_method_throws.get(&(interface.clone(), method_name.to_string())) {
+        return Some(annotation);
+      }
+      queue.extend(self.interface_parents.get(interface).into_iter().flatten());
+    }
+    None
+  }
+
+  /// Every class `class_name` extends, nearest first
+  pub fn ancestors_of(&self, class_name: &str) -> Vec<String> {
+    let mut ancestors: Vec<String> = Vec::new();
+    let mut current = class_name;
+    while let Some(parent) = self.class_parents.get(current) {
+      // Guard against `class A extends B` / `class B extends A` across files
+      if parent == class_name || ancestors.contains(parent) {
+        break;
+      }
+      ancestors.push(parent.clone());
+      current = parent;
     }
+    ancestors
   }
   
   pub fn get_callback_throws(&self, callback_name: &str) -> Option<&ThrowsAnnotation> {
@@ -103,7 +456,37 @@ struct BlockContext {
 }
 
 
-// Helper visitor to find all instanceof checks in a catch block
+/// The error type a catch block selects by comparing `subject` with `value`: `e.constructor`
+/// against a class, or `e.name` against the class name as a string
+pub(crate) fn catch_param_comparison_type(subject: &Expr, value: &Expr, catch_param: &str) -> Option<String> {
+  let Expr::Member(member) = subject else {
+    return None;
+  };
+  if !matches!(&*member.obj, Expr::Ident(obj) if &*obj.sym == catch_param) {
+    return None;
+  }
+  match (&member.prop, value) {
+    (MemberProp::Ident(prop), Expr::Ident(class)) if &*prop.sym == "constructor" => Some(class.sym.to_string()),
+    (MemberProp::Ident(prop), Expr::Lit(Lit::Str(name))) if &*prop.sym == "name" => Some(name.value.to_string()),
+    _ => None,
+  }
+}
+
+/// The error type `e instanceof T`, `e.constructor === T` or `e.name === 'T'` selects, with
+/// the comparisons accepted in either operand order
+pub(crate) fn catch_param_dispatch_type(bin_expr: &BinExpr, catch_param: &str) -> Option<String> {
+  match bin_expr.op {
+    BinaryOp::InstanceOf => match (&*bin_expr.left, &*bin_expr.right) {
+      (Expr::Ident(left), Expr::Ident(right)) if &*left.sym == catch_param => Some(right.sym.to_string()),
+      _ => None,
+    },
+    BinaryOp::EqEqEq | BinaryOp::EqEq => catch_param_comparison_type(&bin_expr.left, &bin_expr.right, catch_param)
+      .or_else(|| catch_param_comparison_type(&bin_expr.right, &bin_expr.left, catch_param)),
+    _ => None,
+  }
+}
+
+// Helper visitor to find every error type a catch block dispatches on
 struct InstanceOfVisitor {
   catch_param: String,
   pub instanceof_types: Vec<String>,
@@ -116,64 +499,139 @@ impl InstanceOfVisitor {
       instanceof_types: Vec::new(),
     }
   }
+
+  fn add_type(&mut self, type_name: String) {
+    if !self.instanceof_types.contains(&type_name) {
+      self.instanceof_types.push(type_name);
+    }
+  }
 }
 
 impl swc_ecma_visit::Visit for InstanceOfVisitor {
-  fn visit_bin_expr(&mut self, bin_expr: &swc_ecma_ast::BinExpr) {
-    if let swc_ecma_ast::BinaryOp::InstanceOf = bin_expr.op {
-      // Check if left side is our catch parameter
-      if let swc_ecma_ast::Expr::Ident(left_ident) = &*bin_expr.left {
-        if left_ident.sym.to_string() == self.catch_param {
-          // Extract the type name from the right side
-          if let swc_ecma_ast::Expr::Ident(right_ident) = &*bin_expr.right {
-            let type_name = right_ident.sym.to_string();
-            if !self.instanceof_types.contains(&type_name) {
-              self.instanceof_types.push(type_name);
-            }
-          }
-        }
-      }
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
+    if let Some(type_name) = catch_param_dispatch_type(bin_expr, &self.catch_param) {
+      self.add_type(type_name);
     }
     
     // Continue visiting child nodes
     swc_ecma_visit::visit_bin_expr(self, bin_expr);
   }
+
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt) {
+    // switch (e.constructor) { case T: } / switch (e.name) { case 'T': }
+    for test in switch_stmt.cases.iter().filter_map(|case| case.test.as_ref()) {
+      if let Some(type_name) = catch_param_comparison_type(&switch_stmt.discriminant, test, &self.catch_param) {
+        self.add_type(type_name);
+      }
+    }
+    swc_ecma_visit::visit_switch_stmt(self, switch_stmt);
+  }
 }
 
-// Helper visitor to analyze function calls within a try block
-struct TryBlockCallAnalyzer {
-  function_calls: Vec<String>,
+/// A user-configured helper that builds errors from a type name passed as an argument:
+/// `createHttpError('NotFoundError', msg)` is `{ name: "createHttpError", type_argument_index: 0 }`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorFactoryConfig {
+  pub name: String,               // Callee as written, e.g. `createHttpError` or `errors.create`
+  pub type_argument_index: usize, // Which argument carries the error type name
 }
 
-impl TryBlockCallAnalyzer {
-  fn new() -> Self {
-    Self {
-      function_calls: Vec::new(),
-    }
-  }
+/// A user-configured helper that throws when its condition is falsy: `invariant(cond, 'message')`
+/// is `{ name: "invariant", error_type: "InvariantError", message_argument_index: 1 }`. A bare name
+/// like `"ensure"` is a helper throwing an `Error` with the message as its second argument.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "AssertionConfigInput")]
+pub struct AssertionConfig {
+  pub name: String,                          // Callee as written, e.g. `invariant` or `assert.ok`
+  pub error_type: String,                    // What a failed assertion throws
+  pub message_argument_index: Option<usize>, // Which argument, if any, carries the message
 }
 
-impl swc_ecma_visit::Visit for TryBlockCallAnalyzer {
-  fn visit_call_expr(&mut self, call_expr: &swc_ecma_ast::CallExpr) {
-    // Extract function name from call expression
-    if let swc_ecma_ast::Callee::Expr(expr) = &call_expr.callee {
-      if let swc_ecma_ast::Expr::Ident(ident) = &**expr {
-        self.function_calls.push(ident.sym.to_string());
-      }
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AssertionConfigInput {
+  Name(String),
+  Config {
+    name: String,
+    error_type: String,
+    #[serde(default)]
+    message_argument_index: Option<usize>,
+  },
+}
+
+impl From<AssertionConfigInput> for AssertionConfig {
+  fn from(input: AssertionConfigInput) -> AssertionConfig {
+    match input {
+      AssertionConfigInput::Name(name) => AssertionConfig {
+        name,
+        error_type: "Error".to_string(),
+        message_argument_index: Some(1),
+      },
+      AssertionConfigInput::Config { name, error_type, message_argument_index } => AssertionConfig {
+        name,
+        error_type,
+        message_argument_index,
+      },
     }
-    // Continue visiting child nodes  
-    swc_ecma_visit::visit_call_expr(self, call_expr);
   }
-  
-  // Don't visit nested try blocks - we only want calls directly in this try block
-  fn visit_try_stmt(&mut self, _try_stmt: &swc_ecma_ast::TryStmt) {
-    // Don't visit children to avoid analyzing nested try blocks
+}
+
+/// Assertion helpers recognized when none are configured: `node:assert`'s `assert(cond, msg)`
+pub fn default_assertion_functions() -> Vec<AssertionConfig> {
+  vec![AssertionConfig {
+    name: "assert".to_string(),
+    error_type: "AssertionError".to_string(),
+    message_argument_index: Some(1),
+  }]
+}
+
+/// The helper among `assertion_functions` that `call` is to, if any
+pub fn assertion_config<'a>(call: &CallExpr, assertion_functions: &'a [AssertionConfig]) -> Option<&'a AssertionConfig> {
+  let name = call.callee.as_expr().and_then(|callee| callee_name(callee))?;
+  assertion_functions.iter().find(|assertion| assertion.name == name)
+}
+
+/// JSDoc tags, without the `@`, that document a function's errors when none are configured
+pub const DEFAULT_THROWS_TAG_ALIASES: [&str; 2] = ["throws", "exception"];
+
+pub fn default_throws_tag_aliases() -> Vec<String> {
+  DEFAULT_THROWS_TAG_ALIASES.iter().map(|tag| tag.to_string()).collect()
+}
+
+/// Finders that read `@throws` JSDoc, whose recognized tags can be configured
+pub trait ThrowsTagAliases: Sized {
+  fn throws_tag_aliases_mut(&mut self) -> &mut Vec<String>;
+
+  /// Recognize these JSDoc tags, rather than the defaults, as documenting thrown errors
+  fn with_throws_tag_aliases(mut self, throws_tag_aliases: Vec<String>) -> Self {
+    *self.throws_tag_aliases_mut() = throws_tag_aliases;
+    self
   }
 }
 
+/// Calls that end the process, recognized when none are configured
+pub const DEFAULT_TERMINATOR_FUNCTIONS: [&str; 2] = ["process.exit", "Deno.exit"];
+
+pub fn default_terminator_functions() -> Vec<String> {
+  DEFAULT_TERMINATOR_FUNCTIONS.iter().map(|name| name.to_string()).collect()
+}
+
+/// How throws behind `process.env` guards (e.g. `if (process.env.NODE_ENV !== 'production')`) are treated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvGuardedThrows {
+  #[default]
+  Include,
+  Exclude, // Drop them before propagation, as they never run in some deployments
+}
+
 pub struct ThrowFinderSettings<'throwfinder_settings> {
   pub include_try_statements: &'throwfinder_settings bool,
   pub ignore_statements: &'throwfinder_settings Vec<String>,
+  pub error_factories: &'throwfinder_settings [ErrorFactoryConfig],
+  pub assertion_functions: &'throwfinder_settings [AssertionConfig], // `assert(cond, msg)`-style calls that throw
+  pub throws_tag_aliases: &'throwfinder_settings [String], // JSDoc tags documenting thrown errors, e.g. `throws`
+  pub next_line_comments: &'throwfinder_settings [NextLineComment], // `@it-throws-next-line` comments in the file
 }
 
 impl<'throwfinder_settings> Clone for ThrowFinderSettings<'throwfinder_settings> {
@@ -181,10 +639,82 @@ impl<'throwfinder_settings> Clone for ThrowFinderSettings<'throwfinder_settings>
       ThrowFinderSettings {
           include_try_statements: self.include_try_statements,
           ignore_statements: self.ignore_statements,
+          error_factories: self.error_factories,
+          assertion_functions: self.assertion_functions,
+          throws_tag_aliases: self.throws_tag_aliases,
+          next_line_comments: self.next_line_comments,
       }
   }
 }
 
+/// The error types an ignore comment like `@it-throws ValidationError, TypeError` is limited to:
+/// empty when it covers every type, None when `text` isn't an ignore comment at all
+pub fn ignore_comment_error_types(text: &str, ignore_statements: &[String]) -> Option<Vec<String>> {
+  let text = text.trim();
+  ignore_statements.iter().find_map(|keyword| {
+    let rest = text.strip_prefix(keyword.as_str())?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+      return None;
+    }
+    Some(
+      rest
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '|' | '{' | '}'))
+        .filter(|error_type| !error_type.is_empty())
+        .map(|error_type| error_type.to_string())
+        .collect(),
+    )
+  })
+}
+
+/// An `@it-throws-next-line` comment, which covers only the throws and calls on the line below it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NextLineComment {
+  pub comment_span: Span,
+  pub line: Span, // The line right below the comment
+  pub error_types: Vec<String>, // Empty when it covers every type
+}
+
+impl NextLineComment {
+  /// Whether `pos` is on the line right below this comment
+  pub fn is_above(&self, pos: BytePos) -> bool {
+    self.line.lo <= pos && pos < self.line.hi
+  }
+
+  /// Whether this comment suppresses `error_type` thrown at `pos`
+  pub fn covers(&self, pos: BytePos, error_type: Option<&str>) -> bool {
+    self.is_above(pos)
+      && (self.error_types.is_empty() || error_type.is_some_and(|error_type| self.error_types.iter().any(|t| t == error_type)))
+  }
+}
+
+/// Every `@it-throws-next-line` comment in `comments`, for each keyword in `ignore_statements`
+/// (`@does-it-throw-ignore-next-line` works too). A comment on the last line covers nothing.
+pub fn next_line_comments(
+  cm: &SourceMap,
+  comments: &SingleThreadedComments,
+  ignore_statements: &[String],
+) -> Vec<NextLineComment> {
+  let keywords: Vec<String> = ignore_statements.iter().map(|keyword| format!("{}-next-line", keyword)).collect();
+  let (leading, trailing) = comments.borrow_all();
+  let mut next_line_comments: Vec<NextLineComment> = leading
+    .values()
+    .chain(trailing.values())
+    .flatten()
+    .filter_map(|comment| {
+      let error_types = ignore_comment_error_types(&comment.text, &keywords)?;
+      let source_line = cm.lookup_line(comment.span.hi).ok()?;
+      if source_line.line + 1 >= source_line.sf.count_lines() {
+        return None;
+      }
+      let (lo, hi) = source_line.sf.line_bounds(source_line.line + 1);
+      Some(NextLineComment { comment_span: comment.span, line: Span::new(lo, hi, Default::default()), error_types })
+    })
+    .collect();
+  next_line_comments.sort_by_key(|comment| comment.comment_span.lo);
+  next_line_comments.dedup_by_key(|comment| comment.comment_span);
+  next_line_comments
+}
+
 pub struct ThrowFinder<'throwfinder_settings> {
   comments: Lrc<dyn Comments>,
   pub throw_spans: Vec<Span>,
@@ -192,6 +722,12 @@ pub struct ThrowFinder<'throwfinder_settings> {
   context_stack: Vec<BlockContext>, // Stack to track try/catch context
   pub throwfinder_settings: &'throwfinder_settings ThrowFinderSettings<'throwfinder_settings>,
   pub used_it_throws_comments: HashSet<Span>, // Track which @it-throws comments were used
+  current_stmt_lo: Option<BytePos>, // Start of the statement being visited, for statement-level @it-throws
+  current_param_lo: Option<BytePos>, // Start of the parameter whose default value is being visited, for @it-throws above it
+  in_promise_executor: bool, // Directly inside a `new Promise(...)` executor, where throws reject the promise
+  in_finally: bool, // Directly inside a `finally` block
+  in_env_guard: bool, // Inside a branch of an `if` comparing against `process.env.X`
+  try_block_calls: Vec<Option<Vec<String>>>, // Functions called in each open try block; None in a nested try's catch/finally
 }
 
 impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
@@ -207,15 +743,88 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
       context_stack: vec![],
       throwfinder_settings,
       used_it_throws_comments: HashSet::new(), // Track used comments
+      current_stmt_lo: None,
+      current_param_lo: None,
+      in_promise_executor: false,
+      in_finally: false,
+      in_env_guard: false,
+      try_block_calls: vec![],
+    }
+  }
+
+  /// Check for an @it-throws comment leading `pos` that covers `error_type`, marking it as used if found.
+  /// `@it-throws` alone covers every type; `@it-throws ValidationError` only that one.
+  fn has_it_throws_comment_at(&mut self, pos: BytePos, error_type: Option<&str>) -> bool {
+    let Some(comments) = self.comments.get_leading(pos) else {
+      return false;
+    };
+    let mut found = false;
+    for c in comments.iter() {
+      let Some(error_types) = ignore_comment_error_types(&c.text, self.throwfinder_settings.ignore_statements) else {
+        continue;
+      };
+      let covers_error_type =
+        error_types.is_empty() || error_type.is_some_and(|error_type| error_types.iter().any(|t| t == error_type));
+      if covers_error_type {
+        self.used_it_throws_comments.insert(c.span);
+        found = true;
+      }
+    }
+    found
+  }
+
+  /// Check for an `@it-throws-next-line` comment on the line above `pos` that covers `error_type`,
+  /// marking it as used if found
+  fn has_next_line_comment_at(&mut self, pos: BytePos, error_type: Option<&str>) -> bool {
+    let covering: Vec<Span> = self
+      .throwfinder_settings
+      .next_line_comments
+      .iter()
+      .filter(|comment| comment.covers(pos, error_type))
+      .map(|comment| comment.comment_span)
+      .collect();
+    let found = !covering.is_empty();
+    self.used_it_throws_comments.extend(covering);
+    found
+  }
+
+  /// The configured assertion helper `call` is to, if any; it throws when its condition is false
+  fn assertion_config(&self, call: &CallExpr) -> Option<&'throwfinder_settings AssertionConfig> {
+    assertion_config(call, self.throwfinder_settings.assertion_functions)
+  }
+
+  /// Check for an @it-throws comment covering `error_type` on a call or on the statement containing it
+  fn call_has_it_throws_comment(&mut self, call: &CallExpr, error_type: Option<&str>) -> bool {
+    let has_call_it_throws_comment = self.has_it_throws_comment_at(call.span.lo(), error_type);
+    let has_stmt_it_throws_comment = match self.current_stmt_lo {
+      Some(stmt_lo) if stmt_lo != call.span.lo() => self.has_it_throws_comment_at(stmt_lo, error_type),
+      _ => false,
+    };
+    let has_next_line_comment = self.has_next_line_comment_at(call.span.lo(), error_type);
+    has_call_it_throws_comment
+      || has_stmt_it_throws_comment
+      || has_next_line_comment
+      || self.param_has_it_throws_comment(error_type)
+  }
+
+  /// Check for an @it-throws comment covering `error_type` above the parameter whose default value is being visited
+  fn param_has_it_throws_comment(&mut self, error_type: Option<&str>) -> bool {
+    match self.current_param_lo {
+      Some(param_lo) => self.has_it_throws_comment_at(param_lo, error_type),
+      None => false,
     }
   }
 
+  fn configured_error_factory(&self, call_expr: &CallExpr) -> Option<&'throwfinder_settings ErrorFactoryConfig> {
+    let name = call_expr.callee.as_expr().and_then(|callee| callee_name(callee))?;
+    self.throwfinder_settings.error_factories.iter().find(|factory| factory.name == name)
+  }
+
   fn analyze_throw_expression(&self, expr: &Expr) -> ThrowDetails {
     match expr {
-      // new Error("message")
+      // new Error("message"), new Error, new errors.NotFound()
       Expr::New(new_expr) => {
-        if let Expr::Ident(ident) = &*new_expr.callee {
-          let error_type = ident.sym.to_string();
+        if let Some(error_type) = constructed_error_type(new_expr) {
           let message = new_expr.args.as_ref()
             .and_then(|args| args.first())
             .and_then(|arg| self.extract_string_literal(&arg.expr));
@@ -224,6 +833,13 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
             error_type: Some(error_type.clone()),
             error_message: message,
             is_custom_error: !is_built_in_error(&error_type),
+            is_rejection: false,
+            via_promise_executor: false,
+            in_finally: false,
+            is_env_guarded: false,
+            arg_span: None,
+            ancestor_types: Vec::new(),
+            is_rethrow: false,
           }
         } else {
           ThrowDetails::default()
@@ -236,6 +852,13 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
             error_type: None,
             error_message: Some(str_lit.value.to_string()),
             is_custom_error: false,
+            is_rejection: false,
+            via_promise_executor: false,
+            in_finally: false,
+            is_env_guarded: false,
+            arg_span: None,
+            ancestor_types: Vec::new(),
+            is_rethrow: false,
           }
         } else {
           ThrowDetails::default()
@@ -246,41 +869,118 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
         let var_name = ident.sym.to_string();
         
         // Check if this variable is a catch parameter in the current context
-        if let Some(context) = self.current_context() {
-          if let Some(ref catch_param) = context.catch_param {
-            if var_name == *catch_param {
-              // This is throwing the catch parameter - determine what types it can be
-              // Use sophisticated control flow analysis instead of simple heuristics
-              let possible_types = self.analyze_catch_parameter_types_at_throw_site();
-              
-              if possible_types.len() == 1 {
-                // If only one type is possible, return that specific type
-                let error_type = possible_types[0].clone();
-                return ThrowDetails {
-                  error_type: Some(error_type.clone()),
-                  error_message: None,
-                  is_custom_error: !is_built_in_error(&error_type),
-                };
-              } else if possible_types.len() > 1 {
-                // Multiple types possible - but don't create a union
-                // Instead, return each type separately by creating multiple ThrowDetails
-                // For now, return the first type and let the caller handle multiple results
-                let error_type = possible_types[0].clone();
-                return ThrowDetails {
-                  error_type: Some(error_type.clone()),
-                  error_message: None,
-                  is_custom_error: !is_built_in_error(&error_type),
-                };
-              }
-            }
+        let is_rethrow = self
+          .current_context()
+          .and_then(|context| context.catch_param.as_ref())
+          .is_some_and(|catch_param| *catch_param == var_name);
+        if is_rethrow {
+          // This is throwing the catch parameter - determine what types it can be
+          // Use sophisticated control flow analysis instead of simple heuristics
+          let possible_types = self.analyze_catch_parameter_types_at_throw_site();
+
+          // Several types may be possible - but don't create a union.
+          // For now, return the first type and let the caller handle multiple results
+          if let Some(error_type) = possible_types.first() {
+            return ThrowDetails {
+              error_type: Some(error_type.clone()),
+              error_message: None,
+              is_custom_error: !is_built_in_error(error_type),
+              is_rejection: false,
+              via_promise_executor: false,
+              in_finally: false,
+              is_env_guarded: false,
+              arg_span: None,
+              ancestor_types: Vec::new(),
+              is_rethrow: true,
+            };
           }
         }
         
-        // Fallback to original behavior for non-catch-parameter variables
+        // Fallback for other variables, and for catch parameters whose type is unknown
         ThrowDetails {
           error_type: Some(format!("variable: {}", var_name)),
           error_message: None,
           is_custom_error: false,
+          is_rejection: false,
+          via_promise_executor: false,
+          in_finally: false,
+          is_env_guarded: false,
+          arg_span: None,
+          ancestor_types: Vec::new(),
+          is_rethrow,
+        }
+      }
+      Expr::Call(call_expr) => {
+        // throw createHttpError("NotFoundError", msg) - a configured factory names the type in an argument
+        if let Some(factory) = self.configured_error_factory(call_expr) {
+          return match call_expr
+            .args
+            .get(factory.type_argument_index)
+            .and_then(|arg| self.extract_string_literal(&arg.expr))
+          {
+            Some(error_type) => ThrowDetails {
+              error_type: Some(error_type),
+              error_message: None,
+              is_custom_error: true,
+              is_rejection: false,
+              via_promise_executor: false,
+              in_finally: false,
+              is_env_guarded: false,
+              arg_span: None,
+              ancestor_types: Vec::new(),
+              is_rethrow: false,
+            },
+            // The type is computed at runtime, so there is nothing to report
+            None => ThrowDetails::default(),
+          };
+        }
+        // throw makeError("NOT_FOUND") - resolved against known error factories once all functions are seen
+        match call_expr.callee.as_expr().and_then(|callee| callee_name(callee)) {
+          Some(name) => ThrowDetails {
+            error_type: Some(format!("{}{}", ERROR_FACTORY_CALL_PREFIX, name)),
+            error_message: None,
+            is_custom_error: false,
+            is_rejection: false,
+            via_promise_executor: false,
+            in_finally: false,
+            is_env_guarded: false,
+            arg_span: None,
+            ancestor_types: Vec::new(),
+            is_rethrow: false,
+          },
+          None => ThrowDetails::default(),
+        }
+      }
+      // throw err`not found` - the tag builds the error, so resolve it like a factory call
+      Expr::TaggedTpl(tagged) => match callee_name(&tagged.tag) {
+        Some(name) => ThrowDetails {
+          error_type: Some(format!("{}{}", ERROR_FACTORY_CALL_PREFIX, name)),
+          error_message: None,
+          is_custom_error: false,
+          is_rejection: false,
+          via_promise_executor: false,
+          in_finally: false,
+          is_env_guarded: false,
+          arg_span: None,
+          ancestor_types: Vec::new(),
+          is_rethrow: false,
+        },
+        None => ThrowDetails::default(),
+      },
+      // throw { name: 'TimeoutError', message: '...' } - the name stands in for the error class
+      Expr::Object(object) => {
+        let (name, message) = object_literal_error_fields(object);
+        ThrowDetails {
+          is_custom_error: name.is_some(),
+          error_type: name,
+          error_message: message,
+          is_rejection: false,
+          via_promise_executor: false,
+          in_finally: false,
+          is_env_guarded: false,
+          arg_span: None,
+          ancestor_types: Vec::new(),
+          is_rethrow: false,
         }
       }
       _ => ThrowDetails::default()
@@ -318,26 +1018,14 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
     }
   }
 
-  // Extract the error type from an instanceof expression like "e instanceof NetworkError"
+  // Extract the error type a branch test selects, like "e instanceof NetworkError" or
+  // "e.name === 'NetworkError'"
   fn extract_instanceof_type(&self, expr: &swc_ecma_ast::Expr) -> Option<String> {
-    if let swc_ecma_ast::Expr::Bin(bin_expr) = expr {
-      if let swc_ecma_ast::BinaryOp::InstanceOf = bin_expr.op {
-        // Check if left side is the catch parameter
-        if let swc_ecma_ast::Expr::Ident(left_ident) = &*bin_expr.left {
-          if let Some(context) = self.current_context() {
-            if let Some(ref catch_param) = context.catch_param {
-              if left_ident.sym.to_string() == *catch_param {
-                // Extract the type name from the right side
-                if let swc_ecma_ast::Expr::Ident(right_ident) = &*bin_expr.right {
-                  return Some(right_ident.sym.to_string());
-                }
-              }
-            }
-          }
-        }
-      }
-    }
-    None
+    let swc_ecma_ast::Expr::Bin(bin_expr) = expr else {
+      return None;
+    };
+    let catch_param = self.current_context()?.catch_param.as_ref()?;
+    catch_param_dispatch_type(bin_expr, catch_param)
   }
 
   // Analyze a catch block to find all instanceof checks
@@ -372,17 +1060,10 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
     }
   }
 
-  // Enhanced method to infer what error types are actually thrown in a try block
-  fn infer_possible_error_types(&self, try_block: &swc_ecma_ast::BlockStmt) -> Vec<String> {
-    // Create a temporary ThrowFinder to analyze just this try block
-    let mut temp_finder = ThrowFinder::new(self.throwfinder_settings, self.comments.clone());
-    
-    // Visit the try block to find all throws
-    temp_finder.visit_block_stmt(try_block);
-    
-    // Extract error types from the found throws
+  // Infer what error types are thrown in a try block from the throws and calls found while visiting it
+  fn infer_possible_error_types(try_block_throws: &[ThrowDetails], try_block_calls: &[String]) -> Vec<String> {
     let mut error_types = Vec::new();
-    for throw_detail in &temp_finder.throw_details {
+    for throw_detail in try_block_throws {
       if let Some(ref error_type) = throw_detail.error_type {
         // Skip variable throws during this analysis phase to avoid recursion
         if !error_type.starts_with("variable: ") {
@@ -391,36 +1072,20 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
       }
     }
     
-    // Also analyze function calls in the try block to get their thrown types
-    let mut call_analyzer = TryBlockCallAnalyzer::new();
-    call_analyzer.visit_block_stmt(try_block);
-    
     // For now, add some common error types that are typically handled
     // This is a heuristic until we have full call graph integration
-    for function_call in &call_analyzer.function_calls {
-      match function_call.as_str() {
-        "validateUserInput" => {
-          if !error_types.contains(&"ValidationError".to_string()) {
-            error_types.push("ValidationError".to_string());
-          }
-        },
-        "fetchUserFromNetwork" => {
-          if !error_types.contains(&"NetworkError".to_string()) {
-            error_types.push("NetworkError".to_string());
-          }
-        },
-        "authenticateUser" => {
-          if !error_types.contains(&"AuthenticationError".to_string()) {
-            error_types.push("AuthenticationError".to_string());
-          }
-        },
-        "saveToDatabase" => {
-          if !error_types.contains(&"DatabaseError".to_string()) {
-            error_types.push("DatabaseError".to_string());
-          }
-        },
-        _ => {
-          // For unknown functions, don't assume error types
+    for function_call in try_block_calls {
+      let known_error_type = match function_call.as_str() {
+        "validateUserInput" => Some("ValidationError"),
+        "fetchUserFromNetwork" => Some("NetworkError"),
+        "authenticateUser" => Some("AuthenticationError"),
+        "saveToDatabase" => Some("DatabaseError"),
+        // For unknown functions, don't assume error types
+        _ => None,
+      };
+      if let Some(error_type) = known_error_type {
+        if !error_types.iter().any(|existing| existing == error_type) {
+          error_types.push(error_type.to_string());
         }
       }
     }
@@ -433,39 +1098,80 @@ impl<'throwfinder_settings> ThrowFinder<'throwfinder_settings> {
   }
 }
 
-fn is_built_in_error(name: &str) -> bool {
+pub fn is_built_in_error(name: &str) -> bool {
   matches!(name, "Error" | "TypeError" | "ReferenceError" | "RangeError" |
                  "SyntaxError" | "URIError" | "EvalError" | "AggregateError")
 }
 
+/// Return the executor if `new_expr` is `new Promise(executor)` with an inline function
+fn promise_executor(new_expr: &NewExpr) -> Option<&Expr> {
+  let is_promise = matches!(&*new_expr.callee, Expr::Ident(ident) if ident.sym == *"Promise");
+  let executor = &*new_expr.args.as_ref()?.first()?.expr;
+  if is_promise && matches!(executor, Expr::Arrow(_) | Expr::Fn(_)) {
+    Some(executor)
+  } else {
+    None
+  }
+}
+
+/// Whether `expr` is `process.env.X` or `process.env['X']`
+fn is_process_env_member(expr: &Expr) -> bool {
+  let Expr::Member(member) = expr else {
+    return false;
+  };
+  let Expr::Member(env) = &*member.obj else {
+    return false;
+  };
+  let is_process = matches!(&*env.obj, Expr::Ident(ident) if ident.sym == *"process");
+  let is_env = matches!(&env.prop, MemberProp::Ident(ident) if ident.sym == *"env");
+  is_process && is_env
+}
+
+/// Whether an `if` test compares a `process.env` variable, e.g. `process.env.NODE_ENV !== 'production'`
+pub fn is_env_guard_test(test: &Expr) -> bool {
+  let Expr::Bin(bin_expr) = test else {
+    return false;
+  };
+  let is_comparison = matches!(
+    bin_expr.op,
+    BinaryOp::EqEq | BinaryOp::EqEqEq | BinaryOp::NotEq | BinaryOp::NotEqEq
+  );
+  is_comparison && (is_process_env_member(&bin_expr.left) || is_process_env_member(&bin_expr.right))
+}
+
+/// Return the rejection reason if `call` is `Promise.reject(reason)`
+fn promise_reject_argument(call: &CallExpr) -> Option<&Expr> {
+  let Callee::Expr(callee) = &call.callee else {
+    return None;
+  };
+  let Expr::Member(member) = &**callee else {
+    return None;
+  };
+  let is_promise = matches!(&*member.obj, Expr::Ident(ident) if ident.sym == *"Promise");
+  let is_reject = matches!(&member.prop, MemberProp::Ident(ident) if ident.sym == *"reject");
+  if is_promise && is_reject {
+    call.args.first().map(|arg| &*arg.expr)
+  } else {
+    None
+  }
+}
+
 
 impl<'throwfinder_settings> Visit for ThrowFinder<'throwfinder_settings> {
   fn visit_throw_stmt(&mut self, node: &ThrowStmt) {
+    // NEW: Extract error details from the throw expression
+    let mut throw_details = self.analyze_throw_expression(&node.arg);
+
     // Check for @it-throws comment directly on this throw statement
-    let has_direct_it_throws_comment = self
-      .comments
-      .get_leading(node.span.lo())
-      .filter(|comments| {
-        comments.iter().any(|c| {
-          let is_ignore_comment = self
-            .throwfinder_settings
-            .ignore_statements
-            .iter()
-            .any(|keyword| c.text.trim() == *keyword);
-          
-          if is_ignore_comment {
-            // Mark this comment as used
-            self.used_it_throws_comments.insert(c.span);
-          }
-          
-          is_ignore_comment
-        })
-      })
-      .is_some();
+    let has_direct_it_throws_comment = self.has_it_throws_comment_at(node.span.lo(), throw_details.error_type.as_deref())
+      || self.has_next_line_comment_at(node.span.lo(), throw_details.error_type.as_deref())
+      || self.param_has_it_throws_comment(throw_details.error_type.as_deref());
 
     if !has_direct_it_throws_comment {
-      // NEW: Extract error details from the throw expression
-      let throw_details = self.analyze_throw_expression(&node.arg);
+      throw_details.arg_span = Some(node.arg.span());
+      throw_details.via_promise_executor = self.in_promise_executor;
+      throw_details.in_finally = self.in_finally;
+      throw_details.is_env_guarded = self.in_env_guard;
 
       // Always collect throws - filtering will happen later based on catch analysis
       // The include_try_statements setting only affects final output, not detection
@@ -474,6 +1180,94 @@ impl<'throwfinder_settings> Visit for ThrowFinder<'throwfinder_settings> {
     }
   }
 
+  fn visit_stmt(&mut self, stmt: &Stmt) {
+    let previous_stmt_lo = self.current_stmt_lo.replace(stmt.span().lo());
+    swc_ecma_visit::visit_stmt(self, stmt);
+    self.current_stmt_lo = previous_stmt_lo;
+  }
+
+  // A default value like `x = (() => { throw ... })()` runs as part of the call, so its throws
+  // belong to the function
+  fn visit_param(&mut self, param: &Param) {
+    let previous_param_lo = self.current_param_lo.replace(param.span.lo());
+    swc_ecma_visit::visit_param(self, param);
+    self.current_param_lo = previous_param_lo;
+  }
+
+  fn visit_call_expr(&mut self, call: &CallExpr) {
+    // `Promise.reject(err)` behaves like `throw err` for callers awaiting the result
+    if let Some(arg) = promise_reject_argument(call) {
+      let mut throw_details = self.analyze_throw_expression(arg);
+      if !self.call_has_it_throws_comment(call, throw_details.error_type.as_deref()) {
+        throw_details.is_rejection = true;
+        throw_details.arg_span = Some(arg.span());
+        self.throw_spans.push(call.span);
+        self.throw_details.push(throw_details);
+      }
+    } else if let Some(assertion) = self.assertion_config(call) {
+      // `assert(cond, msg)` throws the configured type carrying `msg` when the condition is false
+      if !self.call_has_it_throws_comment(call, Some(&assertion.error_type)) {
+        self.throw_spans.push(call.span);
+        self.throw_details.push(ThrowDetails {
+          error_type: Some(assertion.error_type.clone()),
+          error_message: assertion
+            .message_argument_index
+            .and_then(|index| call.args.get(index))
+            .and_then(|arg| self.extract_string_literal(&arg.expr)),
+          is_custom_error: !is_built_in_error(&assertion.error_type),
+          arg_span: Some(call.span),
+          ..Default::default()
+        });
+      }
+    }
+    if let (Some(Some(calls)), Some(Expr::Ident(ident))) =
+      (self.try_block_calls.last_mut(), call.callee.as_expr().map(|callee| &**callee))
+    {
+      calls.push(ident.sym.to_string());
+    }
+    swc_ecma_visit::visit_call_expr(self, call);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr) {
+    let Some(executor) = promise_executor(new_expr) else {
+      swc_ecma_visit::visit_new_expr(self, new_expr);
+      return;
+    };
+    // Visit the executor's body directly so the flag isn't reset on entering it
+    let previous = std::mem::replace(&mut self.in_promise_executor, true);
+    match executor {
+      Expr::Arrow(arrow_expr) => self.visit_block_stmt_or_expr(&arrow_expr.body),
+      Expr::Fn(fn_expr) => {
+        if let Some(body) = &fn_expr.function.body {
+          self.visit_block_stmt(body);
+        }
+      }
+      _ => {}
+    }
+    self.in_promise_executor = previous;
+    for arg in new_expr.args.iter().flatten().skip(1) {
+      self.visit_expr_or_spread(arg);
+    }
+  }
+
+  // A function nested in the executor or finally block runs later, outside the promise's reach
+  // and after the in-flight error is settled
+  fn visit_function(&mut self, function: &Function) {
+    let previous = std::mem::replace(&mut self.in_promise_executor, false);
+    let previous_in_finally = std::mem::replace(&mut self.in_finally, false);
+    swc_ecma_visit::visit_function(self, function);
+    self.in_promise_executor = previous;
+    self.in_finally = previous_in_finally;
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr) {
+    let previous = std::mem::replace(&mut self.in_promise_executor, false);
+    let previous_in_finally = std::mem::replace(&mut self.in_finally, false);
+    swc_ecma_visit::visit_arrow_expr(self, arrow_expr);
+    self.in_promise_executor = previous;
+    self.in_finally = previous_in_finally;
+  }
+
   fn visit_try_stmt(&mut self, try_stmt: &swc_ecma_ast::TryStmt) {
     // Analyze the try block first
     self.context_stack.push(BlockContext {
@@ -483,8 +1277,19 @@ impl<'throwfinder_settings> Visit for ThrowFinder<'throwfinder_settings> {
       current_instanceof_type: None,
     });
 
-    // Visit the try block
+    // Visit the try block, noting what it throws and calls for the catch clause below
+    let first_try_block_throw = self.throw_details.len();
+    self.try_block_calls.push(Some(vec![]));
     self.visit_block_stmt(&try_stmt.block);
+    let try_block_calls = self.try_block_calls.pop().flatten().unwrap_or_default();
+    // Without a catch clause nothing is handled here, so the calls count toward the enclosing try
+    if try_stmt.handler.is_none() {
+      if let Some(Some(enclosing_calls)) = self.try_block_calls.last_mut() {
+        enclosing_calls.extend(try_block_calls.iter().cloned());
+      }
+    }
+    // Calls in this statement's catch and finally belong to no enclosing try block
+    self.try_block_calls.push(None);
 
     // If there's a catch clause, analyze it for control flow
     if let Some(ref catch_clause) = try_stmt.handler {
@@ -494,7 +1299,8 @@ impl<'throwfinder_settings> Visit for ThrowFinder<'throwfinder_settings> {
       let instanceof_checks = self.find_instanceof_checks_in_catch(catch_clause, &catch_param);
       
       // Infer possible error types from try block
-      let possible_error_types = self.infer_possible_error_types(&try_stmt.block);
+      let possible_error_types =
+        Self::infer_possible_error_types(&self.throw_details[first_try_block_throw..], &try_block_calls);
       
       // Update context with catch information
       if let Some(context) = self.context_stack.last_mut() {
@@ -509,9 +1315,12 @@ impl<'throwfinder_settings> Visit for ThrowFinder<'throwfinder_settings> {
 
     // Visit finally block if present
     if let Some(ref finally_block) = try_stmt.finalizer {
+      let previous_in_finally = std::mem::replace(&mut self.in_finally, true);
       self.visit_block_stmt(finally_block);
+      self.in_finally = previous_in_finally;
     }
 
+    self.try_block_calls.pop();
     self.context_stack.pop();
   }
 
@@ -544,6 +1353,14 @@ impl<'throwfinder_settings> Visit for ThrowFinder<'throwfinder_settings> {
       
       return; // Don't call the default visitor
     }
+
+    // Both branches of an environment check are dead code in some deployment
+    if is_env_guard_test(&if_stmt.test) {
+      let previous_in_env_guard = std::mem::replace(&mut self.in_env_guard, true);
+      swc_ecma_visit::visit_if_stmt(self, if_stmt);
+      self.in_env_guard = previous_in_env_guard;
+      return;
+    }
     
     // Default behavior for non-instanceof if statements
     swc_ecma_visit::visit_if_stmt(self, if_stmt);
@@ -557,15 +1374,19 @@ pub struct IdentifierUsage {
   pub identifier_name: String,
   pub usage_context: String,
   pub id: String,
+  pub callee: String, // The call as written, e.g. `parse` or `lib.parse`
+  pub is_accessor: bool, // A property read or write, which only throws if the member is an accessor
 }
 
 impl IdentifierUsage {
-  pub fn new(usage_span: Span, identifier_name: String, usage_context: String, id: String) -> Self {
+  pub fn new(usage_span: Span, identifier_name: String, usage_context: String, id: String, callee: String) -> Self {
     Self {
       usage_span,
       identifier_name,
       usage_context,
       id,
+      callee,
+      is_accessor: false,
     }
   }
 }
@@ -574,7 +1395,7 @@ impl Eq for IdentifierUsage {}
 
 impl PartialEq for IdentifierUsage {
   fn eq(&self, other: &Self) -> bool {
-    self.id == other.id
+    self.id == other.id && self.usage_span == other.usage_span
   }
 }
 
@@ -592,11 +1413,15 @@ impl Hash for IdentifierUsage {
 pub struct ThrowMap {
   pub throw_spans: Vec<Span>,
   pub throw_statement: Span,
+  pub function_span: Span,                          // The whole function, for containment checks
   pub function_or_method_name: String,
   pub class_name: Option<String>,
   pub id: String,
   pub throw_details: Vec<ThrowDetails>,             // NEW: Error details for each throw
   pub throws_annotation: Option<ThrowsAnnotation>,  // NEW: Function-level throws annotation
+  pub is_async: bool,                               // Declared `async`: throws surface as promise rejections
+  pub name_span: Option<Span>,                      // The function's name, where its diagnostics start
+  pub accessor_kind: Option<MethodKind>,            // Getter or Setter: runs on property reads or writes rather than calls
 }
 
 impl PartialEq for ThrowMap {
@@ -615,11 +1440,61 @@ impl Hash for ThrowMap {
   }
 }
 
+/// A call to one of `BUILTIN_THROWERS`
+#[derive(Clone, Debug)]
+pub struct BuiltinThrowCall {
+  pub call_span: Span,
+  pub builtin_name: String, // As listed in `BUILTIN_THROWERS`, e.g. `JSON.parse`
+  pub error_type: String,   // What it throws, e.g. `SyntaxError`
+}
+
+impl BuiltinThrowCall {
+  /// The builtin as a thrower, so calls to it flow through the same pipeline as calls to user functions
+  pub fn throw_map(&self) -> ThrowMap {
+    ThrowMap {
+      throw_spans: vec![self.call_span],
+      throw_statement: self.call_span,
+      function_span: self.call_span,
+      function_or_method_name: self.builtin_name.clone(),
+      class_name: None,
+      id: format!("builtin-{}", self.builtin_name),
+      throw_details: vec![ThrowDetails {
+        error_type: Some(self.error_type.clone()),
+        ..Default::default()
+      }],
+      throws_annotation: None,
+      is_async: false,
+      name_span: None,
+      accessor_kind: None,
+    }
+  }
+}
+
+/// Collects every call to a throwing builtin, however deeply nested
+#[derive(Default)]
+struct BuiltinThrowCallFinder {
+  calls: Vec<BuiltinThrowCall>,
+  flag_json_parse: bool,
+}
+
+impl Visit for BuiltinThrowCallFinder {
+  fn visit_call_expr(&mut self, call: &CallExpr) {
+    if let Some((builtin_name, error_type)) = builtin_thrower(call, self.flag_json_parse) {
+      self.calls.push(BuiltinThrowCall {
+        call_span: call.span,
+        builtin_name: builtin_name.to_string(),
+        error_type: error_type.to_string(),
+      });
+    }
+    swc_ecma_visit::visit_call_expr(self, call);
+  }
+}
+
 pub struct ThrowAnalyzer<'throwfinder_settings> {
   pub comments: Lrc<dyn Comments>,
   pub functions_with_throws: HashSet<ThrowMap>,
-  pub json_parse_calls: Vec<String>,
-  pub fs_access_calls: Vec<String>,
+  pub builtin_throw_calls: Vec<BuiltinThrowCall>, // Calls to builtins known to throw, like `JSON.parse`
+  pub flag_json_parse: bool,                     // Whether `JSON.parse` counts as a throwing builtin
   pub import_sources: HashSet<String>,
   pub imported_identifiers: Vec<String>,
   pub function_name_stack: Vec<String>,
@@ -628,9 +1503,52 @@ pub struct ThrowAnalyzer<'throwfinder_settings> {
   pub throwfinder_settings: ThrowFinderSettings<'throwfinder_settings>,
   pub used_it_throws_comments: HashSet<Span>, // Track which @it-throws comments were used
   pub type_registry: TypeRegistry,             // Track @typedef and @callback definitions
+  pub statement_boundaries: Vec<BytePos>,      // Limits how far back a function's @throws is looked for
 }
 
 impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
+  /// An analyzer that hasn't visited anything yet, with an empty type registry
+  pub fn new(
+    comments: Lrc<dyn Comments>,
+    throwfinder_settings: ThrowFinderSettings<'throwfinder_settings>,
+    user_settings: &UserSettings,
+  ) -> Self {
+    Self {
+      comments,
+      functions_with_throws: HashSet::new(),
+      builtin_throw_calls: Vec::new(),
+      flag_json_parse: user_settings.flag_json_parse,
+      import_sources: HashSet::new(),
+      imported_identifiers: Vec::new(),
+      function_name_stack: Vec::new(),
+      current_class_name: None,
+      current_method_name: None,
+      throwfinder_settings,
+      used_it_throws_comments: HashSet::new(),
+      type_registry: TypeRegistry::new(),
+      statement_boundaries: Vec::new(),
+    }
+  }
+
+  /// Like `visit_module`, but only looks for throws in the top-level items `analyze` picks.
+  /// Builtin throw calls are still collected from the whole module.
+  pub fn visit_module_items_where(
+    &mut self,
+    module: &swc_ecma_ast::Module,
+    mut analyze: impl FnMut(&swc_ecma_ast::ModuleItem) -> bool,
+  ) {
+    self.statement_boundaries = statement_boundaries(module);
+    let mut builtin_finder = BuiltinThrowCallFinder {
+      flag_json_parse: self.flag_json_parse,
+      ..Default::default()
+    };
+    builtin_finder.visit_module(module);
+    self.builtin_throw_calls.extend(builtin_finder.calls);
+    for item in module.body.iter().filter(|item| analyze(item)) {
+      self.visit_module_item(item);
+    }
+  }
+
   fn check_function_for_throws(&mut self, function: &Function) {
     let mut throw_finder = ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
     throw_finder.visit_function(function);
@@ -647,6 +1565,7 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
       let throw_map = ThrowMap {
         throw_spans: throw_finder.throw_spans,
         throw_statement: function.span,
+        function_span: function.span,
         function_or_method_name: self
           .function_name_stack
           .last()
@@ -667,56 +1586,43 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
         ),
         throw_details: throw_finder.throw_details,  // NEW: Pass error details from ThrowFinder
         throws_annotation,                          // NEW: Add throws annotation
+        is_async: function.is_async,
+        name_span: None,
+        accessor_kind: None,
       };
       // Always insert the function - suppression happens in WASM layer
       self.functions_with_throws.insert(throw_map);
     }
   }
 
-  /// Check if a function declaration has @it-throws comment and should be ignored
+  /// Read the `@throws` annotation from the JSDoc leading a whole declaration, e.g. the
+  /// `export const` statement an arrow function is assigned in
+  fn declaration_throws_annotation(&self, declaration_span: Span) -> Option<ThrowsAnnotation> {
+    self
+      .comments
+      .get_leading(declaration_span.lo())?
+      .iter()
+      .rev()
+      .find_map(|comment| self.parse_throws_comment(&comment.text))
+  }
+
+  /// Find the `@throws` annotation documenting a function: its own leading comments, or line
+  /// and block comments above the declaration it belongs to, without reaching past the
+  /// previous statement
   fn extract_throws_annotation(&self, function_span: Span) -> Option<ThrowsAnnotation> {
-    // Simple approach: Look for leading comments before the function
-    // Also search in a range before the function to catch comments attached to parent declarations
-    
-    // Strategy 1: Direct leading comments at function start
-    if let Some(comments) = self.comments.get_leading(function_span.lo()) {
-      for comment in comments {
-        if let Some(annotation) = self.parse_throws_comment(&comment.text) {
-          #[cfg(debug_assertions)]
-          eprintln!("   ✅ Found throws annotation in direct leading comment: {:?}", annotation);
-          return Some(annotation);
-        }
-      }
-    }
-    
-    // Strategy 2: Search backwards for comments (to catch variable declaration comments)
-    for offset in 1..50 {  // Reduced range to prevent cross-function contamination
-      let search_pos = if function_span.lo().0 >= offset {
-        function_span.lo() - swc_common::BytePos(offset)
-      } else {
-        swc_common::BytePos(0)
-      };
-      
-      if let Some(comments) = self.comments.get_leading(search_pos) {
-        for comment in comments {
-          if let Some(annotation) = self.parse_throws_comment(&comment.text) {
-            #[cfg(debug_assertions)]
-            eprintln!("   ✅ Found throws annotation in backward search at -{}: {:?}", offset, annotation);
-            return Some(annotation);
-          }
-        }
-      }
-      
-      // Stop searching if we've gone too far back
-      if search_pos.0 == 0 {
-        break;
-      }
-    }
+    self.extract_throws_annotation_at(function_span.lo())
+  }
+
+  /// `extract_throws_annotation`, searching back from `start` rather than the function's start
+  fn extract_throws_annotation_at(&self, start: BytePos) -> Option<ThrowsAnnotation> {
+    let annotation = comments_since_statement_boundary(&*self.comments, &self.statement_boundaries, start)
+      .iter()
+      .find_map(|comment| self.parse_throws_comment(&comment.text));
 
     #[cfg(debug_assertions)]
-    eprintln!("   ❌ No throws annotation found in leading comments for function span {:?}", function_span);
-    
-    None
+    debug_log!("   Throws annotation for function starting at {:?}: {:?}", start, annotation);
+
+    annotation
   }
 
   /// Mark @it-throws comments on function declarations as used
@@ -729,11 +1635,8 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
       // Strategy 1: Direct leading comments at function start
       if let Some(comments) = self.comments.get_leading(function_span.lo()) {
         for comment in comments {
-          let is_ignore_comment = self
-            .throwfinder_settings
-            .ignore_statements
-            .iter()
-            .any(|keyword| comment.text.trim() == *keyword);
+          let is_ignore_comment =
+            ignore_comment_error_types(&comment.text, self.throwfinder_settings.ignore_statements).is_some();
           
           if is_ignore_comment {
             self.used_it_throws_comments.insert(comment.span);
@@ -751,11 +1654,8 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
         
         if let Some(comments) = self.comments.get_leading(search_pos) {
           for comment in comments {
-            let is_ignore_comment = self
-              .throwfinder_settings
-              .ignore_statements
-              .iter()
-              .any(|keyword| comment.text.trim() == *keyword);
+            let is_ignore_comment =
+              ignore_comment_error_types(&comment.text, self.throwfinder_settings.ignore_statements).is_some();
             
             if is_ignore_comment {
               self.used_it_throws_comments.insert(comment.span);
@@ -771,6 +1671,25 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
     }
   }
 
+  /// Mark @it-throws comments above class declarations as used when a method of the class throws,
+  /// or calls something that does, an error type they cover
+  pub fn mark_class_it_throws_comments_as_used(&mut self, class_comments: &[ClassIgnoreComment], calls: &HashSet<CallToThrowMap>) {
+    for class_comment in class_comments {
+      let throws_in_class = self
+        .functions_with_throws
+        .iter()
+        .filter(|fun| class_comment.class_span.contains(fun.function_span))
+        .flat_map(|fun| &fun.throw_details);
+      let calls_in_class = calls
+        .iter()
+        .filter(|call| class_comment.class_span.contains(call.call_span))
+        .flat_map(|call| &call.throw_map.throw_details);
+      if throws_in_class.chain(calls_in_class).any(|detail| class_comment.covers(detail.error_type.as_deref())) {
+        self.used_it_throws_comments.insert(class_comment.comment_span);
+      }
+    }
+  }
+
   fn parse_throws_comment(&self, comment_text: &str) -> Option<ThrowsAnnotation> {
     // Only support JSDoc @throws syntax:
     // /** @throws {ErrorType} description */
@@ -781,67 +1700,61 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
     //  */
     let text = comment_text.trim();
     let mut error_types: std::collections::HashSet<String> = std::collections::HashSet::new(); // Use HashSet to deduplicate
+    let mut descriptions: Vec<(String, String)> = Vec::new();
+    let mut no_throw = None; // Set by @nothrow and its aliases
+    let mut inherit_doc = false; // Set by @inheritdoc
 
     let lines: Vec<&str> = text.lines()
-      .map(|line| line.trim().trim_start_matches('*').trim())
+      .map(normalize_jsdoc_line)
       .collect();
 
     for line in &lines {
-      if line.to_lowercase().contains("@throws") {
-        if let Some(throws_pos) = line.to_lowercase().find("@throws") {
-          let after_throws = &line[throws_pos + 7..].trim(); // Skip "@throws"
-
-          // Handle @throws {Type} syntax
-          if let Some(start_brace) = after_throws.find('{') {
-            if let Some(end_brace) = after_throws.find('}') {
-              let type_name = &after_throws[start_brace + 1..end_brace].trim();
-              if !type_name.is_empty() {
-                error_types.insert(type_name.to_string());
-                continue;
-              }
-            }
-          }
-          // Handle @throws Type (without braces) - extract comma-separated types
-          if !after_throws.starts_with('{') {
-            let type_section = after_throws
-              .split_whitespace()
-              .take_while(|word| {
-                !["when", "if", "where", "that", "which", "while", "because", "since"].contains(&word.to_lowercase().as_str())
-              })
-              .collect::<Vec<_>>()
-              .join(" ");
-            let types: Vec<String> = type_section
-              .split(',')
-              .map(|s| s.trim().trim_end_matches(',').trim().to_string())
-              .filter(|s| !s.is_empty())
-              .collect();
-            for error_type in types {
-              error_types.insert(error_type);
-            }
+      if is_inheritdoc_tag(line) {
+        inherit_doc = true;
+      }
+      if let Some(tag) = nothrow_tag(line) {
+        no_throw = Some(tag.to_string());
+        continue;
+      }
+      if let Some(after_tag) = throws_tag_text(line, self.throwfinder_settings.throws_tag_aliases) {
+        // Handle both @throws {Type} and @throws Type, Other (without braces), each with an optional description
+        let (types, description) = parse_throws_tag(after_tag);
+        for error_type in types {
+          if let Some(description) = &description {
+            descriptions.push((error_type.clone(), description.clone()));
           }
+          error_types.insert(error_type);
         }
       }
     }
-    if !error_types.is_empty() {
+    if !error_types.is_empty() || no_throw.is_some() || inherit_doc {
+      let covers_all = error_types.iter().any(|t| is_wildcard_throws_type(t));
       Some(ThrowsAnnotation { 
         error_types: error_types.into_iter().collect(),
-        is_documented: true 
+        is_documented: true,
+        covers_all,
+        no_throw,
+        inherit_doc,
+        descriptions,
       })
     } else {
       None
     }
   }
 
-  fn check_arrow_function_for_throws(&mut self, arrow_function: &ArrowExpr) {
+  fn check_arrow_function_for_throws(&mut self, arrow_function: &ArrowExpr, declaration_span: Option<Span>) {
     let mut throw_finder = ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
     throw_finder.visit_arrow_expr(arrow_function);
     if !throw_finder.throw_spans.is_empty() {
-      // NEW: Extract throws annotation from arrow function comments
-      let throws_annotation = self.extract_throws_annotation(arrow_function.span);
+      // Prefer the JSDoc on the enclosing declaration, then comments near the arrow itself
+      let throws_annotation = declaration_span
+        .and_then(|span| self.declaration_throws_annotation(span))
+        .or_else(|| self.extract_throws_annotation(arrow_function.span));
 
       let throw_map = ThrowMap {
         throw_spans: throw_finder.throw_spans,
         throw_statement: arrow_function.span,
+        function_span: arrow_function.span,
         function_or_method_name: self
           .function_name_stack
           .last()
@@ -862,6 +1775,9 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
         ),
         throw_details: throw_finder.throw_details,  // NEW: Pass error details from ThrowFinder
         throws_annotation,                          // NEW: Add throws annotation
+        is_async: arrow_function.is_async,
+        name_span: None,
+        accessor_kind: None,
       };
       // Always insert the function - suppression happens in WASM layer
       self.functions_with_throws.insert(throw_map);
@@ -878,6 +1794,7 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
       let throw_map = ThrowMap {
         throw_spans: throw_finder.throw_spans,
         throw_statement: constructor.span,
+        function_span: constructor.span,
         function_or_method_name: self
           .current_method_name
           .clone()
@@ -896,12 +1813,79 @@ impl<'throwfinder_settings> ThrowAnalyzer<'throwfinder_settings> {
         ),
         throw_details: throw_finder.throw_details,  // NEW: Pass error details from ThrowFinder
         throws_annotation,                          // NEW: Add throws annotation
+        is_async: false,
+        name_span: None,
+        accessor_kind: None,
       };
       // Always insert the function - suppression happens in WASM layer
       self.functions_with_throws.insert(throw_map);
     }
   }
 
+  /// Record a `const name = () => {}` style function. `declaration_span` is the enclosing
+  /// `const`/`export const` statement whose JSDoc documents the function.
+  fn check_var_declarator_for_throws(&mut self, declarator: &VarDeclarator, declaration_span: Option<Span>) {
+    if let Some(ident) = &declarator.name.as_ident() {
+      if let Some(init) = &declarator.init {
+        let function_name = ident.sym.to_string();
+        let throwfinder_settings_clone = self.throwfinder_settings.clone();
+        let mut throw_finder = ThrowFinder::new(&throwfinder_settings_clone, self.comments.clone());
+        let is_async = match &**init {
+          Expr::Fn(fn_expr) => fn_expr.function.is_async,
+          Expr::Arrow(arrow_expr) => arrow_expr.is_async,
+          _ => false,
+        };
+
+        // Check if the init is a function expression or arrow function
+        if let Expr::Fn(fn_expr) = &**init {
+          self.function_name_stack.push(function_name.clone());
+          throw_finder.visit_function(&fn_expr.function);
+          self.function_name_stack.pop();
+        } else if let Expr::Arrow(arrow_expr) = &**init {
+          self.function_name_stack.push(function_name.clone());
+          throw_finder.visit_arrow_expr(arrow_expr);
+
+          self.function_name_stack.pop();
+        }
+
+        if let Expr::Object(object_expr) = &**init {
+          self.current_class_name = Some(function_name.clone());
+          self.visit_object_lit(object_expr);
+          self.current_class_name = None;
+        }
+
+        if !throw_finder.throw_spans.is_empty() {
+          let throws_annotation = declaration_span
+            .and_then(|span| self.declaration_throws_annotation(span))
+            .or_else(|| self.extract_throws_annotation(init.span()));
+          let throw_map = ThrowMap {
+            throw_details: throw_finder.throw_details,
+            throws_annotation,
+            throw_spans: throw_finder.throw_spans,
+            throw_statement: declarator.span,
+            function_span: declarator.span,
+            function_or_method_name: function_name.clone(),
+            class_name: self.current_class_name.clone(),
+            id: format!(
+              "{}-{}",
+              self
+                .current_class_name
+                .clone()
+                .unwrap_or_else(|| "NOT_SET".to_string()),
+              function_name
+            ),
+            is_async,
+            name_span: None,
+            accessor_kind: None,
+          };
+          // Always insert the function - suppression happens in WASM layer
+          self.functions_with_throws.insert(throw_map);
+        }
+      }
+    }
+    swc_ecma_visit::visit_var_declarator(self, declarator);
+  }
+
   fn register_import(&mut self, import: &ImportDecl) {
     self.import_sources.insert(import.src.value.to_string());
     for specifier in &import.specifiers {
@@ -955,7 +1939,7 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
             // are anchored to the callback itself rather than the callee name.
             self.function_name_stack.push("<anonymous>".to_string());
             if let Expr::Arrow(arrow_expr) = &*arg.expr {
-              self.check_arrow_function_for_throws(arrow_expr);
+              self.check_arrow_function_for_throws(arrow_expr, None);
               self.visit_arrow_expr(arrow_expr)
             }
             if let Expr::Fn(fn_expr) = &*arg.expr {
@@ -972,7 +1956,7 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
             // Treat inline callbacks as anonymous for clearer diagnostics
             self.function_name_stack.push("<anonymous>".to_string());
             if let Expr::Arrow(arrow_expr) = &*arg.expr {
-              self.check_arrow_function_for_throws(arrow_expr);
+              self.check_arrow_function_for_throws(arrow_expr, None);
               self.visit_arrow_expr(arrow_expr);
             }
             if let Expr::Fn(fn_expr) = &*arg.expr {
@@ -993,6 +1977,7 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
               throws_annotation,
               throw_spans: throw_finder.throw_spans,
               throw_statement: arrow_expr.span,
+              function_span: arrow_expr.span,
               function_or_method_name: self
                 .function_name_stack
                 .last()
@@ -1011,6 +1996,9 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
                   .cloned()
                   .unwrap_or_else(|| "<anonymous>".to_string())
               ),
+              is_async: arrow_expr.is_async,
+              name_span: None,
+              accessor_kind: None,
             };
             // Always insert the function - suppression happens in WASM layer
             self.functions_with_throws.insert(throw_map);
@@ -1021,6 +2009,10 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
     }
   }
 
+  fn visit_module(&mut self, module: &swc_ecma_ast::Module) {
+    self.visit_module_items_where(module, |_| true);
+  }
+
   fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
     let function_name = fn_decl.ident.sym.to_string();
     self.function_name_stack.push(function_name);
@@ -1033,223 +2025,198 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
   fn visit_object_lit(&mut self, object_lit: &ObjectLit) {
     // Iterate over the properties of the object literal
     for prop in &object_lit.props {
-      match prop {
-        // Check for method properties (e.g., someImportedThrow: () => { ... })
-        PropOrSpread::Prop(prop) => {
-          if let Prop::Method(method_prop) = &**prop {
-            if let Some(method_name) = &method_prop.key.as_ident() {
-              let method_name: String = method_name.sym.to_string();
+      // Check for method properties (e.g., someImportedThrow: () => { ... })
+      if let PropOrSpread::Prop(prop) = prop {
+        if let Prop::Method(method_prop) = &**prop {
+          let method_name = prop_name_to_string(&method_prop.key);
+
+          self.function_name_stack.push(method_name.clone());
+
+          let mut throw_finder =
+            ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
+          throw_finder.visit_function(&method_prop.function);
 
-              self.function_name_stack.push(method_name.clone());
+          if !throw_finder.throw_spans.is_empty() {
+            let throws_annotation = self.extract_throws_annotation(method_prop.function.span);
+            let throw_map = ThrowMap {
+              throw_spans: throw_finder.throw_spans,
+              throw_statement: method_prop.function.span,
+              function_span: method_prop.function.span,
+              function_or_method_name: method_name.clone(),
+              class_name: self.current_class_name.clone(),
+              throw_details: throw_finder.throw_details,
+              throws_annotation,
+              id: format!(
+                "{}-{}",
+                self
+                  .current_class_name
+                  .clone()
+                  .unwrap_or_else(|| "NOT_SET".to_string()),
+                method_name
+              ),
+              is_async: method_prop.function.is_async,
+              name_span: None,
+              accessor_kind: None,
+            };
+            // Always insert the function - suppression happens in WASM layer
+            self.functions_with_throws.insert(throw_map);
+          }
 
+          self.function_name_stack.pop();
+        }
+        // (removed duplicate getter/setter handling that prefixed names)
+        if let Prop::KeyValue(key_value_prop) = &**prop {
+          match &*key_value_prop.value {
+            Expr::Fn(fn_expr) => {
               let mut throw_finder =
                 ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
-              throw_finder.visit_function(&method_prop.function);
+              throw_finder.visit_function(&fn_expr.function);
+              let function_name = prop_name_to_string(&key_value_prop.key);
 
               if !throw_finder.throw_spans.is_empty() {
-                let throws_annotation = self.extract_throws_annotation(method_prop.function.span);
+                let throws_annotation = self.extract_throws_annotation(fn_expr.function.span);
                 let throw_map = ThrowMap {
+                  throw_details: throw_finder.throw_details,
+                  throws_annotation,
                   throw_spans: throw_finder.throw_spans,
-                  throw_statement: method_prop.function.span,
-                  function_or_method_name: method_name.clone(),
+                  throw_statement: fn_expr.function.span,
+                  function_span: fn_expr.function.span,
+                  function_or_method_name: function_name.clone(),
                   class_name: self.current_class_name.clone(),
+                  id: format!(
+                    "{}-{}",
+                    self
+                      .current_class_name
+                      .clone()
+                      .unwrap_or_else(|| "NOT_SET".to_string()),
+                    function_name
+                  ),
+                  is_async: fn_expr.function.is_async,
+                  name_span: None,
+                  accessor_kind: None,
+                };
+                // Always insert the function - suppression happens in WASM layer
+                self.functions_with_throws.insert(throw_map);
+              }
+            }
+            Expr::Arrow(arrow_expr) => {
+              let mut throw_finder =
+                ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
+              throw_finder.visit_arrow_expr(arrow_expr);
+              let function_name = prop_name_to_string(&key_value_prop.key);
+
+              if !throw_finder.throw_spans.is_empty() {
+                let throws_annotation = self.extract_throws_annotation(arrow_expr.span);
+                let throw_map = ThrowMap {
                   throw_details: throw_finder.throw_details,
                   throws_annotation,
+                  throw_spans: throw_finder.throw_spans,
+                  throw_statement: arrow_expr.span,
+                  function_span: arrow_expr.span,
+                  function_or_method_name: function_name.clone(),
+                  class_name: self.current_class_name.clone(),
                   id: format!(
                     "{}-{}",
                     self
                       .current_class_name
                       .clone()
                       .unwrap_or_else(|| "NOT_SET".to_string()),
-                    method_name
+                    function_name
                   ),
+                  is_async: arrow_expr.is_async,
+                  name_span: None,
+                  accessor_kind: None,
                 };
                 // Always insert the function - suppression happens in WASM layer
                 self.functions_with_throws.insert(throw_map);
               }
-
-              self.function_name_stack.pop();
             }
+            _ => {}
           }
-          // (removed duplicate getter/setter handling that prefixed names)
-          if let Prop::KeyValue(key_value_prop) = &**prop {
-            match &*key_value_prop.value {
-              Expr::Fn(fn_expr) => {
-                let mut throw_finder =
-                  ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
-                throw_finder.visit_function(&fn_expr.function);
-                let function_name = prop_name_to_string(&key_value_prop.key);
-
-                if !throw_finder.throw_spans.is_empty() {
-                  let throws_annotation = self.extract_throws_annotation(fn_expr.function.span);
-                  let throw_map = ThrowMap {
-                    throw_details: throw_finder.throw_details,
-                    throws_annotation,
-                    throw_spans: throw_finder.throw_spans,
-                    throw_statement: fn_expr.function.span,
-                    function_or_method_name: function_name.clone(),
-                    class_name: self.current_class_name.clone(),
-                    id: format!(
-                      "{}-{}",
-                      self
-                        .current_class_name
-                        .clone()
-                        .unwrap_or_else(|| "NOT_SET".to_string()),
-                      function_name
-                    ),
-                  };
-                  // Always insert the function - suppression happens in WASM layer
-                  self.functions_with_throws.insert(throw_map);
-                }
-              }
-              Expr::Arrow(arrow_expr) => {
-                let mut throw_finder =
-                  ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
-                throw_finder.visit_arrow_expr(arrow_expr);
-                let function_name = prop_name_to_string(&key_value_prop.key);
-
-                if !throw_finder.throw_spans.is_empty() {
-                  let throws_annotation = self.extract_throws_annotation(arrow_expr.span);
-                  let throw_map = ThrowMap {
-                    throw_details: throw_finder.throw_details,
-                    throws_annotation,
-                    throw_spans: throw_finder.throw_spans,
-                    throw_statement: arrow_expr.span,
-                    function_or_method_name: function_name.clone(),
-                    class_name: self.current_class_name.clone(),
-                    id: format!(
-                      "{}-{}",
-                      self
-                        .current_class_name
-                        .clone()
-                        .unwrap_or_else(|| "NOT_SET".to_string()),
-                      function_name
-                    ),
-                  };
-                  // Always insert the function - suppression happens in WASM layer
-                  self.functions_with_throws.insert(throw_map);
-                }
-              }
-              _ => {}
-            }
+        }
+        if let Prop::Getter(getter_prop) = &**prop {
+          let getter_name = class_member_name(MethodKind::Getter, &prop_name_to_string(&getter_prop.key));
+          let mut throw_finder = ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
+          
+          if let Some(body) = &getter_prop.body {
+            throw_finder.visit_block_stmt(body);
           }
-          if let Prop::Getter(getter_prop) = &**prop {
-            let getter_name = prop_name_to_string(&getter_prop.key);
-            let mut throw_finder = ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
-            
-            if let Some(body) = &getter_prop.body {
-              throw_finder.visit_block_stmt(body);
-            }
 
-            if !throw_finder.throw_spans.is_empty() {
-              let throws_annotation = self.extract_throws_annotation(getter_prop.span);
-              let throw_map = ThrowMap {
-                throw_details: throw_finder.throw_details,
-                throws_annotation,
-                throw_spans: throw_finder.throw_spans,
-                throw_statement: getter_prop.span,
-                function_or_method_name: getter_name.clone(),
-                class_name: self.current_class_name.clone(),
-                id: format!(
-                  "{}-{}",
-                  self
-                    .current_class_name
-                    .clone()
-                    .unwrap_or_else(|| "NOT_SET".to_string()),
-                  getter_name
-                ),
-              };
-              // Always insert the function - suppression happens in WASM layer
-              self.functions_with_throws.insert(throw_map);
-            }
+          if !throw_finder.throw_spans.is_empty() {
+            let throws_annotation = self.extract_throws_annotation(getter_prop.span);
+            let throw_map = ThrowMap {
+              throw_details: throw_finder.throw_details,
+              throws_annotation,
+              throw_spans: throw_finder.throw_spans,
+              throw_statement: getter_prop.span,
+              function_span: getter_prop.span,
+              function_or_method_name: getter_name.clone(),
+              class_name: self.current_class_name.clone(),
+              id: format!(
+                "{}-{}",
+                self
+                  .current_class_name
+                  .clone()
+                  .unwrap_or_else(|| "NOT_SET".to_string()),
+                getter_name
+              ),
+              is_async: false,
+              name_span: None,
+              accessor_kind: Some(MethodKind::Getter),
+            };
+            // Always insert the function - suppression happens in WASM layer
+            self.functions_with_throws.insert(throw_map);
+          }
+        }
+        if let Prop::Setter(setter_prop) = &**prop {
+          let setter_name = class_member_name(MethodKind::Setter, &prop_name_to_string(&setter_prop.key));
+          let mut throw_finder = ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
+          
+          if let Some(body) = &setter_prop.body {
+            throw_finder.visit_block_stmt(body);
           }
-          if let Prop::Setter(setter_prop) = &**prop {
-            let setter_name = prop_name_to_string(&setter_prop.key);
-            let mut throw_finder = ThrowFinder::new(&self.throwfinder_settings, self.comments.clone());
-            
-            if let Some(body) = &setter_prop.body {
-              throw_finder.visit_block_stmt(body);
-            }
 
-            if !throw_finder.throw_spans.is_empty() {
-              let throws_annotation = self.extract_throws_annotation(setter_prop.span);
-              let throw_map = ThrowMap {
-                throw_details: throw_finder.throw_details,
-                throws_annotation,
-                throw_spans: throw_finder.throw_spans,
-                throw_statement: setter_prop.span,
-                function_or_method_name: setter_name.clone(),
-                class_name: self.current_class_name.clone(),
-                id: format!(
-                  "{}-{}",
-                  self
-                    .current_class_name
-                    .clone()
-                    .unwrap_or_else(|| "NOT_SET".to_string()),
-                  setter_name
-                ),
-              };
-              // Always insert the function - suppression happens in WASM layer
-              self.functions_with_throws.insert(throw_map);
-            }
+          if !throw_finder.throw_spans.is_empty() {
+            let throws_annotation = self.extract_throws_annotation(setter_prop.span);
+            let throw_map = ThrowMap {
+              throw_details: throw_finder.throw_details,
+              throws_annotation,
+              throw_spans: throw_finder.throw_spans,
+              throw_statement: setter_prop.span,
+              function_span: setter_prop.span,
+              function_or_method_name: setter_name.clone(),
+              class_name: self.current_class_name.clone(),
+              id: format!(
+                "{}-{}",
+                self
+                  .current_class_name
+                  .clone()
+                  .unwrap_or_else(|| "NOT_SET".to_string()),
+                setter_name
+              ),
+              is_async: false,
+              name_span: None,
+              accessor_kind: Some(MethodKind::Setter),
+            };
+            // Always insert the function - suppression happens in WASM layer
+            self.functions_with_throws.insert(throw_map);
           }
         }
-        _ => {}
       }
     }
     swc_ecma_visit::visit_object_lit(self, object_lit);
   }
 
-  fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
-    if let Some(ident) = &declarator.name.as_ident() {
-      if let Some(init) = &declarator.init {
-        let function_name = ident.sym.to_string();
-        let throwfinder_settings_clone = self.throwfinder_settings.clone();
-        let mut throw_finder = ThrowFinder::new(&throwfinder_settings_clone, self.comments.clone());
-
-        // Check if the init is a function expression or arrow function
-        if let Expr::Fn(fn_expr) = &**init {
-          self.function_name_stack.push(function_name.clone());
-          throw_finder.visit_function(&fn_expr.function);
-          self.function_name_stack.pop();
-        } else if let Expr::Arrow(arrow_expr) = &**init {
-          self.function_name_stack.push(function_name.clone());
-          throw_finder.visit_arrow_expr(arrow_expr);
-
-          self.function_name_stack.pop();
-        }
-
-        if let Expr::Object(object_expr) = &**init {
-          self.current_class_name = Some(function_name.clone());
-          self.visit_object_lit(object_expr);
-          self.current_class_name = None;
-        }
-
-        if !throw_finder.throw_spans.is_empty() {
-          let throws_annotation = self.extract_throws_annotation(declarator.span);
-          let throw_map = ThrowMap {
-            throw_details: throw_finder.throw_details,
-            throws_annotation,
-            throw_spans: throw_finder.throw_spans,
-            throw_statement: declarator.span,
-            function_or_method_name: function_name.clone(),
-            class_name: self.current_class_name.clone(),
-            id: format!(
-              "{}-{}",
-              self
-                .current_class_name
-                .clone()
-                .unwrap_or_else(|| "NOT_SET".to_string()),
-              function_name
-            ),
-          };
-          // Always insert the function - suppression happens in WASM layer
-          self.functions_with_throws.insert(throw_map);
-        }
-      }
+  fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+    for declarator in &var_decl.decls {
+      self.check_var_declarator_for_throws(declarator, Some(var_decl.span));
     }
-    swc_ecma_visit::visit_var_declarator(self, declarator);
   }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+    self.check_var_declarator_for_throws(declarator, None);
+  }
+
   fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
     if let PatOrExpr::Expr(expr) = &assign_expr.left {
       if let Expr::Ident(ident) = &**expr {
@@ -1292,6 +2259,21 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
     swc_ecma_visit::visit_arrow_expr(self, arrow_expr);
   }
 
+  fn visit_new_expr(&mut self, new_expr: &NewExpr) {
+    // A `function` executor's throws reject the promise and are recorded against the
+    // enclosing function, so it is not reported as a function of its own
+    if let Some(Expr::Fn(fn_expr)) = promise_executor(new_expr) {
+      if let Some(body) = &fn_expr.function.body {
+        self.visit_block_stmt(body);
+      }
+      for arg in new_expr.args.iter().flatten().skip(1) {
+        self.visit_expr_or_spread(arg);
+      }
+      return;
+    }
+    swc_ecma_visit::visit_new_expr(self, new_expr);
+  }
+
   fn visit_stmt(&mut self, stmt: &Stmt) {
     match stmt {
       Stmt::Expr(expr_stmt) => {
@@ -1337,7 +2319,7 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
 
   fn visit_class_method(&mut self, class_method: &ClassMethod) {
     if let Some(method_name) = &class_method.key.as_ident() {
-      let method_name = method_name.sym.to_string();
+      let method_name = class_member_name(class_method.kind, &method_name.sym);
 
       self.function_name_stack.push(method_name.clone());
 
@@ -1345,12 +2327,17 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
       throw_finder.visit_class_method(class_method);
 
       if !throw_finder.throw_spans.is_empty() {
-        let throws_annotation = self.extract_throws_annotation(class_method.span);
+        let throws_annotation = self.extract_throws_annotation_at(class_method_doc_start(class_method)).or_else(|| {
+          // A method without its own @throws is documented by the interface it implements
+          let class_name = self.current_class_name.as_ref()?;
+          self.type_registry.implemented_throws_annotation(class_name, &method_name).cloned()
+        });
         let throw_map = ThrowMap {
           throw_details: throw_finder.throw_details,
           throws_annotation,
           throw_spans: throw_finder.throw_spans,
           throw_statement: class_method.span,
+          function_span: class_method.span,
           function_or_method_name: method_name.clone(),
           class_name: self.current_class_name.clone(),
           id: format!(
@@ -1361,6 +2348,9 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
               .unwrap_or_else(|| "NOT_SET".to_string()),
             method_name
           ),
+          is_async: class_method.function.is_async,
+          name_span: None,
+          accessor_kind: accessor_kind(class_method.kind),
         };
         // Always insert the function - suppression happens in WASM layer
         self.functions_with_throws.insert(throw_map);
@@ -1385,21 +2375,22 @@ impl<'throwfinder_settings> Visit for ThrowAnalyzer<'throwfinder_settings> {
       self.current_class_name = Some(class_decl.ident.sym.to_string());
       self.visit_class(&class_decl.class);
       self.current_class_name = None;
-    }
-    // else if let Decl::Var(var_decl) = &export_decl.decl {
-    //   for declar in &var_decl.decls {
-    //     if let Some(ident) = &declar.name.as_ident() {
-    //       self.current_class_name = Some(ident.sym.to_string());
-    //     }
-    //     self.visit_var_declarator(&declar);
-    //     self.current_class_name = None;
-    //   }
-
-    // }
-    else {
+    } else if let Decl::Var(var_decl) = &export_decl.decl {
+      // JSDoc above `export const` attaches to the `export` keyword, not the declarator
+      for declarator in &var_decl.decls {
+        self.check_var_declarator_for_throws(declarator, Some(export_decl.span));
+      }
+    } else {
       swc_ecma_visit::visit_export_decl(self, export_decl);
     }
   }
+
+  fn visit_export_default_expr(&mut self, export_default: &ExportDefaultExpr) {
+    if let Expr::Arrow(arrow_expr) = &*export_default.expr {
+      self.check_arrow_function_for_throws(arrow_expr, Some(export_default.span));
+    }
+    swc_ecma_visit::visit_export_default_expr(self, export_default);
+  }
 }
 
 #[cfg(test)]
@@ -1445,21 +2436,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1487,21 +2469,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1526,21 +2499,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1565,21 +2529,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1608,21 +2563,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1634,6 +2580,8 @@ mod tests {
       assert!(annotation.error_types.contains(&"TypeError".to_string()));
       assert!(annotation.error_types.contains(&"RangeError".to_string()));
       assert_eq!(annotation.error_types.len(), 2);
+      assert_eq!(annotation.description_for("TypeError"), Some("when input is invalid"));
+      assert_eq!(annotation.description_for("RangeError"), Some("when value is out of range"));
     } else {
       panic!("Expected throws annotation to be present");
     }
@@ -1653,21 +2601,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1678,11 +2617,48 @@ mod tests {
       assert!(annotation.is_documented);
       assert!(annotation.error_types.contains(&"Error".to_string()));
       assert_eq!(annotation.error_types.len(), 1);
+      assert_eq!(annotation.description_for("Error"), Some("when something goes wrong"));
     } else {
       panic!("Expected throws annotation to be present");
     }
   }
 
+  #[test]
+  fn test_type_registry_ancestors_of() {
+    let mut registry = TypeRegistry::new();
+    registry.class_parents.insert("NotFoundError".to_string(), "HttpError".to_string());
+    registry.class_parents.insert("HttpError".to_string(), "Error".to_string());
+    assert_eq!(registry.ancestors_of("NotFoundError"), vec!["HttpError", "Error"]);
+    assert!(registry.ancestors_of("Error").is_empty());
+
+    // Cycles stop instead of looping
+    registry.class_parents.insert("A".to_string(), "B".to_string());
+    registry.class_parents.insert("B".to_string(), "A".to_string());
+    assert_eq!(registry.ancestors_of("A"), vec!["B"]);
+  }
+
+  #[test]
+  fn test_parse_throws_tag() {
+    assert_eq!(
+      parse_throws_tag(" {HttpError} see [docs](https://example.com/errors#http) "),
+      (vec!["HttpError".to_string()], Some("see [docs](https://example.com/errors#http)".to_string()))
+    );
+    assert_eq!(
+      parse_throws_tag(" {HttpError} - see [docs](https://example.com)"),
+      (vec!["HttpError".to_string()], Some("see [docs](https://example.com)".to_string()))
+    );
+    assert_eq!(
+      parse_throws_tag(" HttpError, TimeoutError when the request fails"),
+      (
+        vec!["HttpError".to_string(), "TimeoutError".to_string()],
+        Some("when the request fails".to_string())
+      )
+    );
+    // No description
+    assert_eq!(parse_throws_tag(" {TypeError}"), (vec!["TypeError".to_string()], None));
+    assert_eq!(parse_throws_tag(" TypeError"), (vec!["TypeError".to_string()], None));
+  }
+
   #[test]
   fn test_throws_annotation_multiple_types_comma_separated() {
     let code = r#"
@@ -1697,21 +2673,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1742,21 +2709,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1800,21 +2758,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1856,21 +2805,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &false, // This setting now only affects final filtering, not detection
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1899,21 +2839,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1946,21 +2877,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -1998,21 +2920,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2038,21 +2951,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2086,21 +2990,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2128,21 +3023,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2177,21 +3063,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       include_try_statements: &true,
       ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: vec![],
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2223,21 +3100,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       ignore_statements: &ignore_statements,
       include_try_statements: &false,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: Vec::new(),
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2251,6 +3119,44 @@ mod tests {
     assert!(throw_function_ids.contains("NOT_SET-arrowThrow"), "Missing arrowThrow function");
   }
 
+  #[test]
+  fn test_async_functions_are_flagged() {
+    let code = r#"
+      async function loadUser() {
+        throw new Error("not found");
+      }
+      const saveUser = async () => {
+        throw new Error("conflict");
+      };
+      function parseUser() {
+        throw new Error("bad json");
+      }
+    "#;
+    
+    let (module, comments) = parse_code_with_comments(code);
+    let ignore_statements = vec![];
+    let settings = ThrowFinderSettings {
+      ignore_statements: &ignore_statements,
+      include_try_statements: &false,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
+    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
+    
+    analyzer.visit_module(&module);
+    
+    let is_async = |id: &str| {
+      analyzer.functions_with_throws.iter()
+        .find(|tm| tm.id == id)
+        .map(|tm| tm.is_async)
+    };
+    assert_eq!(is_async("NOT_SET-loadUser"), Some(true), "async function should be flagged");
+    assert_eq!(is_async("NOT_SET-saveUser"), Some(true), "async arrow should be flagged");
+    assert_eq!(is_async("NOT_SET-parseUser"), Some(false), "sync function should not be flagged");
+  }
+
   #[test]
   fn test_object_literal_getter_with_throw_detection() {
     let code = r#"
@@ -2266,21 +3172,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       ignore_statements: &ignore_statements,
       include_try_statements: &false,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: Vec::new(),
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2291,11 +3188,12 @@ mod tests {
     
     // Check that getter function ID is present
     let throw_function_ids: std::collections::HashSet<String> = analyzer.functions_with_throws.iter().map(|tm| tm.id.clone()).collect();
-    assert!(throw_function_ids.contains("testGetter-test"), "Missing getter function");
+    assert!(throw_function_ids.contains("testGetter-get test"), "Missing getter function");
     
-    // Check that function name is correct
+    // Check that function name is correct, named like a class getter
     let function_names: std::collections::HashSet<String> = analyzer.functions_with_throws.iter().map(|tm| tm.function_or_method_name.clone()).collect();
-    assert!(function_names.contains("test"), "Missing getter function name");
+    assert!(function_names.contains("get test"), "Missing getter function name");
+    assert!(analyzer.functions_with_throws.iter().all(|tm| tm.accessor_kind == Some(MethodKind::Getter)));
   }
 
   #[test]
@@ -2317,21 +3215,12 @@ mod tests {
     let settings = ThrowFinderSettings {
       ignore_statements: &ignore_statements,
       include_try_statements: &false,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
     };
-    let mut analyzer = ThrowAnalyzer {
-      comments: comments.clone(),
-      functions_with_throws: HashSet::new(),
-      json_parse_calls: vec![],
-      fs_access_calls: vec![],
-      import_sources: HashSet::new(),
-      imported_identifiers: Vec::new(),
-      function_name_stack: vec![],
-      current_class_name: None,
-      current_method_name: None,
-      throwfinder_settings: settings.clone(),
-      used_it_throws_comments: HashSet::new(),
-      type_registry: TypeRegistry::new(),
-    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
     
     analyzer.visit_module(&module);
     
@@ -2350,5 +3239,285 @@ mod tests {
     let function_names: std::collections::HashSet<String> = analyzer.functions_with_throws.iter().map(|tm| tm.function_or_method_name.clone()).collect();
     assert!(function_names.contains("SomeThrow"), "Missing SomeThrow function name");
   }
-}
 
+  fn documented_types_by_function(code: &str) -> std::collections::HashMap<String, Vec<String>> {
+    let (module, comments) = parse_code_with_comments(code);
+    let ignore_statements = vec![];
+    let settings = ThrowFinderSettings {
+      include_try_statements: &true,
+      ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
+    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
+
+    analyzer.visit_module(&module);
+
+    analyzer
+      .functions_with_throws
+      .iter()
+      .map(|throw_map| {
+        let mut types = throw_map
+          .throws_annotation
+          .as_ref()
+          .map(|annotation| annotation.error_types.clone())
+          .unwrap_or_default();
+        types.sort();
+        (throw_map.function_or_method_name.clone(), types)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_throws_annotation_with_hyphen_bullets() {
+    let code = r#"
+      /**
+       * @throws {NotFoundError} - the user does not exist
+       */
+      function findUser() { throw new NotFoundError("x"); }
+
+      /**
+       * Loads settings.
+       * - @throws {ConfigError}
+       */
+      function loadSettings() { throw new ConfigError("x"); }
+
+      /** @throws TimeoutError, AbortError - the request did not complete */
+      function fetchData() { throw new TimeoutError("x"); }
+    "#;
+
+    let documented = documented_types_by_function(code);
+    assert_eq!(documented.get("findUser"), Some(&vec!["NotFoundError".to_string()]));
+    assert_eq!(documented.get("loadSettings"), Some(&vec!["ConfigError".to_string()]));
+    assert_eq!(
+      documented.get("fetchData"),
+      Some(&vec!["AbortError".to_string(), "TimeoutError".to_string()])
+    );
+    assert_eq!(
+      parse_throws_tag(" TimeoutError, AbortError - the request did not complete"),
+      (
+        vec!["TimeoutError".to_string(), "AbortError".to_string()],
+        Some("the request did not complete".to_string())
+      )
+    );
+    assert_eq!(normalize_jsdoc_line("   * - @throws {ConfigError}"), "@throws {ConfigError}");
+  }
+
+  #[test]
+  fn test_throws_tag_text_matches_configured_tags() {
+    let tags = default_throws_tag_aliases();
+    assert_eq!(throws_tag_text("@Exception {IOError} on failure", &tags), Some(" {IOError} on failure"));
+    assert_eq!(throws_tag_text("@throws TypeError, RangeError", &tags), Some(" TypeError, RangeError"));
+    // Tags only match whole, so a longer custom tag needs configuring
+    assert_eq!(throws_tag_text("@throws_async {NetworkError}", &tags), None);
+    assert_eq!(throws_tag_text("@exceptions {IOError}", &tags), None);
+
+    let tags = vec!["throws_async".to_string(), "@raises".to_string()];
+    assert_eq!(throws_tag_text("@throws_async {NetworkError}", &tags), Some(" {NetworkError}"));
+    assert_eq!(throws_tag_text("@RAISES ValueError", &tags), Some(" ValueError"));
+    assert_eq!(throws_tag_text("@throws {TypeError}", &tags), None);
+  }
+
+  #[test]
+  fn test_throws_annotation_on_export_const_arrow() {
+    let code = r#"
+      /**
+       * Handles an incoming request.
+       * @param req the incoming request
+       * @param res the outgoing response
+       * @throws {ValidationError} when the body is invalid
+       */
+      export const handler: RequestHandler<ParamsDictionary, ResponseBody> = async (req, res) => {
+        throw new ValidationError("x");
+      };
+    "#;
+
+    let documented = documented_types_by_function(code);
+    assert_eq!(documented.get("handler"), Some(&vec!["ValidationError".to_string()]));
+  }
+
+  #[test]
+  fn test_throws_annotation_on_export_default_arrow() {
+    let code = r#"
+      /**
+       * Default request handler.
+       * @param req the incoming request
+       * @throws {NotFoundError} when the record is missing
+       */
+      export default async (req: IncomingRequestWithAVeryLongTypeName) => {
+        throw new NotFoundError("x");
+      };
+    "#;
+
+    let documented = documented_types_by_function(code);
+    assert_eq!(documented.get("<anonymous>"), Some(&vec!["NotFoundError".to_string()]));
+  }
+
+  #[test]
+  fn test_throws_annotation_on_plain_const_arrow() {
+    let code = r#"
+      /**
+       * Validates the payload before it is stored.
+       * @param input the raw payload
+       * @param options validation options
+       * @throws {TypeError} when the payload is malformed
+       * @throws {RangeError} when a value is out of bounds
+       */
+      const validateIncomingPayloadBeforeStoring = (input: string, options: ValidationOptions) => {
+        if (!input) throw new TypeError("x");
+        throw new RangeError("y");
+      };
+    "#;
+
+    let documented = documented_types_by_function(code);
+    assert_eq!(
+      documented.get("validateIncomingPayloadBeforeStoring"),
+      Some(&vec!["RangeError".to_string(), "TypeError".to_string()])
+    );
+  }
+
+  #[test]
+  fn test_line_comment_throws_annotation_does_not_bleed() {
+    let code = r#"
+// @throws {ValidationError} on bad input
+const check = (v) => { throw new ValidationError("x") };
+const next = (v) => { throw new TypeError("y") };
+const parse = // @throws {SyntaxError} on malformed json
+  (text) => { throw new SyntaxError("z") };
+"#;
+
+    let documented = documented_types_by_function(code);
+    assert_eq!(documented.get("check"), Some(&vec!["ValidationError".to_string()]));
+    assert_eq!(documented.get("next"), Some(&vec![]));
+    assert_eq!(documented.get("parse"), Some(&vec!["SyntaxError".to_string()]));
+  }
+
+  #[test]
+  fn test_throw_details_object_literal() {
+    let code = r#"
+      function test() {
+        throw { name: 'TimeoutError', code: 504, message: 'upstream timeout' };
+      }
+    "#;
+
+    let (module, comments) = parse_code_with_comments(code);
+    let ignore_statements = vec![];
+    let settings = ThrowFinderSettings {
+      include_try_statements: &true,
+      ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
+    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
+
+    analyzer.visit_module(&module);
+
+    assert_eq!(analyzer.functions_with_throws.len(), 1);
+    let throw_map = analyzer.functions_with_throws.iter().next().unwrap();
+    let details = &throw_map.throw_details[0];
+    assert_eq!(details.error_type, Some("TimeoutError".to_string()));
+    assert_eq!(details.error_message, Some("upstream timeout".to_string()));
+    assert!(details.is_custom_error);
+  }
+
+  fn thrown_error_types(code: &str) -> Vec<(Option<String>, Option<String>)> {
+    let (module, comments) = parse_code_with_comments(code);
+    let ignore_statements = vec!["@it-throws".to_string()];
+    let settings = ThrowFinderSettings {
+      include_try_statements: &true,
+      ignore_statements: &ignore_statements,
+      error_factories: &[],
+      assertion_functions: &[],
+      throws_tag_aliases: &default_throws_tag_aliases(),
+      next_line_comments: &[],
+    };
+    let mut analyzer = ThrowAnalyzer::new(comments.clone(), settings.clone(), &UserSettings::default());
+
+    analyzer.visit_module(&module);
+
+    analyzer
+      .functions_with_throws
+      .iter()
+      .flat_map(|throw_map| throw_map.throw_details.iter())
+      .map(|details| (details.error_type.clone(), details.error_message.clone()))
+      .collect()
+  }
+
+  #[test]
+  fn test_throw_new_with_message() {
+    let types = thrown_error_types(r#"function test() { throw new Error("x"); }"#);
+    assert_eq!(types, vec![(Some("Error".to_string()), Some("x".to_string()))]);
+  }
+
+  #[test]
+  fn test_throw_new_without_parens() {
+    let types = thrown_error_types("function test() { throw new CustomError; }");
+    assert_eq!(types, vec![(Some("CustomError".to_string()), None)]);
+  }
+
+  #[test]
+  fn test_throw_new_namespaced_error() {
+    let types = thrown_error_types(r#"function test() { throw new errors.NotFound("missing"); }"#);
+    assert_eq!(types, vec![(Some("NotFound".to_string()), Some("missing".to_string()))]);
+  }
+
+  #[test]
+  fn test_throw_tagged_template() {
+    let types = thrown_error_types("function test() { throw httpError`not found`; }");
+    assert_eq!(types, vec![(Some(format!("{}httpError", ERROR_FACTORY_CALL_PREFIX)), None)]);
+  }
+
+  #[test]
+  fn test_ignore_comment_error_types() {
+    let ignore_statements = vec!["@it-throws".to_string()];
+    assert_eq!(ignore_comment_error_types(" @it-throws ", &ignore_statements), Some(vec![]));
+    assert_eq!(
+      ignore_comment_error_types(" @it-throws ValidationError, {TypeError | RangeError}", &ignore_statements),
+      Some(vec!["ValidationError".to_string(), "TypeError".to_string(), "RangeError".to_string()])
+    );
+    assert_eq!(ignore_comment_error_types(" @it-throws-disable", &ignore_statements), None);
+    assert_eq!(ignore_comment_error_types(" TODO: @it-throws", &ignore_statements), None);
+  }
+
+  #[test]
+  fn test_next_line_comments_cover_only_the_line_below() {
+    let code = "// @it-throws-next-line TypeError\nthrow new TypeError();\nthrow new TypeError();\n// @does-it-throw-ignore-next-line";
+    let cm: Lrc<SourceMap> = Default::default();
+    let comments = SingleThreadedComments::default();
+    let fm = cm.new_source_file(FileName::Custom("test.ts".into()), code.into());
+    let lexer = Lexer::new(Syntax::Typescript(Default::default()), EsVersion::latest(), StringInput::from(&*fm), Some(&comments));
+    Parser::new_from(lexer).parse_module().expect("Failed to parse module");
+
+    let ignore_statements = vec!["@it-throws".to_string(), "@does-it-throw-ignore".to_string()];
+    let found = next_line_comments(&cm, &comments, &ignore_statements);
+    // The comment on the last line has no line below it to cover
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].error_types, vec!["TypeError".to_string()]);
+    let second_line = fm.start_pos + BytePos(34);
+    let third_line = fm.start_pos + BytePos(57);
+    assert!(found[0].covers(second_line, Some("TypeError")));
+    assert!(!found[0].covers(second_line, Some("RangeError")));
+    assert!(!found[0].covers(third_line, Some("TypeError")));
+  }
+
+  #[test]
+  fn test_typed_it_throws_comment_skips_only_listed_types() {
+    let types = thrown_error_types(
+      r#"
+      function validate(input) {
+        if (!input) {
+          // @it-throws ValidationError
+          throw new ValidationError("missing");
+        }
+        // @it-throws ValidationError
+        throw new TypeError("wrong");
+      }
+      "#,
+    );
+    assert_eq!(types, vec![(Some("TypeError".to_string()), Some("wrong".to_string()))]);
+  }
+}
\ No newline at end of file
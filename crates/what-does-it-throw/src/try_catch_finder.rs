@@ -3,13 +3,20 @@ extern crate swc_ecma_ast;
 extern crate swc_ecma_visit;
 
 use swc_ecma_ast::{
-  BinaryOp, TryStmt, BlockStmt, Expr, Pat, ThrowStmt,
+  BinaryOp, TryStmt, BlockStmt, Expr, IfStmt, Pat, Stmt, SwitchStmt, ThrowStmt,
   BinExpr,
 };
 
+use std::collections::HashMap;
+
 use self::swc_common::{comments::Comments, sync::Lrc, Span};
 use self::swc_ecma_visit::{Visit, VisitWith};
 
+use crate::throw_finder::{
+  callee_name, catch_param_comparison_type, catch_param_dispatch_type, default_terminator_functions, ThrowFinder,
+  ThrowFinderSettings,
+};
+
 
 /// Represents the analysis of a single catch block
 #[derive(Clone, Debug)]
@@ -23,7 +30,11 @@ pub struct CatchAnalysis {
     pub errors_effectively_caught: Vec<String>, // handled but not re-thrown
     pub errors_propagated: Vec<String>, // re-thrown or not handled
     pub has_escape_hatch: bool, // true if `throw e` (catch param) is used
+    pub terminates: bool, // true if the catch body always calls a terminator like `process.exit`
+    pub has_else_fallback: bool, // true if an `if (e instanceof T) ... else` chain handles every other error
+    pub has_empty_body: bool, // true if the catch body has no statements, only comments at most
     pub missing_handlers: Vec<String>, // error types that need instanceof checks
+    pub error_ancestors: HashMap<String, Vec<String>>, // classes each thrown type extends, so `instanceof HttpError` handles NotFoundError
 }
 
 impl CatchAnalysis {
@@ -38,7 +49,11 @@ impl CatchAnalysis {
             errors_effectively_caught: Vec::new(),
             errors_propagated: Vec::new(),
             has_escape_hatch: false,
+            terminates: false,
+            has_else_fallback: false,
+            has_empty_body: false,
             missing_handlers: Vec::new(),
+            error_ancestors: HashMap::new(),
         }
     }
 
@@ -64,22 +79,65 @@ impl CatchAnalysis {
         self.has_escape_hatch = has_escape;
     }
 
+    pub fn set_terminates(&mut self, terminates: bool) {
+        self.terminates = terminates;
+    }
+
+    pub fn set_else_fallback(&mut self, has_else_fallback: bool) {
+        self.has_else_fallback = has_else_fallback;
+    }
+
+    pub fn set_empty_body(&mut self, has_empty_body: bool) {
+        self.has_empty_body = has_empty_body;
+    }
+
+    /// Whether nothing thrown in the try block leaves the catch unless the catch throws it itself:
+    /// a catch without instanceof checks or escape hatch, one whose checks end in an `else`, or one
+    /// that always ends the process. `throw e` may run before the terminator, so an escape hatch
+    /// still lets errors through.
+    fn catches_everything(&self) -> bool {
+        !self.has_escape_hatch
+            && (self.errors_handled_in_catch.is_empty() || self.terminates || self.has_else_fallback)
+    }
+
+    /// Whether the catch discards errors thrown in the try block without doing anything at all,
+    /// e.g. `catch (e) {}`
+    pub fn swallows_silently(&self) -> bool {
+        self.has_empty_body && !self.errors_thrown_in_try.is_empty()
+    }
+
+    /// Whether errors of this type leave the catch as the caught error itself (`throw e`),
+    /// rather than being swallowed or replaced by a new error
+    pub fn rethrows(&self, error_type: &str) -> bool {
+        self.errors_thrown_in_try.iter().any(|thrown| thrown == error_type)
+            && self.errors_propagated.iter().any(|propagated| propagated == error_type)
+            && (self.has_escape_hatch || self.errors_rethrown_in_catch.iter().any(|rethrown| rethrown == error_type))
+    }
+
+    /// Whether an instanceof check in the catch selects this type or a class it extends
+    fn is_handled(&self, error_type: &str) -> bool {
+        let ancestors = self.error_ancestors.get(error_type).map(Vec::as_slice).unwrap_or_default();
+        self.errors_handled_in_catch
+            .iter()
+            .any(|handled| handled == error_type || ancestors.contains(handled))
+    }
+
     /// Calculate which errors are missing handlers and which are effectively caught
     pub fn calculate_error_flow(&mut self) {
         self.missing_handlers.clear();
         self.errors_effectively_caught.clear();
         self.errors_propagated.clear();
 
-        // If there are no instanceof checks and no escape hatch, this is a simple catch block
-        // that catches all errors by default
-        let is_simple_catch_all = self.errors_handled_in_catch.is_empty() && !self.has_escape_hatch;
+        // Without instanceof checks or an escape hatch, or when the catch always ends the process,
+        // the catch block swallows every error by default
+        let catches_everything = self.catches_everything();
 
         for error_type in &self.errors_thrown_in_try {
-            let is_handled = self.errors_handled_in_catch.contains(error_type);
+            let is_handled = self.is_handled(error_type);
             let is_specifically_rethrown = self.errors_rethrown_in_catch.contains(error_type);
 
-            if is_simple_catch_all && !is_specifically_rethrown {
-                // Simple catch block with no instanceof checks - catches all errors
+            if catches_everything && !is_specifically_rethrown {
+                // Simple catch-all or terminating catch - catches all errors
                 self.errors_effectively_caught.push(error_type.clone());
             } else if is_handled && !is_specifically_rethrown && !self.has_escape_hatch {
                 // Error is handled with instanceof and not re-thrown
@@ -90,7 +148,7 @@ impl CatchAnalysis {
             } else if !is_handled && self.has_escape_hatch {
                 // Error is not handled but escape hatch exists - it propagates
                 self.errors_propagated.push(error_type.clone());
-            } else if !is_handled && !is_simple_catch_all {
+            } else if !is_handled && !catches_everything {
                 // Error is not handled and no escape hatch - missing handler!
                 self.missing_handlers.push(error_type.clone());
                 self.errors_propagated.push(error_type.clone());
@@ -99,6 +157,30 @@ impl CatchAnalysis {
                 self.errors_propagated.push(error_type.clone());
             }
         }
+
+        // Errors raised by the catch body itself (e.g. `throw new WrappedError(e)`) replace the
+        // originals they wrap. `throw e` is the escape hatch and was handled above.
+        for error_type in &self.errors_rethrown_in_catch {
+            if error_type.starts_with("variable: ") || self.errors_propagated.contains(error_type) {
+                continue;
+            }
+            self.errors_propagated.push(error_type.clone());
+        }
+    }
+
+    /// Whether the catch body swallows an error of this type (`None` for untyped throws),
+    /// following the same rules as `calculate_error_flow`
+    pub fn catches(&self, error_type: Option<&str>) -> bool {
+        let catches_everything = self.catches_everything();
+        match error_type {
+            Some(error_type) => {
+                let is_handled = self.is_handled(error_type);
+                let is_specifically_rethrown = self.errors_rethrown_in_catch.iter().any(|rethrown| rethrown == error_type);
+                (catches_everything && !is_specifically_rethrown)
+                    || (is_handled && (self.has_escape_hatch || !is_specifically_rethrown))
+            }
+            None => catches_everything,
+        }
     }
 
     /// Check if this catch block is exhaustive (handles all errors or has escape hatch)
@@ -116,6 +198,8 @@ impl CatchAnalysis {
 pub struct TryCatchFinder {
     pub comments: Lrc<dyn Comments>,
     pub all_catches: Vec<CatchAnalysis>,
+    pub terminator_functions: Vec<String>, // Calls that end the process, like `process.exit`
+    pub include_catch_rethrows: bool, // Whether `throw e` passes the caught error on; when false the catch swallows it
     current_try_block: Option<Span>,
     current_catch_analysis: Option<CatchAnalysis>,
 }
@@ -125,6 +209,8 @@ impl TryCatchFinder {
         Self {
             comments,
             all_catches: Vec::new(),
+            terminator_functions: default_terminator_functions(),
+            include_catch_rethrows: true,
             current_try_block: None,
             current_catch_analysis: None,
         }
@@ -141,19 +227,88 @@ impl TryCatchFinder {
         }
     }
 
-    fn find_instanceof_checks(&self, catch_block: &BlockStmt) -> Vec<String> {
-        let mut visitor = InstanceOfVisitor::new();
+    fn find_instanceof_checks(&self, catch_block: &BlockStmt, catch_param: &Option<String>) -> Vec<String> {
+        let mut visitor = InstanceOfVisitor::new(catch_param.clone());
         catch_block.visit_with(&mut visitor);
         visitor.error_types
     }
 
     fn find_rethrows_in_catch(&self, catch_block: &BlockStmt, catch_param: &Option<String>) -> (Vec<String>, bool) {
+        // Every throw inside the catch body is a (re)thrown error, whatever its shape
+        let ignore_statements = Vec::new();
+        let settings = ThrowFinderSettings {
+            include_try_statements: &true,
+            ignore_statements: &ignore_statements,
+            error_factories: &[],
+            assertion_functions: &[],
+            throws_tag_aliases: &[],
+            next_line_comments: &[],
+        };
+        let mut throw_finder = ThrowFinder::new(&settings, self.comments.clone());
+        throw_finder.visit_block_stmt(catch_block);
+        let mut rethrown_types: Vec<String> = Vec::new();
+        for error_type in throw_finder.throw_details.into_iter().filter_map(|detail| detail.error_type) {
+            if !rethrown_types.contains(&error_type) {
+                rethrown_types.push(error_type);
+            }
+        }
+
+        if !self.include_catch_rethrows {
+            return (rethrown_types, false);
+        }
+
+        // `throw e` inside `if (e instanceof T)` only passes on T; anywhere else it is the escape hatch
         let mut visitor = RethrowVisitor::new(catch_param.clone());
         catch_block.visit_with(&mut visitor);
-        (visitor.rethrown_types, visitor.has_escape_hatch)
+        for error_type in visitor.rethrown_types {
+            if !rethrown_types.contains(&error_type) {
+                rethrown_types.push(error_type);
+            }
+        }
+        (rethrown_types, visitor.has_escape_hatch)
+    }
+
+    /// Whether a statement directly in the catch body is an `if (e instanceof T) ... else ...` chain
+    /// ending in a plain `else`, so every error type reaches one of its branches
+    fn has_else_fallback(&self, catch_block: &BlockStmt, catch_param: &Option<String>) -> bool {
+        let Some(catch_param) = catch_param else {
+            return false;
+        };
+        catch_block.stmts.iter().any(|stmt| {
+            let mut stmt = stmt;
+            let mut dispatches = false;
+            while let Stmt::If(if_stmt) = stmt {
+                if !matches!(&*if_stmt.test, Expr::Bin(bin_expr) if catch_param_dispatch_type(bin_expr, catch_param).is_some()) {
+                    return false;
+                }
+                dispatches = true;
+                match &if_stmt.alt {
+                    Some(alt) => stmt = alt,
+                    None => return false,
+                }
+            }
+            dispatches
+        })
     }
 
 
+    /// Whether a statement directly in the catch body calls a terminator, so every path through
+    /// the catch that doesn't return or throw first ends the process
+    fn always_terminates(&self, catch_block: &BlockStmt) -> bool {
+        catch_block.stmts.iter().any(|stmt| {
+            let Stmt::Expr(expr_stmt) = stmt else {
+                return false;
+            };
+            let Expr::Call(call) = &*expr_stmt.expr else {
+                return false;
+            };
+            call.callee
+                .as_expr()
+                .and_then(|callee| callee_name(callee))
+                .is_some_and(|name| self.terminator_functions.contains(&name))
+        })
+    }
+
     // This would be populated by integration with ThrowFinder
     fn analyze_throws_in_try_block(&self, _try_block: &BlockStmt) -> Vec<String> {
         // In real implementation, this would use ThrowFinder to get actual thrown errors
@@ -175,8 +330,13 @@ impl Visit for TryCatchFinder {
                 catch_param.clone(),
             );
 
-            // Find instanceof checks in the catch block
-            let handled_errors = self.find_instanceof_checks(&catch_clause.body);
+            // Find instanceof checks in the catch block. `catch { ... }` (optional catch binding)
+            // has nothing to inspect, so it is a catch-all whatever else its body tests.
+            let handled_errors = if catch_clause.param.is_some() {
+                self.find_instanceof_checks(&catch_clause.body, &catch_param)
+            } else {
+                Vec::new()
+            };
             for error_type in handled_errors {
                 catch_analysis.add_handled_error(error_type);
             }
@@ -184,6 +344,9 @@ impl Visit for TryCatchFinder {
             // Find re-throws in the catch block
             let (rethrown_errors, has_escape_hatch) = self.find_rethrows_in_catch(&catch_clause.body, &catch_param);
             catch_analysis.set_escape_hatch(has_escape_hatch);
+            catch_analysis.set_terminates(self.always_terminates(&catch_clause.body));
+            catch_analysis.set_else_fallback(self.has_else_fallback(&catch_clause.body, &catch_param));
+            catch_analysis.set_empty_body(catch_clause.body.stmts.is_empty());
             for error_type in rethrown_errors {
                 catch_analysis.add_rethrown_error(error_type);
             }
@@ -209,72 +372,106 @@ impl Visit for TryCatchFinder {
     }
 }
 
+/// Collects the error types a catch block dispatches on: `e instanceof T` (including inside
+/// `switch (true) { case e instanceof T: }`), `e.constructor === T`, `e.name === 'T'`, and
+/// `switch` over `e.constructor` or `e.name`
 struct InstanceOfVisitor {
+    catch_param: Option<String>,
     error_types: Vec<String>,
 }
 
 impl InstanceOfVisitor {
-    fn new() -> Self {
+    fn new(catch_param: Option<String>) -> Self {
         Self {
+            catch_param,
             error_types: Vec::new(),
         }
     }
+
+    fn add_error_type(&mut self, error_type: String) {
+        if !self.error_types.contains(&error_type) {
+            self.error_types.push(error_type);
+        }
+    }
 }
 
 impl Visit for InstanceOfVisitor {
     fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
-        if matches!(bin_expr.op, BinaryOp::InstanceOf) {
+        if bin_expr.op == BinaryOp::InstanceOf {
             if let Expr::Ident(ident) = &*bin_expr.right {
-                let error_type = ident.sym.to_string();
-                if !self.error_types.contains(&error_type) {
-                    self.error_types.push(error_type);
-                }
+                self.add_error_type(ident.sym.to_string());
+            }
+        } else if let Some(catch_param) = &self.catch_param {
+            if let Some(error_type) = catch_param_dispatch_type(bin_expr, catch_param) {
+                self.add_error_type(error_type);
             }
         }
         bin_expr.visit_children_with(self);
     }
+
+    fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt) {
+        // switch (e.constructor) { case T: ... } / switch (e.name) { case 'T': ... }
+        if let Some(catch_param) = self.catch_param.clone() {
+            for test in switch_stmt.cases.iter().filter_map(|case| case.test.as_ref()) {
+                if let Some(error_type) = catch_param_comparison_type(&switch_stmt.discriminant, test, &catch_param) {
+                    self.add_error_type(error_type);
+                }
+            }
+        }
+        switch_stmt.visit_children_with(self);
+    }
 }
 
+/// Detects the `throw e` escape hatch, where `e` is the catch parameter, and the error types
+/// rethrown from inside `if (e instanceof T)` branches
 struct RethrowVisitor {
     catch_param: Option<String>,
-    rethrown_types: Vec<String>,
     has_escape_hatch: bool,
+    rethrown_types: Vec<String>,
+    branch_type: Option<String>, // Type selected by the enclosing `if (e instanceof T)` branch
 }
 
 impl RethrowVisitor {
     fn new(catch_param: Option<String>) -> Self {
         Self {
             catch_param,
-            rethrown_types: Vec::new(),
             has_escape_hatch: false,
+            rethrown_types: Vec::new(),
+            branch_type: None,
         }
     }
 }
 
 impl Visit for RethrowVisitor {
+    fn visit_if_stmt(&mut self, if_stmt: &IfStmt) {
+        let branch_type = match (&*if_stmt.test, &self.catch_param) {
+            (Expr::Bin(bin_expr), Some(catch_param)) => catch_param_dispatch_type(bin_expr, catch_param),
+            _ => None,
+        };
+        let Some(branch_type) = branch_type else {
+            if_stmt.visit_children_with(self);
+            return;
+        };
+        if_stmt.test.visit_with(self);
+        let outer_branch_type = self.branch_type.replace(branch_type);
+        if_stmt.cons.visit_with(self);
+        self.branch_type = outer_branch_type;
+        if let Some(alt) = &if_stmt.alt {
+            alt.visit_with(self);
+        }
+    }
+
     fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt) {
-        match &*throw_stmt.arg {
-            Expr::New(new_expr) => {
-                if let Expr::Ident(ident) = &*new_expr.callee {
-                    let error_type = ident.sym.to_string();
-                    if !self.rethrown_types.contains(&error_type) {
-                        self.rethrown_types.push(error_type);
+        if let (Expr::Ident(ident), Some(catch_param)) = (&*throw_stmt.arg, &self.catch_param) {
+            if ident.sym == **catch_param {
+                match &self.branch_type {
+                    Some(branch_type) if !self.rethrown_types.contains(branch_type) => {
+                        self.rethrown_types.push(branch_type.clone());
                     }
+                    Some(_) => {}
+                    None => self.has_escape_hatch = true,
                 }
             }
-            Expr::Ident(ident) => {
-                let var_name = ident.sym.to_string();
-                if let Some(ref catch_param) = self.catch_param {
-                    if var_name == *catch_param {
-                        // This is `throw e` where e is the catch parameter - escape hatch!
-                        self.has_escape_hatch = true;
-                    }
-                }
-                if !self.rethrown_types.contains(&format!("variable: {}", var_name)) {
-                    self.rethrown_types.push(format!("variable: {}", var_name));
-                }
-            }
-            _ => {}
         }
         throw_stmt.visit_children_with(self);
     }
@@ -380,6 +577,36 @@ mod tests {
         assert!(analysis.is_exhaustive()); // No missing handlers because of escape hatch
     }
 
+    #[test]
+    fn test_instanceof_base_class_handles_subclass() {
+        let code = r#"
+            try {
+                // Errors would be detected by ThrowFinder
+            } catch (e) {
+                if (e instanceof HttpError) {
+                    return null;
+                }
+            }
+        "#;
+
+        let (module, comments) = parse_code_with_comments(code);
+        let mut finder = TryCatchFinder::new(comments);
+        finder.visit_module(&module);
+
+        let mut analysis = finder.all_catches[0].clone();
+        analysis.add_thrown_error("NotFoundError".to_string());
+        analysis.add_thrown_error("TypeError".to_string());
+        analysis
+            .error_ancestors
+            .insert("NotFoundError".to_string(), vec!["HttpError".to_string(), "Error".to_string()]);
+        analysis.calculate_error_flow();
+
+        assert_eq!(analysis.errors_effectively_caught, vec!["NotFoundError"]);
+        assert_eq!(analysis.missing_handlers, vec!["TypeError"]);
+        assert!(analysis.catches(Some("NotFoundError")));
+        assert!(!analysis.catches(Some("TypeError")));
+    }
+
     #[test]
     fn test_specific_rethrow() {
         let code = r#"
@@ -422,6 +649,36 @@ mod tests {
         assert!(!analysis.is_exhaustive());
     }
 
+    #[test]
+    fn test_wrapped_rethrow_replaces_propagated_types() {
+        let propagated_for = |catch_body: &str| {
+            let code = format!("try {{}} catch (e) {{ {} }}", catch_body);
+            let (module, comments) = parse_code_with_comments(&code);
+            let mut finder = TryCatchFinder::new(comments);
+            finder.visit_module(&module);
+            let mut analysis = finder.all_catches[0].clone();
+            analysis.add_thrown_error("ValidationError".to_string());
+            analysis.add_thrown_error("NetworkError".to_string());
+            analysis.calculate_error_flow();
+            (analysis.errors_propagated, analysis.errors_effectively_caught)
+        };
+
+        // Wrap-and-rethrow: only the wrapper escapes
+        let (propagated, caught) = propagated_for("throw new WrappedError(e);");
+        assert_eq!(propagated, vec!["WrappedError"]);
+        assert_eq!(caught, vec!["ValidationError", "NetworkError"]);
+
+        // Bare rethrow: the originals escape unchanged
+        let (propagated, caught) = propagated_for("throw e;");
+        assert_eq!(propagated, vec!["ValidationError", "NetworkError"]);
+        assert!(caught.is_empty());
+
+        // Swallow: nothing escapes
+        let (propagated, caught) = propagated_for("console.log(e);");
+        assert!(propagated.is_empty());
+        assert_eq!(caught, vec!["ValidationError", "NetworkError"]);
+    }
+
     #[test]
     fn test_complete_exhaustive_catch() {
         let code = r#"
@@ -462,4 +719,136 @@ mod tests {
         assert!(analysis.missing_handlers.is_empty());
         assert!(analysis.is_exhaustive());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_switch_dispatch_counts_as_handled() {
+        let code = r#"
+            try {
+                doWork();
+            } catch (e) {
+                switch (e.constructor) {
+                    case ValidationError:
+                        break;
+                    case NetworkError:
+                        break;
+                }
+                switch (true) {
+                    case e instanceof AuthError:
+                        break;
+                }
+            }
+        "#;
+
+        let (module, comments) = parse_code_with_comments(code);
+        let mut finder = TryCatchFinder::new(comments);
+        finder.visit_module(&module);
+
+        assert_eq!(finder.all_catches.len(), 1);
+        assert_eq!(
+            finder.all_catches[0].errors_handled_in_catch,
+            vec!["ValidationError", "NetworkError", "AuthError"]
+        );
+    }
+
+    #[test]
+    fn test_terminating_catch_swallows_unhandled_errors() {
+        let flow = |catch_body: &str| {
+            let code = format!(
+                "try {{ doWork(); }} catch (e) {{ if (e instanceof ValidationError) {{ return null; }} {} }}",
+                catch_body
+            );
+            let (module, comments) = parse_code_with_comments(&code);
+            let mut finder = TryCatchFinder::new(comments);
+            finder.visit_module(&module);
+            let mut analysis = finder.all_catches[0].clone();
+            analysis.add_thrown_error("ValidationError".to_string());
+            analysis.add_thrown_error("NetworkError".to_string());
+            analysis.calculate_error_flow();
+            analysis
+        };
+
+        // Exiting after logging: nothing leaves the catch
+        let exits = flow("console.error(e); process.exit(1);");
+        assert!(exits.terminates);
+        assert_eq!(exits.errors_effectively_caught, vec!["ValidationError", "NetworkError"]);
+        assert!(exits.errors_propagated.is_empty());
+        assert!(exits.missing_handlers.is_empty());
+        assert!(exits.catches(Some("NetworkError")));
+        assert!(exits.catches(None));
+
+        // Logging and carrying on: the unhandled type still escapes
+        let continues = flow("console.error(e);");
+        assert!(!continues.terminates);
+        assert_eq!(continues.errors_propagated, vec!["NetworkError"]);
+        assert!(!continues.catches(Some("NetworkError")));
+
+        // Only an unconditional exit counts
+        let conditional = flow("if (fatal) { process.exit(1); }");
+        assert!(!conditional.terminates);
+        assert_eq!(conditional.errors_propagated, vec!["NetworkError"]);
+
+        assert!(flow("Deno.exit(1);").terminates);
+    }
+
+    #[test]
+    fn test_rethrow_in_instanceof_branch_passes_on_only_that_type() {
+        let flow = |catch_body: &str, include_catch_rethrows: bool| {
+            let code = format!("try {{ doWork(); }} catch (e) {{ {} }}", catch_body);
+            let (module, comments) = parse_code_with_comments(&code);
+            let mut finder = TryCatchFinder::new(comments);
+            finder.include_catch_rethrows = include_catch_rethrows;
+            finder.visit_module(&module);
+            let mut analysis = finder.all_catches[0].clone();
+            analysis.add_thrown_error("ValidationError".to_string());
+            analysis.add_thrown_error("NetworkError".to_string());
+            analysis.calculate_error_flow();
+            analysis
+        };
+
+        // Everything else is wrapped by the else branch
+        let wrapped = "if (e instanceof ValidationError) { throw e; } else { throw new AppError(e); }";
+        let analysis = flow(wrapped, true);
+        assert!(!analysis.has_escape_hatch);
+        assert!(analysis.has_else_fallback);
+        assert_eq!(analysis.errors_effectively_caught, vec!["NetworkError"]);
+        assert_eq!(analysis.errors_propagated, vec!["ValidationError", "AppError"]);
+        assert!(analysis.rethrows("ValidationError"));
+        assert!(!analysis.rethrows("AppError"));
+
+        // Without rethrows only the wrapper leaves the catch
+        let analysis = flow(wrapped, false);
+        assert_eq!(analysis.errors_effectively_caught, vec!["ValidationError", "NetworkError"]);
+        assert_eq!(analysis.errors_propagated, vec!["AppError"]);
+
+        // An unconditional `throw e` is still the escape hatch
+        let analysis = flow("console.error(e); throw e;", true);
+        assert!(analysis.has_escape_hatch);
+        assert!(analysis.rethrows("ValidationError") && analysis.rethrows("NetworkError"));
+    }
+
+    #[test]
+    fn test_empty_catch_swallows_silently() {
+        let analyze = |catch_body: &str, thrown: &[&str]| {
+            let code = format!("try {{ doWork(); }} catch (e) {{ {} }}", catch_body);
+            let (module, comments) = parse_code_with_comments(&code);
+            let mut finder = TryCatchFinder::new(comments);
+            finder.visit_module(&module);
+            let mut analysis = finder.all_catches[0].clone();
+            for error_type in thrown {
+                analysis.add_thrown_error(error_type.to_string());
+            }
+            analysis.calculate_error_flow();
+            analysis
+        };
+
+        let empty = analyze("/* ignore */", &["NetworkError"]);
+        assert!(empty.has_empty_body);
+        assert!(empty.swallows_silently());
+        assert_eq!(empty.errors_effectively_caught, vec!["NetworkError"]);
+
+        // Nothing known to be thrown, nothing to report
+        assert!(!analyze("", &[]).swallows_silently());
+        // Doing anything with the error means it wasn't silently dropped
+        assert!(!analyze("console.error(e);", &["NetworkError"]).swallows_silently());
+    }
+}
@@ -7,7 +7,10 @@ use swc_ecma_ast::Module;
 use self::swc_common::{comments::Comments, sync::Lrc, Span, Spanned};
 use self::swc_ecma_visit::{Visit, VisitWith};
 
-use crate::throw_finder::{TypedefDefinition, ThrowsAnnotation};
+use crate::throw_finder::{
+  default_throws_tag_aliases, ends_throws_type_list, is_wildcard_throws_type, normalize_jsdoc_line, throws_tag_text,
+  TypedefDefinition, ThrowsAnnotation, ThrowsTagAliases,
+};
 
 /// Finder for @typedef JSDoc annotations
 /// 
@@ -40,6 +43,7 @@ use crate::throw_finder::{TypedefDefinition, ThrowsAnnotation};
 pub struct TypedefFinder {
   pub comments: Lrc<dyn Comments>,
   pub typedefs: HashMap<String, TypedefDefinition>,
+  pub throws_tag_aliases: Vec<String>, // JSDoc tags documenting thrown errors, e.g. `throws`
 }
 
 impl TypedefFinder {
@@ -47,6 +51,7 @@ impl TypedefFinder {
     Self {
       comments,
       typedefs: HashMap::new(),
+      throws_tag_aliases: default_throws_tag_aliases(),
     }
   }
 
@@ -54,7 +59,7 @@ impl TypedefFinder {
   fn parse_typedef_comment(&mut self, comment_text: &str, span: Span) {
     let text = comment_text.trim();
     let lines: Vec<&str> = text.lines()
-      .map(|line| line.trim().trim_start_matches('*').trim())
+      .map(normalize_jsdoc_line)
       .collect();
 
     let mut typedef_name: Option<String> = None;
@@ -105,11 +110,9 @@ impl TypedefFinder {
       }
       
       // Parse @throws annotations within the same comment block
-      if line.to_lowercase().contains("@throws") {
-        if let Some(annotation) = self.parse_throws_comment(line) {
-          for t in annotation.error_types {
-            aggregated_error_types.insert(t);
-          }
+      if let Some(annotation) = self.parse_throws_comment(line) {
+        for t in annotation.error_types {
+          aggregated_error_types.insert(t);
         }
       }
     }
@@ -122,7 +125,8 @@ impl TypedefFinder {
       }
 
       let throws_annotation = if !aggregated_error_types.is_empty() {
-        Some(ThrowsAnnotation { error_types: aggregated_error_types.into_iter().collect(), is_documented: true })
+        let covers_all = aggregated_error_types.iter().any(|t| is_wildcard_throws_type(t));
+        Some(ThrowsAnnotation { error_types: aggregated_error_types.into_iter().collect(), is_documented: true, covers_all, no_throw: None, inherit_doc: false, descriptions: Vec::new() })
       } else { None };
 
       let typedef_def = TypedefDefinition {
@@ -133,7 +137,7 @@ impl TypedefFinder {
       };
       
       #[cfg(debug_assertions)]
-      eprintln!("🔍 Found @typedef definition: {} (callback: {}) with throws: {:?}", 
+      debug_log!("Found @typedef definition: {} (callback: {}) with throws: {:?}", 
                 name, is_callback, typedef_def.throws_annotation);
       
       self.typedefs.insert(name, typedef_def);
@@ -144,8 +148,8 @@ impl TypedefFinder {
   fn parse_throws_comment(&self, line: &str) -> Option<ThrowsAnnotation> {
     let mut error_types: std::collections::HashSet<String> = std::collections::HashSet::new();
 
-    if let Some(throws_pos) = line.to_lowercase().find("@throws") {
-      let after_throws = &line[throws_pos + 7..].trim(); // Skip "@throws"
+    if let Some(after_throws) = throws_tag_text(line, &self.throws_tag_aliases) {
+      let after_throws = &after_throws.trim();
 
       // Handle @throws {Type} syntax
       if let Some(start_brace) = after_throws.find('{') {
@@ -166,9 +170,7 @@ impl TypedefFinder {
         // Handle @throws Type (without braces) - extract comma-separated types
         let type_section = after_throws
           .split_whitespace()
-          .take_while(|word| {
-            !["when", "if", "where", "that", "which", "while", "because", "since"].contains(&word.to_lowercase().as_str())
-          })
+          .take_while(|word| !ends_throws_type_list(word))
           .collect::<Vec<_>>()
           .join(" ");
         let types: Vec<String> = type_section
@@ -187,9 +189,14 @@ impl TypedefFinder {
     }
 
     if !error_types.is_empty() {
+      let covers_all = error_types.iter().any(|t| is_wildcard_throws_type(t));
       Some(ThrowsAnnotation { 
         error_types: error_types.into_iter().collect(),
-        is_documented: true 
+        is_documented: true,
+        covers_all,
+        no_throw: None,
+        inherit_doc: false,
+        descriptions: Vec::new(),
       })
     } else {
       None
@@ -243,6 +250,12 @@ impl TypedefFinder {
   }
 }
 
+impl ThrowsTagAliases for TypedefFinder {
+  fn throws_tag_aliases_mut(&mut self) -> &mut Vec<String> {
+    &mut self.throws_tag_aliases
+  }
+}
+
 impl Visit for TypedefFinder {
   fn visit_module(&mut self, module: &Module) {
     // Check for leading comments on the module itself
@@ -526,6 +539,28 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_throws_with_hyphen_bullets() {
+    let code = r#"
+      /**
+       * @typedef {function} Fetcher
+       * - @throws {HttpError}
+       * @throws TimeoutError, AbortError - the request did not complete
+       */
+
+      function fetcher() {}
+    "#;
+
+    let (module, comments) = parse_code_with_comments(code);
+    let mut finder = TypedefFinder::new(comments);
+    finder.analyze_module(&module);
+
+    let throws = finder.get_typedef("Fetcher").unwrap().throws_annotation.as_ref().unwrap();
+    let mut error_types = throws.error_types.clone();
+    error_types.sort();
+    assert_eq!(error_types, vec!["AbortError", "HttpError", "TimeoutError"]);
+  }
+
   #[test]
   fn test_malformed_typedef_definitions() {
     let code = r#"
@@ -0,0 +1,51 @@
+extern crate serde_json;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Write `code` to a file of its own in the temp directory, named after the test using it
+fn source_file(name: &str, code: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("what-does-it-throw-cli-{}-{}.ts", name, std::process::id()));
+    fs::write(&path, code).expect("temp file is writable");
+    path
+}
+
+#[test]
+fn test_recoverable_syntax_error_still_reports_the_file() {
+    let path = source_file(
+        "recoverable",
+        "function boom() {\n  throw new Error(\"x\");\n}\nconst n = 012;\n",
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_what-does-it-throw"))
+        .arg(&path)
+        .args(["--format", "json"])
+        .output()
+        .expect("cli runs");
+    fs::remove_file(&path).ok();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("failed to parse"));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json report on stdout");
+    assert_eq!(report["functions"][0]["name"], "boom");
+    assert_eq!(report["functions"][0]["status"], "undocumented");
+    // The recovered error is reported next to the analysis instead of failing the file
+    let parse_errors = report["parse_errors"].as_array().expect("parse errors are reported");
+    assert!(!parse_errors.is_empty());
+    assert!(parse_errors.iter().all(|error| error["code"] == "DIT012" && error["range"]["start"]["line"] == 4));
+}
+
+#[test]
+fn test_fatal_syntax_error_fails_the_file() {
+    let path = source_file("fatal", "function boom( {\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_what-does-it-throw"))
+        .arg(&path)
+        .args(["--format", "json"])
+        .output()
+        .expect("cli runs");
+    fs::remove_file(&path).ok();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed to parse"));
+    assert!(output.stdout.is_empty());
+}
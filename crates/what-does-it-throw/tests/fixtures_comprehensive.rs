@@ -10,8 +10,15 @@ use swc_common::{sync::Lrc, SourceMap, FileName};
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_visit::Visit;
 use what_does_it_throw::{
+    analyze_code,
+    analyze_module,
     call_finder::CallFinder,
-    throw_finder::{ThrowAnalyzer, ThrowFinderSettings, TypeRegistry},
+    default_throws_tag_aliases,
+    report,
+    throw_finder::{ThrowAnalyzer, ThrowFinderSettings},
+    EnvGuardedThrows,
+    UserSettings,
+    DEFAULT_MAX_CALL_DEPTH,
 };
 use swc_common::comments::SingleThreadedComments;
 
@@ -81,22 +88,13 @@ fn analyze_fixture_tree(files: Vec<(&str, String)>, entry_path: &str) -> Fixture
     let settings = ThrowFinderSettings {
         ignore_statements: &ignore_statements,
         include_try_statements: &include_try_statements,
+        error_factories: &[],
+        assertion_functions: &[],
+        throws_tag_aliases: &default_throws_tag_aliases(),
+        next_line_comments: &[],
     };
 
-    let mut throw_analyzer = ThrowAnalyzer {
-        comments: comments.clone(),
-        functions_with_throws: HashSet::new(),
-        json_parse_calls: Vec::new(),
-        fs_access_calls: Vec::new(),
-        import_sources: HashSet::new(),
-        imported_identifiers: Vec::new(),
-        function_name_stack: Vec::new(),
-        current_class_name: None,
-        current_method_name: None,
-        throwfinder_settings: settings,
-        used_it_throws_comments: HashSet::new(),
-        type_registry: TypeRegistry::new(),
-    };
+    let mut throw_analyzer = ThrowAnalyzer::new(comments.clone(), settings, &UserSettings::default());
 
     for module in &modules {
         throw_analyzer.visit_module(module);
@@ -707,4 +705,460 @@ class SomeClass {
 
         expect_exact_diagnostics(&result, &expected);
     }
+
+    #[test]
+    fn test_switch_catch_dispatch_fixture() {
+        let code = load_fixture("switchCatchDispatch.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+
+        // switch (true) / instanceof, switch (err.constructor), err.constructor === T,
+        // err.name === 'T', and switch (err.name); the last two rethrow anything else
+        assert_eq!(result.catch_analyses.len(), 5);
+        let escape_hatches: Vec<bool> = result.catch_analyses.iter().map(|c| c.has_escape_hatch).collect();
+        assert_eq!(escape_hatches, vec![false, false, false, true, true]);
+        for catch_analysis in &result.catch_analyses {
+            assert_eq!(catch_analysis.errors_handled_in_catch, vec!["ValidationError", "NetworkError"]);
+            assert_eq!(catch_analysis.errors_thrown_in_try, vec!["ValidationError", "NetworkError"]);
+            assert_eq!(catch_analysis.errors_effectively_caught, vec!["ValidationError", "NetworkError"]);
+            assert!(catch_analysis.errors_propagated.is_empty());
+            assert!(catch_analysis.is_exhaustive());
+            assert!(!catch_analysis.has_validation_errors());
+        }
+    }
+
+    #[test]
+    fn test_object_literal_throws_fixture() {
+        let code = load_fixture("objectLiteralThrows.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+
+        // The literal's `name` is the error type, so `@throws {TimeoutError}` documents it
+        let call_upstream = result.functions_with_throws.iter()
+            .find(|f| f.function_or_method_name == "callUpstream")
+            .expect("callUpstream should throw");
+        assert_eq!(call_upstream.throw_details[0].error_type.as_deref(), Some("TimeoutError"));
+        assert_eq!(call_upstream.throw_details[0].error_message.as_deref(), Some("upstream timeout"));
+        assert!(call_upstream.throw_details[0].is_custom_error);
+        assert_eq!(report::documentation_status(call_upstream), report::DocumentationStatus::Documented);
+
+        // Without a `name` there is no type to report
+        let legacy = result.functions_with_throws.iter()
+            .find(|f| f.function_or_method_name == "legacyFailure")
+            .expect("legacyFailure should throw");
+        assert_eq!(legacy.throw_details[0].error_type, None);
+        assert_eq!(legacy.throw_details[0].error_message.as_deref(), Some("something broke"));
+
+        // Both called and inline object-literal throws reach the catch analysis
+        let mut thrown_in_try: Vec<Vec<String>> = result.catch_analyses.iter()
+            .map(|catch_analysis| catch_analysis.errors_thrown_in_try.clone())
+            .collect();
+        thrown_in_try.sort();
+        assert_eq!(thrown_in_try, vec![vec!["BadRequestError".to_string()], vec!["TimeoutError".to_string()]]);
+    }
+
+    #[test]
+    fn test_optional_catch_binding_fixture() {
+        let code = load_fixture("optionalCatchBinding.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+
+        // `catch { ... }` handles everything thrown in its try block, even with an unrelated instanceof
+        let mut caught: Vec<(Vec<String>, Vec<String>)> = result.catch_analyses.iter()
+            .map(|catch_analysis| {
+                assert!(catch_analysis.catch_param.is_none());
+                assert!(catch_analysis.is_exhaustive());
+                (catch_analysis.errors_effectively_caught.clone(), catch_analysis.errors_propagated.clone())
+            })
+            .collect();
+        caught.sort();
+        assert_eq!(caught, vec![
+            (vec!["Error".to_string()], vec![]),
+            (vec!["TypeError".to_string()], vec![]),
+            (vec!["TypeError".to_string()], vec!["RangeError".to_string()]),
+        ]);
+
+        // Only the rethrowing catch lets anything out
+        let mut throwing: Vec<String> = result.functions_with_throws.iter()
+            .map(|f| f.function_or_method_name.clone())
+            .collect();
+        throwing.sort();
+        assert_eq!(throwing, vec!["fails", "rethrowCall"]);
+        let rethrow_call = result.functions_with_throws.iter()
+            .find(|f| f.function_or_method_name == "rethrowCall")
+            .unwrap();
+        let rethrown: Vec<_> = rethrow_call.throw_details.iter().map(|d| d.error_type.clone()).collect();
+        assert_eq!(rethrown, vec![Some("RangeError".to_string())]);
+    }
+
+    #[test]
+    fn test_inheritdoc_fixture() {
+        let code = load_fixture("inheritDoc.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+        let documented_types = |class_name: &str| -> Option<Vec<String>> {
+            let method = result.functions_with_throws.iter()
+                .find(|f| f.class_name.as_deref() == Some(class_name) && f.function_or_method_name == "doIt")
+                .unwrap();
+            method.throws_annotation.as_ref().map(|annotation| {
+                let mut error_types = annotation.error_types.clone();
+                error_types.sort();
+                error_types
+            })
+        };
+
+        assert_eq!(documented_types("A"), Some(vec!["IOError".to_string()]));
+        // `@inheritdoc` takes A.doIt's @throws, alongside any the override lists itself
+        assert_eq!(documented_types("B"), Some(vec!["IOError".to_string()]));
+        assert_eq!(documented_types("C"), Some(vec!["IOError".to_string(), "ParseError".to_string()]));
+        // Nothing to inherit from leaves the method undocumented
+        assert_eq!(documented_types("D"), None);
+    }
+
+    #[test]
+    fn test_transitive_propagation_fixture() {
+        let code = load_fixture("transitivePropagation.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+
+        let mut throws_by_function: Vec<(String, Vec<String>)> = result.functions_with_throws.iter()
+            .map(|f| {
+                let mut error_types: Vec<String> = f.throw_details.iter().filter_map(|d| d.error_type.clone()).collect();
+                error_types.sort();
+                (f.function_or_method_name.clone(), error_types)
+            })
+            .collect();
+        throws_by_function.sort();
+
+        // `a -> b -> c` carries c's error all the way up; the recursive pair share each other's errors
+        // and the walk still terminates; `guarded` swallows everything with a catch-all
+        let range_and_syntax = vec!["RangeError".to_string(), "SyntaxError".to_string()];
+        assert_eq!(throws_by_function, vec![
+            ("a".to_string(), vec!["TypeError".to_string()]),
+            ("b".to_string(), vec!["TypeError".to_string()]),
+            ("c".to_string(), vec!["TypeError".to_string()]),
+            ("isEven".to_string(), range_and_syntax.clone()),
+            ("isOdd".to_string(), range_and_syntax.clone()),
+            ("parity".to_string(), range_and_syntax),
+        ]);
+    }
+
+    #[test]
+    fn test_max_call_depth_limits_transitive_propagation() {
+        let code = load_fixture("callDepthLimit.ts");
+        let throwing_functions = |max_call_depth: usize, error_type: &str| {
+            let user_settings = UserSettings {
+                ignore_statements: vec!["@it-throws".to_string()],
+                max_call_depth,
+                ..Default::default()
+            };
+            let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+            let mut names: Vec<String> = result.functions_with_throws.iter()
+                .filter(|f| f.throw_details.iter().any(|d| d.error_type.as_deref() == Some(error_type)))
+                .map(|f| f.function_or_method_name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        // `loadUserSafely` handles DbError itself, so `renderFallback` never inherits it
+        assert_eq!(
+            throwing_functions(DEFAULT_MAX_CALL_DEPTH, "DbError"),
+            vec!["handleRequest", "loadProfile", "loadUser", "query", "renderPage", "validateAndLoad"]
+        );
+        assert_eq!(
+            throwing_functions(2, "DbError"),
+            vec!["handleRequest", "loadProfile", "loadUser", "query", "validateAndLoad"]
+        );
+        assert_eq!(throwing_functions(1, "DbError"), vec!["loadUser", "query", "validateAndLoad"]);
+        assert_eq!(throwing_functions(0, "DbError"), vec!["loadUser", "query", "validateAndLoad"]);
+
+        // `validateAndLoad` throws TypeError itself, so that one still climbs a level where the
+        // DbError it inherited from `query` doesn't
+        assert_eq!(throwing_functions(1, "TypeError"), vec!["handleRequest", "validateAndLoad"]);
+    }
+
+    #[test]
+    fn test_env_guarded_throws_can_be_excluded() {
+        let code = load_fixture("envGuardedThrows.ts");
+        let thrown_types = |treat_env_guarded_throws_as: EnvGuardedThrows| {
+            let user_settings = UserSettings {
+                ignore_statements: vec!["@it-throws".to_string()],
+                treat_env_guarded_throws_as,
+                ..Default::default()
+            };
+            let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+            let mut functions: Vec<(String, Vec<String>)> = result.functions_with_throws.iter()
+                .map(|f| {
+                    let mut types: Vec<String> = f.throw_details.iter().filter_map(|d| d.error_type.clone()).collect();
+                    types.sort();
+                    types.dedup();
+                    (f.function_or_method_name.clone(), types)
+                })
+                .collect();
+            functions.sort();
+            functions
+        };
+
+        let included = thrown_types(EnvGuardedThrows::Include);
+        assert!(included.contains(&("checkInvariants".to_string(), vec!["Error".to_string()])));
+        assert!(included.contains(&("loadConfig".to_string(), vec!["Error".to_string()])));
+        assert!(included.contains(&("readPort".to_string(), vec!["ConfigError".to_string(), "Error".to_string()])));
+
+        // Only the unconditional ConfigError survives, and it still reaches the caller
+        assert_eq!(thrown_types(EnvGuardedThrows::Exclude), vec![
+            ("readPort".to_string(), vec!["ConfigError".to_string()]),
+            ("start".to_string(), vec!["ConfigError".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn test_debug_trace_is_opt_in() {
+        let code = load_fixture("switchCatchDispatch.ts");
+        let analyze = |debug: bool| {
+            let user_settings = UserSettings {
+                debug,
+                ..Default::default()
+            };
+            analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings).0
+        };
+
+        assert!(analyze(false).debug_log.is_empty());
+        let traced = analyze(true);
+        assert!(traced.debug_log.iter().any(|line| line.contains("Registered functions")));
+        assert!(traced.debug_log.iter().any(|line| line.contains("Catch analysis populated")));
+    }
+
+    #[test]
+    fn test_cli_prints_no_trace_without_debug() {
+        let run = |extra_args: &[&str]| {
+            std::process::Command::new(env!("CARGO_BIN_EXE_what-does-it-throw"))
+                .args(["--format", "json", "src/fixtures/switchCatchDispatch.ts"])
+                .args(extra_args)
+                .output()
+                .expect("failed to run the CLI")
+        };
+
+        let quiet = run(&[]);
+        assert!(quiet.stderr.is_empty(), "{}", String::from_utf8_lossy(&quiet.stderr));
+        assert!(serde_json::from_slice::<serde_json::Value>(&quiet.stdout).is_ok());
+
+        // The trace goes to stderr, leaving the JSON on stdout intact
+        let traced = run(&["--debug"]);
+        assert!(String::from_utf8_lossy(&traced.stderr).contains("Registered functions"));
+        assert_eq!(traced.stdout, quiet.stdout);
+    }
+
+    #[test]
+    fn test_cli_baseline_reports_only_new_throws() {
+        let baseline_path = std::env::temp_dir().join(format!("does-it-throw-baseline-{}.json", std::process::id()));
+        let _ = fs::remove_file(&baseline_path);
+        let run = |extra_args: &[&str]| {
+            std::process::Command::new(env!("CARGO_BIN_EXE_what-does-it-throw"))
+                .args(["src/fixtures/transitivePropagation.ts", "--baseline", baseline_path.to_str().unwrap()])
+                .args(extra_args)
+                .output()
+                .expect("failed to run the CLI")
+        };
+
+        // Without a baseline yet everything is new; saved, it becomes the baseline
+        let first = run(&[]);
+        assert!(first.status.success(), "{}", String::from_utf8_lossy(&first.stderr));
+        let everything: report::ResolvedReport = serde_json::from_slice(&first.stdout).unwrap();
+        assert!(!everything.undocumented_functions.is_empty());
+        fs::write(&baseline_path, &first.stdout).unwrap();
+
+        let second = run(&["--fail-on", "any"]);
+        assert!(second.status.success(), "{}", String::from_utf8_lossy(&second.stderr));
+        let delta: report::ResolvedReport = serde_json::from_slice(&second.stdout).unwrap();
+        assert!(delta.undocumented_functions.is_empty() && delta.incomplete_functions.is_empty());
+        let _ = fs::remove_file(&baseline_path);
+    }
+
+    #[test]
+    fn test_cli_report_writes_catalog_without_changing_output() {
+        let report_path = std::env::temp_dir().join(format!("does-it-throw-report-{}.md", std::process::id()));
+        let run = |extra_args: &[&str]| {
+            std::process::Command::new(env!("CARGO_BIN_EXE_what-does-it-throw"))
+                .args(["src/fixtures/transitivePropagation.ts", "--format", "json"])
+                .args(extra_args)
+                .output()
+                .expect("failed to run the CLI")
+        };
+
+        let plain = run(&[]);
+        let reported = run(&["--report", report_path.to_str().unwrap()]);
+        assert!(reported.status.success(), "{}", String::from_utf8_lossy(&reported.stderr));
+        assert_eq!(plain.stdout, reported.stdout);
+        let markdown = fs::read_to_string(&report_path).unwrap();
+        assert!(markdown.starts_with("# Thrown errors\n\n## Documentation coverage\n"));
+        assert!(markdown.contains("## `src/fixtures/transitivePropagation.ts`"));
+
+        let html = run(&["--report", report_path.to_str().unwrap(), "--report-format", "html"]);
+        assert!(html.status.success(), "{}", String::from_utf8_lossy(&html.stderr));
+        assert!(fs::read_to_string(&report_path).unwrap().starts_with("<!DOCTYPE html>"));
+        let _ = fs::remove_file(&report_path);
+    }
+
+    #[test]
+    fn test_ambient_declaration_throws_contract() {
+        let code = r#"/** @throws {IOError} */
+export function readFile(p: string): string;
+
+declare module "net" {
+  /** @throws {TimeoutError} */
+  export function fetchText(url: string): string;
+  export function close(): void;
+}
+
+export function loadConfig(): string {
+  return readFile("config.json");
+}
+"#;
+        let cm = Lrc::new(SourceMap::default());
+        let fm = cm.new_source_file(FileName::Custom("stubs.d.ts".into()), code.into());
+        let comments = Lrc::new(SingleThreadedComments::default());
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig { dts: true, ..Default::default() }),
+            Default::default(),
+            StringInput::from(&*fm),
+            Some(&comments),
+        );
+        let module = Parser::new_from(lexer).parse_module().expect("Failed to parse declarations");
+        let user_settings = UserSettings::default();
+        let (result, _, _) = analyze_module(&module, cm, comments, &user_settings);
+
+        // Bodyless declarations are registered with their contract
+        let read_file = result.all_functions.iter().find(|f| f.name == "readFile").expect("readFile registered");
+        assert_eq!(read_file.throws_annotation.as_ref().map(|a| a.error_types.clone()), Some(vec!["IOError".to_string()]));
+        let fetch_text = result.all_functions.iter().find(|f| f.name == "fetchText").expect("fetchText registered");
+        assert_eq!(fetch_text.throws_annotation.as_ref().map(|a| a.error_types.clone()), Some(vec!["TimeoutError".to_string()]));
+        assert!(result.all_functions.iter().any(|f| f.name == "close" && f.throws_annotation.is_none()));
+        assert!(!result.functions_with_throws.iter().any(|f| f.function_or_method_name == "close"));
+
+        // The undocumented caller picks up the declared error through its call
+        assert!(result.calls_to_throws.iter().any(|c| c.throw_map.function_or_method_name == "readFile"));
+        let caller = result.functions_with_throws.iter()
+            .find(|f| f.function_or_method_name == "loadConfig")
+            .expect("loadConfig should inherit readFile's throws");
+        assert!(caller.throws_annotation.is_none());
+        assert!(caller.throw_details.iter().any(|d| d.error_type.as_deref() == Some("IOError")));
+    }
+
+    #[test]
+    fn test_never_returning_declarations_throw() {
+        let code = r#"export function fail(message: string): never;
+
+/** @throws {AssertionError} */
+export function assertNever(value: never): never;
+
+export function log(message: string): void;
+
+export function check(input: string): string {
+  if (!input) {
+    fail("empty");
+  }
+  return input;
+}
+
+export function exhaustive(value: never): string {
+  return assertNever(value);
+}
+"#;
+        let cm = Lrc::new(SourceMap::default());
+        let fm = cm.new_source_file(FileName::Custom("helpers.ts".into()), code.into());
+        let comments = Lrc::new(SingleThreadedComments::default());
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig::default()),
+            Default::default(),
+            StringInput::from(&*fm),
+            Some(&comments),
+        );
+        let module = Parser::new_from(lexer).parse_module().expect("Failed to parse helpers");
+        let user_settings = UserSettings::default();
+        let (result, _, _) = analyze_module(&module, cm, comments, &user_settings);
+
+        let thrown_by = |name: &str| -> Vec<String> {
+            result
+                .functions_with_throws
+                .iter()
+                .filter(|f| f.function_or_method_name == name)
+                .flat_map(|f| f.throw_details.iter().filter_map(|d| d.error_type.clone()))
+                .collect()
+        };
+        // `never` stands for an `Error` unless @throws names the types
+        assert_eq!(thrown_by("fail"), vec!["Error"]);
+        assert_eq!(thrown_by("assertNever"), vec!["AssertionError"]);
+        assert!(thrown_by("log").is_empty());
+
+        assert!(result.calls_to_throws.iter().any(|c| c.id == "NOT_SET-check" && c.throw_map.function_or_method_name == "fail"));
+        assert_eq!(thrown_by("check"), vec!["Error"]);
+        assert_eq!(thrown_by("exhaustive"), vec!["AssertionError"]);
+    }
+
+    #[test]
+    fn test_decorated_methods_fixture() {
+        let code = load_fixture("decoratedMethods.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+        let status = |name: &str| {
+            let method = result.functions_with_throws.iter()
+                .find(|f| f.class_name.as_deref() == Some("UserService") && f.function_or_method_name == name)
+                .unwrap_or_else(|| panic!("{} should throw", name));
+            report::documentation_status(method)
+        };
+
+        // The JSDoc above the decorators documents the method they wrap, as does one between them and the method
+        assert_eq!(status("findUser"), report::DocumentationStatus::Documented);
+        assert_eq!(status("saveUser"), report::DocumentationStatus::Documented);
+        assert_eq!(status("archiveUser"), report::DocumentationStatus::Documented);
+        assert_eq!(status("deleteUser"), report::DocumentationStatus::Undocumented);
+
+        let documented = result.all_functions.iter().find(|f| f.name == "archiveUser").unwrap();
+        assert_eq!(
+            documented.throws_annotation.as_ref().map(|annotation| annotation.error_types.clone()),
+            Some(vec!["NotFoundError".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_interface_throws_fixture() {
+        let code = load_fixture("interfaceThrows.ts");
+        let user_settings = UserSettings {
+            ignore_statements: vec!["@it-throws".to_string()],
+            ..Default::default()
+        };
+        let (result, _, _) = analyze_code(&code, Lrc::new(SourceMap::default()), &user_settings);
+        let status = |class_name: &str, name: &str| {
+            let method = result.functions_with_throws.iter()
+                .find(|f| f.class_name.as_deref() == Some(class_name) && f.function_or_method_name == name)
+                .unwrap_or_else(|| panic!("{}.{} should throw", class_name, name));
+            report::documentation_status(method)
+        };
+
+        // The interface's @throws documents the implementing method, as do those of interfaces it extends
+        assert_eq!(status("MemoryUserStore", "find"), report::DocumentationStatus::Documented);
+        assert_eq!(status("MemoryUserStore", "close"), report::DocumentationStatus::Documented);
+        // Methods the interface doesn't document, and classes not implementing it, are on their own
+        assert_eq!(status("MemoryUserStore", "save"), report::DocumentationStatus::Undocumented);
+        assert_eq!(status("UnrelatedStore", "find"), report::DocumentationStatus::Undocumented);
+    }
 }
\ No newline at end of file
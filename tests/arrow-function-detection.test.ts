@@ -12,7 +12,7 @@ describe("Arrow function throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       'L1': 'arrowThrow may throw',
-      'L2': 'Throw statement.',
+      'L2': 'Throw: new Error("arrow error")',
     });
   });
 
@@ -33,12 +33,12 @@ describe("Arrow function throw detection", () => {
     // Both should have same structure: function diagnostic + throw diagnostic
     expectExactDiagnostics(normalDiagnostics, {
       'L1': 'normalThrow may throw',
-      'L2': 'Throw statement.',
+      'L2': 'Throw: new Error("normal error")',
     });
 
     expectExactDiagnostics(arrowDiagnostics, {
       'L1': 'arrowThrow may throw',
-      'L2': 'Throw statement.',
+      'L2': 'Throw: new Error("arrow error")',
     });
   });
 
@@ -56,9 +56,9 @@ describe("Arrow function throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       'L1': 'first may throw',
-      'L2': 'Throw statement.',
+      'L2': 'Throw: new Error("first")',
       'L5': 'second may throw',
-      'L6': 'Throw statement.',
+      'L6': 'Throw: new TypeError("second")',
     });
   });
 });
\ No newline at end of file
@@ -12,7 +12,7 @@ describe("Basic throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "simpleThrow may throw",
-      L2: "Throw statement.",
+      L2: 'Throw: new Error("test error")',
     });
   });
 
@@ -29,8 +29,8 @@ describe("Basic throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "multipleThrows may throw",
-      L3: "Throw statement.",
-      L5: "Throw statement.",
+      L3: 'Throw: new Error("first error")',
+      L5: 'Throw: new TypeError("second error")',
     });
   });
 
@@ -48,9 +48,9 @@ describe("Basic throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "arrowThrow may throw",
-      L2: "Throw statement.",
+      L2: 'Throw: new Error("arrow error")',
       L5: "anotherArrow may throw",
-      L6: "Throw statement.",
+      L6: 'Throw: new TypeError("another error")',
     });
   });
 
@@ -90,7 +90,7 @@ describe("Basic throw detection", () => {
     const diagnostics = analyzeCode(code);
     expectExactDiagnostics(diagnostics, {
       L6: "testFunction2 may throw",
-      L7: "Throw statement.",
+      L7: "Throw: new Error('test2')",
     });
   });
 
@@ -109,7 +109,7 @@ describe("Basic throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       "L1": "Function throwsError may throw: {Error}",
-      "L2": "Throw statement.",
+      "L2": 'Throw: new Error("helper error")',
       "L5": [
         "Function callsThrowingFunction may throw: {Error}",
         "Throw statement."
@@ -136,7 +136,7 @@ describe("Basic throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "Function level1 may throw: {Error}",
-      L2: "Throw statement.",
+      L2: 'Throw: new Error("level 1")',
       L5: "level2 may throw {Error} when level 1 is called",
       L6: "level1 may call may throw: {Error}",
       L9: "level3 may throw {Error} when level 2 is called",
@@ -160,9 +160,9 @@ describe("Basic throw detection", () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "throwsBuiltIn may throw",
-      L2: "Throw statement.",
+      L2: 'Throw: new TypeError("type error")',
       L5: "throwsCustom may throw",
-      L6: "Throw statement.",
+      L6: 'Throw: new CustomError("custom error")',
     });
   });
 });
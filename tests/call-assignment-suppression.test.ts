@@ -19,7 +19,7 @@ describe('@it-throws call assignment suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       "L1": "Function throwsError may throw: {Error}",
-      "L2": "Throw statement.",
+      "L2": 'Throw: new Error("test")',
       "L5": [
         "Function testFunction may throw: {Error}",
         "Throw statement."
@@ -49,7 +49,7 @@ describe('@it-throws call assignment suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       "L1": "Function returnsValue may throw: {Error}",
-      "L2": "Throw statement.",
+      "L2": 'Throw: new Error("random error")',
       "L6": [
         "Function testAssignments may throw: {Error}",
         "Throw statement."
@@ -81,7 +81,7 @@ describe('@it-throws call assignment suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       "L1": "Function getData may throw: {Error}",
-      "L2": "Throw statement.",
+      "L2": 'Throw: new Error("data error")',
       "L5": [
         "Function processData may throw: {Error}",
         "Throw statement."
@@ -111,9 +111,9 @@ describe('@it-throws call assignment suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
         "L1": "Function outer may throw: {Error}",
-        "L2": "Throw statement.",
+        "L2": 'Throw: new Error("outer")',
         "L5": "Function inner may throw: {Error}",
-        "L6": "Throw statement.",
+        "L6": 'Throw: new Error("inner")',
         "L9": [
           "Function testNested may throw: {Error}",
           "Throw statement."
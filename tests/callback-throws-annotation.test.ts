@@ -58,7 +58,7 @@ describe('Callback parameter @throws annotation behavior', () => {
     // Expect: anonymous function may throw + throw statement
     expectExactDiagnostics(diagnostics, {
       'L11': 'Anonymous function may throw',
-      'L12': 'Throw statement.'
+      'L12': "Throw: new Error('hi khue')"
     })
 
     const result = parse_js(buildParseOptions(code) as any) as any
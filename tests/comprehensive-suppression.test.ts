@@ -23,10 +23,10 @@ describe('Comprehensive @it-throws suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "throwsError may throw",
-      L2: "Throw statement.",
+      L2: 'Throw: new Error("test")',
       L11: "withoutSuppression may throw",
       L12: "Function call may throw",
-      L13: "Throw statement.",
+      L13: 'Throw: new Error("also not suppressed")',
     });
   });
 
@@ -52,7 +52,7 @@ describe('Comprehensive @it-throws suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       "L1": "Function returnsValue may throw: {Error}",
-      "L2": "Throw statement.",
+      "L2": 'Throw: new Error("random error")',
       "L12": [
         "Function normalFunction may throw: {Error}",
         "Throw statement."
@@ -101,7 +101,7 @@ describe('Comprehensive @it-throws suppression', () => {
     expectExactDiagnostics(diagnostics, {
       "L21": "Function processUserWithoutSuppression may throw: {DatabaseError}",
       "L24": "Function call may throw: {DatabaseError}.",
-      "L27": "Throw statement."
+      "L27": "Throw: e"
     });
   });
 });
\ No newline at end of file
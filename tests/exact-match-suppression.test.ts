@@ -26,7 +26,7 @@ describe("Exact match @it-throws suppression", () => {
     // Should have both function and throw statement diagnostics since comment doesn't match exactly
     expectExactDiagnostics(diagnostics, {
       L2: "testFunction may throw",
-      L3: "Throw statement.",
+      L3: "Throw: new Error('test')",
     });
   });
 
@@ -42,7 +42,7 @@ describe("Exact match @it-throws suppression", () => {
     // Should have both function and throw statement diagnostics
     expectExactDiagnostics(diagnostics, {
       L2: "testFunction may throw",
-      L3: "Throw statement.",
+      L3: "Throw: new Error('test')",
     });
   });
 
@@ -61,7 +61,7 @@ describe("Exact match @it-throws suppression", () => {
 
     expectExactDiagnostics(diagnostics, {
       L6: "testFunction2 may throw",
-      L7: "Throw statement.",
+      L7: "Throw: new Error('test2')",
     });
   });
 
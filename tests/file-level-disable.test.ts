@@ -65,11 +65,11 @@ describe("File-level @it-throws-disable", () => {
 
     expectExactDiagnostics(diagnostics, {
       L2: "Function throwsError may throw: {Error}",
-      L3: "Throw statement."
+      L3: 'Throw: new Error("test error")'
     })
   });
 
-  it("should NOT disable when @it-throws-disable is too far down in file", () => {
+  it("should only disable from the comment on when @it-throws-disable is further down in file", () => {
     const code = stripLineNumbers`
 1 | function throwsError() {
 2 |   throw new Error("test error");
@@ -95,9 +95,44 @@ describe("File-level @it-throws-disable", () => {
     const diagnostics = analyzeCode(code);
     expectExactDiagnostics(diagnostics, {
       L1: "Function throwsError may throw: {Error}",
-      L2: "Throw statement.",
-      L18: "Function lastFunction may throw: {Error}",
-      L19: "Throw statement."
+      L2: 'Throw: new Error("test error")',
+    })
+  });
+
+  it("should re-enable diagnostics after // @it-throws-enable", () => {
+    const code = stripLineNumbers`
+1 | function before() {
+2 |   throw new Error("before");
+3 | }
+4 |
+5 | // @it-throws-disable
+6 | function inside() {
+7 |   throw new Error("inside");
+8 | }
+9 | // @it-throws-enable
+10|
+11| function after() {
+12|   throw new Error("after");
+13| }`;
+
+    const diagnostics = analyzeCode(code);
+    expectExactDiagnostics(diagnostics, {
+      L1: "Function before may throw: {Error}",
+      L2: 'Throw: new Error("before")',
+      L11: "Function after may throw: {Error}",
+      L12: 'Throw: new Error("after")'
+    })
+  });
+
+  it("should flag a disable region that suppresses nothing", () => {
+    const code = stripLineNumbers`
+1 | // @it-throws-disable
+2 | const answer = 42;
+3 | // @it-throws-enable`;
+
+    const diagnostics = analyzeCode(code);
+    expectExactDiagnostics(diagnostics, {
+      L1: "Unused @it-throws-disable region"
     })
   });
 
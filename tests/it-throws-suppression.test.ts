@@ -23,10 +23,10 @@ describe('@it-throws comprehensive suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "throwsError may throw",
-      L2: "Throw statement.",
+      L2: 'Throw: new Error("test")',
       L11: "withoutSuppression may throw",
       L12: "Function call may throw",
-      L13: "Throw statement.",
+      L13: 'Throw: new Error("also not suppressed")',
     });
   });
 
@@ -70,7 +70,7 @@ describe('@it-throws comprehensive suppression', () => {
     expectExactDiagnostics(diagnostics, {
   "L21": "Function processUserWithoutSuppression may throw: {DatabaseError}",
   "L24": "Function call may throw: {DatabaseError}.",
-  "L27": "Throw statement."
+  "L27": "Throw: e"
 });
   });
 
@@ -98,7 +98,7 @@ describe('@it-throws comprehensive suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       "L1": "Function helper may throw: {Error}",
-      "L2": "Throw statement.",
+      "L2": 'Throw: new Error("helper error")',
       "L15": "Function notSuppressed may throw: {Error}",
       "L16": "Function call may throw: {Error}."
     });
@@ -125,10 +125,10 @@ describe('@it-throws comprehensive suppression', () => {
 
     expectExactDiagnostics(diagnostics, {
       L1: "throwsError may throw",
-      L2: "Throw statement.",
+      L2: 'Throw: new Error("arrow error")',
       L11: "normalArrow may throw",
       L12: "Function call may throw",
-      L13: "Throw statement.",
+      L13: 'Throw: new Error("not suppressed")',
     });
   });
 });
\ No newline at end of file
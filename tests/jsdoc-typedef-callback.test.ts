@@ -312,7 +312,7 @@ describe('JSDoc @typedef and @callback support', () => {
       
       expectExactDiagnostics(diagnostics, {
         // Should validate callback compatibility with typedef/callback specifications
-        'L26': 'Throw statement.' // Wrong error type thrown
+        'L26': "Throw: new TypeError('Wrong error type')" // Wrong error type thrown
       })
     })
   })
@@ -18,25 +18,25 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
           "Throw statement.",
         ],
         L29: "Function SomeThrow may throw: {Error}",
-        L30: "Throw statement.",
+        L30: "Throw: new Error('hi khue')",
         L33: "Function SomeThrow2 may throw: {Error}",
-        L34: "Throw statement.",
+        L34: "Throw: new Error('hi khue')",
         L47: [
           "Function call may throw: {Error}.",
           "Function onInitialized may throw",
         ],
         L48: "Function call may throw: {Error}.",
         L51: "Anonymous function may throw: {Error}",
-        L52: "Throw statement.",
+        L52: "Throw: new Error('hi khue')",
         L55: "Anonymous function may throw: {Error}",
-        L56: "Throw statement.",
+        L56: "Throw: new Error('hi khue')",
         L60: "Function call may throw: {Error}.",
         L61: "Function call may throw: {Error}.",
         L64: "Anonymous function may throw: {Error}",
-        L65: "Throw statement.",
+        L65: "Throw: new Error('hi khue')",
         L70: [
           "Function call may throw: {Error}.",
-          "Function getter test may throw",
+          "Function get test may throw",
         ],
         L76: "Function call may throw: {Error}.",
         L77: "Function call may throw: {Error}.",
@@ -48,13 +48,13 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L4: "Function <constructor> may throw: {Error}",
-        L5: "Throw statement.",
+        L5: "Throw: new Error('hi khue')",
         L8: "Function someMethodThatThrows may throw: {Error}",
-        L9: "Throw statement.",
+        L9: "Throw: new Error('hi khue')",
         L16: "Function someMethodThatThrows2 may throw: {Error}",
-        L18: "Throw statement.",
+        L18: "Throw: new Error('hi khue')",
         L22: "Function nestedThrow may throw: {Error}",
-        L26: "Throw statement.",
+        L26: "Throw: new Error('hi khue')",
         L36: ["Function call may throw", "Function callNestedThrow may throw"],
         L42: ["Function call may throw", "Function _somethingCall may throw"],
         L47: ["Function call may throw", "Function somethingCall may throw"],
@@ -68,7 +68,7 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L168: "Function processUserWithIncompleteCatch may throw: {DatabaseError}",
-        L190: "Throw statement.",
+        L190: "Throw: e",
       });
     });
     it("should properly handle exports.ts fixture - export patterns", () => {
@@ -77,18 +77,18 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L7: "Function hiKhue may throw: {Error}",
-        L8: "Throw statement.",
+        L8: "Throw: new Error('hi khue')",
         L11: "Function someConstThatThrows may throw: {Error}",
-        L12: "Throw statement.",
+        L12: "Throw: new Error('hi khue')",
         L26: "Function _ConstThatThrows may throw: {Error}",
-        L27: "Throw statement.",
+        L27: "Throw: new Error('hi khue')",
         L30: [
           "Function callToConstThatThrows may throw: {Error}",
           "Throw statement.",
         ],
         L31: "Function call may throw: {Error}.",
         L35: "Function someConstThatThrows2 may throw: {Error}",
-        L37: "Throw statement.",
+        L37: "Throw: new Error('hi khue')",
         L41: [
           "Function callToConstThatThrows2 may throw: {Error}",
           "Throw statement.",
@@ -112,7 +112,7 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L21: "Function someMethodThatThrows2 may throw: {Error}",
-        L24: "Throw statement.",
+        L24: "Throw: new Error('hi khue')",
         L90: "Function call may throw: {Error}.",
       });
     });
@@ -128,22 +128,22 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L7: "Function basicErrorThrow may throw: {Error}",
-        L8: "Throw statement.",
+        L8: 'Throw: new Error("Something went wrong")',
         L14: "Function basicTypeErrorThrow may throw: {TypeError}",
-        L15: "Throw statement.",
+        L15: 'Throw: new TypeError("Expected a string")',
         L18: "Function customErrorThrow may throw: {ValidationError}",
-        L19: "Throw statement.",
+        L19: 'Throw: new ValidationError("Custom validation failed")',
         L65: "Function partiallyDocumented may throw: {TypeError}",
-        L69: "Throw statement.",
+        L69: 'Throw: new TypeError("This is NOT documented")',
         L75: "Function anotherPartiallyDocumented may throw: {RangeError, ValidationError}",
-        L79: "Throw statement.",
-        L81: "Throw statement.",
+        L79: 'Throw: new RangeError("This is NOT documented")',
+        L81: 'Throw: new ValidationError("This is also NOT documented")',
         L86: "Function throwStringLiteral may throw",
-        L87: "Throw statement.",
+        L87: 'Throw: "This is a string error"',
         L90: "Function throwVariable may throw: {variable: existingError}",
-        L92: "Throw statement.",
+        L92: "Throw: existingError",
         L95: "Function throwExpression may throw: {Error}",
-        L96: "Throw statement.",
+        L96: "Throw: new Error(`Dynamic error: ${Date.now()}`)",
         L100: [
           "Function callsUndocumentedFunction may throw: {Error}",
           "Throw statement.",
@@ -156,14 +156,14 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
         L105: "Function call may throw: {Error}.",
         L124: "Function call may throw: {TypeError, ValidationError}.",
         L148: "Function arrowBasicThrow may throw: {Error}",
-        L149: "Throw statement.",
+        L149: 'Throw: new Error("Arrow function error")',
         L159: [
           "Function arrowCallsDocumented may throw: {TypeError}",
           "Throw statement.",
         ],
         L160: "Function call may throw: {TypeError}.",
         L172: "Function undocumentedMethod may throw: {Error}",
-        L173: "Throw statement.",
+        L173: 'Throw: new Error("Class method error")',
         L183: [
           "Function callsDocumentedMethod may throw: {TypeError}",
           "Throw statement.",
@@ -177,11 +177,11 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L2: "Function objectLiteralThrow may throw: {Error}",
-        L3: "Throw statement.",
+        L3: "Throw: new Error('hi khue')",
         L6: "Function nestedObjectLiteralThrow may throw: {Error}",
-        L7: "Throw statement.",
+        L7: "Throw: new Error('hi khue')",
         L13: "Function someExampleThrow may throw: {Error}",
-        L14: "Throw statement.",
+        L14: "Throw: new Error('hi khue')",
         L19: [
           "Function call may throw: {Error}.",
           "Function callToLiteral may throw: {Error}",
@@ -201,10 +201,10 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L1: "Function someThrow may throw: {Error}",
-        L4: "Throw statement.",
-        L8: "Throw statement.",
+        L4: 'Throw: new Error("oh no")',
+        L8: 'Throw: new Error("oh no")',
         L13: "Function badMethod may throw: {Error}",
-        L14: "Throw statement.",
+        L14: 'Throw: new Error("oh no")',
         L21: "Function call may throw: {Error}.",
         L22: "Function call may throw: {Error}.",
         L23: "Function call may throw: {Error}.",
@@ -219,7 +219,7 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L3: "Function someConstThatThrows may throw: {Error}",
-        L4: "Throw statement.",
+        L4: "Throw: new Error('hi khue')",
         L7: [
           "Function callToConstThatThrows4 may throw: {Error}",
           "Throw statement.",
@@ -233,11 +233,11 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L1: "Function SomeThrow may throw: {Error}",
-        L2: "Throw statement.",
+        L2: "Throw: new Error('never gonna let you down')",
         L5: "Function Something may throw: {Error}",
-        L6: "Throw statement.",
+        L6: "Throw: new Error('never gonna run around and desert you')",
         L15: "Function objectLiteralThrow may throw: {Error}",
-        L16: "Throw statement.",
+        L16: "Throw: new Error('hi khue')",
       });
     });
     it.skip("should properly handle spreadExpr.ts fixture - spread expressions", () => {
@@ -246,10 +246,10 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L6: "Function _contextFromWorkflow may throw: {Error}",
-        L7: "Throw statement.",
+        L7: "Throw: new Error('Some error')",
         L13: "Function call may throw: {Error}.",
         L22: "Function _contextFromWorkflow may throw: {Error}",
-        L23: "Throw statement.",
+        L23: "Throw: new Error('Some error')",
         L26: [
           "Function someCallToThrow may throw: {Error}",
           "Throw statement.",
@@ -263,9 +263,9 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L3: "Function someRandomThrow may throw: {Error}",
-        L4: "Throw statement.",
+        L4: "Throw: new Error('some random throw')",
         L7: "Anonymous function may throw: {Error}",
-        L11: "Throw statement.",
+        L11: "Throw: new Error('')",
         L24: ["Function call may throw", "Function createServer may throw"],
       });
     });
@@ -281,7 +281,7 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L36: "Function someMethodThatThrows2 may throw: {Error}",
-        L38: "Throw statement.",
+        L38: "Throw: new Error('hi khue')",
       });
     });
     it("should properly handle tryStatementNested.ts fixture - nested try-catch patterns", () => {
@@ -290,9 +290,9 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L2: "Function throwInsideCatch may throw: {Error}",
-        L6: "Throw statement.",
+        L6: "Throw: e",
         L10: "Function parentCatchThatisNotCaught may throw: {Error}",
-        L19: "Throw statement.",
+        L19: "Throw: new Error()",
       });
     });
   });
@@ -304,13 +304,13 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L4: "Function <constructor> may throw: {Error}",
-        L5: "Throw statement.",
+        L5: "Throw: new Error('hi khue')",
         L8: "Function someMethodThatThrows may throw: {Error}",
-        L9: "Throw statement.",
+        L9: "Throw: new Error('hi khue')",
         L16: "Function someMethodThatThrows2 may throw: {Error}",
-        L18: "Throw statement.",
+        L18: "Throw: new Error('hi khue')",
         L22: "Function nestedThrow may throw: {Error}",
-        L26: "Throw statement.",
+        L26: "Throw: new Error('hi khue')",
         L36: ["Function call may throw", "Function callNestedThrow may throw"],
         L42: ["Function call may throw", "Function _somethingCall may throw"],
         L47: ["Function call may throw", "Function somethingCall may throw"],
@@ -324,18 +324,18 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L3: "Function hiKhue may throw: {Error}",
-        L4: "Throw statement.",
+        L4: "Throw: new Error('hi khue')",
         L7: "Function someConstThatThrows may throw: {Error}",
-        L8: "Throw statement.",
+        L8: "Throw: new Error('hi khue')",
         L16: "Function _ConstThatThrows may throw: {Error}",
-        L17: "Throw statement.",
+        L17: "Throw: new Error('hi khue')",
         L20: [
           "Function callToConstThatThrows may throw: {Error}",
           "Throw statement.",
         ],
         L21: "Function call may throw",
         L24: "Function someConstThatThrows2 may throw: {Error}",
-        L26: "Throw statement.",
+        L26: "Throw: new Error('hi khue')",
         L30: [
           "Function callToConstThatThrows2 may throw: {Error}",
           "Throw statement.",
@@ -365,22 +365,22 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L5: "Function basicErrorThrow may throw: {Error}",
-        L6: "Throw statement.",
+        L6: 'Throw: new Error("Something went wrong")',
         L12: "Function basicTypeErrorThrow may throw: {TypeError}",
-        L13: "Throw statement.",
+        L13: 'Throw: new TypeError("Expected a string")',
         L16: "Function customErrorThrow may throw: {ValidationError}",
-        L17: "Throw statement.",
+        L17: 'Throw: new ValidationError("Custom validation failed")',
         L63: "Function partiallyDocumented may throw: {TypeError}",
-        L67: "Throw statement.",
+        L67: 'Throw: new TypeError("This is NOT documented")',
         L73: "Function anotherPartiallyDocumented may throw: {RangeError, ValidationError}",
-        L77: "Throw statement.",
-        L79: "Throw statement.",
+        L77: 'Throw: new RangeError("This is NOT documented")',
+        L79: 'Throw: new ValidationError("This is also NOT documented")',
         L84: "Function throwStringLiteral may throw",
-        L85: "Throw statement.",
+        L85: 'Throw: "This is a string error"',
         L88: "Function throwVariable may throw: {variable: existingError}",
-        L90: "Throw statement.",
+        L90: "Throw: existingError",
         L93: "Function throwExpression may throw: {Error}",
-        L94: "Throw statement.",
+        L94: "Throw: new Error(`Dynamic error: ${Date.now()}`)",
         L98: [
           "Function callsUndocumentedFunction may throw: {Error}",
           "Throw statement.",
@@ -393,14 +393,14 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
         L103: "Function call may throw: {Error}.",
         L122: "Function call may throw: {TypeError, ValidationError}.",
         L146: "Function arrowBasicThrow may throw: {Error}",
-        L147: "Throw statement.",
+        L147: 'Throw: new Error("Arrow function error")',
         L157: [
           "Function arrowCallsDocumented may throw: {TypeError}",
           "Throw statement.",
         ],
         L158: "Function call may throw: {TypeError}.",
         L170: "Function undocumentedMethod may throw: {Error}",
-        L171: "Throw statement.",
+        L171: 'Throw: new Error("Class method error")',
         L181: [
           "Function callsDocumentedMethod may throw: {TypeError}",
           "Throw statement.",
@@ -414,11 +414,11 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L2: "Function objectLiteralThrow may throw: {Error}",
-        L3: "Throw statement.",
+        L3: "Throw: new Error('hi khue')",
         L6: "Function nestedObjectLiteralThrow may throw: {Error}",
-        L7: "Throw statement.",
+        L7: "Throw: new Error('hi khue')",
         L13: "Function someExampleThrow may throw: {Error}",
-        L14: "Throw statement.",
+        L14: "Throw: new Error('hi khue')",
         L19: ["Function call may throw", "Function callToLiteral may throw"],
         L23: ["Function call may throw", "Function callToLiteral2 may throw"],
         L26: ["Function callToLiteral3 may throw: {Error}", "Throw statement."],
@@ -442,7 +442,7 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L1: "Function someThrow may throw: {Error}",
-        L2: "Throw statement.",
+        L2: "Throw: new Error('some error')",
         L4: ["Function callToThrow may throw: {Error}", "Throw statement."],
         L5: "Function call may throw: {Error}.",
       });
@@ -454,13 +454,13 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L1: "Function someThrow may throw: {Error}",
-        L2: "Throw statement.",
+        L2: "Throw: new Error('some error')",
         L4: "Function someThrow2 may throw: {Error}",
-        L5: "Throw statement.",
+        L5: "Throw: new Error('some error')",
         L8: "Function someTsx may throw: {Error}",
-        L10: "Throw statement.",
+        L10: "Throw: new Error()",
         L15: "Function someAsyncTsx may throw: {Error}",
-        L17: "Throw statement.",
+        L17: "Throw: new Error()",
         L22: ["Function callToThrow may throw: {Error}", "Throw statement."],
         L23: "Function call may throw",
         L24: "Function call may throw",
@@ -475,13 +475,13 @@ describe("Real-world fixture tests for comprehensive functionality", () => {
 
       expectExactDiagnostics(diagnostics, {
         L2: "Function someThrow may throw: {Error}",
-        L3: "Throw statement.",
+        L3: "Throw: new Error('some error')",
         L5: "Function someThrow2 may throw: {Error}",
-        L6: "Throw statement.",
+        L6: "Throw: new Error('some error')",
         L9: "Function someTsx may throw: {Error}",
-        L11: "Throw statement.",
+        L11: "Throw: new Error()",
         L16: "Function someAsyncTsx may throw: {Error}",
-        L18: "Throw statement.",
+        L18: "Throw: new Error()",
         L23: ["Function callToThrow may throw: {Error}", "Throw statement."],
         L24: "Function call may throw",
         L25: "Function call may throw",
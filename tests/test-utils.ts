@@ -170,13 +170,13 @@ export function loadFixture(fixtureName: string): string {
  * @example
  * expectExactDiagnostics(diagnostics, {
  *   'L21': 'someMethodThatThrows2 may throw',
- *   'L24': 'Throw statement.',
+ *   'L24': 'Throw: new Error("not found")',
  *   'L89': 'Function call may throw'
  * });
  * 
  * // For multiple diagnostics on the same line:
  * expectExactDiagnostics(diagnostics, {
- *   'L1': ['function may throw', 'Throw statement.']
+ *   'L1': ['function may throw', 'Throw: new Error("not found")']
  * });
  */
 import { diffLines } from 'diff';
@@ -76,7 +76,7 @@ describe('Unused @it-throws comment detection', () => {
     // Helper function should have diagnostics (no @it-throws comment)
     expectExactDiagnostics(diagnostics, {
       'L1': 'helper may throw',
-      'L2': 'Throw statement.',
+      'L2': "Throw: new Error('helper throws')",
     });
   });
 